@@ -139,8 +139,9 @@ pub enum AirError {
     BadBoundaryLen { expected: usize, got: usize },
     #[error("target register m={m} out of range (k={k})")]
     RegOutOfRange { m: usize, k: usize },
-    #[error("row.regs length must be k={expected} (got {got})")]
-    BadRowLen { expected: usize, got: usize },
+    /// `row` counts from the first row handed to the evaluator.
+    #[error("row {row}: row.regs length must be k={expected} (got {got})")]
+    BadRowLen { row: usize, expected: usize, got: usize },
 }
 
 /// Evaluate a block **purely** from `(boundary_in, rows[start..end])`.
@@ -166,17 +167,15 @@ pub fn eval_block_r(
     let mut locals: Vec<Locals> = Vec::new();
     let mut boundary_out: Box<[F]> = boundary_in.to_vec().into_boxed_slice();
 
-    let mut row_ctr = 0usize;
-    for row in iter_rows {
+    for (row_ctr, row) in iter_rows.enumerate() {
         if row.regs.len() != air.k {
-            return Err(AirError::BadRowLen { expected: air.k, got: row.regs.len() });
+            return Err(AirError::BadRowLen { row: row_ctr, expected: air.k, got: row.regs.len() });
         }
         reg_m_vals.push(row.regs[m.0]);
         let (id_row, sigma_row) = air.make_id_sigma_row(row_ctr);
         let selectors_row = air.make_selectors_row(row_ctr);
         locals.push(Locals { w_row: row.regs.clone(), id_row, sigma_row, selectors_row });
         boundary_out = row.regs;
-        row_ctr += 1;
     }
 
     Ok(BlockResult { reg_m_vals, locals, boundary_out })
@@ -223,19 +222,17 @@ pub fn eval_block_all_regs_r(
     let mut locals: Vec<Locals> = Vec::new();
     let mut boundary_out: Box<[F]> = boundary_in.to_vec().into_boxed_slice();
 
-    let mut row_ctr = 0usize;
-    for row in iter_rows {
+    for (row_ctr, row) in iter_rows.enumerate() {
         if row.regs.len() != air.k {
-            return Err(AirError::BadRowLen { expected: air.k, got: row.regs.len() });
+            return Err(AirError::BadRowLen { row: row_ctr, expected: air.k, got: row.regs.len() });
         }
-        for m in 0..air.k {
-            regs_vals[m].push(row.regs[m]);
+        for (col, &v) in regs_vals.iter_mut().zip(row.regs.iter()) {
+            col.push(v);
         }
         let (id_row, sigma_row) = air.make_id_sigma_row(row_ctr);
         let selectors_row = air.make_selectors_row(row_ctr);
         locals.push(Locals { w_row: row.regs.clone(), id_row, sigma_row, selectors_row });
         boundary_out = row.regs;
-        row_ctr += 1;
     }
 
     Ok(BlockAllResult { regs_vals, locals, boundary_out })
@@ -288,7 +285,7 @@ pub fn residual_row(
     // Gate demo: s0·(w0+w1−w2) + s1·(w0·w1−w2)
     let w = &locals.w_row;
     let s = &locals.selectors_row;
-    let gate_add = if s.len() >= 1 && air.k >= 3 { s[0] * (w[0] + w[1] - w[2]) } else { F::zero() };
+    let gate_add = if !s.is_empty() && air.k >= 3 { s[0] * (w[0] + w[1] - w[2]) } else { F::zero() };
     let gate_mul = if s.len() >= 2 && air.k >= 3 { s[1] * (w[0] * w[1] - w[2]) } else { F::zero() };
     let gate_part = cfg.alpha * (gate_add + gate_mul);
 
@@ -316,17 +313,46 @@ pub fn residual_stream<'a>(
     rs: &'a impl Restreamer<Item = Row>,
     b_blk: usize,
 ) -> impl Iterator<Item = F> + 'a {
+    residual_stream_r(air, cfg, rs, b_blk).map(|r| r.expect("air::residual_stream failed"))
+}
+
+/// Fallible [`residual_stream`]: a row of the wrong width yields one
+/// [`AirError::BadRowLen`] (with `row` counted from the start of the trace)
+/// and ends the stream.
+pub fn residual_stream_r<'a>(
+    air: &'a AirSpec,
+    cfg: ResidualCfg,
+    rs: &'a impl Restreamer<Item = Row>,
+    b_blk: usize,
+) -> impl Iterator<Item = Result<F, AirError>> + 'a {
     let t_rows = rs.len_rows();
-    let mut z_cur = F::one();
-    let mut global_idx = 0usize;
+    let z_cur = F::one();
+    let global_idx = 0usize;
+    let mut failed = false;
 
-    (0..crate::stream::block_count(t_rows, b_blk)).flat_map(move |t| {
+    (0..crate::stream::block_count(t_rows, b_blk)).flat_map(move |t| -> Box<dyn Iterator<Item = Result<F, AirError>> + 'a> {
+        if failed {
+            return Box::new(std::iter::empty());
+        }
         let (s, e) = crate::stream::block_bounds(crate::stream::BlockIdx(t), t_rows, b_blk);
         let it = rs.stream_rows(s, e);
         let boundary_seed = vec![F::zero(); air.k].into_boxed_slice();
-        let br = eval_block(air, RegIdx(0), BlockIdx(t), &boundary_seed, it);
+        let br = match eval_block_r(air, RegIdx(0), BlockIdx(t), &boundary_seed, it) {
+            Ok(br) => br,
+            Err(err) => {
+                failed = true;
+                let err = match err {
+                    AirError::BadRowLen { row, expected, got } => {
+                        AirError::BadRowLen { row: s.as_usize() + row, expected, got }
+                    }
+                    other => other,
+                };
+                return Box::new(std::iter::once(Err(err)));
+            }
+        };
 
-        br.locals.into_iter().map(move |loc| {
+        let (mut z_cur, mut global_idx) = (z_cur, global_idx);
+        Box::new(br.locals.into_iter().map(move |loc| {
             let (prod_id, prod_sigma) = prod_id_sigma(air, &loc, cfg.beta, cfg.gamma);
             let phi = prod_sigma.inverse().map(|inv| prod_id * inv).unwrap_or(F::zero());
             let z_next = z_cur * phi;
@@ -337,8 +363,8 @@ pub fn residual_stream<'a>(
             let r_i = residual_row(air, &loc, &cfg, z_cur, z_next, is_first, is_last);
             z_cur = z_next;
             global_idx += 1;
-            r_i
-        })
+            Ok(r_i)
+        }))
     })
 }
 
@@ -402,6 +428,7 @@ pub fn residual_stream_tiles<'a>(
 /// - When `--features strict-recompute-r` is enabled, the fast-path is compiled
 ///   out and the function *always* recomputes `R(ζ)` from opened values using
 ///   the verifier’s `(α,β,γ)`.
+#[allow(clippy::too_many_arguments)]
 pub fn residual_eval_at_point_symbolic(
     k: usize,
     header_like: (&u32, &F), // (N, zh_c)
@@ -430,7 +457,7 @@ pub fn residual_eval_at_point_symbolic(
 
     // ---- Gate demo (same as residual_row)
     let s_row = selectors_at_zeta.unwrap_or(&[]);
-    let gate_add = if s_row.len() >= 1 && wires_at_zeta.len() >= 3 {
+    let gate_add = if !s_row.is_empty() && wires_at_zeta.len() >= 3 {
         s_row[0] * (wires_at_zeta[0] + wires_at_zeta[1] - wires_at_zeta[2])
     } else { F::zero() };
     let gate_mul = if s_row.len() >= 2 && wires_at_zeta.len() >= 3 {
@@ -4,7 +4,8 @@
 //! This module wraps the protocol core with a small, ergonomic surface area:
 //! - `ProverBuilder` / `VerifierBuilder` hide PCS/domain wiring (safe defaults)
 //! - one-shot `prove_from_rows` / `prove_from_stream` (sublinear path)
-//! - adapters: `VecRows`, `CsvRows` (streamed) for easy integration
+//! - adapters: `VecRows`, `CsvRows` (streamed, fallible), `GeneratedRows`
+//!   (computed from a step function, checkpointed) for easy integration
 //! - v2 proof I/O helpers: `io::write_proof` / `io::read_proof`
 //! - simple `Tuning` & `estimate_peak_memory`
 //!
@@ -12,17 +13,19 @@
 //! respects the whitepaper’s streaming discipline. No protocol changes.
 
 #![forbid(unsafe_code)]
+#![allow(missing_docs)]
 
-use std::{fs, io::{BufRead, BufReader}, path::{Path, PathBuf}};
+use std::{fs, io::{BufRead, BufReader, Read, Seek, SeekFrom}, path::{Path, PathBuf}};
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use crate::{
-    air::{self, Row, AirSpec},
+    air::{Row, AirSpec},
+    curve::parse_scalar,
     domain::{self, Domain},
     pcs::{self, Basis, PcsParams},
     scheduler,
-    stream::{RowIdx},
+    stream::{RowIdx, TryRestreamer, WitnessError},
     F, Proof, ProveParams, VerifyParams,
 };
 
@@ -41,30 +44,49 @@ pub struct ProverBuilder {
     air: AirSpec,
     b_blk: usize,
     basis_wires: Basis,
+    srs: Option<pcs::Srs>,
+    transform: domain::TransformConfig,
 }
 impl ProverBuilder {
     pub fn new(domain: Domain, air: AirSpec) -> Self {
-        Self { domain, air, b_blk: 128, basis_wires: Basis::Evaluation }
+        Self {
+            domain,
+            air,
+            b_blk: 128,
+            basis_wires: Basis::Evaluation,
+            srs: None,
+            transform: domain::TransformConfig::default(),
+        }
     }
     /// Set the tile/block length used across Blocked-IFFT and openings.
     pub fn b_blk(mut self, b: usize) -> Self { self.b_blk = b.max(1); self }
     /// Choose the basis used for wire commitments (coeff or eval).
     pub fn wires_basis(mut self, basis: Basis) -> Self { self.basis_wires = basis; self }
+    /// Commit against an explicit SRS instead of the process-global one.
+    pub fn srs(mut self, srs: pcs::Srs) -> Self { self.srs = Some(srs); self }
+    /// Choose how blocked IFFTs run (in memory or spilled, and where).
+    pub fn transform(mut self, cfg: domain::TransformConfig) -> Self { self.transform = cfg; self }
 
     /// Build the prover with consistent PCS params (Q is always coefficient-basis).
     pub fn build(self) -> scheduler::Prover<'static> {
         let pcs_wires = PcsParams {
             max_degree: self.domain.n - 1,
             basis: self.basis_wires,
-            srs_placeholder: (),
+            srs: self.srs.clone(),
         };
         let pcs_coeff = PcsParams {
             max_degree: self.domain.n - 1,
             basis: Basis::Coefficient,
-            srs_placeholder: (),
+            srs: self.srs,
         };
-        let params = ProveParams { domain: self.domain.clone(), pcs_wires, pcs_coeff, b_blk: self.b_blk };
-        scheduler::Prover { air: Box::leak(Box::new(self.air)), params: Box::leak(Box::new(params)) }
+        let params = ProveParams {
+            domain: self.domain.clone(),
+            pcs_wires,
+            pcs_coeff,
+            b_blk: self.b_blk,
+            transform: self.transform,
+        };
+        scheduler::Prover::new(Box::leak(Box::new(self.air)), Box::leak(Box::new(params)))
     }
 }
 
@@ -72,26 +94,29 @@ impl ProverBuilder {
 pub struct VerifierBuilder {
     domain: Domain,
     basis_wires: Basis,
+    srs: Option<pcs::Srs>,
 }
 impl VerifierBuilder {
     pub fn new(domain: Domain) -> Self {
-        Self { domain, basis_wires: Basis::Evaluation }
+        Self { domain, basis_wires: Basis::Evaluation, srs: None }
     }
     /// Choose the basis used for *wire* commitments. The proof header remains authoritative.
     pub fn wires_basis(mut self, basis: Basis) -> Self { self.basis_wires = basis; self }
+    /// Verify against an explicit SRS instead of the process-global one.
+    pub fn srs(mut self, srs: pcs::Srs) -> Self { self.srs = Some(srs); self }
     pub fn build(self) -> scheduler::Verifier<'static> {
         let pcs_wires = PcsParams {
             max_degree: self.domain.n - 1,
             basis: self.basis_wires,
-            srs_placeholder: (),
+            srs: self.srs.clone(),
         };
         let pcs_coeff = PcsParams {
             max_degree: self.domain.n - 1,
             basis: Basis::Coefficient,
-            srs_placeholder: (),
+            srs: self.srs,
         };
         let params = VerifyParams { domain: self.domain, pcs_wires, pcs_coeff };
-        scheduler::Verifier { params: Box::leak(Box::new(params)) }
+        scheduler::Verifier::new(Box::leak(Box::new(params)))
     }
 }
 
@@ -104,7 +129,7 @@ impl VerifierBuilder {
 /// Internally wraps the `Vec<Row>` with the repo’s built-in `Restreamer` impl
 /// and delegates to `scheduler::Prover::prove_with_restreamer`.
 pub fn prove_from_rows(
-    prover: &scheduler::Prover,
+    prover: &scheduler::Prover<'_>,
     rows: Vec<Row>,
 ) -> anyhow::Result<Proof> {
     prover.prove_with_restreamer(&rows).map_err(|e| anyhow::anyhow!("prover failed: {e}"))
@@ -112,17 +137,19 @@ pub fn prove_from_rows(
 
 /// Prove from a streaming witness (sublinear path).
 ///
-/// Pass any `Restreamer<Item=Row>` source (e.g., `CsvRows` adapter below).
+/// Pass any `Restreamer<Item=Row>` or fallible `TryRestreamer<Item=Row>`
+/// source (e.g., `CsvRows` adapter below); source errors come back as
+/// `ProveError::Witness` instead of panicking.
 pub fn prove_from_stream(
-    prover: &scheduler::Prover,
-    restreamer: &impl crate::stream::Restreamer<Item = Row>,
+    prover: &scheduler::Prover<'_>,
+    restreamer: &impl TryRestreamer<Item = Row>,
 ) -> anyhow::Result<Proof> {
     prover.prove_with_restreamer(restreamer).map_err(|e| anyhow::anyhow!("prover failed: {e}"))
 }
 
 /// Verify a proof with the given verifier params (header/domain enforced by scheduler).
 pub fn verify(
-    verifier: &scheduler::Verifier,
+    verifier: &scheduler::Verifier<'_>,
     proof: &Proof,
 ) -> anyhow::Result<()> {
     verifier.verify(proof).map_err(|e| anyhow::anyhow!("verification failed: {e}"))
@@ -133,112 +160,340 @@ pub fn verify(
 // ===============================================================================================
 
 pub mod adapters {
-    //! Data-source adapters that implement the repo’s `Restreamer<Item=Row>` trait.
+    //! Data-source adapters that implement the repo’s `Restreamer<Item=Row>`
+    //! (or, when reading can fail, `TryRestreamer<Item=Row>`) trait.
     //!
     //! - `VecRows`: trivial adapter for in-memory data.
-    //! - `CsvRows`: streamed CSV (one row per line, comma/whitespace delimited).
+    //! - `GeneratedRows`: trace computed by a deterministic step function;
+    //!   keeps a state checkpoint every `stride` rows and regenerates any block
+    //!   from the nearest one, so the trace never exists in full.
+    //! - `CsvRows`: streamed CSV (one row per line, comma/whitespace delimited);
+    //!   I/O and parse failures surface as `WitnessError` with the row number.
+    //! - `WitnessFile` (re-exported from `crate::witness`): fixed-width binary
+    //!   witness; any block is one offset computation, no parsing. Convert CSV
+    //!   with `prover convert-witness`.
+    //! - `FramedWitness` (re-exported from `crate::witness_frames`): zstd/gzip
+    //!   container of independently compressed frames; a block inflates only
+    //!   the frames covering it. `prover convert-witness --compress zstd`.
     //!
-    //! Both adapters support *re-streaming*; `CsvRows` re-opens the file for
-    //! each block request (cheap, predictable RAM).
+    //! Both text adapters support *re-streaming*; `CsvRows` re-opens the file for
+    //! each block request and seeks straight to it through a sparse row index
+    //! persisted as `<path>.idx` (cheap, predictable RAM).
 
     use super::*;
     use crate::stream::Restreamer;
 
+    pub use crate::witness::{WitnessFile, WitnessLayout, WitnessWriter};
+    pub use crate::witness_frames::{FramedWitness, FramedWitnessWriter, WitnessCodec};
+
     /// Trivial in-memory adapter.
     pub struct VecRows(pub Vec<Row>);
     impl Restreamer for VecRows {
         type Item = Row;
         fn len_rows(&self) -> usize { self.0.len() }
-        fn stream_rows(&self, start: RowIdx, end: RowIdx) -> Box<dyn Iterator<Item = Row>> {
+        fn stream_rows(&self, start: RowIdx, end: RowIdx) -> Box<dyn Iterator<Item = Row> + '_> {
             let s = start.0.min(self.0.len());
             let e = end.0.min(self.0.len());
             Box::new(self.0[s..e].iter().cloned())
         }
     }
 
+    /// Trace produced by a transition function (e.g. a VM step) instead of
+    /// read from storage.
+    ///
+    /// `step(&mut state, i)` must return row `i` and advance `state` to the
+    /// state before row `i + 1`. Construction runs the whole trace once and
+    /// clones the state every `stride` rows; `stream_rows(start, end)` then
+    /// resumes from the checkpoint at or below `start`, replaying at most
+    /// `stride − 1` rows before the first one it yields. With `stride = b_blk`
+    /// ≈ √T this holds `O(√T)` states, and every block costs `O(b_blk)` steps.
+    ///
+    /// The step must be **deterministic** (a function of `state` and `i`
+    /// only): blocks are regenerated once per prover pass, and a step that
+    /// drifts between passes commits to inconsistent wires. It is an `Fn`, so
+    /// any hidden mutation has to be opted into through interior mutability.
+    pub struct GeneratedRows<S, G> {
+        rows: usize,
+        stride: usize,
+        checkpoints: Vec<S>, // state before row `m·stride`
+        step: G,
+    }
+
+    impl<S: Clone, G: Fn(&mut S, usize) -> Row> GeneratedRows<S, G> {
+        /// Generate `rows` rows from `init`, checkpointing every `stride` rows.
+        /// Pass the prover's `b_blk` so each block resumes from its own checkpoint.
+        pub fn new(init: S, rows: usize, stride: usize, step: G) -> Self {
+            let stride = stride.max(1);
+            let mut checkpoints = Vec::with_capacity(rows.div_ceil(stride));
+            let mut state = init;
+            for i in 0..rows {
+                if i.is_multiple_of(stride) {
+                    checkpoints.push(state.clone());
+                }
+                step(&mut state, i);
+            }
+            Self { rows, stride, checkpoints, step }
+        }
+
+        /// Rows between checkpoints.
+        pub fn stride(&self) -> usize {
+            self.stride
+        }
+
+        /// Number of stored states, `⌈T / stride⌉`.
+        pub fn checkpoints(&self) -> usize {
+            self.checkpoints.len()
+        }
+    }
+
+    impl<S: Clone, G: Fn(&mut S, usize) -> Row> Restreamer for GeneratedRows<S, G> {
+        type Item = Row;
+
+        fn len_rows(&self) -> usize {
+            self.rows
+        }
+
+        fn stream_rows(&self, start: RowIdx, end: RowIdx) -> Box<dyn Iterator<Item = Row> + '_> {
+            let end = end.0.min(self.rows);
+            let start = start.0.min(end);
+            if start == end {
+                return Box::new(std::iter::empty());
+            }
+            let from = start - start % self.stride;
+            let mut state = self.checkpoints[from / self.stride].clone();
+            Box::new((from..end).map(move |i| (self.step)(&mut state, i)).skip(start - from))
+        }
+    }
+
     /// Streamed CSV adapter.
     ///
     /// Format assumptions:
     /// - one witness row per line
     /// - values separated by comma **or** ASCII whitespace
+    /// - values are full-width scalars: decimal or `0x` hex, optionally signed
+    ///   (see `curve::parse_scalar`); values ≥ r are parse errors
     /// - exactly `k` registers per row (extra tokens are rejected; missing tokens error)
     ///
     /// Rows can be greater than the domain size; scheduler will pad/truncate as usual.
+    ///
+    /// Construction makes one pass over the file to count rows and record the
+    /// byte offset of every `stride`-th row. That sparse index is kept next to
+    /// the file as `<path>.idx` and reused while the CSV's length and mtime are
+    /// unchanged, so a block request is one seek plus at most `stride − 1`
+    /// skipped lines instead of a scan from the top. A seek that does not land
+    /// on a line start (a stale or tampered index) is a parse error.
     pub struct CsvRows {
         path: PathBuf,
         k: usize,
         rows: usize, // cached count for len_rows()
+        stride: usize,
+        index: Vec<u64>, // byte offset of row `i·stride`
+    }
+
+    /// Sidecar index header: magic, then `stride, len, mtime_ns, rows, entries`
+    /// as little-endian `u64`s, then the offsets.
+    const CSV_INDEX_MAGIC: &[u8; 8] = b"SSZKPci1";
+
+    /// A line with no tokens. The index and the parser must agree on this, or
+    /// indexed offsets drift from the rows `stream_rows` counts.
+    fn is_blank_line(line: &[u8]) -> bool {
+        line.iter().all(u8::is_ascii_whitespace)
     }
 
     impl CsvRows {
+        /// Index stride used by [`Self::new_from_path`] (the builders' default `b_blk`).
+        pub const DEFAULT_INDEX_STRIDE: usize = 128;
+
         /// Create a CSV adapter. `k` is the number of registers per row.
         pub fn new_from_path(path: impl Into<PathBuf>, k: usize) -> anyhow::Result<Self> {
+            Self::new_indexed(path, k, Self::DEFAULT_INDEX_STRIDE)
+        }
+
+        /// Create a CSV adapter indexed every `stride` rows. Pass the prover's
+        /// `b_blk` so every block request lands exactly on an index entry.
+        pub fn new_indexed(path: impl Into<PathBuf>, k: usize, stride: usize) -> anyhow::Result<Self> {
             let path = path.into();
-            let rows = Self::count_rows(&path)?;
-            Ok(Self { path, k, rows })
+            let stride = stride.max(1);
+            let stamp = Self::stamp(&path)?;
+            let idx_path = Self::index_path_for(&path);
+            let (rows, index) = match Self::load_index(&idx_path, stride, stamp) {
+                Some(loaded) => loaded,
+                None => {
+                    let built = Self::build_index(&path, stride)?;
+                    // Best effort: a read-only directory just means no reuse.
+                    let _ = Self::store_index(&idx_path, stride, stamp, &built);
+                    built
+                }
+            };
+            Ok(Self { path, k, rows, stride, index })
+        }
+
+        /// Where the sparse row index for this file is persisted.
+        pub fn index_path(&self) -> PathBuf {
+            Self::index_path_for(&self.path)
         }
 
-        fn count_rows(path: &Path) -> anyhow::Result<usize> {
+        fn index_path_for(path: &Path) -> PathBuf {
+            let mut p = path.as_os_str().to_owned();
+            p.push(".idx");
+            PathBuf::from(p)
+        }
+
+        /// `(length, mtime in ns)` — identifies the CSV version an index was built from.
+        fn stamp(path: &Path) -> anyhow::Result<(u64, u64)> {
+            let md = fs::metadata(path)
+                .map_err(|e| anyhow::anyhow!("open witness csv {}: {e}", path.display()))?;
+            let mtime = md
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos() as u64);
+            Ok((md.len(), mtime))
+        }
+
+        fn build_index(path: &Path, stride: usize) -> anyhow::Result<(usize, Vec<u64>)> {
             let f = fs::File::open(path)
                 .map_err(|e| anyhow::anyhow!("open witness csv {}: {e}", path.display()))?;
-            let mut n = 0usize;
-            for line in BufReader::new(f).lines() {
-                let l = line?;
-                if !l.trim().is_empty() { n += 1; }
+            let mut r = BufReader::new(f);
+            let mut line = Vec::new();
+            let (mut rows, mut off, mut index) = (0usize, 0u64, Vec::new());
+            loop {
+                line.clear();
+                let n = r.read_until(b'\n', &mut line)?;
+                if n == 0 {
+                    break;
+                }
+                if !is_blank_line(&line) {
+                    if rows.is_multiple_of(stride) {
+                        index.push(off);
+                    }
+                    rows += 1;
+                }
+                off += n as u64;
             }
-            Ok(n)
+            Ok((rows, index))
         }
 
-        fn parse_slice<'a>(&self, start: usize, end: usize) -> anyhow::Result<Vec<Row>> {
-            let f = fs::File::open(&self.path)
-                .map_err(|e| anyhow::anyhow!("open witness csv {}: {e}", self.path.display()))?;
-            let mut out = Vec::with_capacity(end.saturating_sub(start));
-            let mut cur = 0usize;
+        fn load_index(idx_path: &Path, stride: usize, stamp: (u64, u64)) -> Option<(usize, Vec<u64>)> {
+            let bytes = fs::read(idx_path).ok()?;
+            let body = bytes.strip_prefix(CSV_INDEX_MAGIC.as_slice())?;
+            let words: Vec<u64> = body
+                .chunks_exact(8)
+                .map(|c| u64::from_le_bytes(c.try_into().expect("8 bytes")))
+                .collect();
+            let (head, offsets) = words.split_at_checked(5)?;
+            let rows = head[3] as usize;
+            let ok = body.len() % 8 == 0
+                && head[0] == stride as u64
+                && (head[1], head[2]) == stamp
+                && head[4] as usize == offsets.len()
+                && offsets.len() == rows.div_ceil(stride);
+            ok.then(|| (rows, offsets.to_vec()))
+        }
+
+        fn store_index(
+            idx_path: &Path,
+            stride: usize,
+            stamp: (u64, u64),
+            (rows, index): &(usize, Vec<u64>),
+        ) -> std::io::Result<()> {
+            let mut out = Vec::with_capacity(8 * (6 + index.len()));
+            out.extend_from_slice(CSV_INDEX_MAGIC);
+            for w in [stride as u64, stamp.0, stamp.1, *rows as u64, index.len() as u64]
+                .into_iter()
+                .chain(index.iter().copied())
+            {
+                out.extend_from_slice(&w.to_le_bytes());
+            }
+            // Write aside and rename so a concurrent reader never sees a
+            // partial index; the temp file is removed if anything fails.
+            let dir = match idx_path.parent() {
+                Some(d) if !d.as_os_str().is_empty() => d,
+                _ => Path::new("."),
+            };
+            let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+            std::io::Write::write_all(&mut tmp, &out)?;
+            tmp.persist(idx_path).map_err(|e| e.error)?;
+            Ok(())
+        }
+
+        fn io_err(&self, row: usize, err: std::io::Error) -> WitnessError {
+            WitnessError::Io { src: self.path.display().to_string(), row, err }
+        }
+
+        fn parse_err(&self, row: usize, msg: String) -> WitnessError {
+            WitnessError::Parse { src: self.path.display().to_string(), row, msg }
+        }
+
+        fn parse_slice(&self, start: usize, end: usize) -> Result<Vec<Row>, WitnessError> {
+            let end = end.min(self.rows);
+            if start >= end {
+                return Ok(Vec::new());
+            }
+            // Seek to the nearest indexed row at or before `start`.
+            let mut cur = start - start % self.stride;
+            let mut f = fs::File::open(&self.path).map_err(|e| self.io_err(start, e))?;
+            let off = self.index[cur / self.stride];
+            f.seek(SeekFrom::Start(off.saturating_sub(1)))
+                .map_err(|e| self.io_err(start, e))?;
+            if off > 0 {
+                // The indexed row must start right after a newline.
+                let mut prev = [0u8; 1];
+                f.read_exact(&mut prev).map_err(|e| self.io_err(cur, e))?;
+                if prev[0] != b'\n' {
+                    return Err(self.parse_err(
+                        cur,
+                        format!("index {} offset {off} is not at a line start", self.index_path().display()),
+                    ));
+                }
+            }
+            let mut out = Vec::with_capacity(end - start);
 
             for line in BufReader::new(f).lines() {
-                let l = line?;
-                if l.trim().is_empty() { continue; }
+                let l = line.map_err(|e| self.io_err(cur, e))?;
+                if is_blank_line(l.as_bytes()) { continue; }
                 if cur >= end { break; }
                 if cur >= start {
                     let mut regs = Vec::with_capacity(self.k);
                     for tok in l.split(|c: char| c == ',' || c.is_ascii_whitespace()) {
                         if tok.is_empty() { continue; }
                         if regs.len() == self.k {
-                            return Err(anyhow::anyhow!(
-                                "csv row {} has more than k={} fields", cur, self.k
-                            ));
+                            return Err(self.parse_err(cur, format!("more than k={} fields", self.k)));
                         }
-                        // Parse as u128→F. Adjust here if you want hex, etc.
-                        let v = tok.parse::<u128>().map_err(|e| {
-                            anyhow::anyhow!("csv parse error at row {} token `{}`: {}", cur, tok, e)
-                        })?;
-                        regs.push(F::from(v as u64));
+                        let v = parse_scalar(tok)
+                            .map_err(|e| self.parse_err(cur, e.to_string()))?;
+                        regs.push(v);
                     }
                     if regs.len() != self.k {
-                        return Err(anyhow::anyhow!(
-                            "csv row {} has {} fields, expected k={}",
-                            cur, regs.len(), self.k
+                        return Err(self.parse_err(
+                            cur,
+                            format!("{} fields, expected k={}", regs.len(), self.k),
                         ));
                     }
                     out.push(Row { regs: regs.into_boxed_slice() });
                 }
                 cur += 1;
             }
-            // It is valid for (end > actual_rows); we just return fewer rows.
+            if out.len() < end - start {
+                // The file shrank after indexing.
+                return Err(self.parse_err(cur, "unexpected end of file".into()));
+            }
             Ok(out)
         }
     }
 
-    impl Restreamer for CsvRows {
+    impl TryRestreamer for CsvRows {
         type Item = Row;
         fn len_rows(&self) -> usize { self.rows }
-        fn stream_rows(&self, start: RowIdx, end: RowIdx) -> Box<dyn Iterator<Item = Row>> {
-            // Convert the parsing error into a panic-free iterator (the scheduler never expects Err here).
-            let s = start.0;
-            let e = end.0;
-            let parsed = self.parse_slice(s, e)
-                .unwrap_or_else(|e| panic!("CsvRows::stream_rows parse error: {e}"));
-            Box::new(parsed.into_iter())
+        fn try_stream_rows(
+            &self,
+            start: RowIdx,
+            end: RowIdx,
+        ) -> Box<dyn Iterator<Item = Result<Row, WitnessError>> + '_> {
+            match self.parse_slice(start.0, end.0) {
+                Ok(rows) => Box::new(rows.into_iter().map(Ok)),
+                Err(e) => Box::new(std::iter::once(Err(e))),
+            }
         }
     }
 }
@@ -338,8 +593,8 @@ pub fn estimate_peak_memory(b_blk: usize, k_regs: usize) -> usize {
 // let k = 3usize;
 // let rows = 1024usize;
 // let air = crate::air::AirSpec { k, id_table: vec![], sigma_table: vec![], selectors: vec![] };
-// let n = rows.next_power_of_two();
-// let omega = crate::F::get_root_of_unity(n as u64).unwrap();
+// let n = crate::domain::next_smooth_size(rows).unwrap();
+// let omega = crate::domain::root_of_unity(n).unwrap();
 // let domain = crate::domain::Domain { n, omega, zh_c: crate::F::from(1u64) };
 //
 // // Prover / Verifier
@@ -361,3 +616,146 @@ pub fn estimate_peak_memory(b_blk: usize, k_regs: usize) -> usize {
 // crate::api::io::write_proof(std::path::Path::new("proof.bin"), &proof)?;
 // ```
 // ===============================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::adapters::CsvRows;
+    use super::*;
+    use crate::{ipa::Ipa, scheduler::ProveError, test_util::tiny_prove_fixture};
+
+    fn prove_csv(csv: &str) -> Result<Proof<Ipa>, ProveError> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("w.csv");
+        fs::write(&path, csv).unwrap();
+        prove_rows(&CsvRows::new_from_path(&path, 2).unwrap())
+    }
+
+    fn prove_rows(rows: &impl TryRestreamer<Item = Row>) -> Result<Proof<Ipa>, ProveError> {
+        let fx = tiny_prove_fixture(16);
+        scheduler::Prover::with_pcs(&fx.air, &fx.pp, Ipa).prove_with_restreamer(rows)
+    }
+
+    #[test]
+    fn csv_parse_errors_surface_as_witness_errors() {
+        let good: String = (0..12).map(|i| format!("{},{}\n", i + 1, 3 * i)).collect();
+        prove_csv(&good).unwrap();
+
+        let bad = good.replacen("6,15", "6,x15", 1);
+        match prove_csv(&bad) {
+            Err(ProveError::Witness(e @ WitnessError::Parse { .. })) => assert_eq!(e.row(), 5),
+            other => panic!("expected a witness parse error, got {other:?}"),
+        }
+        let short = good.replacen("6,15", "6", 1);
+        assert!(matches!(prove_csv(&short), Err(ProveError::Witness(e)) if e.row() == 5));
+
+        // Full-width values parse exactly; values ≥ r are rejected, not reduced.
+        let wide = good.replacen("6,15", &format!("0x{},-15", "f".repeat(40)), 1);
+        prove_csv(&wide).unwrap();
+        let over = good.replacen("6,15", &format!("6,{}", <F as ark_ff::PrimeField>::MODULUS), 1);
+        assert!(matches!(prove_csv(&over), Err(ProveError::Witness(e)) if e.row() == 5));
+    }
+
+    #[test]
+    fn short_rows_are_witness_errors() {
+        use super::adapters::VecRows;
+
+        let mut rows: Vec<Row> =
+            (0..12u64).map(|i| Row { regs: vec![F::from(i + 1), F::from(3 * i)].into_boxed_slice() }).collect();
+        rows[9].regs = vec![F::from(7u64)].into_boxed_slice();
+        match prove_rows(&VecRows(rows)) {
+            Err(ProveError::Witness(WitnessError::Width { row: 9, expected: 2, got: 1 })) => {}
+            other => panic!("expected a width error at row 9, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn generated_rows_replay_blocks_from_checkpoints() {
+        use super::adapters::{GeneratedRows, VecRows};
+        use crate::stream::Restreamer;
+        use std::cell::Cell;
+
+        // Fibonacci "VM": state (a, b), row [a, b], step (a, b) -> (b, a + b).
+        let steps = Cell::new(0usize);
+        let fib = |s: &mut (F, F), _i: usize| {
+            steps.set(steps.get() + 1);
+            let row = Row { regs: vec![s.0, s.1].into_boxed_slice() };
+            *s = (s.1, s.0 + s.1);
+            row
+        };
+        let gen = GeneratedRows::new((F::from(1u64), F::from(1u64)), 12, 4, fib);
+        let all = VecRows(gen.stream_rows(RowIdx(0), RowIdx(12)).collect());
+        assert_eq!((gen.checkpoints(), all.0[11].regs[0]), (3, F::from(144u64)));
+
+        for (s, e) in [(0, 4), (4, 8), (5, 11), (9, 20), (7, 7)] {
+            steps.set(0);
+            let got: Vec<Row> = gen.stream_rows(RowIdx(s), RowIdx(e)).collect();
+            let want = &all.0[s..e.min(12)];
+            assert!(got.len() == want.len() && got.iter().zip(want).all(|(g, w)| g.regs == w.regs), "[{s}, {e})");
+            // Replay starts at the checkpoint below `s`, never at row 0.
+            assert_eq!(steps.get(), if s == e.min(12) { 0 } else { e.min(12) - (s - s % 4) });
+        }
+
+        // Same witness, same proof as the materialized trace.
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        prove_rows(&gen).unwrap().serialize_compressed(&mut a).unwrap();
+        prove_rows(&all).unwrap().serialize_compressed(&mut b).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn csv_index_seeks_blocks_and_tracks_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("w.csv");
+        let csv_rows = |n: u64| -> String {
+            (0..n).map(|i| format!("{i}, {}\n{}", 2 * i, if i % 7 == 0 { "\n  \n" } else { "" })).collect()
+        };
+        let want = |i: usize| vec![F::from(i as u64), F::from(2 * i as u64)].into_boxed_slice();
+        let read = |rows: &CsvRows, s: usize, e: usize| -> Vec<Box<[F]>> {
+            rows.try_stream_rows(RowIdx(s), RowIdx(e)).map(|r| r.unwrap().regs).collect()
+        };
+        fs::write(&path, csv_rows(1000)).unwrap();
+
+        let rows = CsvRows::new_indexed(&path, 2, 16).unwrap();
+        assert_eq!(rows.len_rows(), 1000);
+        for (s, e) in [(0, 16), (32, 48), (5, 40), (984, 1000), (990, 1010)] {
+            assert_eq!(read(&rows, s, e), (s..e.min(1000)).map(want).collect::<Vec<_>>(), "[{s}, {e})");
+        }
+
+        // Reopening reuses the sidecar; a different stride or a changed file rebuilds it.
+        let idx_mtime = || fs::metadata(rows.index_path()).unwrap().modified().unwrap();
+        let built = idx_mtime();
+        CsvRows::new_indexed(&path, 2, 16).unwrap();
+        assert_eq!(idx_mtime(), built);
+        assert_eq!(CsvRows::new_indexed(&path, 2, 64).unwrap().len_rows(), 1000);
+
+        fs::write(&path, csv_rows(1030)).unwrap();
+        let rows = CsvRows::new_indexed(&path, 2, 16).unwrap();
+        assert_eq!(rows.len_rows(), 1030);
+        assert_eq!(read(&rows, 1024, 1030), (1024..1030).map(want).collect::<Vec<_>>());
+
+        // An index whose offsets drifted off line starts is refused, not misread.
+        let mut idx = fs::read(rows.index_path()).unwrap();
+        let at = 8 + 5 * 8 + 8; // offset of row 16
+        let off = u64::from_le_bytes(idx[at..at + 8].try_into().unwrap()) + 1;
+        idx[at..at + 8].copy_from_slice(&off.to_le_bytes());
+        fs::write(rows.index_path(), idx).unwrap();
+        let rows = CsvRows::new_indexed(&path, 2, 16).unwrap();
+        let err = rows.try_stream_rows(RowIdx(16), RowIdx(32)).next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("not at a line start"), "{err}");
+        assert_eq!(read(&rows, 0, 16), (0..16).map(want).collect::<Vec<_>>());
+
+        // Index and parser agree on what a blank line is: a vertical tab is
+        // not ASCII whitespace, so it is a (malformed) row to both.
+        fs::write(&path, "0, 0\n\x0b\n1, 2\n").unwrap();
+        let rows = CsvRows::new_indexed(&path, 2, 1).unwrap();
+        assert_eq!(rows.len_rows(), 3);
+        assert!(rows.try_stream_rows(RowIdx(1), RowIdx(2)).next().unwrap().is_err());
+        assert_eq!(read(&rows, 2, 3), vec![want(1)]);
+
+        // Stores leave no temp files behind.
+        let mut names: Vec<_> = fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        names.sort();
+        assert_eq!(names, ["w.csv", "w.csv.idx"]);
+    }
+}
@@ -17,9 +17,10 @@
 //! **Multi-party ceremony**: Requires ALL participants to collude to break security
 //! **This tool**: Requires YOU to be honest (don't save tau)
 //!
-//! For a system you control, this is **production-grade** security.
+//! For a system you control, this is **production-grade** security. To spread
+//! the trust, run a local multi-party ceremony with `srs_ceremony` instead.
 
-use ark_bn254::{Bn254, G2Affine};
+use myzkp::curve::{Engine, G2Affine};
 use ark_ec::{Group, pairing::Pairing};
 use ark_ff::UniformRand;
 use ark_serialize::CanonicalSerialize;
@@ -60,7 +61,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Generate random tau using OS entropy
     let mut rng = OsRng;
-    let tau = <Bn254 as Pairing>::ScalarField::rand(&mut rng);
+    let tau = <Engine as Pairing>::ScalarField::rand(&mut rng);
     
     println!("✓ Tau generated (will be destroyed after SRS generation)");
     println!();
@@ -69,9 +70,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔄 Generating {} G1 powers...", max_degree + 1);
     println!("   This may take 30-60 seconds...");
     
-    let g1_gen = <Bn254 as Pairing>::G1::generator();
-    let mut g1_powers: Vec<<Bn254 as Pairing>::G1Affine> = Vec::with_capacity(max_degree + 1);
-    let mut tau_pow = <Bn254 as Pairing>::ScalarField::from(1u64); // Start with τ⁰ = 1
+    let g1_gen = <Engine as Pairing>::G1::generator();
+    let mut g1_powers: Vec<<Engine as Pairing>::G1Affine> = Vec::with_capacity(max_degree + 1);
+    let mut tau_pow = <Engine as Pairing>::ScalarField::from(1u64); // Start with τ⁰ = 1
     
     for i in 0..=max_degree {
         if i % 10000 == 0 && i > 0 {
@@ -86,15 +87,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Generate G2 element: [τ·G2]
     println!("🔄 Generating G2 element...");
-    let g2_gen = <Bn254 as Pairing>::G2::generator();
+    let g2_gen = <Engine as Pairing>::G2::generator();
     let tau_g2: G2Affine = (g2_gen * tau).into();
     println!("✓ Generated τ·G2");
     println!();
 
     // Tau is about to go out of scope and be destroyed!
-    // We explicitly drop it here for clarity
-    drop(tau);
-    drop(tau_pow);
+    // Field elements are `Copy`, so there is nothing to `drop`; we simply stop
+    // using them from here on.
+    let _ = (tau, tau_pow);
     println!("🔒 Tau destroyed (no longer in memory)");
     println!();
 
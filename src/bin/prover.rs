@@ -9,6 +9,38 @@
 //! - **Robust CSV selectors** loader: ragged-row detection, comments, clearer errors.
 //! - **Production SRS validation**: uses `srs_setup` module for comprehensive validation.
 //! - Human-friendly diagnostics: domain/SRS digests and header summary.
+//! - `--pcs kzg|fri|ipa` selects the commitment backend. FRI and IPA are
+//!   transparent, so no SRS is loaded or required. `--fri-blowup-log`,
+//!   `--fri-queries` and `--fri-final-len` override `fri::Fri::default()`;
+//!   the proof header carries the choice for the verifier.
+//! - BLS12-381 builds (`--features bls12-381`) accept
+//!   `--srs-eth-json <transcript.json>` from the Ethereum KZG ceremony.
+//! - `--fs-hash blake3|keccak256|poseidon` selects the transcript hash (default blake3).
+//! - `--trace <file>` writes the prover's Fiat–Shamir schedule as JSON.
+//! - Non-dev builds refuse an SRS whose digests match no known ceremony
+//!   (`srs_setup::KNOWN_CEREMONIES` or `$SSZKP_CEREMONY_REGISTRY`) unless
+//!   `--allow-unknown-srs` or `SSZKP_ALLOW_UNKNOWN_SRS=1` is given.
+//! - `--emit-evm <dir>` (KZG + keccak256, BN254) also writes `Verifier.sol`,
+//!   its compiler-free equivalent `Verifier.bin` (creation bytecode, hex) and
+//!   `calldata.hex` for on-chain verification (see `myzkp::evm`).
+//! - Blocked IFFTs follow `--transform memory|tape|auto[:N]` (default: the
+//!   legacy `SSZKP_BLOCKED_IFFT` switch), with `--spill-dir`,
+//!   `--max-spill-bytes`, `--encrypt-spill` and
+//!   `--spill-cleanup drop|unlink|keep` (see `domain::TransformConfig`).
+//! - `--lagrange-dir <dir>` (KZG) attaches `<dir>/lagrange-<N>.srs` from
+//!   `srs_convert lagrange`, so evaluation-basis wires commit without a
+//!   blocked IFFT; a key derived from another SRS or ω is refused.
+//! - `--zh-c`, `--omega` and `--selectors` values are full-width scalars:
+//!   decimal or `0x` hex, optionally signed (`-x` = r − x); values ≥ r are
+//!   rejected (see `curve::parse_scalar`).
+//! - `--rows` pads to the nearest supported smooth domain size
+//!   (`domain::next_smooth_size`), not the next power of two.
+//! - `prover convert-witness --csv <in> --out <file> --k <k>
+//!   [--layout rows|columns[:B]] [--names a,b,c]` converts a CSV witness into
+//!   the binary format read by `witness::WitnessFile`; with
+//!   `--compress zstd|gzip [--frame-rows B]` it writes a framed compressed
+//!   container instead (`witness_frames::FramedWitness`, one frame per `B`
+//!   rows; match the prover's `--b-blk`).
 
 #![forbid(unsafe_code)]
 #![allow(unused_variables)]
@@ -21,10 +53,14 @@ use ark_ff::{fields::Field, FftField, One, Zero};
 use ark_serialize::CanonicalSerialize;
 use myzkp::{
     air::{AirSpec, Row},
-    domain::{self, domain_digest},
-    pcs::{self, Basis, PcsParams},
+    curve::parse_scalar,
+    domain::{self, domain_digest, TransformConfig},
+    fri::Fri,
+    ipa::Ipa,
+    pcs::{self, Basis, PcsKind, PcsParams, PolynomialCommitment, Srs},
     scheduler::Prover,
-    F, ProveParams,
+    transcript::FsHash,
+    F, Proof, ProveParams,
 };
 
 /// 8-byte magic: "SSZKPv2" + NUL terminator to match the 8-byte read/write.
@@ -40,24 +76,31 @@ fn parse_flag(args: &[String], key: &str) -> Option<String> {
     }
     None
 }
+/// Blocked-IFFT backend and spill policy from the `--transform` family of flags.
+fn parse_transform(args: &[String]) -> anyhow::Result<TransformConfig> {
+    let mut cfg = TransformConfig::from_env();
+    if let Some(b) = parse_flag(args, "--transform") {
+        cfg = cfg.with_backend(b.parse().map_err(|e| anyhow::anyhow!("--transform: {e}"))?);
+    }
+    if let Some(dir) = parse_flag(args, "--spill-dir") {
+        cfg = cfg.with_spill_dir(dir);
+    }
+    if let Some(s) = parse_flag(args, "--max-spill-bytes") {
+        let limit = parse_u64(&s).ok_or_else(|| anyhow::anyhow!("--max-spill-bytes must be a u64"))?;
+        cfg = cfg.with_max_spill_bytes(limit);
+    }
+    if let Some(c) = parse_flag(args, "--spill-cleanup") {
+        cfg = cfg.with_cleanup(c.parse().map_err(|e| anyhow::anyhow!("--spill-cleanup: {e}"))?);
+    }
+    Ok(cfg.with_encrypt_spill(args.iter().any(|a| a == "--encrypt-spill")))
+}
+
 fn parse_bool(s: &str) -> bool {
     matches!(s, "1" | "true" | "True" | "TRUE" | "yes" | "y")
 }
 fn parse_u64(s: &str) -> Option<u64> {
     s.parse::<u64>().ok()
 }
-fn next_power_of_two(mut n: usize) -> usize {
-    if n == 0 { return 1; }
-    n -= 1;
-    n |= n >> 1;
-    n |= n >> 2;
-    n |= n >> 4;
-    n |= n >> 8;
-    n |= n >> 16;
-    n |= n >> 32;
-    n + 1
-}
-
 /// Fast pow: exponentiation-by-squaring on the field.
 #[inline]
 fn pow_u64(mut base: F, mut exp: u64) -> F {
@@ -97,8 +140,8 @@ fn load_selectors_csv(path: &Path) -> anyhow::Result<Vec<Box<[F]>>> {
             .filter(|t| !t.is_empty())
             .enumerate()
         {
-            // Accept decimal u128; callers can pre-scale if needed.
-            let v = tok.parse::<u128>().map_err(|e| {
+            // Full-width scalar: decimal, 0x-hex, optionally signed (see `curve::parse_scalar`).
+            let v = parse_scalar(tok).map_err(|e| {
                 anyhow::anyhow!(
                     "selectors parse error at line {}, column {}: token `{}` ({})",
                     lineno + 1,
@@ -107,7 +150,7 @@ fn load_selectors_csv(path: &Path) -> anyhow::Result<Vec<Box<[F]>>> {
                     e
                 )
             })?;
-            row_vals.push(F::from(v as u64));
+            row_vals.push(v);
         }
         if !row_vals.is_empty() {
             rows.push(row_vals);
@@ -144,7 +187,7 @@ fn load_selectors_csv(path: &Path) -> anyhow::Result<Vec<Box<[F]>>> {
     Ok(cols.into_iter().map(|v| v.into_boxed_slice()).collect())
 }
 
-/// Minimal ω sanity check for power-of-two N (fast pow).
+/// Minimal ω sanity check for a (mixed-radix) N (fast pow).
 fn validate_domain_params(n: usize, omega: F, zh_c: F) -> anyhow::Result<()> {
     if n == 0 {
         return Err(anyhow::anyhow!("domain size N must be positive"));
@@ -156,15 +199,269 @@ fn validate_domain_params(n: usize, omega: F, zh_c: F) -> anyhow::Result<()> {
     if pow_u64(omega, n as u64) != F::one() {
         return Err(anyhow::anyhow!("omega^N != 1; invalid subgroup generator"));
     }
-    // ω is primitive: ω^{N/2} != 1 (only meaningful if N >= 2)
-    if n >= 2 && pow_u64(omega, (n as u64) / 2) == F::one() {
-        return Err(anyhow::anyhow!("omega does not have exact order N (omega^(N/2) == 1)"));
+    // ω is primitive: ω^{N/p} != 1 for each radix p | N
+    for p in domain::SMOOTH_RADICES.into_iter().filter(|p| n.is_multiple_of(*p)) {
+        if pow_u64(omega, (n / p) as u64) == F::one() {
+            return Err(anyhow::anyhow!("omega does not have exact order N (omega^(N/{p}) == 1)"));
+        }
+    }
+    Ok(())
+}
+
+/// Prove with the given backend and return the ark-compressed `Proof` payload.
+fn prove_and_serialize<P: PolynomialCommitment>(
+    prover: Prover<'_, P>,
+    witness_rows: &Vec<Row>,
+    trace_path: Option<&str>,
+) -> anyhow::Result<Vec<u8>> {
+    let proof: Proof<P> = match trace_path {
+        Some(path) => {
+            let (proof, trace) = prover
+                .prove_with_trace(witness_rows)
+                .map_err(|e| anyhow::anyhow!("prover failed: {e}"))?;
+            fs::write(path, trace.to_json()).map_err(|e| anyhow::anyhow!("write {path}: {e}"))?;
+            eprintln!("✓ Wrote transcript trace to {path} ({} events)", trace.events.len());
+            proof
+        }
+        None => prover
+            .prove_with_restreamer(witness_rows)
+            .map_err(|e| anyhow::anyhow!("prover failed: {e}"))?,
+    };
+
+    // Header quick summary for humans (single concise line).
+    eprintln!(
+        "✓ Proof generated: N={}, k={}, ω^N=1 ✓, ω^(N/2)≠1 ✓, zh_c={}, basis_wires={:?}, pcs={}, curve={}, fs_hash={}",
+        proof.header.domain_n,
+        proof.header.k,
+        proof.header.zh_c,
+        proof.header.basis_wires,
+        proof.header.pcs.as_str(),
+        proof.header.curve.as_str(),
+        proof.header.fs_hash.as_str()
+    );
+
+    // --- Emit versioned proof file (magic + version + ark-compressed Proof) ---
+    let mut payload = Vec::new();
+    proof.serialize_compressed(&mut payload)
+        .map_err(|e| anyhow::anyhow!("serialize proof: {e}"))?;
+    Ok(payload)
+}
+
+/// Build the SRS from an Ethereum KZG ceremony `transcript.json` (BLS12-381 builds).
+#[cfg(feature = "bls12-381")]
+fn load_eth_ceremony(path: &str, degree: usize) -> anyhow::Result<Srs> {
+    eprintln!("Loading Ethereum KZG ceremony transcript from {}...", path);
+    let (g1_powers, tau_g2) = myzkp::srs_setup::load_ethereum_ceremony_json(path, degree)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate ceremony transcript: {}", e))?;
+    eprintln!("✓ Loaded and validated {} G1 powers and [τ]G₂", g1_powers.len());
+    Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("ceremony transcript: {e}"))
+}
+
+#[cfg(not(feature = "bls12-381"))]
+fn load_eth_ceremony(_path: &str, _degree: usize) -> anyhow::Result<Srs> {
+    Err(anyhow::anyhow!("--srs-eth-json needs a BLS12-381 build (--features bls12-381)"))
+}
+
+/// The KZG SRS for `degree`: `--srs-eth-json`, `--srs-g1` + `--srs-g2`, or
+/// (dev builds only) the deterministic dev SRS when neither is given.
+fn load_srs(args: &[String], degree: usize) -> anyhow::Result<Srs> {
+    if let Some(p) = parse_flag(args, "--srs-eth-json") {
+        return load_eth_ceremony(&p, degree);
+    }
+
+    let (g1_path_str, g2_path_str) = match (parse_flag(args, "--srs-g1"), parse_flag(args, "--srs-g2")) {
+        (Some(g1), Some(g2)) => (g1, g2),
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(anyhow::anyhow!("--srs-g1 and --srs-g2 must be given together"));
+        }
+        #[cfg(feature = "dev-srs")]
+        (None, None) => {
+            eprintln!("(dev-srs) Using deterministic in-crate SRS.");
+            eprintln!("⚠️  WARNING: Dev SRS is NOT SECURE - for testing only!");
+            eprintln!("    For production, pass --srs-g1 and --srs-g2 with trusted ceremony files.");
+            let (g1_powers, tau_g2) = myzkp::srs_setup::generate_dev_srs(degree);
+            return Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("dev SRS: {e}"));
+        }
+        #[cfg(not(feature = "dev-srs"))]
+        (None, None) => {
+            return Err(anyhow::anyhow!(
+                "Non-dev build: --srs-g1 and --srs-g2 (or --srs-eth-json) are REQUIRED for trusted KZG.\n\
+                 \n\
+                 For development, rebuild with --features dev-srs.\n\
+                 For production, provide SRS files from a trusted ceremony:\n\
+                 https://github.com/privacy-scaling-explorations/perpetualpowersoftau"
+            ));
+        }
+    };
+
+    let g1_path = Path::new(&g1_path_str);
+    eprintln!("Loading G1 SRS from {}...", g1_path.display());
+    let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(g1_path, degree)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate G1 SRS: {}", e))?;
+    eprintln!("✓ Loaded and validated {} G1 powers", g1_powers.len());
+
+    let g2_path = Path::new(&g2_path_str);
+    eprintln!("Loading G2 SRS from {}...", g2_path.display());
+    let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(g2_path)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate G2 SRS: {}", e))?;
+    eprintln!("✓ Loaded and validated G2 element ([τ]G₂)");
+
+    // Optional: cryptographic pairing check (expensive but recommended for first use)
+    if std::env::var("SSZKP_VALIDATE_PAIRING").ok().as_deref() == Some("1") {
+        eprintln!("Performing cryptographic pairing check (this may take ~100ms)...");
+        myzkp::srs_setup::validate_g1_pairing(&g1_powers, tau_g2)
+            .map_err(|e| anyhow::anyhow!("Pairing validation failed: {}", e))?;
+        eprintln!("✓ Pairing check passed - SRS is algebraically consistent");
+    }
+
+    Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("SRS: {e}"))
+}
+
+/// Write `Verifier.sol`, `Verifier.bin` and `calldata.hex` for a KZG/Keccak proof payload.
+#[cfg(not(feature = "bls12-381"))]
+fn emit_evm(dir: &str, payload: &[u8], srs: &Srs) -> anyhow::Result<()> {
+    use ark_serialize::CanonicalDeserialize;
+    use myzkp::evm;
+
+    let proof: Proof = CanonicalDeserialize::deserialize_compressed(payload)
+        .map_err(|e| anyhow::anyhow!("re-read proof: {e}"))?;
+    let vk = evm::EvmVerifyingKey::from_srs(proof.header.clone(), proof.z_comm.is_some(), srs)
+        .map_err(|e| anyhow::anyhow!("--emit-evm: {e}"))?;
+    let calldata = evm::encode_calldata(&vk, &proof).map_err(|e| anyhow::anyhow!("--emit-evm: {e}"))?;
+
+    fs::create_dir_all(dir).map_err(|e| anyhow::anyhow!("create {dir}: {e}"))?;
+    let sol = Path::new(dir).join("Verifier.sol");
+    let bin = Path::new(dir).join("Verifier.bin");
+    let cd = Path::new(dir).join("calldata.hex");
+    fs::write(&sol, evm::generate_solidity(&vk))?;
+    fs::write(&bin, hex::encode(evm::deploy_code(&evm::generate_bytecode(&vk))))?;
+    fs::write(&cd, evm::calldata_hex(&calldata))?;
+    eprintln!(
+        "✓ Wrote {}, {} and {} ({} bytes calldata)",
+        sol.display(),
+        bin.display(),
+        cd.display(),
+        calldata.len()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "bls12-381")]
+fn emit_evm(_dir: &str, _payload: &[u8], _srs: &Srs) -> anyhow::Result<()> {
+    Err(anyhow::anyhow!("--emit-evm needs a BN254 build (the EVM has no BLS12-381 pairing precompile)"))
+}
+
+/// `convert-witness`: stream a CSV witness into a binary witness file, or a
+/// framed compressed container with `--compress`.
+fn convert_witness(args: &[String]) -> anyhow::Result<()> {
+    use myzkp::{
+        api::adapters::CsvRows,
+        stream::{RowIdx, TryRestreamer},
+        witness::{WitnessLayout, WitnessWriter},
+        witness_frames::{FramedWitnessWriter, WitnessCodec},
+    };
+
+    const CHUNK: usize = 1 << 12;
+    let csv = parse_flag(args, "--csv").ok_or_else(|| anyhow::anyhow!("convert-witness needs --csv <file>"))?;
+    let out = parse_flag(args, "--out").ok_or_else(|| anyhow::anyhow!("convert-witness needs --out <file>"))?;
+    let k: usize = parse_flag(args, "--k")
+        .ok_or_else(|| anyhow::anyhow!("convert-witness needs --k <registers>"))?
+        .parse()
+        .map_err(|_| anyhow::anyhow!("--k must be a positive integer"))?;
+    let names: Option<Vec<String>> =
+        parse_flag(args, "--names").map(|n| n.split(',').map(|s| s.trim().to_string()).collect());
+
+    let rows = CsvRows::new_indexed(&csv, k, CHUNK)?;
+    let each_row = |push: &mut dyn FnMut(&[F]) -> std::io::Result<()>| -> anyhow::Result<()> {
+        for start in (0..rows.len_rows()).step_by(CHUNK) {
+            for row in rows.try_stream_rows(RowIdx(start), RowIdx(start + CHUNK)) {
+                push(&row?.regs)?;
+            }
+        }
+        Ok(())
+    };
+
+    if let Some(codec) = parse_flag(args, "--compress") {
+        if parse_flag(args, "--layout").is_some() {
+            anyhow::bail!("--layout does not apply to --compress output (frames are row-major)");
+        }
+        let codec: WitnessCodec = codec.parse().map_err(|e| anyhow::anyhow!("--compress: {e}"))?;
+        let frame_rows: usize = parse_flag(args, "--frame-rows")
+            .map(|s| s.parse().map_err(|_| anyhow::anyhow!("--frame-rows must be a positive integer")))
+            .transpose()?
+            .unwrap_or(128);
+        let mut w = FramedWitnessWriter::create(&out, k, codec, frame_rows)?;
+        if let Some(names) = names {
+            w = w.with_column_names(names)?;
+        }
+        each_row(&mut |r| w.push_row(r))?;
+        let header = w.finish()?;
+        println!(
+            "wrote {out}: {} rows × k={} ({} frames of {} rows, {}, {}), checksum {}",
+            header.rows,
+            header.k,
+            header.frames(),
+            header.frame_rows,
+            header.codec.as_str(),
+            header.curve.as_str(),
+            hex::encode(header.checksum)
+        );
+        return Ok(());
+    }
+
+    let layout: WitnessLayout = parse_flag(args, "--layout")
+        .unwrap_or_else(|| "columns".into())
+        .parse()
+        .map_err(|e| anyhow::anyhow!("--layout: {e}"))?;
+    let mut w = WitnessWriter::create(&out, k, layout)?;
+    if let Some(names) = names {
+        w = w.with_column_names(names)?;
     }
+    each_row(&mut |r| w.push_row(r))?;
+    let header = w.finish()?;
+    println!(
+        "wrote {out}: {} rows × k={} ({:?}, {}), checksum {}",
+        header.rows,
+        header.k,
+        header.layout,
+        header.curve.as_str(),
+        hex::encode(header.checksum)
+    );
     Ok(())
 }
 
+/// FRI parameters from `--fri-blowup-log`, `--fri-queries` and `--fri-final-len`.
+fn fri_params(args: &[String]) -> anyhow::Result<Fri> {
+    fn num<T: std::str::FromStr>(args: &[String], key: &str, default: T) -> anyhow::Result<T> {
+        match parse_flag(args, key) {
+            Some(s) => s.parse().map_err(|_| anyhow::anyhow!("{key}: expected a positive integer, got {s:?}")),
+            None => Ok(default),
+        }
+    }
+    let d = Fri::default();
+    let fri = Fri {
+        blowup_log: num(args, "--fri-blowup-log", d.blowup_log)?,
+        num_queries: num(args, "--fri-queries", d.num_queries)?,
+        final_len: num(args, "--fri-final-len", d.final_len)?,
+    };
+    if fri.blowup_log == 0 || fri.num_queries == 0 || fri.final_len == 0 {
+        return Err(anyhow::anyhow!("FRI parameters must be positive"));
+    }
+    if fri.security_bits() < d.security_bits() {
+        eprintln!(
+            "⚠️  FRI parameters give {} conjectured bits; default verifiers require {}.",
+            fri.security_bits(),
+            d.security_bits()
+        );
+    }
+    Ok(fri)
+}
+
 fn main() -> anyhow::Result<()> {
     let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("convert-witness") {
+        return convert_witness(&args[2..]);
+    }
 
     let n_rows: usize = parse_flag(&args, "--rows").and_then(|s| s.parse().ok()).unwrap_or(1024);
     let b_blk: usize = parse_flag(&args, "--b-blk").and_then(|s| s.parse().ok()).unwrap_or(128);
@@ -175,14 +472,24 @@ fn main() -> anyhow::Result<()> {
         _ => Basis::Evaluation,
     };
     let commit_z = parse_flag(&args, "--commit-z").map(|s| parse_bool(&s)).unwrap_or(true);
+    let pcs_kind: PcsKind = parse_flag(&args, "--pcs")
+        .unwrap_or_else(|| "kzg".into())
+        .parse()
+        .map_err(|e| anyhow::anyhow!("--pcs: {e}"))?;
+    let fs_hash: FsHash = parse_flag(&args, "--fs-hash")
+        .unwrap_or_else(|| "blake3".into())
+        .parse()
+        .map_err(|e| anyhow::anyhow!("--fs-hash: {e}"))?;
+    let emit_evm_dir = parse_flag(&args, "--emit-evm");
+    let trace_path = parse_flag(&args, "--trace");
+    let transform = parse_transform(&args)?;
+    if emit_evm_dir.is_some() && (pcs_kind != PcsKind::Kzg || fs_hash != FsHash::Keccak256) {
+        return Err(anyhow::anyhow!("--emit-evm requires --pcs kzg --fs-hash keccak256"));
+    }
 
     // CLI-selectable Z_H(X)=X^N − zh_c (default 1)
     let zh_c_str = parse_flag(&args, "--zh-c").unwrap_or_else(|| "1".into());
-    let zh_c = F::from(
-        zh_c_str
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("--zh-c must be a u64 (got `{}`)", zh_c_str))?,
-    );
+    let zh_c = parse_scalar(&zh_c_str).map_err(|e| anyhow::anyhow!("--zh-c: {e}"))?;
 
     // Optional: load selector/fixed columns
     let selectors: Vec<Box<[F]>> = if let Some(p) = parse_flag(&args, "--selectors") {
@@ -196,13 +503,16 @@ fn main() -> anyhow::Result<()> {
     };
 
     // --- Domain (with optional omega) ---
-    let n_domain = next_power_of_two(n_rows);
-    let omega_override = parse_flag(&args, "--omega").and_then(|s| parse_u64(&s));
-
-    let omega = if let Some(u) = omega_override {
-        F::from(u)
+    let n_domain = domain::next_smooth_size(n_rows)
+        .ok_or_else(|| anyhow::anyhow!("no supported domain size for {n_rows} rows"))?;
+    let omega_override = parse_flag(&args, "--omega")
+        .map(|s| parse_scalar(&s).map_err(|e| anyhow::anyhow!("--omega: {e}")))
+        .transpose()?;
+
+    let omega = if let Some(w) = omega_override {
+        w
     } else {
-        F::get_root_of_unity(n_domain as u64)
+        domain::root_of_unity(n_domain)
             .expect("field does not support an N-th root of unity for this N")
     };
 
@@ -216,84 +526,51 @@ fn main() -> anyhow::Result<()> {
     // SRS loading with comprehensive validation
     // ============================================================================
     
-    let srs_g1_path = parse_flag(&args, "--srs-g1");
-    let srs_g2_path = parse_flag(&args, "--srs-g2");
-
-    #[cfg(feature = "dev-srs")]
-    {
-        if srs_g1_path.is_none() || srs_g2_path.is_none() {
-            eprintln!("(dev-srs) Using deterministic in-crate SRS.");
-            eprintln!("⚠️  WARNING: Dev SRS is NOT SECURE - for testing only!");
-            eprintln!("    For production, pass --srs-g1 and --srs-g2 with trusted ceremony files.");
-        }
-    }
-
-    #[cfg(not(feature = "dev-srs"))]
-    {
-        if srs_g1_path.is_none() || srs_g2_path.is_none() {
-            return Err(anyhow::anyhow!(
-                "Non-dev build: --srs-g1 and --srs-g2 are REQUIRED for trusted KZG verification.\n\
-                 \n\
-                 For development, rebuild with --features dev-srs.\n\
-                 For production, provide SRS files from a trusted ceremony:\n\
-                 https://github.com/privacy-scaling-explorations/perpetualpowersoftau"
-            ));
-        }
-    }
-
-    // Load and validate SRS files (if provided)
-    if let Some(g1_path_str) = srs_g1_path {
-        let g1_path = Path::new(&g1_path_str);
-        eprintln!("Loading G1 SRS from {}...", g1_path.display());
-
-        let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(g1_path, n_domain - 1)
-            .map_err(|e| anyhow::anyhow!("Failed to load/validate G1 SRS: {}", e))?;
-
-        pcs::load_srs_g1(&g1_powers);
-        eprintln!("✓ Loaded and validated {} G1 powers", g1_powers.len());
-
-        // Optional: cryptographic pairing check (expensive but recommended for first use)
-        if std::env::var("SSZKP_VALIDATE_PAIRING").ok().as_deref() == Some("1") {
-            eprintln!("Performing cryptographic pairing check (this may take ~100ms)...");
-            
-            // Need G2 for pairing check
-            if let Some(g2_path_str) = &srs_g2_path {
-                let g2_path = Path::new(g2_path_str);
-                let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(g2_path)
-                    .map_err(|e| anyhow::anyhow!("Failed to load G2 for pairing check: {}", e))?;
-                
-                myzkp::srs_setup::validate_g1_pairing(&g1_powers, tau_g2)
-                    .map_err(|e| anyhow::anyhow!("Pairing validation failed: {}", e))?;
-                
-                eprintln!("✓ Pairing check passed - SRS is algebraically consistent");
+    let srs = if pcs_kind != PcsKind::Kzg {
+        eprintln!("Using transparent {} backend (no SRS).", pcs_kind.as_str());
+        eprintln!("  Domain digest: {:02x?}", dom_digest);
+        eprintln!();
+        None
+    } else {
+        let mut srs = load_srs(&args, n_domain - 1)?;
+        if let Some(dir) = parse_flag(&args, "--lagrange-dir") {
+            let path = Path::new(&dir).join(pcs::LagrangeKey::file_name(n_domain));
+            if path.exists() {
+                srs = srs
+                    .with_lagrange_file(&path, &domain)
+                    .map_err(|e| anyhow::anyhow!("Lagrange key {}: {e}", path.display()))?;
+                eprintln!("✓ Attached Lagrange key {}", path.display());
+            } else {
+                eprintln!("Note: {} not found; wires commit via the blocked IFFT.", path.display());
             }
         }
-    }
-
-    if let Some(g2_path_str) = srs_g2_path {
-        let g2_path = Path::new(&g2_path_str);
-        eprintln!("Loading G2 SRS from {}...", g2_path.display());
 
-        let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(g2_path)
-            .map_err(|e| anyhow::anyhow!("Failed to load/validate G2 SRS: {}", e))?;
-
-        pcs::load_srs_g2(tau_g2);
-        eprintln!("✓ Loaded and validated G2 element ([τ]G₂)");
-    }
-
-    // Compute and display SRS digests for audit trail
-    let srs_g1_d = pcs::srs_g1_digest();
-    let srs_g2_d = pcs::srs_g2_digest();
-    
-    eprintln!();
-    eprintln!("Cryptographic parameters:");
-    eprintln!("  Domain digest: {:02x?}", dom_digest);
-    eprintln!("  SRS G1 digest: {:02x?}", srs_g1_d);
-    eprintln!("  SRS G2 digest: {:02x?}", srs_g2_d);
-    eprintln!();
-    eprintln!("Note: These digests will be embedded in the proof header.");
-    eprintln!("      Verifiers must use the same SRS to validate the proof.");
-    eprintln!();
+        // Compute and display SRS digests for audit trail. The header binds
+        // only the first N powers; the registry matches on prefixes too.
+        let srs_g2_d = srs.g2_digest();
+        let header_g1_d = srs.g1_prefix_digest(n_domain - 1);
+
+        eprintln!();
+        eprintln!("Cryptographic parameters:");
+        eprintln!("  Domain digest: {:02x?}", dom_digest);
+        eprintln!("  SRS G1 digest: {:02x?} (first {} powers)", header_g1_d, n_domain);
+        eprintln!("  SRS G2 digest: {:02x?}", srs_g2_d);
+
+        // Production builds only accept ceremonies in the registry (once it has any).
+        let allow_unknown = cfg!(feature = "dev-srs")
+            || args.iter().any(|a| a == "--allow-unknown-srs")
+            || myzkp::srs_setup::allow_unknown_srs_from_env();
+        match myzkp::srs_setup::require_known_ceremony(&srs, n_domain - 1, allow_unknown) {
+            Ok(Some(c)) => eprintln!("  Ceremony:      {}", c.name),
+            Ok(None) => eprintln!("  Ceremony:      unknown (not in registry)"),
+            Err(e) => return Err(anyhow::anyhow!("Refusing SRS: {}", e)),
+        }
+        eprintln!();
+        eprintln!("Note: These digests will be embedded in the proof header.");
+        eprintln!("      Verifiers need an SRS from the same ceremony with at least {} powers.", n_domain);
+        eprintln!();
+        Some(srs)
+    };
 
     // ============================================================================
     // Build AIR, PCS params, and generate proof
@@ -302,18 +579,18 @@ fn main() -> anyhow::Result<()> {
     let air = AirSpec { k: k_regs, id_table: Vec::new(), sigma_table: Vec::new(), selectors };
 
     // Keep PCS shapes identical to previous build; wires basis selectable.
-    let pcs_wires = PcsParams { max_degree: n_domain - 1, basis: basis_wires, srs_placeholder: () };
-    let pcs_coeff = PcsParams { max_degree: n_domain - 1, basis: Basis::Coefficient, srs_placeholder: () };
+    let pcs_wires = PcsParams { max_degree: n_domain - 1, basis: basis_wires, srs: srs.clone() };
+    let pcs_coeff = PcsParams { max_degree: n_domain - 1, basis: Basis::Coefficient, srs: srs.clone() };
 
-    let prove_params = ProveParams { domain: domain.clone(), pcs_wires, pcs_coeff, b_blk };
+    let prove_params = ProveParams { domain: domain.clone(), pcs_wires, pcs_coeff, b_blk, transform };
 
     // Non-trivial witness (deterministic)
     let witness_rows: Vec<Row> = (0..n_rows)
         .map(|i| {
             let mut regs = vec![F::from(0u64); k_regs];
             let base = F::from((i as u64) + 1);
-            for m in 0..k_regs {
-                regs[m] = base.pow([(m as u64) + 1]);
+            for (m, r) in regs.iter_mut().enumerate() {
+                *r = base.pow([(m as u64) + 1]);
             }
             Row { regs: regs.into_boxed_slice() }
         })
@@ -321,20 +598,23 @@ fn main() -> anyhow::Result<()> {
 
     // --- Run scheduler.Prover (restreaming path kept intact) ---
     eprintln!("Generating proof...");
-    let prover = Prover { air: &air, params: &prove_params };
-    let proof = prover.prove_with_restreamer(&witness_rows)
-        .map_err(|e| anyhow::anyhow!("prover failed: {e}"))?;
-
-    // Header quick summary for humans (single concise line).
-    eprintln!(
-        "✓ Proof generated: N={}, k={}, ω^N=1 ✓, ω^(N/2)≠1 ✓, zh_c={}, basis_wires={:?}",
-        proof.header.domain_n, proof.header.k, proof.header.zh_c, proof.header.basis_wires
-    );
-
-    // --- Emit versioned proof file (magic + version + ark-compressed Proof) ---
-    let mut payload = Vec::new();
-    proof.serialize_compressed(&mut payload)
-        .map_err(|e| anyhow::anyhow!("serialize proof: {e}"))?;
+    let payload = match pcs_kind {
+        PcsKind::Kzg => prove_and_serialize(
+            Prover::new(&air, &prove_params).with_fs_hash(fs_hash),
+            &witness_rows,
+            trace_path.as_deref(),
+        )?,
+        PcsKind::Fri => prove_and_serialize(
+            Prover::with_pcs(&air, &prove_params, fri_params(&args)?).with_fs_hash(fs_hash),
+            &witness_rows,
+            trace_path.as_deref(),
+        )?,
+        PcsKind::Ipa => prove_and_serialize(
+            Prover::with_pcs(&air, &prove_params, Ipa).with_fs_hash(fs_hash),
+            &witness_rows,
+            trace_path.as_deref(),
+        )?,
+    };
 
     let mut f = fs::File::create("proof.bin").map_err(|e| anyhow::anyhow!("create proof.bin: {e}"))?;
     f.write_all(FILE_MAGIC)?;
@@ -344,9 +624,16 @@ fn main() -> anyhow::Result<()> {
 
     eprintln!();
     eprintln!("✓ Wrote proof.bin (v{}, {} bytes payload)", FILE_VERSION, payload.len());
+    if let (Some(dir), Some(srs)) = (&emit_evm_dir, &srs) {
+        emit_evm(dir, &payload, srs)?;
+    }
     eprintln!();
     eprintln!("To verify this proof, run:");
-    eprintln!("  cargo run --bin verifier -- --srs-g1 <G1.bin> --srs-g2 <G2.bin>");
+    if pcs_kind != PcsKind::Kzg {
+        eprintln!("  cargo run --bin verifier");
+    } else {
+        eprintln!("  cargo run --bin verifier -- --srs-g1 <G1.bin> --srs-g2 <G2.bin>");
+    }
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Local multi-party SRS ceremony (see `myzkp::ceremony`)
+//!
+//! Usage:
+//! ```text
+//! srs_ceremony init <dir> <max_degree>
+//! srs_ceremony contribute <in_dir> <out_dir> --name NAME [--entropy TEXT]
+//! srs_ceremony verify <dir>
+//! ```
+//!
+//! The coordinator runs `init` once and hands the directory to the first
+//! participant. Each participant runs `contribute` on the directory they
+//! received and passes `out_dir` on; anyone can `verify` any directory.
+//! `verify` prints a ceremony registry entry for the result (see
+//! `srs_setup::CEREMONY_REGISTRY_ENV`).
+//!
+//! The secret is drawn from the OS RNG, mixed with `--entropy` if given, and
+//! never written anywhere.
+
+#![forbid(unsafe_code)]
+
+use std::{borrow::Cow, env};
+
+use myzkp::{ceremony, srs_setup::CeremonyDigests};
+use rand::{rngs::OsRng, rngs::StdRng, RngCore, SeedableRng};
+
+const USAGE: &str = "usage:\n  srs_ceremony init <dir> <max_degree>\n  srs_ceremony contribute <in_dir> <out_dir> --name NAME [--entropy TEXT]\n  srs_ceremony verify <dir>";
+
+fn init(args: &[String]) -> anyhow::Result<()> {
+    let [dir, max_degree] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let max_degree: usize = max_degree.parse()?;
+    ceremony::init(dir, max_degree)?;
+    eprintln!("✓ Initialized degree-{max_degree} ceremony in {dir} (τ = 1, needs contributions)");
+    Ok(())
+}
+
+fn contribute(args: &[String]) -> anyhow::Result<()> {
+    let [in_dir, out_dir, rest @ ..] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let mut name = None;
+    let mut entropy = String::new();
+    let mut it = rest.iter();
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().ok_or_else(|| anyhow::anyhow!("{flag} needs a value"));
+        match flag.as_str() {
+            "--name" => name = Some(value()?.clone()),
+            "--entropy" => entropy = value()?.clone(),
+            other => return Err(anyhow::anyhow!("unknown flag {other}\n{USAGE}")),
+        }
+    }
+    let name = name.ok_or_else(|| anyhow::anyhow!("--name is required\n{USAGE}"))?;
+
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+    let mut h = blake3::Hasher::new();
+    h.update(&seed);
+    h.update(entropy.as_bytes());
+    let mut rng = StdRng::from_seed(*h.finalize().as_bytes());
+
+    eprintln!("Verifying {in_dir} and contributing as \"{name}\"...");
+    let proof = ceremony::contribute(in_dir, out_dir, &name, &mut rng)?;
+    eprintln!("✓ Wrote updated SRS and transcript to {out_dir}");
+    eprintln!("  G1 digest: {}", hex::encode(proof.g1_digest));
+    eprintln!("  G2 digest: {}", hex::encode(proof.g2_digest));
+    eprintln!("  Publish these digests so later participants can confirm your contribution.");
+    Ok(())
+}
+
+fn verify(args: &[String]) -> anyhow::Result<()> {
+    let [dir] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let report = ceremony::verify(dir)?;
+    eprintln!("✓ {} contribution(s) verified for degree {}", report.contributors.len(), report.max_degree);
+    for (i, name) in report.contributors.iter().enumerate() {
+        eprintln!("  {:>3}. {}", i + 1, name);
+    }
+    if report.contributors.is_empty() {
+        eprintln!("⚠️  No contributions: τ = 1, this SRS is NOT SECURE");
+        return Ok(());
+    }
+    let entry = CeremonyDigests {
+        name: Cow::Owned(format!("local ceremony ({} contributions)", report.contributors.len())),
+        max_degree: report.max_degree,
+        g1_digest: report.g1_digest,
+        g2_digest: report.g2_digest,
+    };
+    println!("{}", entry.to_registry_json());
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.split_first() {
+        Some((cmd, rest)) if cmd == "init" => init(rest),
+        Some((cmd, rest)) if cmd == "contribute" => contribute(rest),
+        Some((cmd, rest)) if cmd == "verify" => verify(rest),
+        _ => Err(anyhow::anyhow!(USAGE)),
+    }
+}
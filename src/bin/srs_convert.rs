@@ -0,0 +1,235 @@
+//! SRS format conversions
+//!
+//! Usage:
+//!   srs_convert ptau <in.ptau> <out_dir> [--max-degree N] [--no-hash-check]
+//!   srs_convert fixed-stride <G1.bin> <G1.srs>
+//!   srs_convert digest <G1.bin|G1.srs> <G2.bin> [--name NAME] [--max-degree D]
+//!   srs_convert lagrange <G1.bin|G1.srs> <out_dir> [--min-log K] [--max-log K]
+//!
+//! `ptau` imports a snarkjs powers-of-tau file (Hermez, Perpetual Powers of
+//! Tau) into `out_dir/G1.bin` + `out_dir/G2.bin`, checking the points and
+//! the ceremony's embedded hash (see `myzkp::ptau`).
+//!
+//! `fixed-stride` takes the compressed `Vec<G1Affine>` produced by `ptau`,
+//! `generate_production_srs` or any arkworks dump, validates it once, and
+//! writes the layout `myzkp::srs_file::SrsFile` opens near-instantly (point
+//! `SSZKP_SRS_G1_PATH` at it).
+//!
+//! `digest` prints the ceremony registry entry for a G1/G2 pair (the same
+//! digests proofs carry); append it to the `SSZKP_CEREMONY_REGISTRY` file or
+//! to `srs_setup::KNOWN_CEREMONIES`. Entries cover powers `0..=max_degree`
+//! (default: the whole file); `--max-degree D` registers a shorter prefix,
+//! which any longer SRS of the same ceremony also matches. `ptau` prints the
+//! entry for its output too.
+//!
+//! `lagrange` derives a `pcs::LagrangeKey` for every power-of-two domain size
+//! `2^min-log ..= 2^max-log` the SRS covers (default: all of them) and writes
+//! each to `out_dir/lagrange-<N>.srs`. The prover (`--lagrange-dir`) and the
+//! API (`SSZKP_LAGRANGE_DIR`) attach them so evaluation-basis wires commit
+//! without a blocked IFFT.
+
+#![forbid(unsafe_code)]
+
+use std::env;
+
+use std::{borrow::Cow, path::Path};
+
+use myzkp::{
+    curve::G2Affine,
+    domain::{self, Domain},
+    pcs::{Basis, LagrangeKey, Srs},
+    ptau, srs_file,
+    srs_setup::{self, CeremonyDigests},
+    F,
+};
+
+const USAGE: &str = "usage:\n  srs_convert ptau <in.ptau> <out_dir> [--max-degree N] [--no-hash-check]\n  srs_convert fixed-stride <G1.bin> <G1.srs>\n  srs_convert digest <G1.bin|G1.srs> <G2.bin> [--name NAME] [--max-degree D]\n  srs_convert lagrange <G1.bin|G1.srs> <out_dir> [--min-log K] [--max-log K]";
+
+/// G1 powers from either layout (a fixed-stride file is validated in full).
+fn open_g1(g1_path: &Path, tau_g2: Option<G2Affine>) -> anyhow::Result<Srs> {
+    if srs_file::is_srs_file(g1_path) {
+        let file = srs_file::SrsFile::open(g1_path)?;
+        if file.basis() != Basis::Coefficient {
+            return Err(anyhow::anyhow!("{} holds Lagrange-basis points, not G1 powers", g1_path.display()));
+        }
+        if let srs_file::ValidationStatus::Invalid { index } = file.validate() {
+            return Err(anyhow::anyhow!("{}: G1 power {index} is invalid", g1_path.display()));
+        }
+        Ok(Srs::from_file(file, tau_g2))
+    } else {
+        Ok(Srs::new(srs_setup::load_and_validate_g1_srs(g1_path, 0)?, tau_g2)?)
+    }
+}
+
+/// Registry entry for the first `max_degree + 1` powers (default: all) of a
+/// G1 file (either layout) and a G2 file.
+fn ceremony_entry(
+    name: String,
+    g1_path: &Path,
+    g2_path: &Path,
+    max_degree: Option<usize>,
+) -> anyhow::Result<CeremonyDigests> {
+    let tau_g2 = srs_setup::load_and_validate_g2_srs(g2_path)?;
+    let srs = open_g1(g1_path, Some(tau_g2))?;
+    let max_degree = max_degree.unwrap_or(srs.max_degree());
+    if max_degree > srs.max_degree() {
+        return Err(anyhow::anyhow!(
+            "--max-degree {max_degree}: {} holds only {} powers",
+            g1_path.display(),
+            srs.max_degree() + 1
+        ));
+    }
+    Ok(CeremonyDigests {
+        name: Cow::Owned(name),
+        max_degree,
+        g1_digest: srs.g1_prefix_digest(max_degree)?,
+        g2_digest: srs.g2_digest(),
+    })
+}
+
+fn import_ptau(args: &[String]) -> anyhow::Result<()> {
+    let [input, out_dir, rest @ ..] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let mut max_degree = None;
+    let mut check_hash = true;
+    let mut it = rest.iter();
+    while let Some(flag) = it.next() {
+        match flag.as_str() {
+            "--max-degree" => {
+                let v = it.next().ok_or_else(|| anyhow::anyhow!("--max-degree needs a value"))?;
+                max_degree = Some(v.parse::<usize>()?);
+            }
+            "--no-hash-check" => check_hash = false,
+            other => return Err(anyhow::anyhow!("unknown flag {other}\n{USAGE}")),
+        }
+    }
+
+    eprintln!("Importing {input}...");
+    let rep = ptau::import(input, out_dir, max_degree, check_hash)?;
+    eprintln!(
+        "  power {} (ceremony power {}), {} contributions",
+        rep.header.power,
+        rep.header.ceremony_power,
+        rep.contributions.len()
+    );
+    if let Some(last) = rep.contributions.last() {
+        eprintln!("  last contribution: {}", last.name.as_deref().unwrap_or("<unnamed>"));
+    }
+    if rep.challenge_hash_checked {
+        eprintln!("✓ Ceremony challenge hash verified");
+    } else {
+        eprintln!("  Challenge hash not checked (truncated file or --no-hash-check)");
+    }
+    eprintln!("✓ Wrote {} G1 powers to {}", rep.g1_written, rep.g1_path.display());
+    eprintln!("✓ Wrote [τ]G2 to {}", rep.g2_path.display());
+
+    let name = Path::new(input)
+        .file_stem()
+        .map_or_else(|| input.clone(), |s| s.to_string_lossy().into_owned());
+    let entry = ceremony_entry(name, &rep.g1_path, &rep.g2_path, None)?;
+    eprintln!("  Ceremony registry entry:");
+    println!("{}", entry.to_registry_json());
+    Ok(())
+}
+
+fn fixed_stride(args: &[String]) -> anyhow::Result<()> {
+    let [input, output] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+
+    eprintln!("Loading and validating {input}...");
+    let powers = srs_setup::load_and_validate_g1_srs(input, 0)?;
+    srs_file::write_g1(output, &powers)?;
+
+    let file = srs_file::SrsFile::open_with_cache(output, None)?;
+    eprintln!(
+        "✓ Wrote {} powers ({} bytes each) to {output}",
+        file.len(),
+        srs_file::g1_stride()
+    );
+    eprintln!("  file BLAKE3: {}", hex::encode(file.file_digest()?));
+    Ok(())
+}
+
+fn digest(args: &[String]) -> anyhow::Result<()> {
+    let [g1, g2, rest @ ..] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let mut name = None;
+    let mut max_degree = None;
+    let mut it = rest.iter();
+    while let Some(flag) = it.next() {
+        let v = it.next().ok_or_else(|| anyhow::anyhow!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--name" => name = Some(v.clone()),
+            "--max-degree" => max_degree = Some(v.parse::<usize>()?),
+            other => return Err(anyhow::anyhow!("unknown flag {other}\n{USAGE}")),
+        }
+    }
+    let name = name.unwrap_or_else(|| {
+        Path::new(g1)
+            .parent()
+            .and_then(|d| d.file_name())
+            .map_or_else(|| "unnamed".to_string(), |s| s.to_string_lossy().into_owned())
+    });
+
+    let entry = ceremony_entry(name, Path::new(g1), Path::new(g2), max_degree)?;
+    if let Some(known) = srs_setup::identify_ceremony(entry.g1_digest, entry.g2_digest)? {
+        eprintln!("  Already registered as \"{}\"", known.name);
+    }
+    println!("{}", entry.to_registry_json());
+    Ok(())
+}
+
+fn lagrange(args: &[String]) -> anyhow::Result<()> {
+    let [input, out_dir, rest @ ..] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let srs = open_g1(Path::new(input), None)?;
+    let top_log = (srs.max_degree() + 1).ilog2();
+    let (mut min_log, mut max_log) = (1, top_log);
+    let mut it = rest.iter();
+    while let Some(flag) = it.next() {
+        let slot = match flag.as_str() {
+            "--min-log" => &mut min_log,
+            "--max-log" => &mut max_log,
+            other => return Err(anyhow::anyhow!("unknown flag {other}\n{USAGE}")),
+        };
+        let v = it.next().ok_or_else(|| anyhow::anyhow!("{flag} needs a value"))?;
+        *slot = v.parse::<u32>()?;
+    }
+    if max_log > top_log {
+        return Err(anyhow::anyhow!(
+            "--max-log {max_log}: the SRS has {} powers (at most 2^{top_log})",
+            srs.max_degree() + 1
+        ));
+    }
+    if min_log > max_log {
+        return Err(anyhow::anyhow!("--min-log {min_log} exceeds --max-log {max_log}"));
+    }
+
+    std::fs::create_dir_all(out_dir)?;
+    for log_n in min_log..=max_log {
+        let n = 1usize << log_n;
+        let omega = domain::root_of_unity(n)
+            .ok_or_else(|| anyhow::anyhow!("no {n}-th root of unity in the scalar field"))?;
+        let dom = Domain { n, omega, zh_c: F::from(1u64) };
+        let key = LagrangeKey::derive(srs.committer_key(), &dom)?;
+        let path = Path::new(out_dir).join(LagrangeKey::file_name(n));
+        key.write(&path)?;
+        eprintln!("✓ Wrote Lagrange key for N={n} to {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.split_first() {
+        Some((cmd, rest)) if cmd == "ptau" => import_ptau(rest),
+        Some((cmd, rest)) if cmd == "fixed-stride" => fixed_stride(rest),
+        Some((cmd, rest)) if cmd == "digest" => digest(rest),
+        Some((cmd, rest)) if cmd == "lagrange" => lagrange(rest),
+        _ => Err(anyhow::anyhow!(USAGE)),
+    }
+}
@@ -30,8 +30,30 @@
 //!
 //! Notes:
 //! - Proof format is v2 (magic + u16 + ark-compressed).
-//! - SRS must be initialized via `/v1/admin/srs/init` before proving/verifying.
+//! - SRS must be initialized via `/v1/admin/srs/init` before proving/verifying
+//!   with KZG. `pcs.backend = "fri"` or `"ipa"` selects a transparent
+//!   backend, which needs no SRS.
 //! - Dev builds can use in-memory SRS (feature `dev-srs`); production requires files.
+//! - Production builds only accept an SRS listed in the ceremony registry
+//!   (`srs_setup::KNOWN_CEREMONIES` plus `$SSZKP_CEREMONY_REGISTRY`) unless
+//!   `SSZKP_ALLOW_UNKNOWN_SRS=1`; `/v1/version` reports the ceremony name.
+//!   The built-in list is empty and an empty registry enforces nothing.
+//! - `SSZKP_LAGRANGE_DIR` holds Lagrange keys from `srs_convert lagrange`;
+//!   every `lagrange-<N>.srs` the SRS covers is attached at load time, so
+//!   power-of-two, evaluation-basis proofs commit without a blocked IFFT.
+//! - Each proof resolves the server's `TransformConfig` against its own domain
+//!   size: large domains spill (encrypted, unlinked) to `TINYZKP_SPILL_DIR`,
+//!   capped per proof by `TINYZKP_MAX_SPILL_BYTES`; requests whose spill
+//!   would exceed the cap are rejected with 413 when spilling is forced.
+//! - Domains pad `rows` to the nearest supported smooth size
+//!   (`domain::next_smooth_size`) rather than the next power of two.
+//! - `witness.format = "framed"` takes `data_b64`, a base64 compressed
+//!   container (`witness_frames`, zstd or gzip frames). Frames stay
+//!   compressed and are inflated one block at a time while proving; build one
+//!   with `prover convert-witness --compress zstd --frame-rows <b_blk>`.
+//! - Witness values (`json_rows`), selector CSV tokens and `zh_c` are
+//!   full-width scalars: JSON integers, or strings in decimal / `0x` hex,
+//!   optionally signed; values ≥ r are rejected with 400, never reduced.
 
 #![forbid(unsafe_code)]
 #![allow(dead_code)]
@@ -41,7 +63,6 @@ use std::net::SocketAddr;
 use std::sync::{OnceLock, Mutex};
 
 use anyhow::{self};
-use ark_ff::FftField; // for get_root_of_unity
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use axum::{
     body::Bytes,
@@ -63,9 +84,16 @@ use axum::http::Method;
 
 use myzkp::{
     air::{AirSpec, Row},
-    pcs::{Basis, PcsParams},
-    scheduler::{Prover, Verifier as SchedVerifier},
-    F, Proof, ProveParams, VerifyParams,
+    curve::{parse_scalar, ScalarParseError},
+    domain::{self, SpillCleanup, TransformBackend, TransformConfig},
+    fri::Fri,
+    ipa::Ipa,
+    pcs::{Basis, Kzg, PcsKind, PcsParams, PolynomialCommitment, Srs},
+    scheduler::{ProveError, Prover, Verifier as SchedVerifier},
+    stream::TryRestreamer,
+    transcript::FsHash,
+    witness_frames::FramedWitness,
+    F, Proof, ProofHeader, ProveParams, VerifyParams,
 };
 
 // Stripe SDK (async-stripe 0.37.x)
@@ -90,8 +118,12 @@ use rand::rngs::OsRng;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Global flag tracking whether SRS has been initialized.
-static SRS_INITIALIZED: OnceLock<bool> = OnceLock::new();
+/// The SRS every prove/verify request binds into its `PcsParams`. Set once;
+/// requests only read it, so they never contend on a lock.
+static SERVER_SRS: OnceLock<Srs> = OnceLock::new();
+
+/// Registry name of the ceremony `SERVER_SRS` came from (unset if unknown).
+static SRS_CEREMONY: OnceLock<String> = OnceLock::new();
 
 /// Flag tracking whether SRS loading is in progress.
 static SRS_LOADING: AtomicBool = AtomicBool::new(false);
@@ -99,11 +131,91 @@ static SRS_LOADING: AtomicBool = AtomicBool::new(false);
 /// Mutex to ensure only one thread starts the loading task.
 static SRS_INIT_LOCK: Mutex<()> = Mutex::new(());
 
+/// Open a fixed-stride G1 file (see `myzkp::srs_file`) without decoding it.
+///
+/// Points are decoded per tile; subgroup checks run on a background thread
+/// unless the validation cache already vouches for this exact file. Until
+/// that pass reports `Valid`, `require_srs` refuses KZG requests.
+#[cfg(not(feature = "dev-srs"))]
+fn open_srs_file(
+    g1_path: &str,
+    tau_g2: myzkp::curve::G2Affine,
+    max_degree: usize,
+) -> Result<Srs, String> {
+    use myzkp::srs_file::{SrsFile, ValidationStatus};
+
+    let file = SrsFile::open(g1_path).map_err(|e| format!("Failed to open G1 SRS file: {}", e))?;
+    if file.basis() != myzkp::pcs::Basis::Coefficient {
+        return Err(format!("{} holds Lagrange-basis points, not G1 powers", g1_path));
+    }
+    if file.len() <= max_degree {
+        return Err(format!(
+            "G1 SRS file has {} powers, need {} for degree {}",
+            file.len(),
+            max_degree + 1,
+            max_degree
+        ));
+    }
+    match file.status() {
+        ValidationStatus::Valid => eprintln!("  G1 file validated previously (cache hit)"),
+        _ => {
+            eprintln!("  G1 file opened; validating {} points in background", file.len());
+            let validation = file.spawn_validation();
+            std::thread::spawn(move || match validation.join() {
+                Ok(ValidationStatus::Valid) => eprintln!("✓ G1 SRS file validated"),
+                Ok(status) => eprintln!("❌ G1 SRS file failed validation ({:?}); KZG requests stay disabled", status),
+                Err(_) => eprintln!("❌ G1 SRS validation thread panicked; KZG requests stay disabled"),
+            });
+        }
+    }
+    Ok(Srs::from_file(file, Some(tau_g2)))
+}
+
+/// Attach the Lagrange keys in `$SSZKP_LAGRANGE_DIR` (one file per
+/// power-of-two domain size, named by `LagrangeKey::file_name`). A key bound
+/// to another SRS or root of unity fails the load.
+fn attach_lagrange_keys(mut srs: Srs) -> Result<Srs, String> {
+    let Some(dir) = std::env::var_os("SSZKP_LAGRANGE_DIR") else {
+        return Ok(srs);
+    };
+    let dir = std::path::PathBuf::from(dir);
+    let mut n = 2usize;
+    while n <= srs.max_degree() + 1 {
+        let path = dir.join(myzkp::pcs::LagrangeKey::file_name(n));
+        if path.exists() {
+            let omega = domain::root_of_unity(n).ok_or_else(|| format!("no {n}-th root of unity"))?;
+            let dom = domain::Domain { n, omega, zh_c: F::from(1u64) };
+            srs = srs
+                .with_lagrange_file(&path, &dom)
+                .map_err(|e| format!("Lagrange key {}: {}", path.display(), e))?;
+            eprintln!("  Attached Lagrange key for N={}", n);
+        }
+        n *= 2;
+    }
+    Ok(srs)
+}
+
+/// Look the SRS up in the ceremony registry by the prefix it will serve
+/// (powers `0..=max_degree`; the rest of a large file is never hashed).
+/// Production builds with a non-empty registry refuse an unknown SRS unless
+/// `SSZKP_ALLOW_UNKNOWN_SRS` is set.
+fn identify_srs_ceremony(srs: &Srs, max_degree: usize) -> Result<Option<String>, String> {
+    let allow_unknown =
+        cfg!(feature = "dev-srs") || myzkp::srs_setup::allow_unknown_srs_from_env();
+    let ceremony = myzkp::srs_setup::require_known_ceremony(srs, max_degree, allow_unknown)
+        .map_err(|e| e.to_string())?;
+    match &ceremony {
+        Some(c) => eprintln!("✓ SRS is from ceremony: {}", c.name),
+        None => eprintln!("⚠️  SRS is not in the ceremony registry"),
+    }
+    Ok(ceremony.map(|c| c.name.into_owned()))
+}
+
 /// Background task: load SRS from files without blocking HTTP requests.
 /// This runs in a separate tokio task, allowing immediate 503 responses.
 fn start_srs_loading_background(max_degree: usize) {
     // Fast path: if already initialized or loading, do nothing
-    if SRS_INITIALIZED.get().is_some() || SRS_LOADING.load(Ordering::Relaxed) {
+    if SERVER_SRS.get().is_some() || SRS_LOADING.load(Ordering::Relaxed) {
         return;
     }
 
@@ -111,7 +223,7 @@ fn start_srs_loading_background(max_degree: usize) {
     let _guard = SRS_INIT_LOCK.lock().unwrap();
 
     // Double-check after acquiring lock
-    if SRS_INITIALIZED.get().is_some() || SRS_LOADING.load(Ordering::Relaxed) {
+    if SERVER_SRS.get().is_some() || SRS_LOADING.load(Ordering::Relaxed) {
         return;
     }
 
@@ -122,9 +234,9 @@ fn start_srs_loading_background(max_degree: usize) {
     tokio::spawn(async move {
         eprintln!("⏳ Starting background SRS loading (16MB, ~60 seconds)...");
         
-        let result: Result<(), String> = (|| {
+        let result: Result<(Srs, Option<String>), String> = (|| {
             #[cfg(not(feature = "dev-srs"))]
-            {
+            let srs = {
                 let g1_path = std::env::var("SSZKP_SRS_G1_PATH")
                     .map_err(|e| format!("SSZKP_SRS_G1_PATH not set: {}", e))?;
                 let g2_path = std::env::var("SSZKP_SRS_G2_PATH")
@@ -144,47 +256,50 @@ fn start_srs_loading_background(max_degree: usize) {
 
                 eprintln!("  Files exist, loading...");
 
-                let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(&g1_path, max_degree)
-                    .map_err(|e| format!("Failed to load G1 SRS: {}", e))?;
-
-                eprintln!("  G1 loaded, loading G2...");
-
                 let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(&g2_path)
                     .map_err(|e| format!("Failed to load G2 SRS: {}", e))?;
 
-                eprintln!("  G2 loaded, initializing PCS...");
-
-                myzkp::pcs::load_srs_g1(&g1_powers);
-                myzkp::pcs::load_srs_g2(tau_g2);
+                let srs = if myzkp::srs_file::is_srs_file(&g1_path) {
+                    open_srs_file(&g1_path, tau_g2, max_degree)?
+                } else {
+                    let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(&g1_path, max_degree)
+                        .map_err(|e| format!("Failed to load G1 SRS: {}", e))?;
+                    Srs::new(g1_powers, Some(tau_g2)).map_err(|e| format!("Invalid SRS: {}", e))?
+                };
 
                 eprintln!("✓ SRS loaded successfully (degree={})", max_degree);
-            }
+                srs
+            };
 
             #[cfg(feature = "dev-srs")]
-            {
+            let srs = {
                 eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                 eprintln!("⚠️  WARNING: Using DEVELOPMENT SRS (NOT FOR PRODUCTION!)");
                 eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
                 let (g1_powers, tau_g2) = myzkp::srs_setup::generate_dev_srs(max_degree);
-                myzkp::pcs::load_srs_g1(&g1_powers);
-                myzkp::pcs::load_srs_g2(tau_g2);
+                let srs = Srs::new(g1_powers, Some(tau_g2))
+                    .map_err(|e| format!("Invalid SRS: {}", e))?;
 
                 eprintln!("✓ Dev SRS generated (degree={})", max_degree);
-            }
+                srs
+            };
 
-            let g1_dig = myzkp::pcs::srs_g1_digest();
-            let g2_dig = myzkp::pcs::srs_g2_digest();
+            let g1_dig = srs.g1_prefix_digest(max_degree).map_err(|e| format!("SRS digest: {}", e))?;
+            eprintln!("  G1 digest: {:02x?} (first {} powers)", &g1_dig[..8], max_degree + 1);
+            eprintln!("  G2 digest: {:02x?}", &srs.g2_digest()[..8]);
 
-            eprintln!("  G1 digest: {:02x?}", &g1_dig[..8]);
-            eprintln!("  G2 digest: {:02x?}", &g2_dig[..8]);
-            
-            Ok(())
+            let srs = attach_lagrange_keys(srs)?;
+            let ceremony = identify_srs_ceremony(&srs, max_degree)?;
+            Ok((srs, ceremony))
         })();
 
         match result {
-            Ok(_) => {
-                SRS_INITIALIZED.set(true).ok();
+            Ok((srs, ceremony)) => {
+                if let Some(name) = ceremony {
+                    SRS_CEREMONY.set(name).ok();
+                }
+                SERVER_SRS.set(srs).ok();
                 SRS_LOADING.store(false, Ordering::Relaxed);
                 eprintln!("✓ API ready for proof generation");
             }
@@ -198,12 +313,24 @@ fn start_srs_loading_background(max_degree: usize) {
     eprintln!("🚀 Background SRS loading started");
 }
 
-/// Middleware: ensure SRS is initialized before handling prove/verify requests.
-/// Returns 503 if loading is in progress, triggers background loading if not started.
+/// Middleware: ensure SRS is initialized and validated before handling
+/// prove/verify requests. Returns 503 while loading or validating, triggers
+/// background loading if not started, and 500 for an SRS that failed validation.
 async fn require_srs(max_degree: usize) -> Result<(), (StatusCode, String)> {
-    // Fast path: if initialized, proceed immediately
-    if SRS_INITIALIZED.get().is_some() {
-        return Ok(());
+    use myzkp::srs_file::ValidationStatus;
+
+    if let Some(srs) = SERVER_SRS.get() {
+        return match srs.validation_status() {
+            ValidationStatus::Valid => Ok(()),
+            ValidationStatus::Pending => Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                "SRS is being validated in background. Please retry shortly. Check /v1/health for status.".to_string(),
+            )),
+            ValidationStatus::Invalid { index } => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("SRS failed validation at G1 power {index}; KZG proving and verification are disabled"),
+            )),
+        };
     }
 
     // If not loading yet, start background loading
@@ -329,8 +456,7 @@ fn end_of_month_ttl_secs() -> u64 {
     let (y, m) = (now.year(), now.month());
     let (ny, nm) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
     let eom = Utc.with_ymd_and_hms(ny, nm, 1, 0, 0, 0).earliest().unwrap();
-    let secs = (eom - now).num_seconds().max(86400) as u64;
-    secs
+    (eom - now).num_seconds().max(86400) as u64
 }
 
 // ------------------------------ Types ------------------------------
@@ -342,6 +468,9 @@ struct Health {
     srs_initialized: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     srs_loading: Option<bool>,
+    /// `pending`, `valid` or `invalid` once an SRS is installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    srs_validation: Option<&'static str>,
 }
 
 #[derive(Serialize)]
@@ -349,6 +478,8 @@ struct Version {
     api: &'static str,
     protocol: &'static str,
     curve: &'static str,
+    /// Ceremony of the loaded SRS, if it is in the registry.
+    ceremony: Option<String>,
     features: VersionFeatures,
 }
 
@@ -379,7 +510,30 @@ struct DomainPlanRes {
 #[derive(Deserialize)]
 #[serde(rename_all = "snake_case", tag = "format")]
 enum WitnessInput {
-    JsonRows { rows: Vec<Vec<u64>> },
+    JsonRows { rows: Vec<Vec<JsonScalar>> },
+    /// Base64 of a framed compressed container (`witness_frames`); only the
+    /// frames covering each block are inflated while proving.
+    Framed { data_b64: String },
+}
+
+/// A witness value in JSON: a (possibly negative) integer, or a string in any
+/// encoding `curve::parse_scalar` accepts (decimal bignum, `0x` hex, signed).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonScalar {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl JsonScalar {
+    fn to_field(&self) -> Result<F, ScalarParseError> {
+        match self {
+            JsonScalar::Unsigned(v) => Ok(F::from(*v)),
+            JsonScalar::Signed(v) => Ok(F::from(*v)),
+            JsonScalar::Text(s) => parse_scalar(s),
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -392,6 +546,9 @@ struct ProveReq {
     witness: WitnessInput,
     #[serde(default)]
     return_proof: bool,
+    /// Transcript hash: "blake3" (default), "keccak256" or "poseidon".
+    #[serde(default = "blake3_hash")]
+    fs_hash: String,
 }
 
 #[derive(Deserialize)]
@@ -420,6 +577,9 @@ struct DomainCfg {
 struct PcsCfg {
     #[serde(default = "eval_basis")]
     basis_wires: String,
+    /// Commitment backend: "kzg" (default), "fri" or "ipa".
+    #[serde(default = "kzg_backend")]
+    backend: String,
 }
 
 #[derive(Deserialize)]
@@ -436,6 +596,14 @@ fn eval_basis() -> String {
     "eval".into()
 }
 
+fn kzg_backend() -> String {
+    "kzg".into()
+}
+
+fn blake3_hash() -> String {
+    "blake3".into()
+}
+
 #[derive(Serialize)]
 struct ProveRes {
     header: ProofHeaderView,
@@ -450,6 +618,9 @@ struct ProofHeaderView {
     zh_c_hex: String,
     k: usize,
     basis_wires: String,
+    pcs: String,
+    curve: String,
+    fs_hash: String,
     srs_g1_digest_hex: String,
     srs_g2_digest_hex: String,
 }
@@ -597,6 +768,7 @@ struct AppState {
     success_url: String,
     cancel_url: String,
     portal_return_url: String,
+    transform: TransformConfig,
 }
 
 // ------------------------------ Helpers ------------------------------
@@ -617,22 +789,101 @@ fn fe_hex(x: F) -> String {
     s
 }
 
-fn header_view(p: &Proof) -> ProofHeaderView {
+fn header_view(h: &ProofHeader) -> ProofHeaderView {
     ProofHeaderView {
-        n: p.header.domain_n as usize,
-        omega_hex: fe_hex(p.header.domain_omega),
-        zh_c_hex: fe_hex(p.header.zh_c),
-        k: p.header.k as usize,
-        basis_wires: match p.header.basis_wires {
+        n: h.domain_n as usize,
+        omega_hex: fe_hex(h.domain_omega),
+        zh_c_hex: fe_hex(h.zh_c),
+        k: h.k as usize,
+        basis_wires: match h.basis_wires {
             Basis::Coefficient => "Coefficient",
             Basis::Evaluation => "Evaluation",
         }
         .into(),
-        srs_g1_digest_hex: hex_bytes(&p.header.srs_g1_digest),
-        srs_g2_digest_hex: hex_bytes(&p.header.srs_g2_digest),
+        pcs: h.pcs.as_str().into(),
+        curve: h.curve.as_str().into(),
+        fs_hash: h.fs_hash.as_str().into(),
+        srs_g1_digest_hex: hex_bytes(&h.srs_g1_digest),
+        srs_g2_digest_hex: hex_bytes(&h.srs_g2_digest),
     }
 }
 
+fn parse_backend(s: &str) -> Result<PcsKind, (StatusCode, String)> {
+    s.parse().map_err(|e| (StatusCode::BAD_REQUEST, format!("pcs.backend: {e}")))
+}
+
+/// Run the prover for backend `P`; returns the header view and, if requested,
+/// the base64 v2 proof file.
+fn prove_with<P: PolynomialCommitment>(
+    prover: Prover<'_, P>,
+    witness: &impl TryRestreamer<Item = Row>,
+    return_proof: bool,
+) -> Result<ProveRes, (StatusCode, String)> {
+    let proof: Proof<P> = prover.prove_with_restreamer(witness).map_err(|e| {
+        // A bad upload (corrupt frame, non-canonical value) is the caller's fault.
+        let status = match e {
+            ProveError::Witness(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, format!("prover failed: {e}"))
+    })?;
+
+    let header_v = header_view(&proof.header);
+
+    let proof_b64 = if return_proof {
+        let mut payload = Vec::new();
+        proof
+            .serialize_compressed(&mut payload)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("serialize proof: {e}")))?;
+        let mut out = Vec::with_capacity(8 + 2 + payload.len());
+        out.extend_from_slice(b"SSZKPv2\0");
+        out.extend_from_slice(&2u16.to_be_bytes());
+        out.extend_from_slice(&payload);
+        Some(base64::engine::general_purpose::STANDARD.encode(out))
+    } else {
+        None
+    };
+
+    Ok(ProveRes {
+        header: header_v,
+        proof_b64,
+    })
+}
+
+/// Decode a v2 payload for backend `P` and run the scheduler verifier.
+/// The outer error is a malformed request; the inner one a failed proof.
+fn verify_with<P: PolynomialCommitment>(
+    payload: &[u8],
+    backend: P,
+) -> Result<Result<(), String>, (StatusCode, String)> {
+    let mut slice = payload;
+    let proof: Proof<P> = CanonicalDeserialize::deserialize_compressed(&mut slice)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("deserialize proof: {e}")))?;
+
+    let domain = myzkp::domain::Domain {
+        n: proof.header.domain_n as usize,
+        omega: proof.header.domain_omega,
+        zh_c: proof.header.zh_c,
+    };
+    let pcs_wires = PcsParams {
+        max_degree: domain.n - 1,
+        basis: proof.header.basis_wires,
+        srs: SERVER_SRS.get().cloned(),
+    };
+    let pcs_coeff = PcsParams {
+        max_degree: domain.n - 1,
+        basis: Basis::Coefficient,
+        srs: SERVER_SRS.get().cloned(),
+    };
+    let vp = VerifyParams {
+        domain,
+        pcs_wires,
+        pcs_coeff,
+    };
+    let verifier = SchedVerifier::with_pcs(&vp, backend);
+    Ok(verifier.verify(&proof).map_err(|e| format!("{e}")))
+}
+
 fn hex_bytes(b: &[u8; 32]) -> String {
     let mut s = String::with_capacity(2 + 64);
     s.push_str("0x");
@@ -642,7 +893,51 @@ fn hex_bytes(b: &[u8; 32]) -> String {
     s
 }
 
-fn rows_from_json(rows: &[Vec<u64>], k: usize) -> anyhow::Result<Vec<Row>> {
+/// A prover for whichever backend the request selected.
+enum Backend<'a> {
+    Kzg(Prover<'a>),
+    Fri(Prover<'a, Fri>),
+    Ipa(Prover<'a, Ipa>),
+}
+
+impl Backend<'_> {
+    fn prove(
+        self,
+        witness: &impl TryRestreamer<Item = Row>,
+        return_proof: bool,
+    ) -> Result<ProveRes, (StatusCode, String)> {
+        match self {
+            Backend::Kzg(p) => prove_with(p, witness, return_proof),
+            Backend::Fri(p) => prove_with(p, witness, return_proof),
+            Backend::Ipa(p) => prove_with(p, witness, return_proof),
+        }
+    }
+}
+
+/// Decode an uploaded framed container and check it matches the AIR width
+/// and fits in `max_rows` (the requested domain rows, already capped by tier).
+/// Frames stay compressed in memory until the prover asks for their block.
+fn framed_witness(data_b64: &str, k: usize, max_rows: usize) -> Result<FramedWitness, (StatusCode, String)> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data_b64)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("witness.data_b64: {e}")))?;
+    let frames = FramedWitness::from_bytes("witness", bytes).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    if frames.header().k != k {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("witness has k={}, air.k is {k}", frames.header().k),
+        ));
+    }
+    if frames.header().rows > max_rows {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("witness has {} rows, more than domain.rows ({max_rows})", frames.header().rows),
+        ));
+    }
+    Ok(frames)
+}
+
+fn rows_from_json(rows: &[Vec<JsonScalar>], k: usize) -> anyhow::Result<Vec<Row>> {
     let mut out = Vec::with_capacity(rows.len());
     for (i, r) in rows.iter().enumerate() {
         if r.len() != k {
@@ -653,7 +948,11 @@ fn rows_from_json(rows: &[Vec<u64>], k: usize) -> anyhow::Result<Vec<Row>> {
                 k
             ));
         }
-        let regs: Vec<F> = r.iter().copied().map(F::from).collect();
+        let regs = r
+            .iter()
+            .enumerate()
+            .map(|(j, v)| v.to_field().map_err(|e| anyhow::anyhow!("row {i}, column {j}: {e}")))
+            .collect::<anyhow::Result<Vec<F>>>()?;
         out.push(Row {
             regs: regs.into_boxed_slice(),
         });
@@ -665,16 +964,15 @@ fn plan_b_blk(rows: usize, provided: Option<usize>) -> usize {
     if let Some(b) = provided {
         return b.max(1);
     }
-    let n = next_pow2(rows.max(1));
+    let n = domain_size_for(rows);
     let approx = (n as f64).sqrt().round() as usize;
     approx.clamp(8, 1 << 12)
 }
 
-fn next_pow2(n: usize) -> usize {
-    if n <= 1 {
-        return 1;
-    }
-    n.next_power_of_two()
+/// Smallest supported (mixed-radix) domain size ≥ `n`, falling back to the
+/// next power of two if the field has none.
+fn domain_size_for(n: usize) -> usize {
+    domain::next_smooth_size(n).unwrap_or_else(|| n.max(1).next_power_of_two())
 }
 
 fn max_rows_for_tier(st: &AppState, tier: Tier) -> usize {
@@ -804,11 +1102,20 @@ async fn check_and_count(
 // ------------------------------ Public Handlers ------------------------------
 
 async fn health() -> impl IntoResponse {
+    use myzkp::srs_file::ValidationStatus;
+
     let loading = SRS_LOADING.load(Ordering::Relaxed);
+    let validation = SERVER_SRS.get().map(|srs| srs.validation_status());
     Json(Health { 
         status: "ok",
-        srs_initialized: SRS_INITIALIZED.get().copied(),
+        // Ready only once the SRS is fully validated.
+        srs_initialized: validation.map(|v| v == ValidationStatus::Valid),
         srs_loading: if loading { Some(true) } else { None },
+        srs_validation: validation.map(|v| match v {
+            ValidationStatus::Pending => "pending",
+            ValidationStatus::Valid => "valid",
+            ValidationStatus::Invalid { .. } => "invalid",
+        }),
     })
 }
 
@@ -817,6 +1124,7 @@ async fn version() -> impl IntoResponse {
         api: "tinyzkp-api/0.3",
         protocol: "sszkp-v2",
         curve: "bn254/kzg",
+        ceremony: SRS_CEREMONY.get().cloned(),
         features: VersionFeatures {
             dev_srs: cfg!(feature = "dev-srs"),
             zeta_shift: cfg!(feature = "zeta-shift"),
@@ -828,27 +1136,11 @@ async fn version() -> impl IntoResponse {
 async fn domain_plan(
     Json(req): Json<DomainPlanReq>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let n = next_pow2(req.rows.max(1));
+    let n = domain_size_for(req.rows);
     let b_blk = plan_b_blk(req.rows, req.b_blk);
-    let omega = F::get_root_of_unity(n as u64)
+    let omega = domain::root_of_unity(n)
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "no n-th root of unity".into()))?;
-    let mut ok = true;
-    let mut pow = F::from(1u64);
-    for _ in 0..n {
-        pow *= omega;
-    }
-    if pow != F::from(1u64) {
-        ok = false;
-    }
-    if n >= 2 {
-        let mut pow2 = F::from(1u64);
-        for _ in 0..(n / 2) {
-            pow2 *= omega;
-        }
-        if pow2 == F::from(1u64) {
-            ok = false;
-        }
-    }
+    let ok = domain::Domain::new_with_c_r(n, omega, F::from(1u64)).is_ok();
     let mem_hint_bytes = b_blk * 64;
     Ok(Json(DomainPlanRes {
         n,
@@ -1250,20 +1542,16 @@ async fn prove(
 
     let n_rows = req.domain.rows;
     let b_blk = req.domain.b_blk.max(1);
-    let n_domain = next_pow2(n_rows);
-    let omega = F::get_root_of_unity(n_domain as u64)
+    let n_domain = domain_size_for(n_rows);
+    let omega = domain::root_of_unity(n_domain)
         .ok_or_else(|| {
             (
                 StatusCode::BAD_REQUEST,
                 format!("no {}-th root of unity", n_domain),
             )
         })?;
-    let zh_u = req
-        .domain
-        .zh_c
-        .parse::<u64>()
-        .map_err(|_| (StatusCode::BAD_REQUEST, "zh_c must be decimal u64".into()))?;
-    let zh_c = F::from(zh_u);
+    let zh_c = parse_scalar(&req.domain.zh_c)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("zh_c: {e}")))?;
 
     let selectors = if let Some(sel) = &req.air.selectors {
         let mut rows: Vec<Vec<F>> = Vec::new();
@@ -1277,7 +1565,7 @@ async fn prove(
                 if tok.is_empty() {
                     continue;
                 }
-                let v = tok.parse::<u64>().map_err(|e| {
+                let v = parse_scalar(tok).map_err(|e| {
                     (
                         StatusCode::BAD_REQUEST,
                         format!(
@@ -1288,7 +1576,7 @@ async fn prove(
                         ),
                     )
                 })?;
-                r.push(F::from(v));
+                r.push(v);
             }
             if !r.is_empty() {
                 rows.push(r);
@@ -1339,57 +1627,49 @@ async fn prove(
     let pcs_wires = PcsParams {
         max_degree: n_domain - 1,
         basis: basis_wires,
-        srs_placeholder: (),
+        srs: SERVER_SRS.get().cloned(),
     };
     let pcs_coeff = PcsParams {
         max_degree: n_domain - 1,
         basis: Basis::Coefficient,
-        srs_placeholder: (),
+        srs: SERVER_SRS.get().cloned(),
     };
+    // Per-request transform strategy: the server policy resolved for this N.
+    let transform = st.transform.clone();
+    transform
+        .spills(n_domain)
+        .map_err(|e| (StatusCode::PAYLOAD_TOO_LARGE, format!("transform: {e}")))?;
     let prove_params = ProveParams {
         domain: domain.clone(),
         pcs_wires,
         pcs_coeff,
         b_blk,
+        transform,
     };
 
-    let witness_rows: Vec<Row> = match &req.witness {
-        WitnessInput::JsonRows { rows } => {
-            rows_from_json(rows, req.air.k).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    let fs_hash: FsHash = req
+        .fs_hash
+        .parse()
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("fs_hash: {e}")))?;
+    let prover = match parse_backend(&req.pcs.backend)? {
+        PcsKind::Kzg => Backend::Kzg(Prover::new(&air, &prove_params).with_fs_hash(fs_hash)),
+        PcsKind::Fri => {
+            Backend::Fri(Prover::with_pcs(&air, &prove_params, Fri::default()).with_fs_hash(fs_hash))
         }
+        PcsKind::Ipa => Backend::Ipa(Prover::with_pcs(&air, &prove_params, Ipa).with_fs_hash(fs_hash)),
     };
-
-    let prover = Prover {
-        air: &air,
-        params: &prove_params,
-    };
-    let proof = prover.prove_with_restreamer(&witness_rows).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("prover failed: {e}"),
-        )
-    })?;
-
-    let header_v = header_view(&proof);
-
-    let proof_b64 = if req.return_proof {
-        let mut payload = Vec::new();
-        proof
-            .serialize_compressed(&mut payload)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("serialize proof: {e}")))?;
-        let mut out = Vec::with_capacity(8 + 2 + payload.len());
-        out.extend_from_slice(b"SSZKPv2\0");
-        out.extend_from_slice(&2u16.to_be_bytes());
-        out.extend_from_slice(&payload);
-        Some(base64::engine::general_purpose::STANDARD.encode(out))
-    } else {
-        None
+    let res = match &req.witness {
+        WitnessInput::JsonRows { rows } => {
+            let rows = rows_from_json(rows, req.air.k).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+            prover.prove(&rows, req.return_proof)?
+        }
+        WitnessInput::Framed { data_b64 } => {
+            // `n_rows ≤ tier_max` was checked on entry.
+            prover.prove(&framed_witness(data_b64, req.air.k, n_rows)?, req.return_proof)?
+        }
     };
 
-    Ok(Json(ProveRes {
-        header: header_v,
-        proof_b64,
-    }))
+    Ok(Json(res))
 }
 
 async fn verify(
@@ -1425,38 +1705,37 @@ async fn verify(
         return Err((StatusCode::BAD_REQUEST, format!("unsupported proof version {ver}")));
     }
     let payload = &buf[10..];
-    let mut slice = payload;
-    let proof: Proof = CanonicalDeserialize::deserialize_compressed(&mut slice)
-        .map_err(|e| (StatusCode::BAD_REQUEST, format!("deserialize proof: {e}")))?;
-
-    let domain = myzkp::domain::Domain {
-        n: proof.header.domain_n as usize,
-        omega: proof.header.domain_omega,
-        zh_c: proof.header.zh_c,
-    };
-    let pcs_wires = PcsParams {
-        max_degree: domain.n - 1,
-        basis: proof.header.basis_wires,
-        srs_placeholder: (),
-    };
-    let pcs_coeff = PcsParams {
-        max_degree: domain.n - 1,
-        basis: Basis::Coefficient,
-        srs_placeholder: (),
-    };
-    let vp = VerifyParams {
-        domain,
-        pcs_wires,
-        pcs_coeff,
+    // The header leads the payload; it selects the backend (and whether an SRS is needed).
+    let header: ProofHeader = CanonicalDeserialize::deserialize_compressed(&mut &payload[..])
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("deserialize proof header: {e}")))?;
+
+    let outcome = match header.pcs {
+        PcsKind::Kzg => {
+            require_srs(st.max_rows).await?;
+            verify_with(payload, Kzg)?
+        }
+        PcsKind::Fri => {
+            let fri = Fri::from_header(&header).ok_or((
+                StatusCode::BAD_REQUEST,
+                "FRI parameters in the proof header are malformed or unbound".to_string(),
+            ))?;
+            if fri.security_bits() < Fri::default().security_bits() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("FRI proof offers only {} conjectured bits", fri.security_bits()),
+                ));
+            }
+            verify_with(payload, fri)?
+        }
+        PcsKind::Ipa => verify_with(payload, Ipa)?,
     };
-    let verifier = SchedVerifier { params: &vp };
 
-    if let Err(e) = verifier.verify(&proof) {
+    if let Err(reason) = outcome {
         return Ok((
             StatusCode::OK,
             Json(VerifyRes {
                 status: "failed",
-                reason: Some(format!("{e}")),
+                reason: Some(reason),
             }),
         ));
     }
@@ -1503,10 +1782,10 @@ async fn inspect(
     }
     let payload = &buf[10..];
     let mut slice = payload;
-    let proof: Proof = CanonicalDeserialize::deserialize_compressed(&mut slice)
-        .map_err(|e| (StatusCode::BAD_REQUEST, format!("deserialize proof: {e}")))?;
+    let header: ProofHeader = CanonicalDeserialize::deserialize_compressed(&mut slice)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("deserialize proof header: {e}")))?;
 
-    Ok(Json(header_view(&proof)))
+    Ok(Json(header_view(&header)))
 }
 
 async fn prove_checked(
@@ -1514,7 +1793,9 @@ async fn prove_checked(
     headers: HeaderMap,
     req: Json<ProveReq>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    require_srs(st.max_rows).await?;
+    if parse_backend(&req.pcs.backend)? == PcsKind::Kzg {
+        require_srs(st.max_rows).await?;
+    }
     prove(st, headers, req).await
 }
 
@@ -1523,7 +1804,8 @@ async fn verify_checked(
     headers: HeaderMap,
     mp: Multipart,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    require_srs(st.max_rows).await?;
+    // SRS readiness depends on the proof's backend, so `verify` checks it
+    // after reading the header.
     verify(st, headers, mp).await
 }
 
@@ -1855,7 +2137,7 @@ async fn admin_init_srs(
         return Err((StatusCode::UNAUTHORIZED, "bad admin token".into()));
     }
 
-    if SRS_INITIALIZED.get().is_some() {
+    if SERVER_SRS.get().is_some() {
         return Err((
             StatusCode::CONFLICT,
             "SRS already initialized (restart server to reinitialize)".into(),
@@ -1863,7 +2145,7 @@ async fn admin_init_srs(
     }
 
     #[cfg(not(feature = "dev-srs"))]
-    {
+    let srs = {
         let g1_path = std::env::var("SSZKP_SRS_G1_PATH").map_err(|_| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -1881,42 +2163,65 @@ async fn admin_init_srs(
         eprintln!("  G1: {}", g1_path);
         eprintln!("  G2: {}", g2_path);
 
-        let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(&g1_path, req.max_degree)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Load G1 SRS: {}", e)))?;
-
         let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(&g2_path)
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Load G2 SRS: {}", e)))?;
 
+        let srs = if myzkp::srs_file::is_srs_file(&g1_path) {
+            open_srs_file(&g1_path, tau_g2, req.max_degree)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        } else {
+            let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(&g1_path, req.max_degree)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Load G1 SRS: {}", e)))?;
+            Srs::new(g1_powers, Some(tau_g2))
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Invalid SRS: {}", e)))?
+        };
+
         if req.validate_pairing {
             eprintln!("Performing cryptographic pairing check...");
-            myzkp::srs_setup::validate_g1_pairing(&g1_powers, tau_g2)
+            let head = srs
+                .committer_key()
+                .powers(0, 2.min(srs.max_degree() + 1))
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Pairing check: {}", e)))?;
+            myzkp::srs_setup::validate_g1_pairing(&head, tau_g2)
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Pairing check: {}", e)))?;
             eprintln!("✓ Pairing check passed");
         }
 
-        myzkp::pcs::load_srs_g1(&g1_powers);
-        myzkp::pcs::load_srs_g2(tau_g2);
-
         eprintln!("✓ Production SRS loaded successfully");
-    }
+        srs
+    };
 
     #[cfg(feature = "dev-srs")]
-    {
+    let srs = {
         eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         eprintln!("⚠️  WARNING: Using DEVELOPMENT SRS (NOT FOR PRODUCTION!)");
         eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
         let (g1_powers, tau_g2) = myzkp::srs_setup::generate_dev_srs(req.max_degree);
-        myzkp::pcs::load_srs_g1(&g1_powers);
-        myzkp::pcs::load_srs_g2(tau_g2);
+        let srs = Srs::new(g1_powers, Some(tau_g2))
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Invalid SRS: {}", e)))?;
 
         eprintln!("✓ Dev SRS generated (degree={})", req.max_degree);
-    }
+        srs
+    };
 
-    SRS_INITIALIZED.set(true).ok();
+    let srs = attach_lagrange_keys(srs).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    // The prefix proofs carry, not a scan of the whole file.
+    let g1_dig = srs
+        .g1_prefix_digest(req.max_degree)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("SRS digest: {}", e)))?;
+    let g2_dig = srs.g2_digest();
+    let ceremony = identify_srs_ceremony(&srs, req.max_degree).map_err(|e| (StatusCode::FORBIDDEN, e))?;
+    if SERVER_SRS.set(srs).is_err() {
+        return Err((
+            StatusCode::CONFLICT,
+            "SRS already initialized (restart server to reinitialize)".into(),
+        ));
+    }
 
-    let g1_dig = myzkp::pcs::srs_g1_digest();
-    let g2_dig = myzkp::pcs::srs_g2_digest();
+    if let Some(name) = &ceremony {
+        SRS_CEREMONY.set(name.clone()).ok();
+    }
 
     eprintln!("SRS initialized successfully:");
     eprintln!("  G1 digest: {:02x?}", g1_dig);
@@ -1947,6 +2252,22 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Starting TinyZKP API server");
 
+    // A broken registry file is a config error. An empty registry is not:
+    // the transparent backends need no SRS, and with nothing registered any
+    // KZG SRS is accepted (and reported as an unknown ceremony).
+    let ceremonies = myzkp::srs_setup::ceremony_registry()?;
+    let allow_unknown_srs = myzkp::srs_setup::allow_unknown_srs_from_env();
+    if allow_unknown_srs {
+        warn!("⚠️  SSZKP_ALLOW_UNKNOWN_SRS set: SRS files outside the ceremony registry are accepted");
+    } else if ceremonies.is_empty() && !cfg!(feature = "dev-srs") {
+        warn!(
+            "⚠️  No known ceremonies registered: any KZG SRS is accepted until {} names a \
+             registry file (see `srs_convert digest`)",
+            myzkp::srs_setup::CEREMONY_REGISTRY_ENV
+        );
+    }
+    info!("Ceremony registry: {} known SRS", ceremonies.len());
+
     let addr: SocketAddr = std::env::var("TINYZKP_ADDR")
         .ok()
         .and_then(|s| s.parse().ok())
@@ -1990,6 +2311,34 @@ async fn main() -> anyhow::Result<()> {
         .map(|s| s == "true")
         .unwrap_or(true);
 
+    // Blocked-IFFT policy: spill only large domains, never more than the cap,
+    // encrypted and unlinked so nothing witness-derived outlives a request.
+    let mut transform = TransformConfig::default()
+        .with_backend(
+            std::env::var("TINYZKP_TRANSFORM")
+                .ok()
+                .map(|s| s.parse())
+                .transpose()
+                .map_err(|e: String| anyhow::anyhow!("TINYZKP_TRANSFORM: {e}"))?
+                .unwrap_or(TransformBackend::Auto {
+                    min_spill_n: TransformBackend::DEFAULT_AUTO_SPILL_N,
+                }),
+        )
+        .with_max_spill_bytes(
+            std::env::var("TINYZKP_MAX_SPILL_BYTES")
+                .ok()
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("TINYZKP_MAX_SPILL_BYTES: {e}"))?
+                .unwrap_or(1 << 30),
+        )
+        .with_encrypt_spill(true)
+        .with_cleanup(SpillCleanup::Unlinked);
+    if let Ok(dir) = std::env::var("TINYZKP_SPILL_DIR") {
+        transform = transform.with_spill_dir(dir);
+    }
+    info!("✅ Transform policy: {:?}", transform);
+
     let stripe = StripeClient::new(std::env::var("STRIPE_SECRET_KEY")?);
     let price_pro = std::env::var("STRIPE_PRICE_PRO")?;
     let price_scale = std::env::var("STRIPE_PRICE_SCALE")
@@ -2084,6 +2433,7 @@ async fn main() -> anyhow::Result<()> {
             success_url,
             cancel_url,
             portal_return_url,
+            transform,
         })
         .layer(cors)
         .layer(TraceLayer::new_for_http());
@@ -0,0 +1,39 @@
+//! Compare two Fiat–Shamir transcript traces
+//!
+//! Usage:
+//!   transcript_diff <left.json> <right.json>
+//!
+//! Traces come from `prover --trace`, `verifier --trace` or any other
+//! implementation that emits the `transcript_trace` JSON format. Prints the
+//! first divergent step and exits with status 1, or exits 0 if the traces
+//! are identical.
+
+#![forbid(unsafe_code)]
+
+use std::{env, fs, process::ExitCode};
+
+use myzkp::transcript_trace::{diff, TranscriptTrace};
+
+fn load(path: &str) -> anyhow::Result<TranscriptTrace> {
+    let text = fs::read_to_string(path).map_err(|e| anyhow::anyhow!("read {path}: {e}"))?;
+    TranscriptTrace::from_json(&text).map_err(|e| anyhow::anyhow!("parse {path}: {e}"))
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        return Err(anyhow::anyhow!("usage: transcript_diff <left.json> <right.json>"));
+    }
+    let (left, right) = (load(&args[1])?, load(&args[2])?);
+
+    match diff(&left, &right) {
+        None => {
+            eprintln!("✓ Traces match ({} events)", left.events.len());
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(d) => {
+            println!("{d}");
+            Ok(ExitCode::from(1))
+        }
+    }
+}
@@ -5,7 +5,8 @@
 //!
 //! Updates in this revision (format unchanged):
 //! - **SRS digest enforcement**: compare loaded SRS (G1/G2) digests against the
-//!   proof header and error clearly on mismatch.
+//!   proof header and error clearly on mismatch. The G1 digest covers the
+//!   first N powers only, so a larger SRS from the same ceremony verifies.
 //! - **Production SRS validation**: uses `srs_setup` module for comprehensive validation.
 //! - **Header authority**: the verifier *trusts the proof header* for domain
 //!   parameters. Any `--zh-c` CLI flag is politely ignored (we print a note).
@@ -15,6 +16,20 @@
 //!   matches feature flags (e.g., `zeta-shift` adds Z@ω·ζ).
 //! - Delegation to `scheduler::Verifier` is unchanged; this wrapper only handles
 //!   IO, basic shape sanity, and environment/header consistency.
+//! - **Backend dispatch**: the header's `pcs` field selects KZG, FRI or IPA.
+//!   FRI/IPA proofs are transparent, so no SRS is loaded; their `srs_g1_digest`
+//!   slot must match this build's backend parameters instead.
+//! - **Curve check**: the header's curve id must match the build (BN254 by
+//!   default, BLS12-381 with `--features bls12-381`). BLS12-381 builds also
+//!   accept `--srs-eth-json <transcript.json>` from the Ethereum KZG ceremony.
+//! - **Ceremony registry**: non-dev builds refuse a KZG SRS whose digests
+//!   match no known ceremony unless `--allow-unknown-srs` or
+//!   `SSZKP_ALLOW_UNKNOWN_SRS=1` is given.
+//! - **FRI parameters** come from the proof header (`fri::Fri::from_header`);
+//!   proofs below `--fri-min-bits` conjectured security (default: that of
+//!   `Fri::default()`, 128) are refused.
+//! - `--trace <file>` writes the replayed Fiat–Shamir schedule as JSON (also on
+//!   failure); compare it with a prover trace using `transcript_diff`.
 
 #![forbid(unsafe_code)]
 #![allow(unused_imports)]
@@ -27,10 +42,13 @@ use std::{env, fs, io::Read, path::Path};
 use ark_ff::{fields::Field, FftField, One, Zero};
 use ark_serialize::CanonicalDeserialize;
 use myzkp::{
+    curve::{CurveId, CURVE_ID},
     domain::{self, domain_digest},
-    pcs::{self, Basis, PcsParams},
+    fri::Fri,
+    ipa::Ipa,
+    pcs::{self, Basis, PcsKind, PcsParams, PolynomialCommitment, Srs},
     scheduler::Verifier,
-    VerifyParams, F,
+    Proof, ProofHeader, VerifyParams, F,
 };
 
 // 8-byte magic: "SSZKPv2" + NUL terminator to match the 8-byte read/write.
@@ -47,139 +65,23 @@ fn parse_flag(args: &[String], key: &str) -> Option<String> {
     None
 }
 
-fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = env::args().collect();
-
-    // Optional CLI hint for wires basis — for UX only. The header is authoritative.
-    let basis_str = parse_flag(&args, "--basis").unwrap_or_else(|| "eval".to_string());
-    let basis_wires_cli = match basis_str.as_str() {
-        "coeff" | "coefficient" => Basis::Coefficient,
-        _ => Basis::Evaluation,
-    };
-
-    // Users may pass --zh-c out of habit; make it explicit we ignore it.
-    if let Some(cli_zh) = parse_flag(&args, "--zh-c") {
-        eprintln!("Note: Ignoring CLI --zh-c={}; verifier uses zh_c from the proof header.", cli_zh);
-    }
-
-    // ============================================================================
-    // SRS loading with comprehensive validation
-    // ============================================================================
-    
-    let srs_g1_path = parse_flag(&args, "--srs-g1");
-    let srs_g2_path = parse_flag(&args, "--srs-g2");
-
-    #[cfg(feature = "dev-srs")]
-    {
-        if srs_g1_path.is_none() || srs_g2_path.is_none() {
-            eprintln!("(dev-srs) Using deterministic in-crate SRS.");
-            eprintln!("⚠️  WARNING: Dev SRS is NOT SECURE - for testing only!");
-            eprintln!("    For production, pass --srs-g1 and --srs-g2 with trusted ceremony files.");
-        }
-    }
-
-    #[cfg(not(feature = "dev-srs"))]
-    {
-        if srs_g1_path.is_none() || srs_g2_path.is_none() {
-            return Err(anyhow::anyhow!(
-                "Non-dev build: --srs-g1 and --srs-g2 are REQUIRED for trusted KZG verification.\n\
-                 \n\
-                 For development, rebuild with --features dev-srs.\n\
-                 For production, provide SRS files from a trusted ceremony:\n\
-                 https://github.com/privacy-scaling-explorations/perpetualpowersoftau"
-            ));
-        }
-    }
-
-    // Load and validate SRS files (if provided)
-    if let Some(g1_path_str) = srs_g1_path {
-        let g1_path = Path::new(&g1_path_str);
-        eprintln!("Loading G1 SRS from {}...", g1_path.display());
-
-        // Note: We don't know the required degree yet (it's in the proof header),
-        // so we just load and do basic validation. Degree check happens after
-        // reading the proof.
-        let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(g1_path, 0)
-            .map_err(|e| anyhow::anyhow!("Failed to load/validate G1 SRS: {}", e))?;
-
-        pcs::load_srs_g1(&g1_powers);
-        eprintln!("✓ Loaded and validated {} G1 powers", g1_powers.len());
-    }
-
-    if let Some(g2_path_str) = srs_g2_path {
-        let g2_path = Path::new(&g2_path_str);
-        eprintln!("Loading G2 SRS from {}...", g2_path.display());
-
-        let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(g2_path)
-            .map_err(|e| anyhow::anyhow!("Failed to load/validate G2 SRS: {}", e))?;
-
-        pcs::load_srs_g2(tau_g2);
-        eprintln!("✓ Loaded and validated G2 element ([τ]G₂)");
-    }
-
-    // ============================================================================
-    // Read and parse proof file
-    // ============================================================================
-
-    eprintln!();
-    eprintln!("Reading proof from proof.bin...");
-    
-    let mut file = fs::File::open("proof.bin").map_err(|e| anyhow::anyhow!("open proof.bin: {e}"))?;
-    let mut magic = [0u8; 8];
-    file.read_exact(&mut magic)?;
-    if &magic != FILE_MAGIC {
-        return Err(anyhow::anyhow!("bad proof file: missing magic header"));
-    }
-    let mut ver_bytes = [0u8; 2];
-    file.read_exact(&mut ver_bytes)?;
-    let file_ver = u16::from_be_bytes(ver_bytes);
-    if file_ver != FILE_VERSION_SUPPORTED {
-        return Err(anyhow::anyhow!(
-            "unsupported proof version: got {}, support {}",
-            file_ver, FILE_VERSION_SUPPORTED
-        ));
-    }
-    let mut payload = Vec::new();
-    file.read_to_end(&mut payload)?;
-    let mut slice = payload.as_slice();
-    let proof: myzkp::Proof = CanonicalDeserialize::deserialize_compressed(&mut slice)
+/// Decode the full ark-compressed `Proof` payload for backend `P`.
+fn decode_proof<P: PolynomialCommitment>(payload: &[u8]) -> anyhow::Result<Proof<P>> {
+    let mut slice = payload;
+    let proof: Proof<P> = CanonicalDeserialize::deserialize_compressed(&mut slice)
         .map_err(|e| anyhow::anyhow!("deserialize proof: {}", e))?;
+    eprintln!("✓ Proof file parsed successfully");
+    Ok(proof)
+}
 
-    eprintln!("✓ Proof file parsed successfully (v{}, {} bytes)", file_ver, payload.len());
-
-    // ============================================================================
-    // Verify SRS digests match proof header
-    // ============================================================================
-
-    eprintln!();
-    eprintln!("Verifying cryptographic parameters...");
-
-    // SRS digests are the *only* binding between proof and locally loaded SRS.
-    let srs_g1_d = pcs::srs_g1_digest();
-    let srs_g2_d = pcs::srs_g2_digest();
-    
-    if proof.header.srs_g1_digest != srs_g1_d {
-        eprintln!("ERROR: SRS G1 digest mismatch!");
-        eprintln!("  Proof expects:  {:02x?}", proof.header.srs_g1_digest);
-        eprintln!("  Loaded SRS has: {:02x?}", srs_g1_d);
-        eprintln!();
-        eprintln!("This means the proof was generated with a different G1 SRS.");
-        eprintln!("You must use the EXACT SAME SRS files that were used to generate the proof.");
-        return Err(anyhow::anyhow!("SRS G1 digest mismatch vs proof header"));
-    }
-    
-    if proof.header.srs_g2_digest != srs_g2_d {
-        eprintln!("ERROR: SRS G2 digest mismatch!");
-        eprintln!("  Proof expects:  {:02x?}", proof.header.srs_g2_digest);
-        eprintln!("  Loaded SRS has: {:02x?}", srs_g2_d);
-        eprintln!();
-        eprintln!("This means the proof was generated with a different G2 SRS.");
-        eprintln!("You must use the EXACT SAME SRS files that were used to generate the proof.");
-        return Err(anyhow::anyhow!("SRS G2 digest mismatch vs proof header"));
-    }
-
-    eprintln!("✓ SRS digests match proof header");
-
+/// Header-driven shape checks, then delegation to `scheduler::Verifier`.
+fn check_and_verify<P: PolynomialCommitment>(
+    proof: &Proof<P>,
+    backend: P,
+    srs: Option<Srs>,
+    basis_wires_cli: Basis,
+    trace_path: Option<&str>,
+) -> anyhow::Result<()> {
     // Domain from the header (authoritative). We do not accept CLI overrides.
     let domain = myzkp::domain::Domain {
         n: proof.header.domain_n as usize,
@@ -196,8 +98,8 @@ fn main() -> anyhow::Result<()> {
             basis_wires_cli, basis_wires
         );
     }
-    let pcs_wires = PcsParams { max_degree: domain.n - 1, basis: basis_wires, srs_placeholder: () };
-    let pcs_coeff = PcsParams { max_degree: domain.n - 1, basis: Basis::Coefficient, srs_placeholder: () };
+    let pcs_wires = PcsParams { max_degree: domain.n - 1, basis: basis_wires, srs: srs.clone() };
+    let pcs_coeff = PcsParams { max_degree: domain.n - 1, basis: Basis::Coefficient, srs };
 
     // Human-friendly summary for quick inspection.
     eprintln!();
@@ -244,10 +146,241 @@ fn main() -> anyhow::Result<()> {
     eprintln!("Running cryptographic verification...");
     
     let verify_params = VerifyParams { domain: domain.clone(), pcs_wires, pcs_coeff };
-    let verifier = Verifier { params: &verify_params };
+    let verifier = Verifier::with_pcs(&verify_params, backend);
 
     // Replay Fiat–Shamir and enforce pairings via the scheduler.
-    verifier.verify(&proof).map_err(|e| anyhow::anyhow!("verification failed: {e}"))?;
+    let res = match trace_path {
+        Some(path) => {
+            let (res, trace) = verifier.verify_with_trace(proof);
+            fs::write(path, trace.to_json()).map_err(|e| anyhow::anyhow!("write {path}: {e}"))?;
+            eprintln!("✓ Wrote transcript trace to {path} ({} events)", trace.events.len());
+            res
+        }
+        None => verifier.verify(proof),
+    };
+    res.map_err(|e| anyhow::anyhow!("verification failed: {e}"))?;
+
+    Ok(())
+}
+
+/// Build the SRS from an Ethereum KZG ceremony `transcript.json` (BLS12-381 builds).
+#[cfg(feature = "bls12-381")]
+fn load_eth_ceremony(path: &str, degree: usize) -> anyhow::Result<Srs> {
+    eprintln!("Loading Ethereum KZG ceremony transcript from {}...", path);
+    let (g1_powers, tau_g2) = myzkp::srs_setup::load_ethereum_ceremony_json(path, degree)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate ceremony transcript: {}", e))?;
+    eprintln!("✓ Loaded and validated {} G1 powers and [τ]G₂", g1_powers.len());
+    Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("ceremony transcript: {e}"))
+}
+
+#[cfg(not(feature = "bls12-381"))]
+fn load_eth_ceremony(_path: &str, _degree: usize) -> anyhow::Result<Srs> {
+    Err(anyhow::anyhow!("--srs-eth-json needs a BLS12-381 build (--features bls12-381)"))
+}
+
+/// The KZG SRS for a proof of `degree`: `--srs-eth-json`, `--srs-g1` +
+/// `--srs-g2`, or (dev builds only) the deterministic dev SRS when neither is given.
+fn load_srs(args: &[String], degree: usize) -> anyhow::Result<Srs> {
+    if let Some(p) = parse_flag(args, "--srs-eth-json") {
+        return load_eth_ceremony(&p, degree);
+    }
+
+    let (g1_path_str, g2_path_str) = match (parse_flag(args, "--srs-g1"), parse_flag(args, "--srs-g2")) {
+        (Some(g1), Some(g2)) => (g1, g2),
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(anyhow::anyhow!("--srs-g1 and --srs-g2 must be given together"));
+        }
+        #[cfg(feature = "dev-srs")]
+        (None, None) => {
+            eprintln!("(dev-srs) Using deterministic in-crate SRS.");
+            eprintln!("⚠️  WARNING: Dev SRS is NOT SECURE - for testing only!");
+            eprintln!("    For production, pass --srs-g1 and --srs-g2 with trusted ceremony files.");
+            let (g1_powers, tau_g2) = myzkp::srs_setup::generate_dev_srs(degree);
+            return Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("dev SRS: {e}"));
+        }
+        #[cfg(not(feature = "dev-srs"))]
+        (None, None) => {
+            return Err(anyhow::anyhow!(
+                "Non-dev build: --srs-g1 and --srs-g2 (or --srs-eth-json) are REQUIRED for trusted KZG.\n\
+                 \n\
+                 For development, rebuild with --features dev-srs.\n\
+                 For production, provide SRS files from a trusted ceremony:\n\
+                 https://github.com/privacy-scaling-explorations/perpetualpowersoftau"
+            ));
+        }
+    };
+
+    let g1_path = Path::new(&g1_path_str);
+    eprintln!("Loading G1 SRS from {}...", g1_path.display());
+    // Only basic validation here: a short file surfaces below as a digest
+    // mismatch, with a hint about the power count.
+    let g1_powers = myzkp::srs_setup::load_and_validate_g1_srs(g1_path, 0)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate G1 SRS: {}", e))?;
+    eprintln!("✓ Loaded and validated {} G1 powers", g1_powers.len());
+
+    let g2_path = Path::new(&g2_path_str);
+    eprintln!("Loading G2 SRS from {}...", g2_path.display());
+    let tau_g2 = myzkp::srs_setup::load_and_validate_g2_srs(g2_path)
+        .map_err(|e| anyhow::anyhow!("Failed to load/validate G2 SRS: {}", e))?;
+    eprintln!("✓ Loaded and validated G2 element ([τ]G₂)");
+
+    Srs::new(g1_powers, Some(tau_g2)).map_err(|e| anyhow::anyhow!("SRS: {e}"))
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().collect();
+
+    // Optional CLI hint for wires basis — for UX only. The header is authoritative.
+    let basis_str = parse_flag(&args, "--basis").unwrap_or_else(|| "eval".to_string());
+    let trace_path = parse_flag(&args, "--trace");
+    let basis_wires_cli = match basis_str.as_str() {
+        "coeff" | "coefficient" => Basis::Coefficient,
+        _ => Basis::Evaluation,
+    };
+
+    // Users may pass --zh-c out of habit; make it explicit we ignore it.
+    if let Some(cli_zh) = parse_flag(&args, "--zh-c") {
+        eprintln!("Note: Ignoring CLI --zh-c={}; verifier uses zh_c from the proof header.", cli_zh);
+    }
+
+    // ============================================================================
+    // Read and parse proof file
+    // ============================================================================
+
+    eprintln!();
+    eprintln!("Reading proof from proof.bin...");
+    
+    let mut file = fs::File::open("proof.bin").map_err(|e| anyhow::anyhow!("open proof.bin: {e}"))?;
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic)?;
+    if &magic != FILE_MAGIC {
+        return Err(anyhow::anyhow!("bad proof file: missing magic header"));
+    }
+    let mut ver_bytes = [0u8; 2];
+    file.read_exact(&mut ver_bytes)?;
+    let file_ver = u16::from_be_bytes(ver_bytes);
+    if file_ver != FILE_VERSION_SUPPORTED {
+        return Err(anyhow::anyhow!(
+            "unsupported proof version: got {}, support {}",
+            file_ver, FILE_VERSION_SUPPORTED
+        ));
+    }
+    let mut payload = Vec::new();
+    file.read_to_end(&mut payload)?;
+
+    // The curve id sits right after the u16 header version. Check it before
+    // decoding any curve-specific field elements.
+    let curve = payload
+        .get(2..3)
+        .and_then(|b| CurveId::deserialize_compressed(b).ok())
+        .ok_or_else(|| anyhow::anyhow!("proof header truncated or has unknown curve id"))?;
+    if curve != CURVE_ID {
+        return Err(anyhow::anyhow!(
+            "proof was produced on {}, this verifier is built for {}",
+            curve.as_str(),
+            CURVE_ID.as_str()
+        ));
+    }
+
+    // The header is the first field of `Proof`, so it can be read on its own to
+    // pick the backend before decoding the rest.
+    let header: ProofHeader = CanonicalDeserialize::deserialize_compressed(&mut payload.as_slice())
+        .map_err(|e| anyhow::anyhow!("deserialize proof header: {}", e))?;
+    eprintln!("✓ Proof header parsed (v{}, {} bytes, pcs={})", file_ver, payload.len(), header.pcs.as_str());
+
+
+    // Transparent backends derive their digests from fixed parameters only.
+    let header_pcs = PcsParams {
+        max_degree: (header.domain_n as usize).saturating_sub(1),
+        basis: header.basis_wires,
+        srs: None,
+    };
+    match header.pcs {
+        PcsKind::Fri => {
+            let fri = Fri::from_header(&header)
+                .ok_or_else(|| anyhow::anyhow!("FRI parameters in the proof header are malformed or unbound"))?;
+            let min_bits: usize = parse_flag(&args, "--fri-min-bits")
+                .map(|s| s.parse().map_err(|_| anyhow::anyhow!("--fri-min-bits: expected an integer")))
+                .transpose()?
+                .unwrap_or_else(|| Fri::default().security_bits());
+            if fri.security_bits() < min_bits {
+                return Err(anyhow::anyhow!(
+                    "FRI proof offers {} conjectured bits (blowup 2^{}, {} queries); at least {} required",
+                    fri.security_bits(),
+                    fri.blowup_log,
+                    fri.num_queries,
+                    min_bits
+                ));
+            }
+            eprintln!(
+                "Transparent FRI proof: no SRS required (blowup 2^{}, {} queries, final length {}).",
+                fri.blowup_log, fri.num_queries, fri.final_len
+            );
+            let proof: Proof<Fri> = decode_proof(&payload)?;
+            check_and_verify(&proof, fri, None, basis_wires_cli, trace_path.as_deref())?;
+        }
+        PcsKind::Ipa => {
+            if header.srs_g1_digest != Ipa.srs_digests(&header_pcs)?.0 {
+                return Err(anyhow::anyhow!("IPA generator digest mismatch vs proof header"));
+            }
+            eprintln!("Transparent IPA proof: no SRS required.");
+            let proof: Proof<Ipa> = decode_proof(&payload)?;
+            check_and_verify(&proof, Ipa, None, basis_wires_cli, trace_path.as_deref())?;
+        }
+        PcsKind::Kzg => {
+            // ============================================================================
+            // SRS loading with comprehensive validation
+            // ============================================================================
+    
+            let srs = load_srs(&args, header_pcs.max_degree)?;
+
+            // ============================================================================
+            // Verify SRS digests match proof header
+            // ============================================================================
+
+            eprintln!();
+            eprintln!("Verifying cryptographic parameters...");
+
+            // SRS digests are the *only* binding between proof and locally loaded SRS.
+            // The header covers the first N powers, so any large enough copy matches.
+            let (prefix_g1_d, srs_g2_d) = pcs::Kzg.srs_digests(&header_pcs.clone().with_srs(srs.clone()))?;
+
+            if header.srs_g1_digest != prefix_g1_d {
+                eprintln!("ERROR: SRS G1 digest mismatch!");
+                eprintln!("  Proof expects:  {:02x?}", header.srs_g1_digest);
+                eprintln!("  Loaded SRS has: {:02x?} (first {} powers)", prefix_g1_d, header.domain_n);
+                eprintln!();
+                eprintln!("This means the proof was generated with a different G1 SRS,");
+                eprintln!("or the loaded SRS has fewer than {} powers.", header.domain_n);
+                eprintln!("Use an SRS from the same ceremony that was used to generate the proof.");
+                return Err(anyhow::anyhow!("SRS G1 digest mismatch vs proof header"));
+            }
+    
+            if header.srs_g2_digest != srs_g2_d {
+                eprintln!("ERROR: SRS G2 digest mismatch!");
+                eprintln!("  Proof expects:  {:02x?}", header.srs_g2_digest);
+                eprintln!("  Loaded SRS has: {:02x?}", srs_g2_d);
+                eprintln!();
+                eprintln!("This means the proof was generated with a different G2 SRS.");
+                eprintln!("Use an SRS from the same ceremony that was used to generate the proof.");
+                return Err(anyhow::anyhow!("SRS G2 digest mismatch vs proof header"));
+            }
+
+            eprintln!("✓ SRS digests match proof header");
+
+            // Production builds only accept ceremonies in the registry (once it has any).
+            let allow_unknown = cfg!(feature = "dev-srs")
+                || args.iter().any(|a| a == "--allow-unknown-srs")
+                || myzkp::srs_setup::allow_unknown_srs_from_env();
+            match myzkp::srs_setup::require_known_ceremony(&srs, header_pcs.max_degree, allow_unknown) {
+                Ok(Some(c)) => eprintln!("✓ SRS is from ceremony: {}", c.name),
+                Ok(None) => eprintln!("  SRS ceremony unknown (not in registry)"),
+                Err(e) => return Err(anyhow::anyhow!("Refusing SRS: {}", e)),
+            }
+            let proof: Proof = decode_proof(&payload)?;
+            check_and_verify(&proof, pcs::Kzg, Some(srs), basis_wires_cli, trace_path.as_deref())?;
+        }
+    }
 
     eprintln!();
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -255,7 +388,7 @@ fn main() -> anyhow::Result<()> {
     eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     eprintln!();
     eprintln!("The proof is cryptographically valid and was generated using");
-    eprintln!("the same SRS as loaded by this verifier.");
+    eprintln!("the same commitment parameters (SRS or FRI settings) as this verifier.");
     
     println!("Verifier result: ok");
     Ok(())
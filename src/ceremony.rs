@@ -0,0 +1,359 @@
+//! Local multi-party powers-of-tau ceremony
+//!
+//! `generate_production_srs` trusts a single machine to forget τ. This module
+//! runs the standard sequential update ceremony instead: every participant
+//! takes the current SRS directory, multiplies in a fresh secret `s`, and
+//! publishes the updated SRS plus a proof that they know `s`. τ stays unknown
+//! as long as *one* participant discarded their `s`.
+//!
+//! A ceremony directory holds:
+//!
+//! ```text
+//! G1.bin          [τ^i]G1, i ≤ max_degree   (srs_setup::save_g1_srs format)
+//! G2.bin          [G2, τG2]                 (srs_setup::save_g2_srs format)
+//! transcript.bin  "SSZKPct1" ‖ ark-compressed Transcript
+//! ```
+//!
+//! so the latest directory can be passed straight to the prover, the verifier
+//! or the API server. Participants exchange directories as plain files.
+//!
+//! # Update proofs
+//!
+//! Contribution `j` updates `τ_j = s_j·τ_{j−1}` (starting from `τ_0 = 1`) and
+//! records `[τ_j]G1`, `[τ_j]G2`, `[s_j]G1`, `[s_j]G2` and a Schnorr proof of
+//! knowledge of `s_j` whose challenge binds `[τ_{j−1}]G1`, `[τ_{j−1}]G2` and
+//! the participant's name. [`verify_transcript`](crate::ceremony::verify_transcript) checks, per contribution:
+//!
+//! 1. the Schnorr proof: `z·G1 = R + c·[s]G1`;
+//! 2. `e([s]G1, G2) = e(G1, [s]G2)` — both keys carry the same `s`;
+//! 3. `e([τ_j]G1, G2) = e([τ_{j−1}]G1, [s]G2)` — the update used that `s`;
+//! 4. `e(G1, [τ_j]G2) = e([τ_j]G1, G2)` — G1 and G2 agree on `τ_j`.
+//!
+//! [`verify`](crate::ceremony::verify) then ties the chain to the files: `G1.bin[1]` and `G2.bin` must
+//! be the last recorded `[τ]`, every G1 power must be a consecutive power of
+//! it ([`crate::srs_setup::validate_g1_powers`]), and the SRS digests must
+//! match the last record. [`contribute`](crate::ceremony::contribute) runs the same checks on its input
+//! before building on it.
+
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+
+use crate::curve::{Engine, G1Affine, G1Projective, G2Affine};
+use crate::pcs::Srs;
+use crate::srs_setup::{self, SrsSetupError};
+use crate::F;
+
+/// G1 powers file inside a ceremony directory.
+pub const G1_FILE: &str = "G1.bin";
+/// `[G2, τG2]` file inside a ceremony directory.
+pub const G2_FILE: &str = "G2.bin";
+/// Contribution transcript inside a ceremony directory.
+pub const TRANSCRIPT_FILE: &str = "transcript.bin";
+
+const TRANSCRIPT_MAGIC: &[u8; 8] = b"SSZKPct1";
+const POK_DOMAIN: &[u8] = b"SSZKP.ceremony.pok.v1";
+
+/// One participant's public record.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ContributionProof {
+    /// Participant name (bound into the proof of knowledge).
+    pub name: String,
+    /// `[τ_j]G1` after this contribution.
+    pub tau_g1: G1Affine,
+    /// `[τ_j]G2` after this contribution.
+    pub tau_g2: G2Affine,
+    /// `[s]G1` for the participant's secret `s`.
+    pub s_g1: G1Affine,
+    /// `[s]G2`.
+    pub s_g2: G2Affine,
+    /// Schnorr commitment `R = k·G1`.
+    pub pok_r: G1Affine,
+    /// Schnorr response `z = k + c·s`.
+    pub pok_z: F,
+    /// `pcs::srs_g1_digest` of the SRS this contribution produced.
+    pub g1_digest: [u8; 32],
+    /// `pcs::srs_g2_digest` of the SRS this contribution produced.
+    pub g2_digest: [u8; 32],
+}
+
+/// Ordered list of contributions for one SRS size.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Transcript {
+    /// Degree of the SRS (`max_degree + 1` G1 powers).
+    pub max_degree: u64,
+    /// Contributions, oldest first.
+    pub contributions: Vec<ContributionProof>,
+}
+
+impl Transcript {
+    /// Read a `transcript.bin`.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, SrsSetupError> {
+        let bytes = std::fs::read(path.as_ref())?;
+        let body = bytes
+            .strip_prefix(TRANSCRIPT_MAGIC.as_slice())
+            .ok_or_else(|| SrsSetupError::Deserialize("not a ceremony transcript (bad magic)".into()))?;
+        Self::deserialize_compressed(body)
+            .map_err(|e| SrsSetupError::Deserialize(format!("ceremony transcript: {}", e)))
+    }
+
+    /// Write a `transcript.bin`.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), SrsSetupError> {
+        let mut bytes = TRANSCRIPT_MAGIC.to_vec();
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| SrsSetupError::Validation(format!("transcript serialize: {}", e)))?;
+        std::fs::write(path.as_ref(), bytes)?;
+        Ok(())
+    }
+}
+
+/// Outcome of [`verify`].
+#[derive(Debug, Clone)]
+pub struct CeremonyReport {
+    /// Degree of the SRS.
+    pub max_degree: usize,
+    /// Participant names, oldest first. Empty means τ = 1 (insecure).
+    pub contributors: Vec<String>,
+    /// `pcs::srs_g1_digest` of the directory's SRS.
+    pub g1_digest: [u8; 32],
+    /// `pcs::srs_g2_digest` of the directory's SRS.
+    pub g2_digest: [u8; 32],
+}
+
+/// Fiat–Shamir challenge for a contribution's proof of knowledge.
+fn pok_challenge(
+    prev_g1: &G1Affine,
+    prev_g2: &G2Affine,
+    name: &str,
+    s_g1: &G1Affine,
+    s_g2: &G2Affine,
+    r: &G1Affine,
+) -> F {
+    let mut bytes = Vec::new();
+    for p in [prev_g1, s_g1, r] {
+        p.serialize_compressed(&mut bytes).expect("serialize G1");
+    }
+    for p in [prev_g2, s_g2] {
+        p.serialize_compressed(&mut bytes).expect("serialize G2");
+    }
+    let mut h = blake3::Hasher::new();
+    h.update(POK_DOMAIN);
+    h.update(&(name.len() as u64).to_be_bytes());
+    h.update(name.as_bytes());
+    h.update(&bytes);
+    let mut wide = [0u8; 64];
+    h.finalize_xof().fill(&mut wide);
+    F::from_le_bytes_mod_order(&wide)
+}
+
+/// Check every contribution against its predecessor (see module docs).
+///
+/// Returns the final `([τ]G1, [τ]G2)`, which is `(G1, G2)` for an empty
+/// transcript.
+pub fn verify_transcript(transcript: &Transcript) -> Result<(G1Affine, G2Affine), SrsSetupError> {
+    let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+    let (mut prev_g1, mut prev_g2) = (g1, g2);
+    for (j, c) in transcript.contributions.iter().enumerate() {
+        let fail = |what: &str| {
+            SrsSetupError::PairingCheck(format!("contribution {} ({}): {}", j + 1, c.name, what))
+        };
+        if c.s_g1.is_zero() || c.tau_g1.is_zero() {
+            return Err(fail("zero secret"));
+        }
+        let ch = pok_challenge(&prev_g1, &prev_g2, &c.name, &c.s_g1, &c.s_g2, &c.pok_r);
+        if g1 * c.pok_z != c.pok_r + c.s_g1 * ch {
+            return Err(fail("proof of knowledge does not verify"));
+        }
+        if Engine::pairing(c.s_g1, g2) != Engine::pairing(g1, c.s_g2) {
+            return Err(fail("[s]G1 and [s]G2 disagree"));
+        }
+        if Engine::pairing(c.tau_g1, g2) != Engine::pairing(prev_g1, c.s_g2) {
+            return Err(fail("[τ]G1 is not the previous [τ]G1 times s"));
+        }
+        if Engine::pairing(g1, c.tau_g2) != Engine::pairing(c.tau_g1, g2) {
+            return Err(fail("[τ]G1 and [τ]G2 disagree"));
+        }
+        (prev_g1, prev_g2) = (c.tau_g1, c.tau_g2);
+    }
+    Ok((prev_g1, prev_g2))
+}
+
+/// Fully checked contents of a ceremony directory.
+struct Verified {
+    powers: Vec<G1Affine>,
+    tau_g2: G2Affine,
+    transcript: Transcript,
+    report: CeremonyReport,
+}
+
+fn load_verified(dir: &Path) -> Result<Verified, SrsSetupError> {
+    let transcript = Transcript::read(dir.join(TRANSCRIPT_FILE))?;
+    let max_degree = transcript.max_degree as usize;
+    let powers = srs_setup::load_and_validate_g1_srs(dir.join(G1_FILE), max_degree)?;
+    if powers.len() != max_degree + 1 {
+        return Err(SrsSetupError::Validation(format!(
+            "{} has {} powers, transcript is for degree {}",
+            G1_FILE,
+            powers.len(),
+            max_degree
+        )));
+    }
+    let tau_g2 = srs_setup::load_and_validate_g2_srs(dir.join(G2_FILE))?;
+
+    let (tau_g1, last_g2) = verify_transcript(&transcript)?;
+    if powers[1] != tau_g1 || tau_g2 != last_g2 {
+        return Err(SrsSetupError::Validation(
+            "SRS files do not match the last contribution in the transcript".into(),
+        ));
+    }
+    srs_setup::validate_g1_powers(&powers, tau_g2)?;
+
+    let srs = Srs::new(powers.clone(), Some(tau_g2))
+        .map_err(|e| SrsSetupError::Validation(e.to_string()))?;
+    let g1_digest = srs.g1_digest().map_err(|e| SrsSetupError::Validation(e.to_string()))?;
+    let g2_digest = srs.g2_digest();
+    if let Some(last) = transcript.contributions.last() {
+        if (last.g1_digest, last.g2_digest) != (g1_digest, g2_digest) {
+            return Err(SrsSetupError::Validation(
+                "SRS digests do not match the last contribution in the transcript".into(),
+            ));
+        }
+    }
+
+    let report = CeremonyReport {
+        max_degree,
+        contributors: transcript.contributions.iter().map(|c| c.name.clone()).collect(),
+        g1_digest,
+        g2_digest,
+    };
+    Ok(Verified { powers, tau_g2, transcript, report })
+}
+
+/// Start a ceremony in `dir` from τ = 1 (every power is the generator).
+///
+/// The result is public knowledge; it only becomes usable after at least one
+/// [`contribute`].
+pub fn init(dir: impl AsRef<Path>, max_degree: usize) -> Result<(), SrsSetupError> {
+    if max_degree == 0 {
+        return Err(SrsSetupError::Validation("ceremony needs max_degree ≥ 1".into()));
+    }
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    srs_setup::save_g1_srs(dir.join(G1_FILE), &vec![G1Affine::generator(); max_degree + 1])?;
+    srs_setup::save_g2_srs(dir.join(G2_FILE), G2Affine::generator())?;
+    Transcript { max_degree: max_degree as u64, contributions: Vec::new() }
+        .write(dir.join(TRANSCRIPT_FILE))
+}
+
+/// Verify a ceremony directory: the whole contribution chain plus the files.
+pub fn verify(dir: impl AsRef<Path>) -> Result<CeremonyReport, SrsSetupError> {
+    load_verified(dir.as_ref()).map(|v| v.report)
+}
+
+/// Verify `in_dir`, multiply in a fresh secret drawn from `rng`, and write the
+/// updated SRS and transcript to `out_dir` (which may equal `in_dir`).
+///
+/// The secret only lives inside this call; `rng` should be a CSPRNG the
+/// participant does not keep the seed of.
+pub fn contribute<R: RngCore + CryptoRng>(
+    in_dir: impl AsRef<Path>,
+    out_dir: impl AsRef<Path>,
+    name: &str,
+    rng: &mut R,
+) -> Result<ContributionProof, SrsSetupError> {
+    let Verified { powers, tau_g2, mut transcript, .. } = load_verified(in_dir.as_ref())?;
+
+    let s = loop {
+        let s = F::rand(rng);
+        if !s.is_zero() {
+            break s;
+        }
+    };
+    let mut scaled = Vec::with_capacity(powers.len());
+    let mut s_pow = F::from(1u64);
+    for p in &powers {
+        scaled.push(*p * s_pow);
+        s_pow *= s;
+    }
+    let powers = G1Projective::normalize_batch(&scaled);
+    drop(scaled);
+    let new_tau_g2 = (tau_g2 * s).into_affine();
+
+    let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+    let (s_g1, s_g2) = ((g1 * s).into_affine(), (g2 * s).into_affine());
+    let k = F::rand(rng);
+    let pok_r = (g1 * k).into_affine();
+    let prev_g1 = transcript.contributions.last().map_or(g1, |c| c.tau_g1);
+    let ch = pok_challenge(&prev_g1, &tau_g2, name, &s_g1, &s_g2, &pok_r);
+    let pok_z = k + ch * s;
+
+    let out_dir = out_dir.as_ref();
+    std::fs::create_dir_all(out_dir)?;
+    srs_setup::save_g1_srs(out_dir.join(G1_FILE), &powers)?;
+    srs_setup::save_g2_srs(out_dir.join(G2_FILE), new_tau_g2)?;
+
+    let tau_g1 = powers[1];
+    let srs = Srs::new(powers, Some(new_tau_g2))
+        .map_err(|e| SrsSetupError::Validation(e.to_string()))?;
+    let g1_digest = srs.g1_digest().map_err(|e| SrsSetupError::Validation(e.to_string()))?;
+    let proof = ContributionProof {
+        name: name.to_string(),
+        tau_g1,
+        tau_g2: new_tau_g2,
+        s_g1,
+        s_g2,
+        pok_r,
+        pok_z,
+        g1_digest,
+        g2_digest: srs.g2_digest(),
+    };
+    transcript.contributions.push(proof.clone());
+    transcript.write(out_dir.join(TRANSCRIPT_FILE))?;
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contributions_chain_verifies_and_tampering_is_caught() {
+        let mut rng = rand::thread_rng();
+        let root = tempfile::tempdir().unwrap();
+        let (d0, d1, d2) = (root.path().join("0"), root.path().join("1"), root.path().join("2"));
+
+        init(&d0, 7).unwrap();
+        assert!(verify(&d0).unwrap().contributors.is_empty());
+        contribute(&d0, &d1, "alice", &mut rng).unwrap();
+        let bob = contribute(&d1, &d2, "bob", &mut rng).unwrap();
+
+        let report = verify(&d2).unwrap();
+        assert_eq!(report.contributors, ["alice", "bob"]);
+        assert_eq!((report.g1_digest, report.g2_digest), (bob.g1_digest, bob.g2_digest));
+
+        // The output is an ordinary SRS directory.
+        let powers = srs_setup::load_and_validate_g1_srs(d2.join(G1_FILE), 7).unwrap();
+        let tau_g2 = srs_setup::load_and_validate_g2_srs(d2.join(G2_FILE)).unwrap();
+        srs_setup::validate_g1_pairing(&powers, tau_g2).unwrap();
+
+        // A renamed contribution no longer matches its proof of knowledge.
+        let mut t = Transcript::read(d2.join(TRANSCRIPT_FILE)).unwrap();
+        t.contributions[0].name = "mallory".into();
+        assert!(verify_transcript(&t).is_err());
+
+        // Dropping a contribution breaks the chain.
+        let mut t = Transcript::read(d2.join(TRANSCRIPT_FILE)).unwrap();
+        t.contributions.remove(0);
+        assert!(verify_transcript(&t).is_err());
+
+        // Files from an earlier step do not match the latest transcript.
+        std::fs::copy(d1.join(G1_FILE), d2.join(G1_FILE)).unwrap();
+        assert!(verify(&d2).is_err());
+        assert!(contribute(&d2, root.path().join("3"), "carol", &mut rng).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Curve selection: BN254 (default) or BLS12-381
+//!
+//! Every module that needs the scalar field, the G₁/G₂ groups or the pairing
+//! engine imports them from here instead of naming a curve crate directly.
+//! The curve is chosen at **compile time**:
+//!
+//! - default: `ark_bn254` (EVM precompiles, existing ceremonies);
+//! - `--features bls12-381`: `ark_bls12_381` (Ethereum KZG ceremony,
+//!   Filecoin, Zcash-style tooling).
+//!
+//! A binary therefore proves and verifies on exactly one curve. The choice
+//! is recorded in `ProofHeader::curve` as a [`CurveId`], so a verifier built
+//! for the other curve rejects the proof up front with a clear error instead
+//! of failing inside deserialization or a pairing.
+//!
+//! [`CurveId`]: crate::curve::CurveId
+
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+#[cfg(not(feature = "bls12-381"))]
+pub use ark_bn254::{
+    Bn254 as Engine, Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+
+#[cfg(feature = "bls12-381")]
+pub use ark_bls12_381::{
+    Bls12_381 as Engine, Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+
+/// Pairing-friendly curve a proof was produced on (recorded in the header).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveId {
+    /// BN254 (alt_bn128).
+    Bn254,
+    /// BLS12-381.
+    Bls12_381,
+}
+
+/// Curve this build was compiled for.
+#[cfg(not(feature = "bls12-381"))]
+pub const CURVE_ID: CurveId = CurveId::Bn254;
+/// Curve this build was compiled for.
+#[cfg(feature = "bls12-381")]
+pub const CURVE_ID: CurveId = CurveId::Bls12_381;
+
+impl CurveId {
+    /// Stable lowercase name (CLI output, API payloads).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CurveId::Bn254 => "bn254",
+            CurveId::Bls12_381 => "bls12-381",
+        }
+    }
+}
+
+impl CanonicalSerialize for CurveId {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut w: W,
+        _cm: Compress,
+    ) -> Result<(), SerializationError> {
+        let byte = match self {
+            CurveId::Bn254 => 0u8,
+            CurveId::Bls12_381 => 1u8,
+        };
+        w.write_all(&[byte])?;
+        Ok(())
+    }
+    fn serialized_size(&self, _cm: Compress) -> usize {
+        1
+    }
+}
+impl CanonicalDeserialize for CurveId {
+    fn deserialize_with_mode<R: Read>(
+        mut r: R,
+        _cm: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut b = [0u8; 1];
+        r.read_exact(&mut b)?;
+        match b[0] {
+            0 => Ok(CurveId::Bn254),
+            1 => Ok(CurveId::Bls12_381),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+impl Valid for CurveId {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+// ------------------------- Scalar parsing -------------------------
+
+/// Why a textual scalar was rejected by [`parse_scalar`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScalarParseError {
+    /// No digits after the optional sign / `0x` prefix.
+    #[error("empty field element `{0}`")]
+    Empty(String),
+    /// A character that is not a digit of the encoding.
+    #[error("invalid digit in field element `{0}`")]
+    Digit(String),
+    /// The magnitude is `≥ r`; values are never reduced silently.
+    #[error("field element `{0}` is not below the scalar field modulus")]
+    OutOfRange(String),
+}
+
+/// Parse a scalar-field element from text, at full width.
+///
+/// Accepted encodings, each with an optional leading `-` or `+`:
+/// - decimal of any length (`12345`, a 77-digit hash, …);
+/// - hexadecimal with a `0x`/`0X` prefix (`0xdeadbeef`).
+///
+/// `_` digit separators are ignored. `-x` means `r − x`, so negative integers
+/// and fixed-point values scaled to integers round-trip. The magnitude must be
+/// `< r`: out-of-range values are an error rather than being reduced or
+/// truncated.
+pub fn parse_scalar(s: &str) -> Result<Fr, ScalarParseError> {
+    use ark_ff::PrimeField;
+
+    let t = s.trim();
+    let (neg, t) = match t.as_bytes().first() {
+        Some(b'-') => (true, &t[1..]),
+        Some(b'+') => (false, &t[1..]),
+        _ => (false, t),
+    };
+    let (radix, digits) = match t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
+        Some(h) => (16u32, h),
+        None => (10u32, t),
+    };
+    if digits.chars().all(|c| c == '_') {
+        return Err(ScalarParseError::Empty(s.to_string()));
+    }
+    let mut acc = <Fr as PrimeField>::BigInt::from(0u64);
+    for c in digits.chars().filter(|c| *c != '_') {
+        let d = c.to_digit(radix).ok_or_else(|| ScalarParseError::Digit(s.to_string()))?;
+        // acc = acc·radix + d, failing on any carry out of the top limb.
+        let mut carry = d as u128;
+        for limb in acc.as_mut() {
+            let v = (*limb as u128) * (radix as u128) + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 || acc >= Fr::MODULUS {
+            return Err(ScalarParseError::OutOfRange(s.to_string()));
+        }
+    }
+    let x = Fr::from_bigint(acc).ok_or_else(|| ScalarParseError::OutOfRange(s.to_string()))?;
+    Ok(if neg { -x } else { x })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{BigInteger, PrimeField};
+
+    #[test]
+    fn parse_scalar_full_width_encodings() {
+        assert_eq!(parse_scalar("42").unwrap(), Fr::from(42u64));
+        assert_eq!(parse_scalar(" 0x2A ").unwrap(), Fr::from(42u64));
+        assert_eq!(parse_scalar("-7").unwrap(), -Fr::from(7u64));
+        assert_eq!(parse_scalar("-0").unwrap(), Fr::from(0u64));
+        assert_eq!(parse_scalar("+1_000").unwrap(), Fr::from(1000u64));
+
+        // Above 2^64 / 2^128 is kept exactly: (2^128 + 1) = (2^64)^2 + 1.
+        let two64 = Fr::from(u64::MAX) + Fr::from(1u64);
+        assert_eq!(parse_scalar("340282366920938463463374607431768211457").unwrap(), two64 * two64 + Fr::from(1u64));
+
+        // r − 1 is the largest accepted value; r itself and beyond are errors.
+        let r = Fr::MODULUS.to_string();
+        let mut r_minus_1 = Fr::MODULUS;
+        r_minus_1.sub_with_borrow(&1u64.into());
+        assert_eq!(parse_scalar(&r_minus_1.to_string()).unwrap(), -Fr::from(1u64));
+        assert!(matches!(parse_scalar(&r), Err(ScalarParseError::OutOfRange(_))));
+        assert!(matches!(parse_scalar(&format!("-{r}")), Err(ScalarParseError::OutOfRange(_))));
+        assert!(matches!(parse_scalar(&format!("0x1{}", "0".repeat(64))), Err(ScalarParseError::OutOfRange(_))));
+
+        assert!(matches!(parse_scalar("0x"), Err(ScalarParseError::Empty(_))));
+        assert!(matches!(parse_scalar("-_"), Err(ScalarParseError::Empty(_))));
+        assert!(matches!(parse_scalar("12a"), Err(ScalarParseError::Digit(_))));
+        assert!(matches!(parse_scalar("1.5"), Err(ScalarParseError::Digit(_))));
+    }
+}
@@ -1,7 +1,7 @@
 //! Domain & Transform Primitives
 //!
 //! Evaluation domain `H` with vanishing polynomial `Z_H(X) = X^N − zh_c`,
-//! streaming barycentric evaluation, radix-2 NTT/IFFT, and **tile emitters**
+//! streaming barycentric evaluation, mixed-radix NTT/IFFT, and **tile emitters**
 //! used by commitment/opening streams (never materialize full polynomials).
 //!
 //! ## This revision
@@ -15,16 +15,30 @@
 //! - **Vanishing polynomial**: we explicitly model `Z_H(X) = X^N − zh_c` and
 //!   carry `zh_c` in `Domain` so pads/cosets and extended variants are easy.
 //!
-//! All public APIs are conservative and production-ready; the file-backed,
-//! *in-place* blocked IFFT is optional and off by default, but the façade keeps
-//! the same streaming shape either way.
+//! - **Out-of-core IFFT**: when a [`crate::domain::TransformConfig`] selects the
+//!   tape backend, evaluations spill to a raw fixed-width tape (optionally
+//!   encrypted) and the INTT runs as a recursive six-step (Bailey) transform
+//!   in page-sized reads/writes, about `4·⌈log N / log b_blk⌉` passes over
+//!   the file with `O(b_blk)` elements resident. The prover takes the config
+//!   from `ProveParams::transform`; the legacy `SSZKP_BLOCKED_IFFT=1` switch
+//!   only applies to callers that pass none.
+//!
+//! - **Smooth domain sizes**: `N` need not be a power of two. Any `N | r − 1`
+//!   whose prime factors are in [`crate::domain::SMOOTH_RADICES`] is supported
+//!   (radix-2 fast path, odd radices peeled off recursively), and
+//!   [`crate::domain::next_smooth_size`] picks the smallest such `N ≥ rows`, so
+//!   e.g. a 600K-row trace pads to `9·2^16` instead of `2^20` on BN254.
+//!
+//! All public APIs are conservative and production-ready; the file-backed
+//! blocked IFFT is optional and off by default, but the façade keeps the same
+//! streaming shape either way.
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)]
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-use ark_ff::{Field, One, Zero};
+use ark_ff::{BigInteger, FftField, Field, One, PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use blake3::Hasher;
 
@@ -37,7 +51,8 @@ use crate::F;
 /// Evaluation domain with vanishing polynomial `Z_H(X) = X^N - zh_c`.
 #[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Domain {
-    /// Domain size `N`. Implementations in this crate assume `N` is a power of two.
+    /// Domain size `N`: a power of two, or more generally a smooth size (see
+    /// [`supports_domain_size`]).
     pub n: usize,
     /// Generator `ω` of the multiplicative subgroup `H = {1, ω, …, ω^{N-1}}`.
     pub omega: F,
@@ -56,12 +71,16 @@ pub enum DomainError {
     OmegaNPowNotOne,
     #[error("omega is not primitive: omega^(N/{0}) == 1")]
     OmegaNotPrimitive(usize),
-    #[error("length must be positive power-of-two and divide N (len={len}, N={n})")]
+    #[error("length must be positive and divide N (len={len}, N={n})")]
     BadLen { len: usize, n: usize },
     #[error("evaluation point ζ lies in H (ζ^N == zh_c)")]
     ZetaInDomain,
     #[error("time stream length must be exactly N (got {got}, N={n})")]
     BadStream { got: usize, n: usize },
+    #[error("spilling a size-{n} transform needs {needed} bytes, over the {limit}-byte limit")]
+    SpillLimit { n: usize, needed: u64, limit: u64 },
+    #[error("spill file: {0}")]
+    Spill(String),
 }
 
 impl Domain {
@@ -113,9 +132,9 @@ fn prime_factors(mut n: usize) -> Vec<usize> {
     let mut out = Vec::new();
     let mut p = 2usize;
     while p * p <= n {
-        if n % p == 0 {
+        if n.is_multiple_of(p) {
             out.push(p);
-            while n % p == 0 {
+            while n.is_multiple_of(p) {
                 n /= p;
             }
         }
@@ -127,6 +146,83 @@ fn prime_factors(mut n: usize) -> Vec<usize> {
     out
 }
 
+// ------------------------- Smooth domain sizes -------------------------
+
+/// Prime radices the transforms handle directly. A domain size is usable when
+/// it factors over these and divides `r − 1` (so `F` has an `N`-th root of unity).
+pub const SMOOTH_RADICES: [usize; 6] = [2, 3, 5, 7, 11, 13];
+
+/// `(r − 1) / d` if `d` divides the multiplicative group order, else `None`.
+fn group_order_div(d: u64) -> Option<<F as PrimeField>::BigInt> {
+    let mut q = F::MODULUS;
+    q.sub_with_borrow(&1u64.into());
+    let mut rem = 0u128;
+    for limb in q.as_mut().iter_mut().rev() {
+        let cur = (rem << 64) | *limb as u128;
+        *limb = (cur / d as u128) as u64;
+        rem = cur % d as u128;
+    }
+    (rem == 0).then_some(q)
+}
+
+/// Whether `n` is a valid domain size: positive, [`SMOOTH_RADICES`]-smooth and
+/// a divisor of `r − 1`.
+pub fn supports_domain_size(n: usize) -> bool {
+    if n == 0 {
+        return false;
+    }
+    let mut m = n;
+    for p in SMOOTH_RADICES {
+        while m.is_multiple_of(p) {
+            m /= p;
+        }
+    }
+    m == 1 && group_order_div(n as u64).is_some()
+}
+
+/// Primitive `n`-th root of unity for a supported size. Powers of two use the
+/// field's two-adic root (so existing radix-2 domains are unchanged); other
+/// sizes use `g^{(r−1)/n}` for the field's multiplicative generator `g`.
+pub fn root_of_unity(n: usize) -> Option<F> {
+    if !supports_domain_size(n) {
+        return None;
+    }
+    if n.is_power_of_two() {
+        return F::get_root_of_unity(n as u64);
+    }
+    Some(F::GENERATOR.pow(group_order_div(n as u64)?))
+}
+
+/// Smallest supported domain size `≥ n`: `2^a · o` over the odd smooth
+/// divisors `o` of `r − 1`. `None` only if even `2^{two-adicity}` is too small.
+pub fn next_smooth_size(n: usize) -> Option<usize> {
+    let n = n.max(1);
+    let mut odd = vec![1usize];
+    for p in &SMOOTH_RADICES[1..] {
+        let mut grown = Vec::new();
+        for &o in &odd {
+            let mut v = o;
+            while let Some(next) = v.checked_mul(*p) {
+                if group_order_div(next as u64).is_none() {
+                    break;
+                }
+                grown.push(next);
+                v = next;
+            }
+        }
+        odd.extend(grown);
+    }
+    odd.into_iter()
+        .filter_map(|o| {
+            let mut m = o;
+            while m < n {
+                m = m.checked_mul(2)?;
+            }
+            supports_domain_size(m).then_some(m)
+        })
+        .min()
+}
+
 fn validate_domain_r(d: &Domain) -> Result<(), DomainError> {
     if d.n == 0 {
         return Err(DomainError::NZero);
@@ -243,7 +339,7 @@ pub fn eval_stream_barycentric(
 #[inline]
 fn validate_len_r(d: &Domain, len: usize) -> Result<(), DomainError> {
     validate_domain_r(d)?;
-    if !(len > 0 && len.is_power_of_two() && d.n % len == 0) {
+    if !(len > 0 && d.n.is_multiple_of(len)) {
         return Err(DomainError::BadLen { len, n: d.n });
     }
     Ok(())
@@ -254,7 +350,54 @@ fn primitive_len_root_r(d: &Domain, len: usize) -> Result<F, DomainError> {
     Ok(pow_u64(d.omega, (d.n / len) as u64))
 }
 
+/// Forward DFT with the primitive `a.len()`-th root `root`. Powers of two take
+/// the iterative radix-2 path; other lengths peel off their smallest odd prime
+/// factor with [`ntt_mixed_radix`].
 fn ntt_in_place(a: &mut [F], root: F) {
+    let n = a.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        ntt_radix2_in_place(a, root);
+    } else {
+        ntt_mixed_radix(a, root);
+    }
+}
+
+/// One decimation-in-time step of radix `p` (the smallest odd prime dividing
+/// `n`): DFT the `p` strided subsequences of length `m = n/p`, then combine
+/// `X[k + m·q] = Σ_r root^{r·k} S_r[k] · w^{r·q}` with `w = root^m`.
+fn ntt_mixed_radix(a: &mut [F], root: F) {
+    let n = a.len();
+    let p = prime_factors(n).into_iter().find(|&p| p != 2).expect("odd factor");
+    let m = n / p;
+    let root_p = pow_u64(root, p as u64);
+    let subs: Vec<Vec<F>> = (0..p)
+        .map(|r| {
+            let mut s: Vec<F> = (0..m).map(|j| a[r + p * j]).collect();
+            ntt_in_place(&mut s, root_p);
+            s
+        })
+        .collect();
+    let w = pow_u64(root, m as u64);
+    let w_pows: Vec<F> = std::iter::successors(Some(F::one()), |x| Some(*x * w)).take(p).collect();
+    let mut t = vec![F::zero(); p];
+    let mut root_k = F::one();
+    for k in 0..m {
+        let mut f = F::one();
+        for (r, s) in subs.iter().enumerate() {
+            t[r] = s[k] * f;
+            f *= root_k;
+        }
+        for q in 0..p {
+            a[k + m * q] = t.iter().enumerate().map(|(r, x)| *x * w_pows[(r * q) % p]).sum();
+        }
+        root_k *= root;
+    }
+}
+
+fn ntt_radix2_in_place(a: &mut [F], root: F) {
     let n = a.len();
     debug_assert!(n.is_power_of_two());
 
@@ -292,7 +435,6 @@ fn ntt_in_place(a: &mut [F], root: F) {
 }
 fn intt_in_place(a: &mut [F], root: F) {
     let n = a.len();
-    debug_assert!(n.is_power_of_two());
     let inv_root = root.inverse().expect("root non-zero");
     ntt_in_place(a, inv_root);
     let inv_n = F::from(n as u64).inverse().expect("n != 0");
@@ -335,21 +477,203 @@ pub enum CoeffTileOrder {
     HighToLow,
 }
 
+// -----------------------------------------------------------------------------
+// Transform configuration (backend, spill location and policy)
+// -----------------------------------------------------------------------------
+
+/// Where [`BlockedIfft`] keeps evaluations while transforming.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransformBackend {
+    /// Collect all `N` evaluations in memory and run one IFFT.
+    InMemory,
+    /// Spill to a file and run the out-of-core six-step INTT.
+    Tape,
+    /// Spill when `N >= min_spill_n` and the spill fits `max_spill_bytes`;
+    /// in memory otherwise.
+    Auto { min_spill_n: usize },
+}
+
+impl TransformBackend {
+    /// `min_spill_n` used by `auto` when none is given.
+    pub const DEFAULT_AUTO_SPILL_N: usize = 1 << 20;
+}
+
+impl std::str::FromStr for TransformBackend {
+    type Err = String;
+    /// `memory`, `tape`, `auto` or `auto:<min_spill_n>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "memory" | "in-memory" => Ok(Self::InMemory),
+            "tape" => Ok(Self::Tape),
+            "auto" => Ok(Self::Auto { min_spill_n: Self::DEFAULT_AUTO_SPILL_N }),
+            other => match other.strip_prefix("auto:").map(str::parse) {
+                Some(Ok(min_spill_n)) => Ok(Self::Auto { min_spill_n }),
+                _ => Err(format!(
+                    "unknown transform backend `{other}` (expected memory|tape|auto[:N])"
+                )),
+            },
+        }
+    }
+}
+
+/// What happens to spill files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpillCleanup {
+    /// Delete the file when the transform drops it.
+    OnDrop,
+    /// Unlink the file right after creating it, so nothing is left behind
+    /// even if the process dies (Unix; falls back to `OnDrop` elsewhere).
+    Unlinked,
+    /// Leave the file in place (debugging only).
+    Keep,
+}
+
+impl std::str::FromStr for SpillCleanup {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "drop" | "on-drop" => Ok(Self::OnDrop),
+            "unlink" | "unlinked" => Ok(Self::Unlinked),
+            "keep" => Ok(Self::Keep),
+            other => Err(format!("unknown spill cleanup `{other}` (expected drop|unlink|keep)")),
+        }
+    }
+}
+
+/// How blocked transforms run: backend choice, where and how much they may
+/// spill, and how spill files are protected and cleaned up.
+///
+/// The default is fully in memory. Carried by [`crate::ProveParams`] so each
+/// proof (e.g. each API request) can pick its own strategy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransformConfig {
+    /// Backend selection.
+    pub backend: TransformBackend,
+    /// Directory for spill files; `None` uses [`std::env::temp_dir`].
+    pub spill_dir: Option<PathBuf>,
+    /// Upper bound on the bytes one transform may spill (it needs two tapes
+    /// of `N` elements). `Tape` errors above it; `Auto` stays in memory.
+    pub max_spill_bytes: Option<u64>,
+    /// Encrypt spilled (witness-derived) data under a per-file key that only
+    /// lives in memory.
+    pub encrypt_spill: bool,
+    /// Spill file cleanup policy.
+    pub cleanup: SpillCleanup,
+}
+
+impl Default for TransformConfig {
+    fn default() -> Self {
+        Self {
+            backend: TransformBackend::InMemory,
+            spill_dir: None,
+            max_spill_bytes: None,
+            encrypt_spill: false,
+            cleanup: SpillCleanup::OnDrop,
+        }
+    }
+}
+
+impl TransformConfig {
+    /// The legacy process-wide switch: `SSZKP_BLOCKED_IFFT=1` selects `Tape`,
+    /// anything else `InMemory`. Only used where no config is passed in.
+    pub fn from_env() -> Self {
+        let blocked = std::env::var("SSZKP_BLOCKED_IFFT")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let backend = if blocked { TransformBackend::Tape } else { TransformBackend::InMemory };
+        Self { backend, ..Self::default() }
+    }
+
+    pub fn with_backend(mut self, backend: TransformBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_spill_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_max_spill_bytes(mut self, limit: u64) -> Self {
+        self.max_spill_bytes = Some(limit);
+        self
+    }
+
+    pub fn with_encrypt_spill(mut self, on: bool) -> Self {
+        self.encrypt_spill = on;
+        self
+    }
+
+    pub fn with_cleanup(mut self, cleanup: SpillCleanup) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
+
+    /// Bytes a size-`n` transform spills on the tape backend.
+    pub fn spill_bytes(&self, n: usize) -> u64 {
+        2 * n as u64 * SpillTape::slot_size(self.encrypt_spill) as u64
+    }
+
+    /// Whether a size-`n` transform spills under this config, or
+    /// [`DomainError::SpillLimit`] if `Tape` is forced past the limit.
+    pub fn spills(&self, n: usize) -> Result<bool, DomainError> {
+        let needed = self.spill_bytes(n);
+        let fits = self.max_spill_bytes.is_none_or(|limit| needed <= limit);
+        match self.backend {
+            TransformBackend::InMemory => Ok(false),
+            TransformBackend::Tape if fits => Ok(true),
+            TransformBackend::Tape => Err(DomainError::SpillLimit {
+                n,
+                needed,
+                limit: self.max_spill_bytes.unwrap_or(u64::MAX),
+            }),
+            TransformBackend::Auto { min_spill_n } => Ok(n >= min_spill_n && fits),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // File-backed tape for blocked transforms (optional path)
 // -----------------------------------------------------------------------------
 
+/// Raw fixed-width element file backing the blocked transforms.
+///
+/// Element `i` lives in slot `i` of `slot_size` bytes: its uncompressed
+/// encoding, or with encryption an 8-byte write counter followed by that
+/// encoding XORed with a BLAKE3 keystream of `(key, counter, i)`. Every write
+/// takes a fresh counter, so rewriting a slot never reuses keystream. All I/O
+/// goes through whole ranges (one seek plus one `read`/`write` per page);
+/// callers size pages to their working set.
 #[derive(Debug)]
 struct SpillTape {
     path: PathBuf,
     file: File,
-    elem_size: usize,
     len: usize, // number of elements written (logical)
+    io_calls: u64, // page reads + writes (diagnostics)
+    key: Option<[u8; 32]>,
+    ks: Vec<u8>, // keystream scratch, one page at a time
+    writes: u64,
+    remove_on_drop: bool,
+}
+
+/// Spill-file I/O failure as a [`DomainError`].
+fn spill_err(e: std::io::Error) -> DomainError {
+    DomainError::Spill(e.to_string())
 }
 
 impl SpillTape {
     fn create() -> std::io::Result<Self> {
-        let mut path = std::env::temp_dir();
+        Self::create_in(&TransformConfig::default())
+    }
+
+    fn create_in(cfg: &TransformConfig) -> std::io::Result<Self> {
+        let mut path = match &cfg.spill_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                dir.clone()
+            }
+            None => std::env::temp_dir(),
+        };
         let unique = format!(
             "sszkp_tape_{}_{}.bin",
             std::process::id(),
@@ -357,78 +681,276 @@ impl SpillTape {
         );
         path.push(unique);
         let file = OpenOptions::new()
-            .create(true)
+            .create_new(true)
             .read(true)
             .write(true)
-            .truncate(true)
             .open(&path)?;
-        Ok(Self { path, file, elem_size: 0, len: 0 })
+        let key = cfg.encrypt_spill.then(|| {
+            let mut k = [0u8; 32];
+            rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut k);
+            k
+        });
+        let mut remove_on_drop = cfg.cleanup != SpillCleanup::Keep;
+        if cfg.cleanup == SpillCleanup::Unlinked && cfg!(unix) {
+            std::fs::remove_file(&path)?;
+            remove_on_drop = false;
+        }
+        Ok(Self { path, file, len: 0, io_calls: 0, key, ks: Vec::new(), writes: 0, remove_on_drop })
+    }
+
+    /// Bytes per element slot.
+    fn slot_size(encrypted: bool) -> usize {
+        F::zero().uncompressed_size() + if encrypted { 8 } else { 0 }
+    }
+
+    /// XOR the keystream over every slot of the page `buf` (elements from
+    /// `start`), each keyed by the counter stored in its first 8 bytes. The
+    /// page's keystream is generated into the reused `ks` buffer.
+    fn apply_keystream(key: &[u8; 32], ks: &mut Vec<u8>, start: usize, buf: &mut [u8], slot: usize) {
+        let body = slot - 8;
+        ks.resize(buf.len() / slot * body, 0);
+        for (k, (c, out)) in buf.chunks_exact(slot).zip(ks.chunks_exact_mut(body)).enumerate() {
+            let mut h = Hasher::new_keyed(key);
+            h.update(&c[..8]);
+            h.update(&((start + k) as u64).to_le_bytes());
+            h.finalize_xof().fill(out);
+        }
+        for (c, k) in buf.chunks_exact_mut(slot).zip(ks.chunks_exact(body)) {
+            c[8..].iter_mut().zip(k).for_each(|(b, k)| *b ^= k);
+        }
     }
 
     fn remove(&mut self) {
         // Best-effort cleanup.
-        let _ = std::fs::remove_file(&self.path);
+        if self.remove_on_drop {
+            let _ = std::fs::remove_file(&self.path);
+        }
     }
 
-    #[inline]
-    fn ensure_elem_size(&mut self, first: &F) -> std::io::Result<()> {
-        if self.elem_size == 0 {
-            let mut buf = Vec::with_capacity(64);
-            first
-                .serialize_compressed(&mut buf)
-                .expect("field serialize");
-            self.elem_size = buf.len();
+    fn append_block(&mut self, block: &[F]) -> std::io::Result<()> {
+        self.write_range(self.len, block)
+    }
+
+    /// Elements `start .. start + len` with one seek and one read.
+    fn read_range(&mut self, start: usize, len: usize) -> std::io::Result<Vec<F>> {
+        if len == 0 {
+            return Ok(Vec::new());
         }
-        Ok(())
+        let slot = Self::slot_size(self.key.is_some());
+        self.seek_elem(start)?;
+        let mut buf = vec![0u8; len * slot];
+        self.file.read_exact(&mut buf)?;
+        self.io_calls += 1;
+        let body_at = if let Some(key) = &self.key {
+            Self::apply_keystream(key, &mut self.ks, start, &mut buf, slot);
+            8
+        } else {
+            0
+        };
+        Ok(buf
+            .chunks_exact(slot)
+            .map(|c| F::deserialize_uncompressed_unchecked(&c[body_at..]).expect("field deserialize"))
+            .collect())
     }
 
-    fn append_block(&mut self, block: &[F]) -> std::io::Result<()> {
-        if block.is_empty() {
+    /// Overwrite (or extend) elements from `start` with one seek and one write.
+    fn write_range(&mut self, start: usize, vals: &[F]) -> std::io::Result<()> {
+        if vals.is_empty() {
             return Ok(());
         }
-        self.ensure_elem_size(&block[0])?;
-        self.file.seek(SeekFrom::End(0))?;
-        let mut tmp = Vec::with_capacity(self.elem_size);
-        for x in block {
-            tmp.clear();
-            x.serialize_compressed(&mut tmp).expect("field serialize");
-            debug_assert_eq!(tmp.len(), self.elem_size);
-            self.file.write_all(&tmp)?;
-            self.len += 1;
+        let slot = Self::slot_size(self.key.is_some());
+        let counter = self.writes;
+        self.writes += 1;
+        let mut buf = Vec::with_capacity(vals.len() * slot);
+        for x in vals {
+            if self.key.is_some() {
+                buf.extend_from_slice(&counter.to_le_bytes());
+            }
+            x.serialize_uncompressed(&mut buf).expect("field serialize");
+        }
+        debug_assert_eq!(buf.len(), vals.len() * slot);
+        if let Some(key) = &self.key {
+            Self::apply_keystream(key, &mut self.ks, start, &mut buf, slot);
         }
+        self.seek_elem(start)?;
+        self.file.write_all(&buf)?;
+        self.io_calls += 1;
+        self.len = self.len.max(start + vals.len());
         Ok(())
     }
 
     #[inline]
     fn seek_elem(&mut self, idx: usize) -> std::io::Result<()> {
-        debug_assert!(self.elem_size > 0);
-        self.file
-            .seek(SeekFrom::Start((idx as u64) * (self.elem_size as u64)))?;
+        let slot = Self::slot_size(self.key.is_some()) as u64;
+        self.file.seek(SeekFrom::Start(idx as u64 * slot))?;
         Ok(())
     }
+}
 
-    fn read_elem(&mut self, idx: usize) -> std::io::Result<F> {
-        self.seek_elem(idx)?;
-        let mut buf = vec![0u8; self.elem_size];
-        self.file.read_exact(&mut buf)?;
-        let mut rd = &buf[..];
-        let v = F::deserialize_compressed(&mut rd).expect("field deserialize");
-        Ok(v)
+impl Drop for SpillTape {
+    fn drop(&mut self) {
+        self.remove();
     }
+}
 
-    fn write_elem(&mut self, idx: usize, v: &F) -> std::io::Result<()> {
-        self.seek_elem(idx)?;
-        let mut buf = Vec::with_capacity(self.elem_size);
-        v.serialize_compressed(&mut buf).expect("field serialize");
-        debug_assert_eq!(buf.len(), self.elem_size);
-        self.file.write_all(&buf)?;
-        Ok(())
+// -----------------------------------------------------------------------------
+// Out-of-core six-step (Bailey) transform over two tapes
+// -----------------------------------------------------------------------------
+
+/// Smallest working set (elements) used for tape transforms, so tiny `b_blk`
+/// values do not degrade into element-sized I/O.
+const TAPE_MIN_WORKSET: usize = 1 << 10;
+
+/// Working-set size for tape transforms: `b_blk` (at least
+/// [`TAPE_MIN_WORKSET`]) rounded down to a power of two.
+fn tape_workset(b_blk: usize) -> usize {
+    let m = b_blk.max(TAPE_MIN_WORKSET);
+    1usize << (usize::BITS - 1 - m.leading_zeros())
+}
+
+/// Length-`len` DFT with primitive root `root` of `src[off..off+len]` into
+/// `dst[off..off+len]`, every output multiplied by `scale`. `src`'s range is
+/// clobbered (used as scratch). Holds at most `mem` elements at a time.
+///
+/// For `len > mem` this is Bailey's six-step split `len = n1·n2` with `n1`
+/// the largest divisor of `len` that fits in `mem`: transpose, `n1` recursive length-`n2` DFTs, transpose with the
+/// `root^{j1·k2}` twiddles folded in, `n2` in-memory length-`n1` DFTs,
+/// transpose. Every step is a pass of page-sized reads and writes, so the
+/// whole transform takes about `4·⌈log len / log mem⌉` sequential-ish passes.
+fn fft_on_tapes(
+    src: &mut SpillTape,
+    dst: &mut SpillTape,
+    off: usize,
+    len: usize,
+    root: F,
+    scale: F,
+    mem: usize,
+) -> std::io::Result<()> {
+    debug_assert!(mem.is_power_of_two() && mem >= 4);
+    let n1 = (2..=mem.min(len)).rev().find(|d| len.is_multiple_of(*d)).unwrap_or(len);
+    if len <= mem || n1 == len {
+        let mut a = src.read_range(off, len)?;
+        ntt_in_place(&mut a, root);
+        if !scale.is_one() {
+            a.iter_mut().for_each(|x| *x *= scale);
+        }
+        return dst.write_range(off, &a);
+    }
+    let n2 = len / n1;
+
+    // 1) x[j1 + n1·j2] (n2 × n1) → rows j1 of length n2.
+    transpose_on_tapes(src, dst, off, n2, n1, None, F::one(), mem)?;
+    // 2) Row DFTs of length n2 (recursive when n2 > mem); results land in `src`.
+    let root_n2 = pow_u64(root, n1 as u64);
+    for r in 0..n1 {
+        fft_on_tapes(dst, src, off + r * n2, n2, root_n2, F::one(), mem)?;
     }
+    // 3+4) Twiddle by root^{j1·k2} while transposing to rows k2 of length n1.
+    transpose_on_tapes(src, dst, off, n1, n2, Some(root), F::one(), mem)?;
+    // 5) Row DFTs of length n1 (fit in memory).
+    let root_n1 = pow_u64(root, n2 as u64);
+    for r in 0..n2 {
+        let start = off + r * n1;
+        let mut a = dst.read_range(start, n1)?;
+        ntt_in_place(&mut a, root_n1);
+        src.write_range(start, &a)?;
+    }
+    // 6) Z[k2][k1] → X[n2·k1 + k2].
+    transpose_on_tapes(src, dst, off, n2, n1, None, scale, mem)
 }
 
-impl Drop for SpillTape {
-    fn drop(&mut self) {
-        self.remove();
+/// Transpose the row-major `rows × cols` matrix at `src[off..]` into
+/// `dst[off..]` (`cols × rows`), multiplying entry `(i, j)` by
+/// `twiddle^{i·j}` if given and by `scale`. Works in tiles of at most `mem`
+/// elements (clipped at the matrix edge): one read per tile row, one write
+/// per tile column.
+#[allow(clippy::too_many_arguments)]
+fn transpose_on_tapes(
+    src: &mut SpillTape,
+    dst: &mut SpillTape,
+    off: usize,
+    rows: usize,
+    cols: usize,
+    twiddle: Option<F>,
+    scale: F,
+    mem: usize,
+) -> std::io::Result<()> {
+    let page = 1usize << (mem.trailing_zeros() / 2);
+    let th = rows.min(page);
+    let tw = cols.min(mem / th);
+    let mut tile = vec![F::zero(); th * tw];
+    for r0 in (0..rows).step_by(th) {
+        let rh = th.min(rows - r0);
+        let w_row = twiddle.map(|w| (r0..r0 + rh).map(|i| pow_u64(w, i as u64)).collect::<Vec<_>>());
+        for c0 in (0..cols).step_by(tw) {
+            let cw = tw.min(cols - c0);
+            for i in 0..rh {
+                let row = src.read_range(off + (r0 + i) * cols + c0, cw)?;
+                let mut f = match &w_row {
+                    Some(w) => pow_u64(w[i], c0 as u64) * scale,
+                    None => scale,
+                };
+                for (j, x) in row.into_iter().enumerate() {
+                    tile[j * rh + i] = x * f;
+                    if let Some(w) = &w_row {
+                        f *= w[i];
+                    }
+                }
+            }
+            for j in 0..cw {
+                dst.write_range(off + (c0 + j) * rows + r0, &tile[j * rh..(j + 1) * rh])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Low→high coefficients with random access by index range: on a spill tape
+/// when produced out-of-core, in memory otherwise.
+///
+/// [`BlockedIfft::finish_coeffs`] hands its result over in this form so a
+/// consumer that needs a non-sequential walk (the quotient fold-down reads
+/// `R` at stride `N`) keeps only the tiles it is working on.
+pub struct CoeffTape {
+    store: CoeffStore,
+    len: usize,
+}
+
+enum CoeffStore {
+    Memory(Vec<F>),
+    Tape(SpillTape),
+}
+
+impl CoeffTape {
+    /// Spill low→high coefficient tiles to a fresh tape file.
+    pub fn from_tiles(tiles: impl IntoIterator<Item = Vec<F>>) -> std::io::Result<Self> {
+        let mut tape = SpillTape::create()?;
+        for tile in tiles {
+            tape.append_block(&tile)?;
+        }
+        Ok(Self { len: tape.len, store: CoeffStore::Tape(tape) })
+    }
+
+    /// Number of coefficients.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no coefficients.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Coefficients `start .. start + len`, clipped to [`Self::len`].
+    pub fn read_range(&mut self, start: usize, len: usize) -> std::io::Result<Vec<F>> {
+        let end = start.saturating_add(len).min(self.len);
+        if start >= end {
+            return Ok(Vec::new());
+        }
+        match &mut self.store {
+            CoeffStore::Memory(v) => Ok(v[start..end].to_vec()),
+            CoeffStore::Tape(t) => t.read_range(start, end - start),
+        }
     }
 }
 
@@ -440,8 +962,8 @@ impl Drop for SpillTape {
 /// coefficient tiles. Feed time blocks as they arrive, then call one of the
 /// `finish_*` methods to obtain tiles in the requested order.
 ///
-/// - If `SSZKP_BLOCKED_IFFT=1`, a file-backed in-place GS-INTT is used and
-///   peak live memory remains ≈ `O(b_blk)`.
+/// - If the [`TransformConfig`] spills, a file-backed six-step INTT is used
+///   and peak live memory remains ≈ `O(b_blk)`.
 /// - Otherwise, we collect in-memory (back-compat) and still emit tiles.
 pub struct BlockedIfft<'d> {
     domain: &'d Domain,
@@ -449,7 +971,7 @@ pub struct BlockedIfft<'d> {
 
     // Two modes:
     // - legacy: collect all evals in-memory (Vec<F>) then IFFT
-    // - blocked: spill to disk and run GS-INTT in-place on the tape
+    // - blocked: spill to disk and run the six-step INTT across two tapes
     legacy_collect: bool,
 
     // Legacy buffer
@@ -463,25 +985,42 @@ pub struct BlockedIfft<'d> {
     // diagnostics
     memlog: bool,
     peak_buffered: usize, // tracks max in-memory buffered evals (≈ b_blk in blocked mode)
+
+    // Spill location/policy for the INTT's scratch tape.
+    config: TransformConfig,
 }
 
 impl<'d> BlockedIfft<'d> {
-    /// Create a new blocked-IFFT façade.
+    /// Create a new blocked-IFFT façade configured by
+    /// [`TransformConfig::from_env`].
     pub fn new(domain: &'d Domain, b_blk: usize) -> Self {
+        Self::with_config(domain, b_blk, &TransformConfig::from_env())
+    }
+
+    /// Create a new blocked-IFFT façade with an explicit [`TransformConfig`].
+    /// Panics if the config forces a spill past its limit or the spill file
+    /// cannot be created; see [`Self::try_with_config`].
+    pub fn with_config(domain: &'d Domain, b_blk: usize, config: &TransformConfig) -> Self {
+        Self::try_with_config(domain, b_blk, config).expect("blocked IFFT setup")
+    }
+
+    /// Fallible form of [`Self::with_config`].
+    pub fn try_with_config(
+        domain: &'d Domain,
+        b_blk: usize,
+        config: &TransformConfig,
+    ) -> Result<Self, DomainError> {
         assert!(b_blk > 0, "b_blk must be positive");
         let memlog = std::env::var("SSZKP_MEMLOG").ok().as_deref() == Some("1");
-        let use_blocked = std::env::var("SSZKP_BLOCKED_IFFT")
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(false);
 
-        let (legacy_collect, tape) = if use_blocked {
-            let t = SpillTape::create().expect("create spill tape");
+        let (legacy_collect, tape) = if config.spills(domain.n)? {
+            let t = SpillTape::create_in(config).map_err(spill_err)?;
             (false, Some(t))
         } else {
             (true, None)
         };
 
-        Self {
+        Ok(Self {
             domain,
             b_blk,
             legacy_collect,
@@ -490,7 +1029,8 @@ impl<'d> BlockedIfft<'d> {
             finished: false,
             memlog,
             peak_buffered: 0,
-        }
+            config: config.clone(),
+        })
     }
 
     #[inline]
@@ -502,7 +1042,8 @@ impl<'d> BlockedIfft<'d> {
 
     /// Append a time-slice (block) of evaluations. Blocks must be provided
     /// in **global increasing** index order end-to-end across all calls.
-    pub fn feed_eval_block(&mut self, evals: &[F]) {
+    /// Errors only if appending to the spill tape fails.
+    pub fn feed_eval_block(&mut self, evals: &[F]) -> Result<(), DomainError> {
         assert!(!self.finished, "blocked IFFT already finalized");
 
         if self.legacy_collect {
@@ -516,10 +1057,11 @@ impl<'d> BlockedIfft<'d> {
         } else {
             // Blocked path: append directly to the spill tape; keep only a tiny staging buffer.
             let t = self.tape.as_mut().expect("tape");
-            t.append_block(evals).expect("tape append");
+            t.append_block(evals).map_err(spill_err)?;
             // In blocked mode, only the caller's `evals` slice is resident; track its peak.
             self.bump_peak(self.peak_buffered.max(evals.len()).max(self.b_blk).min(self.b_blk));
         }
+        Ok(())
     }
 
     /// Number of time evaluations fed so far (T).
@@ -532,42 +1074,44 @@ impl<'d> BlockedIfft<'d> {
     }
 
     /// Finalize and emit **low→high** coefficient tiles (≤ `b_blk` each).
+    /// Panics if the spill tape fails; see [`Self::finish_low_to_high_checked`].
     pub fn finish_low_to_high(mut self) -> impl Iterator<Item = Vec<F>> {
         self.finished = true;
         self.finish_common(/*checked=*/false, /*hi_to_lo=*/false)
+            .expect("blocked IFFT spill tape")
+            .map(|t| t.expect("blocked IFFT spill tape"))
     }
 
     /// Finalize and emit **high→low** coefficient tiles (≤ `b_blk` each).
+    /// Panics if the spill tape fails; see [`Self::finish_high_to_low_checked`].
     pub fn finish_high_to_low(mut self) -> impl Iterator<Item = Vec<F>> {
         self.finished = true;
         self.finish_common(/*checked=*/false, /*hi_to_lo=*/true)
+            .expect("blocked IFFT spill tape")
+            .map(|t| t.expect("blocked IFFT spill tape"))
     }
 
-    /// **Checked** finisher: errors if more than `N` items were fed.
-    pub fn finish_low_to_high_checked(mut self) -> Result<impl Iterator<Item = Vec<F>>, DomainError>
-    {
+    /// **Checked** finisher: errors if more than `N` items were fed or the
+    /// spill tape fails. A tape read failing later ends the tiles early; see
+    /// [`CoeffTiles::check`].
+    pub fn finish_low_to_high_checked(mut self) -> Result<CoeffTiles, DomainError> {
         self.finished = true;
-        self.finish_common_checked(false)
+        Ok(CoeffTiles::new(self.finish_common(true, false)?))
     }
 
-    /// **Checked** finisher: errors if more than `N` items were fed.
-    pub fn finish_high_to_low_checked(
-        mut self,
-    ) -> Result<impl Iterator<Item = Vec<F>>, DomainError> {
+    /// **Checked** finisher: errors if more than `N` items were fed or the
+    /// spill tape fails. A tape read failing later ends the tiles early; see
+    /// [`CoeffTiles::check`].
+    pub fn finish_high_to_low_checked(mut self) -> Result<CoeffTiles, DomainError> {
         self.finished = true;
-        self.finish_common_checked(true)
+        Ok(CoeffTiles::new(self.finish_common(true, true)?))
     }
 
-    fn finish_common(
-        &mut self,
-        checked: bool,
-        hi_to_lo: bool,
-    ) -> Box<dyn Iterator<Item = Vec<F>>> {
-        if let Some(it) = self.try_finish_blocked(checked, hi_to_lo) {
-            return it;
+    fn finish_common(&mut self, checked: bool, hi_to_lo: bool) -> Result<TileStream, DomainError> {
+        if !self.legacy_collect {
+            return self.finish_blocked(checked, hi_to_lo);
         }
-        // Legacy fallback:
-        let mut coeffs = self.materialize_coefficients(checked).expect("coeff materialization");
+        let mut coeffs = self.materialize_coefficients(checked)?;
         if hi_to_lo {
             coeffs.reverse();
         }
@@ -577,47 +1121,43 @@ impl<'d> BlockedIfft<'d> {
                 self.domain.n, self.b_blk, self.peak_buffered
             );
         }
-        if hi_to_lo {
-            Box::new(TileIterHiToLo { coeffs, idx: 0, tile: self.b_blk, memlog: self.memlog, peak_tile: 0 })
+        let (tile, memlog) = (self.b_blk, self.memlog);
+        Ok(if hi_to_lo {
+            Box::new(TileIterHiToLo { coeffs, idx: 0, tile, memlog, peak_tile: 0 }.map(Ok))
         } else {
-            Box::new(TileIterLoToHi { coeffs, idx: 0, tile: self.b_blk, memlog: self.memlog, peak_tile: 0 })
-        }
+            Box::new(TileIterLoToHi { coeffs, idx: 0, tile, memlog, peak_tile: 0 }.map(Ok))
+        })
     }
 
-    fn finish_common_checked(
-        &mut self,
-        hi_to_lo: bool,
-    ) -> Result<Box<dyn Iterator<Item = Vec<F>>>, DomainError> {
-        if let Some(it) = self.try_finish_blocked(true, hi_to_lo) {
-            return Ok(it);
+    /// Finalize into exactly `N` low→high coefficients with random access,
+    /// left on the spill tape in blocked mode (no tile iterator, no copy).
+    /// Errors if more than `N` evaluations were fed.
+    pub fn finish_coeffs(mut self) -> Result<CoeffTape, DomainError> {
+        self.finished = true;
+        let n = self.domain.n;
+        if self.legacy_collect {
+            let coeffs = self.materialize_coefficients(true)?;
+            return Ok(CoeffTape { len: coeffs.len(), store: CoeffStore::Memory(coeffs) });
         }
-        // Legacy path:
-        let mut coeffs = self.materialize_coefficients(true)?;
-        if hi_to_lo {
-            coeffs.reverse();
+        let t = self.tape.as_mut().expect("tape");
+        if t.len > n {
+            return Err(DomainError::BadStream { got: t.len, n });
         }
+        let zeros = vec![F::zero(); n - t.len];
+        t.append_block(&zeros).map_err(spill_err)?;
+        self.intt_on_tape()?;
         if self.memlog {
             eprintln!(
                 "[memlog] BlockedIfft: N={}, b_blk={}, peak_buffered_evals={}",
-                self.domain.n, self.b_blk, self.peak_buffered
+                n, self.b_blk, self.peak_buffered
             );
         }
-        Ok(if hi_to_lo {
-            Box::new(TileIterHiToLo { coeffs, idx: 0, tile: self.b_blk, memlog: self.memlog, peak_tile: 0 })
-        } else {
-            Box::new(TileIterLoToHi { coeffs, idx: 0, tile: self.b_blk, memlog: self.memlog, peak_tile: 0 })
-        })
+        Ok(CoeffTape { len: n, store: CoeffStore::Tape(self.tape.take().expect("tape")) })
     }
 
-    /// Blocked path (if enabled): return an iterator over tiles, or `None` if legacy.
-    fn try_finish_blocked(
-        &mut self,
-        checked: bool,
-        hi_to_lo: bool,
-    ) -> Option<Box<dyn Iterator<Item = Vec<F>>>> {
-        if self.legacy_collect {
-            return None;
-        }
+    /// Blocked path: pad (or truncate) the tape to `N`, run the INTT and
+    /// stream tiles back off it.
+    fn finish_blocked(&mut self, checked: bool, hi_to_lo: bool) -> Result<TileStream, DomainError> {
         let n = self.domain.n;
         let t = self.tape.as_mut().expect("tape");
 
@@ -625,22 +1165,17 @@ impl<'d> BlockedIfft<'d> {
         let fed = t.len;
         if fed > n {
             if checked {
-                // Surface an error by returning a one-shot iterator that panics; callers of
-                // checked finishers should use the Result-returning variant above.
-                return Some(Box::new(std::iter::once_with(|| {
-                    panic!("BadStream error surfaced in checked finisher")
-                })));
-            } else {
-                // truncate the extra by simply ignoring; we won't read past N.
+                return Err(DomainError::BadStream { got: fed, n });
             }
+            // Unchecked: truncate the extra by simply ignoring; we won't read past N.
         } else if fed < n {
             // pad zeros to N
             let zeros = vec![F::zero(); n - fed];
-            t.append_block(&zeros).expect("tape pad");
+            t.append_block(&zeros).map_err(spill_err)?;
         }
 
-        // Run in-place GS-INTT on the tape (decimation-in-frequency).
-        self.gs_intt_in_place_on_tape();
+        // Run the out-of-core INTT on the tape.
+        self.intt_on_tape()?;
 
         if self.memlog {
             eprintln!(
@@ -650,78 +1185,35 @@ impl<'d> BlockedIfft<'d> {
         }
 
         // Build a streaming iterator that reads tiles from the tape.
-        let it: Box<dyn Iterator<Item = Vec<F>>> = if !hi_to_lo {
-            Box::new(TileFromTape::new_forward(
-                self.tape.take().unwrap(),
-                n,
-                self.b_blk,
-                self.memlog,
-            ))
+        let tape = self.tape.take().expect("tape");
+        Ok(if !hi_to_lo {
+            Box::new(TileFromTape::new_forward(tape, n, self.b_blk, self.memlog))
         } else {
-            Box::new(TileFromTape::new_reverse(
-                self.tape.take().unwrap(),
-                n,
-                self.b_blk,
-                self.memlog,
-            ))
-        };
-        Some(it)
+            Box::new(TileFromTape::new_reverse(tape, n, self.b_blk, self.memlog))
+        })
     }
 
-    /// In-place Gentleman–Sande INTT on the file-backed tape.
-    /// After completion, tape holds **coefficients** in low→high order.
-    fn gs_intt_in_place_on_tape(&mut self) {
+    /// Out-of-core INTT of the (padded) tape via [`fft_on_tapes`] with a
+    /// working set of [`tape_workset`]`(b_blk)` elements. The result replaces
+    /// the tape: **coefficients** in low→high order.
+    fn intt_on_tape(&mut self) -> Result<(), DomainError> {
         let n = self.domain.n;
-        debug_assert!(n.is_power_of_two());
-        let m = n.trailing_zeros() as usize;
-
-        // Stage loop: s = 1..=m, L = 2^s, H = L/2, ω_step = ω^{N/L}
-        for s in 1..=m {
-            let l = 1usize << s;
-            let h = l >> 1;
-            let w_step = pow_u64(self.domain.omega, (self.domain.n / l) as u64);
-
-            // j loop (twiddle powers)
-            let mut tw_j = F::one();
-            for j in 0..h {
-                // k loop over butterflies in this column
-                let mut k = j;
-                while k < n {
-                    // read pair
-                    let u = self.tape.as_mut().unwrap().read_elem(k).expect("tape read");
-                    let v_raw = self
-                        .tape
-                        .as_mut()
-                        .unwrap()
-                        .read_elem(k + h)
-                        .expect("tape read");
-                    let v = v_raw * tw_j;
-
-                    // butterfly
-                    let a0 = u + v;
-                    let a1 = u - v;
-
-                    // write back
-                    self.tape.as_mut().unwrap().write_elem(k, &a0).expect("tape write");
-                    self.tape
-                        .as_mut()
-                        .unwrap()
-                        .write_elem(k + h, &a1)
-                        .expect("tape write");
-
-                    k += l;
-                }
-                tw_j *= w_step;
-            }
-        }
-
-        // Final scaling by 1/N
-        let inv_n = F::from(n as u64).inverse().expect("n!=0");
-        for i in 0..n {
-            let mut ai = self.tape.as_mut().unwrap().read_elem(i).expect("tape read");
-            ai *= inv_n;
-            self.tape.as_mut().unwrap().write_elem(i, &ai).expect("tape write");
+        let mut src = self.tape.take().expect("tape");
+        let mut dst = SpillTape::create_in(&self.config).map_err(spill_err)?;
+        let omega_inv = self.domain.omega.inverse().expect("omega non-zero");
+        let inv_n = F::from(n as u64).inverse().expect("n != 0");
+        let mem = tape_workset(self.b_blk);
+        fft_on_tapes(&mut src, &mut dst, 0, n, omega_inv, inv_n, mem).map_err(spill_err)?;
+        if self.memlog {
+            eprintln!(
+                "[memlog] BlockedIfft tape INTT: N={}, workset={}, page_io_calls={}",
+                n,
+                mem,
+                src.io_calls + dst.io_calls
+            );
         }
+        self.tape = Some(dst);
+        Ok(())
     }
 
     /// Legacy: ensure exactly N items (pad/truncate), run a single IFFT, and return
@@ -794,6 +1286,45 @@ impl Iterator for TileIterHiToLo {
     }
 }
 
+/// Fallible tile stream behind the finishers.
+type TileStream = Box<dyn Iterator<Item = Result<Vec<F>, DomainError>>>;
+
+/// Coefficient tiles from a checked [`BlockedIfft`] finisher (or the
+/// `ifft_time_stream_*_with` helpers). A failed spill-tape read ends the
+/// iteration early; call [`CoeffTiles::check`] once drained to tell that
+/// from the last tile.
+pub struct CoeffTiles {
+    inner: TileStream,
+    error: Option<DomainError>,
+}
+
+impl CoeffTiles {
+    fn new(inner: TileStream) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// The spill error that cut the tiles short, if any.
+    pub fn check(&mut self) -> Result<(), DomainError> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Iterator for CoeffTiles {
+    type Item = Vec<F>;
+    fn next(&mut self) -> Option<Vec<F>> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.inner.next()? {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
 // ------------------ Tile iterators (blocked, from tape) ------------------
 
 struct TileFromTape {
@@ -840,8 +1371,16 @@ impl TileFromTape {
     }
 }
 
+impl TileFromTape {
+    /// End the stream on a read error.
+    fn fail(&mut self, e: std::io::Error) -> DomainError {
+        self.done = true;
+        spill_err(e)
+    }
+}
+
 impl Iterator for TileFromTape {
-    type Item = Vec<F>;
+    type Item = Result<Vec<F>, DomainError>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
             return None;
@@ -855,17 +1394,16 @@ impl Iterator for TileFromTape {
                 return None;
             }
             let start = self.next_idx as usize;
-            let end = ((start + self.tile).min(self.n)) as usize;
-            let mut out = Vec::with_capacity(end - start);
-            for i in start..end {
-                let v = self.tape.read_elem(i).expect("tape read");
-                out.push(v);
-            }
+            let end = (start + self.tile).min(self.n);
+            let out = match self.tape.read_range(start, end - start) {
+                Ok(out) => out,
+                Err(e) => return Some(Err(self.fail(e))),
+            };
             self.next_idx = end as isize;
             if out.len() > self.peak_tile {
                 self.peak_tile = out.len();
             }
-            Some(out)
+            Some(Ok(out))
         } else {
             if self.next_idx <= self.end_idx_exclusive {
                 if self.memlog {
@@ -876,11 +1414,11 @@ impl Iterator for TileFromTape {
             }
             let end_inclusive = self.next_idx as usize;
             let start_inclusive = end_inclusive.saturating_sub(self.tile - 1);
-            let mut out = Vec::with_capacity(end_inclusive - start_inclusive + 1);
-            for i in (start_inclusive..=end_inclusive).rev() {
-                let v = self.tape.read_elem(i).expect("tape read");
-                out.push(v);
-            }
+            let mut out = match self.tape.read_range(start_inclusive, end_inclusive - start_inclusive + 1) {
+                Ok(out) => out,
+                Err(e) => return Some(Err(self.fail(e))),
+            };
+            out.reverse();
             // Move prev
             if start_inclusive == 0 {
                 self.next_idx = -1;
@@ -890,7 +1428,7 @@ impl Iterator for TileFromTape {
             if out.len() > self.peak_tile {
                 self.peak_tile = out.len();
             }
-            Some(out)
+            Some(Ok(out))
         }
     }
 }
@@ -909,21 +1447,20 @@ pub fn ifft_time_stream_to_coeff_tiles<'a>(
     b_blk: usize,
     evals: impl Iterator<Item = F> + 'a,
 ) -> impl Iterator<Item = Vec<F>> + 'a {
-    // Feed in blocks of size ≤ b_blk.
-    let mut bifft = BlockedIfft::new(domain, b_blk);
-    let mut buf: Vec<F> = Vec::with_capacity(b_blk);
-    let mut iter = evals;
-    while let Some(x) = iter.next() {
-        buf.push(x);
-        if buf.len() == b_blk {
-            bifft.feed_eval_block(&buf);
-            buf.clear();
-        }
-    }
-    if !buf.is_empty() {
-        bifft.feed_eval_block(&buf);
-    }
-    bifft.finish_low_to_high()
+    time_stream_tiles(domain, b_blk, &TransformConfig::from_env(), evals, false)
+        .expect("blocked IFFT spill tape")
+        .map(|t| t.expect("blocked IFFT spill tape"))
+}
+
+/// [`ifft_time_stream_to_coeff_tiles`] with an explicit [`TransformConfig`];
+/// spill failures are errors (see [`CoeffTiles::check`]).
+pub fn ifft_time_stream_to_coeff_tiles_with(
+    domain: &Domain,
+    b_blk: usize,
+    config: &TransformConfig,
+    evals: impl Iterator<Item = F>,
+) -> Result<CoeffTiles, DomainError> {
+    Ok(CoeffTiles::new(time_stream_tiles(domain, b_blk, config, evals, false)?))
 }
 
 /// Emit **high→low** coefficient tiles (each length ≤ `b_blk`) from a time-ordered
@@ -936,18 +1473,227 @@ pub fn ifft_time_stream_to_coeff_tiles_hi_to_lo<'a>(
     b_blk: usize,
     evals: impl Iterator<Item = F> + 'a,
 ) -> impl Iterator<Item = Vec<F>> + 'a {
-    let mut bifft = BlockedIfft::new(domain, b_blk);
+    time_stream_tiles(domain, b_blk, &TransformConfig::from_env(), evals, true)
+        .expect("blocked IFFT spill tape")
+        .map(|t| t.expect("blocked IFFT spill tape"))
+}
+
+/// [`ifft_time_stream_to_coeff_tiles_hi_to_lo`] with an explicit
+/// [`TransformConfig`]; spill failures are errors (see [`CoeffTiles::check`]).
+pub fn ifft_time_stream_to_coeff_tiles_hi_to_lo_with(
+    domain: &Domain,
+    b_blk: usize,
+    config: &TransformConfig,
+    evals: impl Iterator<Item = F>,
+) -> Result<CoeffTiles, DomainError> {
+    Ok(CoeffTiles::new(time_stream_tiles(domain, b_blk, config, evals, true)?))
+}
+
+/// Feed `evals` through a [`BlockedIfft`] in blocks of `b_blk` and finish it
+/// (unchecked: extra evaluations are dropped).
+fn time_stream_tiles(
+    domain: &Domain,
+    b_blk: usize,
+    config: &TransformConfig,
+    evals: impl Iterator<Item = F>,
+    hi_to_lo: bool,
+) -> Result<TileStream, DomainError> {
+    let mut bifft = BlockedIfft::try_with_config(domain, b_blk, config)?;
     let mut buf: Vec<F> = Vec::with_capacity(b_blk);
-    let mut iter = evals;
-    while let Some(x) = iter.next() {
+    for x in evals {
         buf.push(x);
         if buf.len() == b_blk {
-            bifft.feed_eval_block(&buf);
+            bifft.feed_eval_block(&buf)?;
             buf.clear();
         }
     }
     if !buf.is_empty() {
-        bifft.feed_eval_block(&buf);
+        bifft.feed_eval_block(&buf)?;
+    }
+    bifft.finished = true;
+    bifft.finish_common(false, hi_to_lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{FftField, UniformRand};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Run the tape INTT on `evals` with working set `mem`; return the
+    /// coefficients and the page I/O calls it took.
+    fn tape_intt(evals: &[F], mem: usize) -> (Vec<F>, u64) {
+        let n = evals.len();
+        let omega = root_of_unity(n).unwrap();
+        let mut src = SpillTape::create().unwrap();
+        let mut dst = SpillTape::create().unwrap();
+        src.append_block(evals).unwrap();
+        let before = src.io_calls;
+        let inv_n = F::from(n as u64).inverse().unwrap();
+        fft_on_tapes(&mut src, &mut dst, 0, n, omega.inverse().unwrap(), inv_n, mem).unwrap();
+        let calls = src.io_calls + dst.io_calls - before;
+        (dst.read_range(0, n).unwrap(), calls)
+    }
+
+    #[test]
+    fn six_step_tape_intt_matches_in_memory() {
+        let mut rng = StdRng::seed_from_u64(42);
+        // Single block, one six-step level, several levels of recursion, and
+        // mixed-radix lengths whose split leaves partial transpose tiles.
+        for (n, mem) in [(16, 16), (256, 16), (1 << 10, 4), (1 << 11, 32), (48, 16), (3 << 10, 16), (3 << 10, 64)] {
+            let evals: Vec<F> = (0..n).map(|_| F::rand(&mut rng)).collect();
+            let d = Domain::new_with_c(n, root_of_unity(n).unwrap(), F::one());
+            let (got, _) = tape_intt(&evals, mem);
+            assert_eq!(got, ifft_block_evals_to_coeffs(&d, &evals), "N={n}, mem={mem}");
+        }
+    }
+
+    #[test]
+    fn mixed_radix_ntt_matches_naive_dft() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let sizes: Vec<usize> = (1..=200).filter(|n| supports_domain_size(*n)).collect();
+        assert!(sizes.iter().any(|n| !n.is_power_of_two()), "field has an odd smooth subgroup");
+        for n in sizes {
+            let omega = root_of_unity(n).unwrap();
+            let d = Domain::new_with_c(n, omega, F::one());
+            let coeffs: Vec<F> = (0..n).map(|_| F::rand(&mut rng)).collect();
+            let naive: Vec<F> = (0..n)
+                .map(|k| {
+                    let x = pow_u64(omega, k as u64);
+                    coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c)
+                })
+                .collect();
+            let evals = ntt_block_coeffs_to_evals(&d, &coeffs);
+            assert_eq!(evals, naive, "N={n}");
+            assert_eq!(ifft_block_evals_to_coeffs(&d, &evals), coeffs, "N={n}");
+        }
+    }
+
+    #[test]
+    fn next_smooth_size_pads_to_nearest_supported() {
+        assert_eq!(next_smooth_size(0), Some(1));
+        assert_eq!(next_smooth_size(1 << 10), Some(1 << 10));
+        assert_eq!(root_of_unity(1 << 10), F::get_root_of_unity(1 << 10));
+        assert!(!supports_domain_size(17) && root_of_unity(17).is_none());
+
+        let rows = 600_000;
+        let n = next_smooth_size(rows).unwrap();
+        assert!(n < 1 << 20 && supports_domain_size(n), "600K rows padded to {n}");
+        assert!((rows..n).all(|m| !supports_domain_size(m)));
+        Domain::new_with_c_r(n, root_of_unity(n).unwrap(), F::one()).unwrap();
+    }
+
+    #[test]
+    fn proves_and_verifies_on_mixed_radix_domain() {
+        use crate::ipa::Ipa;
+        use crate::scheduler::{Prover, Verifier};
+        use crate::test_util::tiny_prove_fixture;
+
+        let n = next_smooth_size(20).unwrap();
+        assert!(!n.is_power_of_two(), "20 rows pad to {n}");
+        for transform in [TransformConfig::default(), TransformConfig::default().with_backend(TransformBackend::Tape)] {
+            let fx = tiny_prove_fixture(n).with_rows(20).with_transform(transform);
+            let proof = Prover::with_pcs(&fx.air, &fx.pp, Ipa).prove_with_restreamer(&fx.rows).unwrap();
+            Verifier::with_pcs(&fx.vp, Ipa).verify(&proof).unwrap();
+        }
+    }
+
+    #[test]
+    fn transform_config_picks_backend_and_spill_policy() {
+        let n = 1 << 10;
+        let need = TransformConfig::default().spill_bytes(n);
+        assert!(!TransformConfig::default().spills(n).unwrap());
+        let tape = TransformConfig::default().with_backend(TransformBackend::Tape);
+        assert!(tape.spills(n).unwrap());
+        assert!(matches!(
+            tape.clone().with_max_spill_bytes(need - 1).spills(n),
+            Err(DomainError::SpillLimit { .. })
+        ));
+        let auto = tape.with_backend("auto:1024".parse().unwrap());
+        assert!(auto.spills(n).unwrap());
+        assert!(!auto.spills(n / 2).unwrap());
+        assert!(!auto.with_max_spill_bytes(need - 1).spills(n).unwrap());
+    }
+
+    #[test]
+    fn spill_dir_cleanup_and_encryption() {
+        let n = 64;
+        let d = Domain::new_with_c(n, F::get_root_of_unity(n as u64).unwrap(), F::one());
+        let evals: Vec<F> = (0..n as u64).map(|i| F::from(i * i + 7)).collect();
+        let want = ifft_block_evals_to_coeffs(&d, &evals);
+        let mut needle = Vec::new();
+        want[1].serialize_uncompressed(&mut needle).unwrap();
+
+        // Returns the coefficients and every spill file left in the directory.
+        let run = |cfg: TransformConfig| {
+            let dir = tempfile::tempdir().unwrap();
+            let cfg = cfg.with_backend(TransformBackend::Tape).with_spill_dir(dir.path());
+            let mut b = BlockedIfft::try_with_config(&d, 8, &cfg).unwrap();
+            b.feed_eval_block(&evals).unwrap();
+            let mut tape = b.finish_coeffs().unwrap();
+            let files: Vec<Vec<u8>> = std::fs::read_dir(dir.path())
+                .unwrap()
+                .map(|e| std::fs::read(e.unwrap().path()).unwrap())
+                .collect();
+            (tape.read_range(0, n).unwrap(), files)
+        };
+        let contains = |f: &[u8]| f.windows(needle.len()).any(|w| w == needle.as_slice());
+
+        let (got, files) = run(TransformConfig::default());
+        assert_eq!(got, want);
+        assert!(files.iter().any(|f| contains(f)), "plain spill holds raw coefficients");
+
+        let (got, files) = run(TransformConfig::default().with_encrypt_spill(true));
+        assert_eq!(got, want);
+        assert!(!files.is_empty() && !files.iter().any(|f| contains(f)));
+
+        #[cfg(unix)]
+        {
+            let (got, files) = run(TransformConfig::default().with_cleanup(SpillCleanup::Unlinked));
+            assert_eq!(got, want);
+            assert!(files.is_empty(), "unlinked spill files are never visible");
+        }
+    }
+
+    #[test]
+    fn prover_output_does_not_depend_on_transform_backend() {
+        use crate::ipa::Ipa;
+        use crate::scheduler::{ProveError, Prover};
+        use crate::test_util::tiny_prove_fixture;
+
+        // A transparent backend keeps this independent of any SRS.
+        let prove = |transform: TransformConfig| -> Result<Vec<u8>, ProveError> {
+            let fx = tiny_prove_fixture(16).with_rows(12).with_transform(transform);
+            let mut out = Vec::new();
+            let proof = Prover::with_pcs(&fx.air, &fx.pp, Ipa).prove_with_restreamer(&fx.rows)?;
+            proof.serialize_compressed(&mut out).unwrap();
+            Ok(out)
+        };
+        let tape = TransformConfig::default().with_backend(TransformBackend::Tape).with_encrypt_spill(true);
+        assert_eq!(prove(TransformConfig::default()).unwrap(), prove(tape.clone()).unwrap());
+
+        // A spill dir that cannot be created fails the proof, not the process.
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let res = prove(tape.with_spill_dir(file.path().join("spill")));
+        assert!(matches!(res, Err(ProveError::Transform(DomainError::Spill(_)))), "{res:?}");
+    }
+
+    #[test]
+    fn six_step_tape_intt_uses_page_io() {
+        let n = 1 << 12;
+        let evals: Vec<F> = (0..n as u64).map(F::from).collect();
+        let mem = 64;
+        let (_, calls) = tape_intt(&evals, mem);
+        // One six-step level (n1 = n2 = 64): three tiled transposes that read
+        // and write every page of the tape once, with pages of √mem elements,
+        // plus two row passes of one read and one write per length-mem row.
+        let slot = SpillTape::slot_size(false);
+        let page_bytes = 8 * slot;
+        let pages = (n * slot).div_ceil(page_bytes) as u64;
+        let rows = (n / mem) as u64;
+        let bound = 3 * 2 * pages + 2 * 2 * rows + 2;
+        assert!(calls <= bound, "{calls} page I/O calls for N={n}, bound {bound}");
+        // Element-wise radix-2 on a tape would cost ~4·N·log N calls.
+        assert!(calls >= 2 * pages, "{calls} page I/O calls for N={n}");
     }
-    bifft.finish_high_to_low()
 }
@@ -0,0 +1,1247 @@
+//! Solidity verifier generator and calldata encoder (BN254 · KZG · Keccak)
+//!
+//! Turns a verifying key — the fixed [`ProofHeader`], the shape of the
+//! opening set and the SRS elements `[1]G₁` / `[τ]G₂` — into a self-contained
+//! Solidity contract whose `verify(uint256[])` accepts exactly the proofs that
+//! `scheduler::Verifier::verify` accepts (default feature set), using only the
+//! precompiles `modexp` (0x05), `ecAdd` (0x06), `ecMul` (0x07) and
+//! `ecPairing` (0x08).
+//! [`generate_bytecode`](crate::evm::generate_bytecode) emits the same
+//! verifier as ready-to-deploy EVM bytecode, for deployments without a
+//! Solidity compiler.
+//!
+//! ## Requirements on the proof
+//! - `header.pcs == Kzg`, `header.curve == Bn254`;
+//! - `header.fs_hash == Keccak256` (prove with
+//!   `Prover::with_fs_hash(FsHash::Keccak256)`). The absorb schedule is the
+//!   same as with BLAKE3; see `transcript.rs` for the challenge expansion.
+//! - one evaluation point ζ, no `zeta-shift` opening and no
+//!   `strict-recompute-r` (the contract mirrors the default verifier).
+//!
+//! ## What the contract does
+//! 1. Rebuilds the transcript bytes: preamble and header item (a constant),
+//!    then one item per wire / Z / Q commitment. Points arrive as `(x, y)`
+//!    words and are re-encoded in arkworks' compressed form (x little-endian,
+//!    sign bit `0x80` if `y > p − y`, `0x40` for the identity).
+//! 2. Derives ζ with the Keccak challenge rule (β, γ, α use counters 0–2 and
+//!    do not change the absorb state, so only ζ at counter 3 is needed).
+//! 3. Absorbs the claimed evaluations as one `opening_evals` item (each
+//!    scalar little-endian, as arkworks serializes it) and derives the batching
+//!    challenge ν at counter 4.
+//! 4. Runs the algebraic check at ζ as the Rust verifier does:
+//!    `Z_H(ζ)·Q(ζ) − R(ζ) = 0` with `Z_H(ζ) = ζᴺ − c` and `R(ζ)` evaluated by
+//!    the default `air::residual_eval_at_point_symbolic` path, i.e. from the
+//!    claimed `Q(ζ)`.
+//! 5. Folds every opening `(Cᵢ, vᵢ, Wᵢ)` with weight `ρᵢ = νⁱ` into one
+//!    pairing check
+//!    `e(Σρᵢ(Cᵢ + ζ·Wᵢ) − (Σρᵢvᵢ)·G₁, G₂) · e(−ΣρᵢWᵢ, [τ]G₂) = 1`, the same
+//!    product `Kzg::batch_verify` evaluates. The weights stop a prover from
+//!    shifting value between two claimed evaluations.
+//!
+//! ## Calldata
+//! `verify(uint256[] proof)` with words, in order: commitments `(x, y)` for
+//! wires `0..k`, Z (if present) and Q; then one claimed evaluation per
+//! commitment; then the witness `W` `(x, y)` per opening. The identity is
+//! `(0, 0)`. ζ is not sent; the contract recomputes it.
+//!
+//! ## Testing
+//! The unit tests deploy the output of
+//! [`generate_bytecode`](crate::evm::generate_bytecode) in revm and call
+//! `verify` with real proofs, honest and tampered. The Solidity source is
+//! compiled with a pinned `solc` (fetched once by `svm-rs` into its data
+//! directory) and run the same way; both must agree with
+//! `scheduler::Verifier`. Without network access that test skips, except
+//! under `CI`, where a missing compiler fails it.
+
+#![forbid(unsafe_code)]
+
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    curve::{CurveId, Fq, G1Affine, G2Affine},
+    pcs::{self, Commitment, Kzg, OpeningProof, PcsKind, VerifyError},
+    transcript::{FsHash, FsLabel, Transcript},
+    F, Proof, ProofHeader,
+};
+
+/// Transcript label used by the scheduler (`Transcript::new("sszkp.proof")`).
+const PROOF_TRANSCRIPT_LABEL: &str = "sszkp.proof";
+
+/// Challenge counter of the evaluation point ζ (after β, γ, α).
+const ZETA_CTR: u64 = 3;
+
+/// Challenge counter of the opening batch weight ν (after ζ).
+const NU_CTR: u64 = 4;
+
+/// Errors from building a verifying key or encoding/decoding calldata.
+#[derive(Debug, thiserror::Error)]
+pub enum EvmError {
+    /// The header or build configuration cannot be checked on-chain.
+    #[error("unsupported for EVM verification: {0}")]
+    Unsupported(&'static str),
+    /// The proof was produced under a different header than the key's.
+    #[error("proof header does not match the verifying key")]
+    HeaderMismatch,
+    /// Wrong number of evaluations / words.
+    #[error("shape mismatch: expected {expected} words, got {got}")]
+    Shape {
+        /// Count required by the key.
+        expected: usize,
+        /// Count found.
+        got: usize,
+    },
+    /// Calldata that `encode_calldata` could not have produced.
+    #[error("malformed calldata: {0}")]
+    Calldata(&'static str),
+    /// `[1]G₁` / `[τ]G₂` are not loaded.
+    #[error(transparent)]
+    Srs(#[from] VerifyError),
+}
+
+/// Everything the generated contract hard-codes.
+#[derive(Clone, Debug)]
+pub struct EvmVerifyingKey {
+    /// Header every accepted proof must carry (absorbed as a constant).
+    pub header: ProofHeader,
+    /// Whether proofs carry a permutation commitment `Z` (the CLI prover always does).
+    pub has_z: bool,
+    /// `[1]G₁` (power 0 of the SRS).
+    pub g1: G1Affine,
+    /// `[τ]G₂`.
+    pub tau_g2: G2Affine,
+}
+
+impl EvmVerifyingKey {
+    /// Validate that `header` describes a proof the contract can check.
+    pub fn new(
+        header: ProofHeader,
+        has_z: bool,
+        g1: G1Affine,
+        tau_g2: G2Affine,
+    ) -> Result<Self, EvmError> {
+        if header.curve != CurveId::Bn254 {
+            return Err(EvmError::Unsupported("curve must be bn254"));
+        }
+        if header.pcs != PcsKind::Kzg {
+            return Err(EvmError::Unsupported("commitment backend must be kzg"));
+        }
+        if header.fs_hash != FsHash::Keccak256 {
+            return Err(EvmError::Unsupported("transcript hash must be keccak256"));
+        }
+        if cfg!(feature = "zeta-shift") {
+            return Err(EvmError::Unsupported("zeta-shift openings"));
+        }
+        if cfg!(feature = "strict-recompute-r") {
+            return Err(EvmError::Unsupported("strict-recompute-r"));
+        }
+        Ok(Self { header, has_z, g1, tau_g2 })
+    }
+
+    /// Build the key from the globally loaded SRS (`pcs::load_srs_g1/g2`).
+    pub fn from_loaded_srs(header: ProofHeader, has_z: bool) -> Result<Self, EvmError> {
+        let (g1, tau_g2) = pcs::loaded_verifier_key()?;
+        Self::new(header, has_z, g1, tau_g2)
+    }
+
+    /// Build the key from an explicit SRS.
+    pub fn from_srs(header: ProofHeader, has_z: bool, srs: &pcs::Srs) -> Result<Self, EvmError> {
+        let vk = srs.verifier_key()?;
+        Self::new(header, has_z, vk.g1, vk.tau_g2)
+    }
+
+    /// Commitments absorbed in order: wires, Z (optional), Q.
+    pub fn num_commitments(&self) -> usize {
+        self.header.k as usize + usize::from(self.has_z) + 1
+    }
+
+    /// Length of the `uint256[]` argument: `2n` commitment words, `n`
+    /// evaluations and `2n` witness words.
+    pub fn proof_words(&self) -> usize {
+        5 * self.num_commitments()
+    }
+}
+
+// ============================================================================
+// Transcript bytes (shared by the generator, the decoder and the tests)
+// ============================================================================
+
+/// `"item:" ‖ label ‖ ":len:" ‖ u64be(len) ‖ ":data:"`, as in `Transcript::absorb_bytes`.
+fn item_prefix(label: FsLabel, len: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(b"item:");
+    out.extend_from_slice(label.as_str().as_bytes());
+    out.extend_from_slice(b":len:");
+    out.extend_from_slice(&(len as u64).to_be_bytes());
+    out.extend_from_slice(b":data:");
+    out
+}
+
+/// Preamble plus the protocol-header item; constant for a verifying key.
+fn transcript_prefix(header: &ProofHeader) -> Vec<u8> {
+    let mut hdr = Vec::new();
+    header.serialize_compressed(&mut hdr).expect("serialize header");
+    let mut out = Vec::new();
+    out.extend_from_slice(b"SSZKP.transcript.v1");
+    out.extend_from_slice(PROOF_TRANSCRIPT_LABEL.as_bytes());
+    out.extend_from_slice(&item_prefix(FsLabel::ProtocolHeader, hdr.len()));
+    out.extend_from_slice(&hdr);
+    out
+}
+
+/// Challenge DST for `label` at counter `ctr`.
+fn challenge_dst(label: FsLabel, ctr: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(b"challenge:SSZKP.v1:tlabel:");
+    out.extend_from_slice(PROOF_TRANSCRIPT_LABEL.as_bytes());
+    out.extend_from_slice(b":label:");
+    out.extend_from_slice(label.as_str().as_bytes());
+    out.extend_from_slice(b":ctr:");
+    out.extend_from_slice(&ctr.to_be_bytes());
+    out
+}
+
+/// Challenge DST for ζ, including its counter.
+fn zeta_dst() -> Vec<u8> {
+    challenge_dst(FsLabel::EvalPoints, ZETA_CTR)
+}
+
+/// Challenge DST for ν, including its counter.
+fn nu_dst() -> Vec<u8> {
+    challenge_dst(FsLabel::OpeningBatch, NU_CTR)
+}
+
+/// Label of the `i`-th absorbed commitment.
+fn commitment_label(vk: &EvmVerifyingKey, i: usize) -> FsLabel {
+    let k = vk.header.k as usize;
+    if i < k {
+        FsLabel::WireCommit
+    } else if vk.has_z && i == k {
+        FsLabel::PermZCommit
+    } else {
+        FsLabel::QuotientCommit
+    }
+}
+
+// ============================================================================
+// Word encoding
+// ============================================================================
+
+type Word = [u8; 32];
+
+fn word_from_bigint<B: BigInteger>(b: B) -> Word {
+    let bytes = b.to_bytes_be();
+    let mut w = [0u8; 32];
+    w[32 - bytes.len()..].copy_from_slice(&bytes);
+    w
+}
+
+fn fr_word(x: &F) -> Word {
+    word_from_bigint(x.into_bigint())
+}
+
+fn fq_word(x: &Fq) -> Word {
+    word_from_bigint(x.into_bigint())
+}
+
+/// `(x, y)` words; the identity is `(0, 0)` as the precompiles expect.
+fn g1_words(p: &G1Affine) -> [Word; 2] {
+    match p.xy() {
+        Some((x, y)) => [fq_word(x), fq_word(y)],
+        None => [[0u8; 32]; 2],
+    }
+}
+
+/// Parse a canonical (`< modulus`) big-endian word.
+fn parse_canonical<P: PrimeField>(w: &[u8]) -> Option<P> {
+    let x = P::from_be_bytes_mod_order(w);
+    (word_from_bigint(x.into_bigint()) == w).then_some(x)
+}
+
+fn parse_g1(x: &[u8], y: &[u8]) -> Result<G1Affine, EvmError> {
+    if x.iter().chain(y).all(|&b| b == 0) {
+        return Ok(G1Affine::zero());
+    }
+    let x = parse_canonical::<Fq>(x).ok_or(EvmError::Calldata("coordinate not below p"))?;
+    let y = parse_canonical::<Fq>(y).ok_or(EvmError::Calldata("coordinate not below p"))?;
+    let p = G1Affine::new_unchecked(x, y);
+    if !p.is_on_curve() {
+        return Err(EvmError::Calldata("point not on curve"));
+    }
+    Ok(p)
+}
+
+fn u64_word(x: u64) -> Word {
+    let mut w = [0u8; 32];
+    w[24..].copy_from_slice(&x.to_be_bytes());
+    w
+}
+
+/// Decimal literal of a field element (arkworks' `BigInt` displays in base 10).
+fn dec<P: PrimeField>(x: &P) -> String {
+    x.into_bigint().to_string()
+}
+
+// ============================================================================
+// Calldata
+// ============================================================================
+
+/// Selector of `verify(uint256[])`.
+pub fn verify_selector() -> [u8; 4] {
+    let h = Keccak256::digest(b"verify(uint256[])");
+    [h[0], h[1], h[2], h[3]]
+}
+
+/// Flatten `proof` into the contract's `uint256[]` words.
+pub fn proof_to_words(vk: &EvmVerifyingKey, proof: &Proof<Kzg>) -> Result<Vec<Word>, EvmError> {
+    let mut want = Vec::new();
+    let mut got = Vec::new();
+    vk.header.serialize_compressed(&mut want).expect("serialize header");
+    proof.header.serialize_compressed(&mut got).expect("serialize header");
+    if want != got {
+        return Err(EvmError::HeaderMismatch);
+    }
+    if proof.z_comm.is_some() != vk.has_z {
+        return Err(EvmError::Unsupported("Z commitment presence differs from the key"));
+    }
+    if proof.eval_points.len() != 1 {
+        return Err(EvmError::Unsupported("exactly one evaluation point"));
+    }
+    let n = vk.num_commitments();
+    if proof.evals.len() != n || proof.opening_proofs.len() != n {
+        return Err(EvmError::Shape { expected: n, got: proof.evals.len() });
+    }
+
+    let mut words = Vec::with_capacity(vk.proof_words());
+    let comms = proof.wire_comms.iter().chain(proof.z_comm.iter()).chain([&proof.q_comm]);
+    for c in comms {
+        words.extend(g1_words(&c.0));
+    }
+    words.extend(proof.evals.iter().map(fr_word));
+    for pr in &proof.opening_proofs {
+        words.extend(g1_words(&pr.witness_comm.0));
+    }
+    Ok(words)
+}
+
+/// ABI-encode `verify(proof_words)`.
+pub fn encode_calldata(vk: &EvmVerifyingKey, proof: &Proof<Kzg>) -> Result<Vec<u8>, EvmError> {
+    let words = proof_to_words(vk, proof)?;
+    let mut out = Vec::with_capacity(4 + 64 + 32 * words.len());
+    out.extend_from_slice(&verify_selector());
+    out.extend_from_slice(&u64_word(0x20));
+    out.extend_from_slice(&u64_word(words.len() as u64));
+    for w in &words {
+        out.extend_from_slice(w);
+    }
+    Ok(out)
+}
+
+/// Split calldata into its `uint256[]` words (checks selector, offset and length).
+fn calldata_words(vk: &EvmVerifyingKey, calldata: &[u8]) -> Result<Vec<Word>, EvmError> {
+    if calldata.len() < 68 || calldata[..4] != verify_selector() {
+        return Err(EvmError::Calldata("not a verify(uint256[]) call"));
+    }
+    let head = |i: usize| -> Result<usize, EvmError> {
+        let w = &calldata[4 + 32 * i..4 + 32 * (i + 1)];
+        if w[..24].iter().any(|&b| b != 0) {
+            return Err(EvmError::Calldata("oversized ABI head"));
+        }
+        Ok(u64::from_be_bytes(w[24..].try_into().expect("8 bytes")) as usize)
+    };
+    if head(0)? != 0x20 {
+        return Err(EvmError::Calldata("unexpected array offset"));
+    }
+    let len = head(1)?;
+    if len != vk.proof_words() {
+        return Err(EvmError::Shape { expected: vk.proof_words(), got: len });
+    }
+    let body = &calldata[68..];
+    if body.len() != 32 * len {
+        return Err(EvmError::Calldata("truncated array"));
+    }
+    Ok(body.chunks_exact(32).map(|c| c.try_into().expect("32 bytes")).collect())
+}
+
+/// Inverse of [`encode_calldata`]: rebuild the `Proof`, recomputing ζ with
+/// the Keccak transcript exactly as the contract does.
+pub fn decode_calldata(vk: &EvmVerifyingKey, calldata: &[u8]) -> Result<Proof<Kzg>, EvmError> {
+    let words = calldata_words(vk, calldata)?;
+    let n = vk.num_commitments();
+
+    let mut comms = Vec::with_capacity(n);
+    for i in 0..n {
+        comms.push(Commitment(parse_g1(&words[2 * i], &words[2 * i + 1])?));
+    }
+    let mut evals = Vec::with_capacity(n);
+    for w in &words[2 * n..3 * n] {
+        evals.push(parse_canonical::<F>(w).ok_or(EvmError::Calldata("evaluation not below r"))?);
+    }
+    let mut witnesses = Vec::with_capacity(n);
+    for i in 0..n {
+        let base = 3 * n + 2 * i;
+        witnesses.push(Commitment(parse_g1(&words[base], &words[base + 1])?));
+    }
+
+    // β, γ, α do not change the absorb state; drawing them only advances the
+    // counter so that ζ lands on counter 3.
+    let mut fs = Transcript::with_hash(PROOF_TRANSCRIPT_LABEL, FsHash::Keccak256);
+    fs.absorb_protocol_header(&vk.header);
+    for (i, c) in comms.iter().enumerate() {
+        fs.absorb_commitment_l(commitment_label(vk, i), c);
+    }
+    for label in [FsLabel::Beta, FsLabel::Gamma, FsLabel::Alpha] {
+        let _ = fs.challenge_f_l(label);
+    }
+    let zeta = fs.challenge_points_l(FsLabel::EvalPoints, 1)[0];
+
+    let opening_proofs = evals
+        .iter()
+        .zip(witnesses)
+        .map(|(&value, witness_comm)| OpeningProof { zeta, value, witness_comm })
+        .collect();
+    let q_comm = comms.pop().expect("at least Q");
+    let z_comm = if vk.has_z { comms.pop() } else { None };
+    Ok(Proof {
+        header: vk.header.clone(),
+        wire_comms: comms,
+        z_comm,
+        q_comm,
+        eval_points: vec![zeta],
+        evals,
+        opening_proofs,
+    })
+}
+
+// ============================================================================
+// Solidity generator
+// ============================================================================
+
+const TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+// Generated by myzkp::evm::generate_solidity. Do not edit; regenerate from
+// the proof header and SRS instead.
+pragma solidity ^0.8.20;
+
+/// @title SSZKP KZG verifier (BN254, Keccak-256 transcript)
+/// @notice Accepts proofs for domain N = {{N}} with k = {{K}} registers whose
+///         header matches HEADER exactly. Pass the words produced by
+///         `myzkp::evm::encode_calldata`.
+contract SszkpVerifier {
+    // BN254 base field p and scalar field r.
+    uint256 internal constant P_MOD = {{P_MOD}};
+    uint256 internal constant R_MOD = {{R_MOD}};
+    // 2^256 mod r: reduces a 512-bit challenge h0 || h1.
+    uint256 internal constant R256 = {{R256}};
+
+    uint256 internal constant N = {{N}};
+    // Constant c of the vanishing polynomial Z_H(X) = X^N - c.
+    uint256 internal constant ZH_C = {{ZH_C}};
+    uint256 internal constant K = {{K}};
+    bool internal constant HAS_Z = {{HAS_Z}};
+    uint256 internal constant NUM_COMMITMENTS = {{NC}};
+    uint256 internal constant PROOF_WORDS = {{NW}};
+
+    // [1]G1 from the SRS.
+    uint256 internal constant G1_X = {{G1_X}};
+    uint256 internal constant G1_Y = {{G1_Y}};
+    // [1]G2 and [tau]G2, EIP-197 order (x.c1, x.c0, y.c1, y.c0).
+    uint256 internal constant G2_X1 = {{G2_X1}};
+    uint256 internal constant G2_X0 = {{G2_X0}};
+    uint256 internal constant G2_Y1 = {{G2_Y1}};
+    uint256 internal constant G2_Y0 = {{G2_Y0}};
+    uint256 internal constant TAU_X1 = {{TAU_X1}};
+    uint256 internal constant TAU_X0 = {{TAU_X0}};
+    uint256 internal constant TAU_Y1 = {{TAU_Y1}};
+    uint256 internal constant TAU_Y0 = {{TAU_Y0}};
+
+    // Compressed ProofHeader (informational; bound via TRANSCRIPT_PREFIX).
+    bytes public constant HEADER = hex"{{HEADER}}";
+    // "SSZKP.transcript.v1" || "sszkp.proof" || header item.
+    bytes internal constant TRANSCRIPT_PREFIX = hex"{{PREFIX}}";
+    // "item:" || label || ":len:" || u64be(32) || ":data:"
+    bytes internal constant WIRE_ITEM = hex"{{WIRE_ITEM}}";
+    bytes internal constant Z_ITEM = hex"{{Z_ITEM}}";
+    bytes internal constant Q_ITEM = hex"{{Q_ITEM}}";
+    // Item preamble of the claimed evaluations (NUM_COMMITMENTS scalars).
+    bytes internal constant EVALS_ITEM = hex"{{EVALS_ITEM}}";
+    // Challenge DSTs for zeta (counter 3, after beta, gamma, alpha) and the
+    // opening batch weight nu (counter 4).
+    bytes internal constant ZETA_DST = hex"{{ZETA_DST}}";
+    bytes internal constant NU_DST = hex"{{NU_DST}}";
+
+    /// @notice Returns true iff `proof` verifies against this key.
+    function verify(uint256[] calldata proof) external view returns (bool) {
+        if (proof.length != PROOF_WORDS || !_canonical(proof)) return false;
+        bytes memory t = _transcript(proof);
+        uint256 zeta = _challenge(t, ZETA_DST);
+        t = abi.encodePacked(t, EVALS_ITEM, _evalsLe(proof));
+        uint256 nu = _challenge(t, NU_DST);
+        if (!_algebraicCheck(proof, zeta)) return false;
+        (uint256[2] memory lhs, uint256[2] memory sumW, bool ok) = _weightedSums(proof, zeta, nu);
+        if (!ok) return false;
+        return _pairingCheck(lhs, sumW);
+    }
+
+    /// Coordinates below p, evaluations below r.
+    function _canonical(uint256[] calldata proof) internal pure returns (bool) {
+        for (uint256 i = 0; i < 2 * NUM_COMMITMENTS; i++) {
+            if (proof[i] >= P_MOD || proof[3 * NUM_COMMITMENTS + i] >= P_MOD) return false;
+        }
+        for (uint256 i = 0; i < NUM_COMMITMENTS; i++) {
+            if (proof[2 * NUM_COMMITMENTS + i] >= R_MOD) return false;
+        }
+        return true;
+    }
+
+    /// Replay the Keccak transcript: header, then wires, Z? and Q.
+    function _transcript(uint256[] calldata proof) internal pure returns (bytes memory t) {
+        t = TRANSCRIPT_PREFIX;
+        for (uint256 i = 0; i < NUM_COMMITMENTS; i++) {
+            bytes memory item = i < K ? WIRE_ITEM : ((HAS_Z && i == K) ? Z_ITEM : Q_ITEM);
+            t = abi.encodePacked(t, item, _compress(proof[2 * i], proof[2 * i + 1]));
+        }
+    }
+
+    /// Keccak challenge: (h0 * 2^256 + h1) mod r with h_i = keccak(t || dst || u64be(i)).
+    function _challenge(bytes memory t, bytes memory dst) internal pure returns (uint256) {
+        uint256 h0 = uint256(keccak256(abi.encodePacked(t, dst, uint64(0))));
+        uint256 h1 = uint256(keccak256(abi.encodePacked(t, dst, uint64(1))));
+        return addmod(mulmod(h0, R256, R_MOD), h1, R_MOD);
+    }
+
+    /// Claimed evaluations in arkworks' encoding (32 bytes, little-endian).
+    function _evalsLe(uint256[] calldata proof) internal pure returns (bytes memory out) {
+        for (uint256 i = 0; i < NUM_COMMITMENTS; i++) {
+            out = abi.encodePacked(out, bytes32(_reverseBytes(proof[2 * NUM_COMMITMENTS + i])));
+        }
+    }
+
+    /// Z_H(zeta) * Q(zeta) - R(zeta) == 0, as `scheduler::Verifier` checks it.
+    function _algebraicCheck(uint256[] calldata proof, uint256 zeta) internal view returns (bool) {
+        (uint256 zetaN, bool ok) = _expMod(zeta, N);
+        if (!ok) return false;
+        uint256 zh = addmod(zetaN, R_MOD - ZH_C, R_MOD);
+        uint256 q = proof[3 * NUM_COMMITMENTS - 1];
+        return mulmod(zh, q, R_MOD) == _residualAtZeta(zh, q);
+    }
+
+    /// R(zeta) on the default `air::residual_eval_at_point_symbolic` path,
+    /// which derives it from the claimed Q(zeta).
+    function _residualAtZeta(uint256 zh, uint256 q) internal pure returns (uint256) {
+        return mulmod(zh, q, R_MOD);
+    }
+
+    /// L = sum rho_i * (C_i + zeta * W_i) - (sum rho_i * v_i) * G1 and
+    /// sumW = sum rho_i * W_i, with rho_i = nu^i.
+    function _weightedSums(uint256[] calldata proof, uint256 zeta, uint256 nu)
+        internal
+        view
+        returns (uint256[2] memory lhs, uint256[2] memory sumW, bool ok)
+    {
+        uint256 rho = 1;
+        uint256 sumV;
+        uint256[2] memory t;
+        ok = true;
+        for (uint256 i = 0; i < NUM_COMMITMENTS && ok; i++) {
+            sumV = addmod(sumV, mulmod(rho, proof[2 * NUM_COMMITMENTS + i], R_MOD), R_MOD);
+            (t, ok) = _ecMul([proof[2 * i], proof[2 * i + 1]], rho);
+            if (ok) (lhs, ok) = _ecAdd(lhs, t);
+            if (ok) {
+                (t, ok) = _ecMul([proof[3 * NUM_COMMITMENTS + 2 * i], proof[3 * NUM_COMMITMENTS + 2 * i + 1]], rho);
+            }
+            if (ok) (sumW, ok) = _ecAdd(sumW, t);
+            rho = mulmod(rho, nu, R_MOD);
+        }
+        if (ok) (t, ok) = _ecMul(sumW, zeta);
+        if (ok) (lhs, ok) = _ecAdd(lhs, t);
+        if (ok) (t, ok) = _ecMul([G1_X, G1_Y], R_MOD - sumV);
+        if (ok) (lhs, ok) = _ecAdd(lhs, t);
+    }
+
+    /// e(L, G2) * e(-sumW, [tau]G2) == 1
+    function _pairingCheck(uint256[2] memory lhs, uint256[2] memory sumW) internal view returns (bool) {
+        bool ok;
+        uint256[12] memory input = [
+            lhs[0], lhs[1], G2_X1, G2_X0, G2_Y1, G2_Y0,
+            sumW[0], sumW[1] == 0 ? 0 : P_MOD - sumW[1], TAU_X1, TAU_X0, TAU_Y1, TAU_Y0
+        ];
+        uint256[1] memory out;
+        assembly {
+            ok := staticcall(gas(), 0x08, input, 384, out, 0x20)
+        }
+        return ok && out[0] == 1;
+    }
+
+    /// arkworks compressed G1: x little-endian, 0x80 if y > p - y, 0x40 for identity.
+    function _compress(uint256 x, uint256 y) internal pure returns (bytes32) {
+        if (x == 0 && y == 0) return bytes32(uint256(0x40));
+        uint256 flag = y > P_MOD - y ? 0x80 : 0;
+        return bytes32(_reverseBytes(x) | flag);
+    }
+
+    function _reverseBytes(uint256 v) internal pure returns (uint256) {
+        v = ((v >> 8) & 0x{{M8}}) | ((v & 0x{{M8}}) << 8);
+        v = ((v >> 16) & 0x{{M16}}) | ((v & 0x{{M16}}) << 16);
+        v = ((v >> 32) & 0x{{M32}}) | ((v & 0x{{M32}}) << 32);
+        v = ((v >> 64) & 0x{{M64}}) | ((v & 0x{{M64}}) << 64);
+        return (v >> 128) | (v << 128);
+    }
+
+    function _ecAdd(uint256[2] memory a, uint256[2] memory b)
+        internal
+        view
+        returns (uint256[2] memory r, bool ok)
+    {
+        uint256[4] memory input = [a[0], a[1], b[0], b[1]];
+        assembly {
+            ok := staticcall(gas(), 0x06, input, 0x80, r, 0x40)
+        }
+    }
+
+    function _expMod(uint256 b, uint256 e) internal view returns (uint256 r, bool ok) {
+        uint256[6] memory input = [uint256(32), 32, 32, b, e, R_MOD];
+        uint256[1] memory out;
+        assembly {
+            ok := staticcall(gas(), 0x05, input, 0xc0, out, 0x20)
+        }
+        r = out[0];
+    }
+
+    function _ecMul(uint256[2] memory a, uint256 s)
+        internal
+        view
+        returns (uint256[2] memory r, bool ok)
+    {
+        uint256[3] memory input = [a[0], a[1], s];
+        assembly {
+            ok := staticcall(gas(), 0x07, input, 0x60, r, 0x40)
+        }
+    }
+}
+"#;
+
+/// Emit the Solidity source of a verifier contract for `vk`.
+pub fn generate_solidity(vk: &EvmVerifyingKey) -> String {
+    let mut header = Vec::new();
+    vk.header.serialize_compressed(&mut header).expect("serialize header");
+    let (g1_x, g1_y) = vk.g1.xy().expect("[1]G1 is not the identity");
+    let g2 = G2Affine::generator();
+    let (g2_x, g2_y) = g2.xy().expect("G2 generator");
+    let (tau_x, tau_y) = vk.tau_g2.xy().expect("[τ]G2 is not the identity");
+
+    let subst: Vec<(&str, String)> = vec![
+        ("{{N}}", vk.header.domain_n.to_string()),
+        ("{{K}}", vk.header.k.to_string()),
+        ("{{ZH_C}}", dec(&vk.header.zh_c)),
+        ("{{P_MOD}}", Fq::MODULUS.to_string()),
+        ("{{R_MOD}}", F::MODULUS.to_string()),
+        ("{{R256}}", dec(&F::from(2u64).pow([256u64]))),
+        ("{{HAS_Z}}", vk.has_z.to_string()),
+        ("{{NC}}", vk.num_commitments().to_string()),
+        ("{{NW}}", vk.proof_words().to_string()),
+        ("{{G1_X}}", dec(g1_x)),
+        ("{{G1_Y}}", dec(g1_y)),
+        ("{{G2_X1}}", dec(&g2_x.c1)),
+        ("{{G2_X0}}", dec(&g2_x.c0)),
+        ("{{G2_Y1}}", dec(&g2_y.c1)),
+        ("{{G2_Y0}}", dec(&g2_y.c0)),
+        ("{{TAU_X1}}", dec(&tau_x.c1)),
+        ("{{TAU_X0}}", dec(&tau_x.c0)),
+        ("{{TAU_Y1}}", dec(&tau_y.c1)),
+        ("{{TAU_Y0}}", dec(&tau_y.c0)),
+        ("{{HEADER}}", hex::encode(&header)),
+        ("{{PREFIX}}", hex::encode(transcript_prefix(&vk.header))),
+        ("{{WIRE_ITEM}}", hex::encode(item_prefix(FsLabel::WireCommit, 32))),
+        ("{{Z_ITEM}}", hex::encode(item_prefix(FsLabel::PermZCommit, 32))),
+        ("{{Q_ITEM}}", hex::encode(item_prefix(FsLabel::QuotientCommit, 32))),
+        ("{{EVALS_ITEM}}", hex::encode(item_prefix(FsLabel::OpeningEvals, 32 * vk.num_commitments()))),
+        ("{{ZETA_DST}}", hex::encode(zeta_dst())),
+        ("{{NU_DST}}", hex::encode(nu_dst())),
+        ("{{M8}}", "00ff".repeat(16)),
+        ("{{M16}}", "0000ffff".repeat(8)),
+        ("{{M32}}", "00000000ffffffff".repeat(4)),
+        ("{{M64}}", "0000000000000000ffffffffffffffff".repeat(2)),
+    ];
+    subst.iter().fold(TEMPLATE.to_string(), |out, (k, v)| out.replace(k, v))
+}
+
+// ============================================================================
+// Bytecode generator
+// ============================================================================
+
+/// EVM opcodes used by the generated verifier.
+mod op {
+    pub const ADD: u8 = 0x01;
+    pub const SUB: u8 = 0x03;
+    pub const MOD: u8 = 0x06;
+    pub const ADDMOD: u8 = 0x08;
+    pub const MULMOD: u8 = 0x09;
+    pub const LT: u8 = 0x10;
+    pub const GT: u8 = 0x11;
+    pub const EQ: u8 = 0x14;
+    pub const ISZERO: u8 = 0x15;
+    pub const AND: u8 = 0x16;
+    pub const OR: u8 = 0x17;
+    pub const SHL: u8 = 0x1b;
+    pub const SHR: u8 = 0x1c;
+    pub const KECCAK256: u8 = 0x20;
+    pub const CALLVALUE: u8 = 0x34;
+    pub const CALLDATALOAD: u8 = 0x35;
+    pub const CALLDATASIZE: u8 = 0x36;
+    pub const CALLDATACOPY: u8 = 0x37;
+    pub const CODECOPY: u8 = 0x39;
+    pub const POP: u8 = 0x50;
+    pub const MLOAD: u8 = 0x51;
+    pub const MSTORE: u8 = 0x52;
+    pub const JUMP: u8 = 0x56;
+    pub const JUMPI: u8 = 0x57;
+    pub const GAS: u8 = 0x5a;
+    pub const JUMPDEST: u8 = 0x5b;
+    pub const PUSH1: u8 = 0x60;
+    pub const PUSH2: u8 = 0x61;
+    pub const DUP1: u8 = 0x80;
+    pub const SWAP1: u8 = 0x90;
+    pub const RETURN: u8 = 0xf3;
+    pub const STATICCALL: u8 = 0xfa;
+    pub const INVALID: u8 = 0xfe;
+}
+
+/// Jump target, resolved when the program is finished.
+#[derive(Clone, Copy)]
+struct Label(usize);
+
+/// Constant appended after the code, read with `CODECOPY`.
+#[derive(Clone, Copy)]
+struct Blob(usize);
+
+/// Minimal assembler: straight-line opcodes, `PUSH2` label / blob fixups.
+#[derive(Default)]
+struct Asm {
+    code: Vec<u8>,
+    labels: Vec<Option<usize>>,
+    label_fixups: Vec<(usize, Label)>,
+    blobs: Vec<Vec<u8>>,
+    blob_fixups: Vec<(usize, Blob)>,
+}
+
+impl Asm {
+    fn op(&mut self, op: u8) -> &mut Self {
+        self.code.push(op);
+        self
+    }
+
+    fn ops(&mut self, ops: &[u8]) -> &mut Self {
+        self.code.extend_from_slice(ops);
+        self
+    }
+
+    /// Shortest `PUSHn` of a big-endian value (at least one byte).
+    fn push(&mut self, be: &[u8]) -> &mut Self {
+        let start = be.iter().position(|&b| b != 0).unwrap_or(be.len() - 1);
+        let v = &be[start..];
+        self.code.push(op::PUSH1 + (v.len() as u8 - 1));
+        self.code.extend_from_slice(v);
+        self
+    }
+
+    fn push_u(&mut self, v: usize) -> &mut Self {
+        self.push(&(v as u64).to_be_bytes())
+    }
+
+    fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    fn mark(&mut self, l: Label) -> &mut Self {
+        self.labels[l.0] = Some(self.code.len());
+        self.op(op::JUMPDEST)
+    }
+
+    fn push_label(&mut self, l: Label) -> &mut Self {
+        self.code.push(op::PUSH2);
+        self.label_fixups.push((self.code.len(), l));
+        self.ops(&[0, 0])
+    }
+
+    fn jump(&mut self, l: Label) -> &mut Self {
+        self.push_label(l).op(op::JUMP)
+    }
+
+    fn jumpi(&mut self, l: Label) -> &mut Self {
+        self.push_label(l).op(op::JUMPI)
+    }
+
+    /// Jump to `l` if the top of the stack is zero.
+    fn jump_unless(&mut self, l: Label) -> &mut Self {
+        self.op(op::ISZERO).jumpi(l)
+    }
+
+    /// Call a subroutine that takes its return address on top of the stack.
+    fn call(&mut self, sub: Label) -> &mut Self {
+        let ret = self.new_label();
+        self.push_label(ret).jump(sub).mark(ret)
+    }
+
+    /// Call a subroutine expecting `[ret, arg]`, with `arg` already on the stack.
+    fn call_with_arg(&mut self, sub: Label) -> &mut Self {
+        let ret = self.new_label();
+        self.push_label(ret).op(op::SWAP1).jump(sub).mark(ret)
+    }
+
+    fn blob(&mut self, bytes: Vec<u8>) -> Blob {
+        self.blobs.push(bytes);
+        Blob(self.blobs.len() - 1)
+    }
+
+    fn blob_len(&self, b: Blob) -> usize {
+        self.blobs[b.0].len()
+    }
+
+    fn push_blob(&mut self, b: Blob) -> &mut Self {
+        self.code.push(op::PUSH2);
+        self.blob_fixups.push((self.code.len(), b));
+        self.ops(&[0, 0])
+    }
+
+    /// `memory[dest..] = blob` with `dest` on the stack.
+    fn copy_blob_to_top(&mut self, b: Blob) -> &mut Self {
+        let len = self.blob_len(b);
+        self.push_u(len).op(op::SWAP1).push_blob(b).op(op::SWAP1).op(op::CODECOPY)
+    }
+
+    fn mload(&mut self, addr: usize) -> &mut Self {
+        self.push_u(addr).op(op::MLOAD)
+    }
+
+    fn mstore(&mut self, addr: usize) -> &mut Self {
+        self.push_u(addr).op(op::MSTORE)
+    }
+
+    /// `STATICCALL` a precompile; a failed call jumps to `fail`.
+    fn precompile(&mut self, addr: u8, input: usize, len: usize, out: usize, out_len: usize, fail: Label) -> &mut Self {
+        self.push_u(out_len).push_u(out).push_u(len).push_u(input).push_u(addr as usize);
+        self.ops(&[op::GAS, op::STATICCALL]).jump_unless(fail)
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.code.push(op::INVALID);
+        let mut offsets = Vec::with_capacity(self.blobs.len());
+        for b in &self.blobs {
+            offsets.push(self.code.len());
+            self.code.extend_from_slice(b);
+        }
+        let patch = |code: &mut Vec<u8>, at: usize, v: usize| {
+            let v = u16::try_from(v).expect("verifier exceeds 64 KiB");
+            code[at..at + 2].copy_from_slice(&v.to_be_bytes());
+        };
+        for (at, l) in std::mem::take(&mut self.label_fixups) {
+            patch(&mut self.code, at, self.labels[l.0].expect("unplaced label"));
+        }
+        for (at, b) in std::mem::take(&mut self.blob_fixups) {
+            patch(&mut self.code, at, offsets[b.0]);
+        }
+        self.code
+    }
+}
+
+// Memory map of the generated verifier (byte offsets).
+const M_SCRATCH: usize = 0x000; // precompile input, up to 384 bytes
+const M_ACC: usize = 0x180; // Σ ρᵢ·Cᵢ, then L
+const M_ACW: usize = 0x1c0; // Σ ρᵢ·Wᵢ
+const M_TMP: usize = 0x200; // precompile output
+const M_ZETA: usize = 0x240;
+const M_NU: usize = 0x260;
+const M_RHO: usize = 0x280;
+const M_SUMV: usize = 0x2a0;
+const M_PTR: usize = 0x2c0; // end of the transcript bytes
+const M_ARG_OFF: usize = 0x2e0; // subroutine arguments
+const M_ARG_LEN: usize = 0x300;
+const M_ARG_CD: usize = 0x320;
+const M_RES: usize = 0x340;
+const M_TRANSCRIPT: usize = 0x400;
+
+/// Calldata offset of `uint256[]` word `i` (after selector, offset and length).
+fn cd_word(i: usize) -> usize {
+    4 + 64 + 32 * i
+}
+
+/// Runtime bytecode of the verifier for `vk`.
+///
+/// Same ABI, checks and acceptance as [`generate_solidity`]'s contract. It
+/// returns an ABI `bool` for well-formed calls and `false` for anything else.
+/// Per-opening work is unrolled, so the code grows linearly with `k` (about
+/// 3.7 KiB at `k = 2`); EIP-170 caps deployed code at 24 KiB.
+pub fn generate_bytecode(vk: &EvmVerifyingKey) -> Vec<u8> {
+    let n = vk.num_commitments();
+    let k = vk.header.k as usize;
+    let p_mod = word_from_bigint(Fq::MODULUS);
+    let r_mod = word_from_bigint(F::MODULUS);
+    let r256 = fr_word(&F::from(2u64).pow([256u64]));
+    let [g1_x, g1_y] = g1_words(&vk.g1);
+    let g2 = [G2Affine::generator(), vk.tau_g2].map(|p| {
+        let (x, y) = p.xy().expect("G2 points are not the identity");
+        [fq_word(&x.c1), fq_word(&x.c0), fq_word(&y.c1), fq_word(&y.c0)]
+    });
+
+    let mut a = Asm::default();
+    let prefix = a.blob(transcript_prefix(&vk.header));
+    let wire_item = a.blob(item_prefix(FsLabel::WireCommit, 32));
+    let z_item = a.blob(item_prefix(FsLabel::PermZCommit, 32));
+    let q_item = a.blob(item_prefix(FsLabel::QuotientCommit, 32));
+    let evals_item = a.blob(item_prefix(FsLabel::OpeningEvals, 32 * n));
+    let zeta_dst = a.blob(zeta_dst());
+    let nu_dst = a.blob(nu_dst());
+    let (fail, rev, absorb, challenge) = (a.new_label(), a.new_label(), a.new_label(), a.new_label());
+
+    // ABI: no value, exact size, selector, array offset 0x20 and length.
+    a.op(op::CALLVALUE).jumpi(fail);
+    a.op(op::CALLDATASIZE).push_u(cd_word(vk.proof_words())).op(op::EQ).jump_unless(fail);
+    a.push_u(0).op(op::CALLDATALOAD).push_u(224).op(op::SHR).push(&verify_selector());
+    a.op(op::EQ).jump_unless(fail);
+    a.push_u(4).op(op::CALLDATALOAD).push_u(0x20).op(op::EQ).jump_unless(fail);
+    a.push_u(36).op(op::CALLDATALOAD).push_u(vk.proof_words()).op(op::EQ).jump_unless(fail);
+
+    // Coordinates below p, evaluations below r.
+    for (from, to, modulus) in [(0, 2 * n, p_mod), (2 * n, 3 * n, r_mod), (3 * n, 5 * n, p_mod)] {
+        let (top, done) = (a.new_label(), a.new_label());
+        a.push_u(cd_word(from)).mark(top);
+        a.op(op::DUP1).push_u(cd_word(to)).op(op::EQ).jumpi(done);
+        a.push(&modulus).ops(&[op::DUP1 + 1, op::CALLDATALOAD, op::LT]).jump_unless(fail);
+        a.push_u(32).op(op::ADD).jump(top);
+        a.mark(done).op(op::POP);
+    }
+
+    // Transcript: prefix, then one item per commitment.
+    a.push_u(M_TRANSCRIPT).copy_blob_to_top(prefix);
+    let prefix_len = a.blob_len(prefix);
+    a.push_u(M_TRANSCRIPT + prefix_len).mstore(M_PTR);
+    let absorb_at = |a: &mut Asm, item: Blob, word: usize| {
+        let len = a.blob_len(item);
+        a.push_blob(item).mstore(M_ARG_OFF).push_u(len).mstore(M_ARG_LEN);
+        a.push_u(cd_word(word)).mstore(M_ARG_CD).call(absorb);
+    };
+    for i in 0..k {
+        absorb_at(&mut a, wire_item, 2 * i);
+    }
+    if vk.has_z {
+        absorb_at(&mut a, z_item, 2 * k);
+    }
+    absorb_at(&mut a, q_item, 2 * (n - 1));
+
+    // ζ, then the claimed evaluations (little-endian) and ν.
+    let draw = |a: &mut Asm, dst: Blob, into: usize| {
+        let len = a.blob_len(dst);
+        a.push_blob(dst).mstore(M_ARG_OFF).push_u(len).mstore(M_ARG_LEN);
+        a.call(challenge).mload(M_RES).mstore(into);
+    };
+    draw(&mut a, zeta_dst, M_ZETA);
+    a.mload(M_PTR).copy_blob_to_top(evals_item);
+    let evals_len = a.blob_len(evals_item);
+    a.mload(M_PTR).push_u(evals_len).op(op::ADD).mstore(M_PTR);
+    for i in 0..n {
+        a.push_u(cd_word(2 * n + i)).op(op::CALLDATALOAD).call_with_arg(rev);
+        a.mload(M_PTR).op(op::MSTORE).mload(M_PTR).push_u(32).op(op::ADD).mstore(M_PTR);
+    }
+    draw(&mut a, nu_dst, M_NU);
+
+    // Algebraic check: Z_H(ζ)·Q(ζ) − R(ζ) = 0, R(ζ) from the claimed Q(ζ)
+    // as on the default `air::residual_eval_at_point_symbolic` path.
+    for (i, w) in [32, 32, 32].into_iter().enumerate() {
+        a.push_u(w).mstore(M_SCRATCH + 32 * i);
+    }
+    a.mload(M_ZETA).mstore(M_SCRATCH + 96);
+    a.push_u(vk.header.domain_n as usize).mstore(M_SCRATCH + 128);
+    a.push(&r_mod).mstore(M_SCRATCH + 160);
+    a.precompile(0x05, M_SCRATCH, 192, M_TMP, 32, fail);
+    a.push(&r_mod).push(&fr_word(&-vk.header.zh_c)).mload(M_TMP).op(op::ADDMOD).mstore(M_RES);
+    let q_at_zeta = |a: &mut Asm| {
+        a.push(&r_mod).push_u(cd_word(3 * n - 1)).op(op::CALLDATALOAD).mload(M_RES).op(op::MULMOD);
+    };
+    q_at_zeta(&mut a); // R(ζ)
+    q_at_zeta(&mut a); // Z_H(ζ)·Q(ζ)
+    a.op(op::EQ).jump_unless(fail);
+
+    // Weighted sums: ACC = Σ ρᵢ·Cᵢ, ACW = Σ ρᵢ·Wᵢ, SUMV = Σ ρᵢ·vᵢ, ρᵢ = νⁱ.
+    let ec_add_into = |a: &mut Asm, acc: usize| {
+        a.mload(acc).mstore(M_SCRATCH).mload(acc + 32).mstore(M_SCRATCH + 32);
+        a.mload(M_TMP).mstore(M_SCRATCH + 64).mload(M_TMP + 32).mstore(M_SCRATCH + 96);
+        a.precompile(0x06, M_SCRATCH, 128, acc, 64, fail);
+    };
+    a.push_u(1).mstore(M_RHO);
+    for i in 0..n {
+        for (word, acc) in [(2 * i, M_ACC), (3 * n + 2 * i, M_ACW)] {
+            a.push_u(64).push_u(cd_word(word)).push_u(M_SCRATCH).op(op::CALLDATACOPY);
+            a.mload(M_RHO).mstore(M_SCRATCH + 64);
+            a.precompile(0x07, M_SCRATCH, 96, M_TMP, 64, fail);
+            ec_add_into(&mut a, acc);
+        }
+        a.push(&r_mod).push(&r_mod).push_u(cd_word(2 * n + i)).op(op::CALLDATALOAD);
+        a.mload(M_RHO).op(op::MULMOD).mload(M_SUMV).op(op::ADDMOD).mstore(M_SUMV);
+        a.push(&r_mod).mload(M_NU).mload(M_RHO).op(op::MULMOD).mstore(M_RHO);
+    }
+
+    // L = ACC + ζ·ACW − SUMV·G₁
+    a.mload(M_ACW).mstore(M_SCRATCH).mload(M_ACW + 32).mstore(M_SCRATCH + 32);
+    a.mload(M_ZETA).mstore(M_SCRATCH + 64);
+    a.precompile(0x07, M_SCRATCH, 96, M_TMP, 64, fail);
+    ec_add_into(&mut a, M_ACC);
+    a.push(&g1_x).mstore(M_SCRATCH).push(&g1_y).mstore(M_SCRATCH + 32);
+    a.mload(M_SUMV).push(&r_mod).op(op::SUB).mstore(M_SCRATCH + 64);
+    a.precompile(0x07, M_SCRATCH, 96, M_TMP, 64, fail);
+    ec_add_into(&mut a, M_ACC);
+
+    // e(L, G₂) · e(−ACW, [τ]G₂) = 1
+    a.mload(M_ACC).mstore(M_SCRATCH).mload(M_ACC + 32).mstore(M_SCRATCH + 32);
+    for (i, w) in g2[0].iter().enumerate() {
+        a.push(w).mstore(M_SCRATCH + 64 + 32 * i);
+    }
+    a.mload(M_ACW).mstore(M_SCRATCH + 192);
+    a.push(&p_mod).mload(M_ACW + 32).push(&p_mod).ops(&[op::SUB, op::MOD]).mstore(M_SCRATCH + 224);
+    for (i, w) in g2[1].iter().enumerate() {
+        a.push(w).mstore(M_SCRATCH + 256 + 32 * i);
+    }
+    a.precompile(0x08, M_SCRATCH, 384, M_TMP, 32, fail);
+    a.mload(M_TMP).push_u(0).op(op::MSTORE).push_u(32).push_u(0).op(op::RETURN);
+
+    a.mark(fail).push_u(0).push_u(0).op(op::MSTORE).push_u(32).push_u(0).op(op::RETURN);
+
+    // rev: [ret, v] → [reverse_bytes(v)]
+    a.mark(rev);
+    for (shift, mask) in [(8, "00ff"), (16, "0000ffff"), (32, "00000000ffffffff"), (64, "0000000000000000ffffffffffffffff")] {
+        let mask = hex::decode(mask.repeat(32 / (mask.len() / 2))).expect("hex mask");
+        a.op(op::DUP1).push_u(shift).op(op::SHR).push(&mask).op(op::AND);
+        a.op(op::SWAP1).push(&mask).op(op::AND).push_u(shift).op(op::SHL).op(op::OR);
+    }
+    a.op(op::DUP1).push_u(128).op(op::SHR).op(op::SWAP1).push_u(128).op(op::SHL).op(op::OR);
+    a.ops(&[op::SWAP1, op::JUMP]);
+
+    // absorb: item ARG_OFF/ARG_LEN, then the compressed point at calldata ARG_CD.
+    let (identity, store) = (a.new_label(), a.new_label());
+    a.mark(absorb);
+    a.mload(M_ARG_LEN).mload(M_ARG_OFF).mload(M_PTR).op(op::CODECOPY);
+    a.mload(M_PTR).mload(M_ARG_LEN).op(op::ADD).mstore(M_PTR);
+    a.mload(M_ARG_CD).op(op::CALLDATALOAD);
+    a.mload(M_ARG_CD).push_u(32).op(op::ADD).op(op::CALLDATALOAD);
+    a.ops(&[op::DUP1 + 1, op::DUP1 + 1, op::OR, op::ISZERO]).jumpi(identity);
+    // 0x80 if y > p − y, over x little-endian.
+    a.op(op::DUP1).push(&p_mod).op(op::SUB).op(op::SWAP1).op(op::GT).push_u(7).op(op::SHL);
+    a.op(op::SWAP1).call_with_arg(rev).op(op::OR).jump(store);
+    a.mark(identity).ops(&[op::POP, op::POP]).push_u(0x40);
+    a.mark(store).mload(M_PTR).op(op::MSTORE);
+    a.mload(M_PTR).push_u(32).op(op::ADD).mstore(M_PTR);
+    a.op(op::JUMP);
+
+    // challenge: RES = (h0·2²⁵⁶ + h1) mod r, hᵢ = keccak(t ‖ dst ‖ u64be(i)).
+    let hash = |a: &mut Asm| {
+        a.push_u(M_TRANSCRIPT).mload(M_PTR).mload(M_ARG_LEN).op(op::ADD).push_u(8).op(op::ADD);
+        a.op(op::SUB).push_u(M_TRANSCRIPT).op(op::KECCAK256);
+    };
+    let counter = |a: &mut Asm, i: usize| {
+        a.push_u(i).push_u(192).op(op::SHL).mload(M_PTR).mload(M_ARG_LEN).op(op::ADD).op(op::MSTORE);
+    };
+    a.mark(challenge);
+    a.mload(M_ARG_LEN).mload(M_ARG_OFF).mload(M_PTR).op(op::CODECOPY);
+    a.push(&r_mod);
+    counter(&mut a, 1);
+    hash(&mut a);
+    a.push(&r_mod).push(&r256);
+    counter(&mut a, 0);
+    hash(&mut a);
+    a.ops(&[op::MULMOD, op::ADDMOD]).mstore(M_RES).op(op::JUMP);
+
+    a.finish()
+}
+
+/// Creation code that deploys `runtime` as is.
+pub fn deploy_code(runtime: &[u8]) -> Vec<u8> {
+    let len = u16::try_from(runtime.len()).expect("runtime exceeds 64 KiB").to_be_bytes();
+    let mut out = vec![op::PUSH2, len[0], len[1], op::DUP1, op::PUSH2, 0, 13, op::PUSH1, 0, op::CODECOPY];
+    out.extend_from_slice(&[op::PUSH1, 0, op::RETURN]);
+    out.extend_from_slice(runtime);
+    out
+}
+
+/// Calldata as a `0x`-prefixed hex string (what `cast send` / ethers expect).
+pub fn calldata_hex(calldata: &[u8]) -> String {
+    format!("0x{}", hex::encode(calldata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        scheduler::{Prover, Verifier},
+        test_util::tiny_prove_fixture,
+        VerifyParams,
+    };
+    use ark_ec::{CurveGroup, Group};
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Keccak/KZG proof over a small deterministic SRS, plus its key.
+    fn keccak_proof() -> (EvmVerifyingKey, Proof<Kzg>, VerifyParams) {
+        let n = 16usize;
+        let tau = F::rand(&mut StdRng::seed_from_u64(7));
+        let g = crate::curve::G1Projective::generator();
+        let powers: Vec<G1Affine> =
+            (0..n as u64).map(|i| (g * tau.pow([i])).into_affine()).collect();
+        let srs = pcs::Srs::new(powers, Some((crate::curve::G2Projective::generator() * tau).into_affine()))
+            .unwrap();
+
+        let fx = tiny_prove_fixture(n).with_srs(&srs);
+        let proof = Prover::new(&fx.air, &fx.pp)
+            .with_fs_hash(FsHash::Keccak256)
+            .prove_with_restreamer(&fx.rows)
+            .unwrap();
+        let vk = EvmVerifyingKey::from_srs(proof.header.clone(), true, &srs).unwrap();
+        (vk, proof, fx.vp)
+    }
+
+    /// Deploy `creation` in a fresh revm instance and call it with `calldata`.
+    /// Returns the decoded `bool`, or `None` if the call reverted or halted.
+    fn evm_call(creation: &[u8], calldata: &[u8]) -> Option<bool> {
+        use revm::{
+            db::InMemoryDB,
+            primitives::{address, ExecutionResult, Output, TxKind},
+            Evm,
+        };
+        let caller = address!("1000000000000000000000000000000000000000");
+        let mut evm = Evm::builder()
+            .with_db(InMemoryDB::default())
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Create;
+                tx.data = creation.to_vec().into();
+                tx.gas_limit = 10_000_000;
+            })
+            .build();
+        let ExecutionResult::Success { output: Output::Create(_, Some(verifier)), .. } =
+            evm.transact_commit().expect("deploy")
+        else {
+            panic!("verifier deployment failed");
+        };
+        evm.tx_mut().transact_to = TxKind::Call(verifier);
+        evm.tx_mut().data = calldata.to_vec().into();
+        evm.tx_mut().nonce = Some(1);
+        match evm.transact().expect("call").result {
+            ExecutionResult::Success { output, .. } => {
+                let out = output.into_data();
+                assert_eq!(out.len(), 32);
+                Some(out[31] == 1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Calldata variants the contract must reject, each paired with a name.
+    fn tampered(vk: &EvmVerifyingKey, calldata: &[u8]) -> Vec<(&'static str, Vec<u8>)> {
+        let n = vk.num_commitments();
+        let eval = |i: usize| cd_word(2 * n + i);
+        let mut out = Vec::new();
+
+        let mut bad = calldata.to_vec();
+        bad[eval(0) + 31] ^= 1;
+        out.push(("evaluation", bad));
+
+        let mut bad = calldata.to_vec();
+        bad[cd_word(0) + 31] ^= 1; // wire 0 x: off-curve / different point
+        out.push(("commitment", bad));
+
+        // Shift δ from one claimed value to another: the unweighted sums stay
+        // put, so only the ν weights catch it.
+        let delta = F::from(977u64);
+        let mut bad = calldata.to_vec();
+        for (i, d) in [(0, delta), (1, -delta)] {
+            let v = F::from_be_bytes_mod_order(&bad[eval(i)..eval(i) + 32]) + d;
+            bad[eval(i)..eval(i) + 32].copy_from_slice(&fr_word(&v));
+        }
+        out.push(("traded evaluations", bad));
+
+        let mut bad = calldata.to_vec();
+        bad.truncate(bad.len() - 32);
+        out.push(("short", bad));
+        out
+    }
+
+    #[test]
+    fn calldata_roundtrip_and_bytecode_verifies_in_evm() {
+        let (vk, proof, vp) = keccak_proof();
+        assert_eq!(proof.header.fs_hash, FsHash::Keccak256);
+        Verifier::new(&vp).verify(&proof).unwrap();
+
+        let calldata = encode_calldata(&vk, &proof).unwrap();
+        assert_eq!(calldata.len(), 4 + 64 + 32 * vk.proof_words());
+        let back = decode_calldata(&vk, &calldata).unwrap();
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        proof.serialize_compressed(&mut a).unwrap();
+        back.serialize_compressed(&mut b).unwrap();
+        assert_eq!(a, b);
+
+        let creation = deploy_code(&generate_bytecode(&vk));
+        assert_eq!(evm_call(&creation, &calldata), Some(true));
+        for (what, bad) in tampered(&vk, &calldata) {
+            assert_eq!(evm_call(&creation, &bad), Some(false), "{what}");
+            if let Ok(p) = decode_calldata(&vk, &bad) {
+                assert!(Verifier::new(&vp).verify(&p).is_err(), "{what}");
+            }
+        }
+
+        let sol = generate_solidity(&vk);
+        assert!(!sol.contains("{{"));
+        assert!(sol.contains(&hex::encode(transcript_prefix(&vk.header))));
+        assert!(sol.contains(&hex::encode(nu_dst())));
+        assert!(sol.contains(&format!("PROOF_WORDS = {};", vk.proof_words())));
+    }
+
+    /// Compiler the Solidity test is pinned to.
+    const SOLC_VERSION: &str = "0.8.24";
+
+    /// Path of the pinned `solc`, installing it with svm-rs on first use.
+    /// `None` (skip) if it cannot be fetched, unless `CI` is set.
+    fn pinned_solc() -> Option<std::path::PathBuf> {
+        let bin = svm_lib::version_path(SOLC_VERSION).join(format!("solc-{SOLC_VERSION}"));
+        if bin.is_file() {
+            return Some(bin);
+        }
+        match svm_lib::blocking_install(&semver::Version::parse(SOLC_VERSION).unwrap()) {
+            Ok(bin) => Some(bin),
+            Err(e) if std::env::var_os("CI").is_none() => {
+                eprintln!("skipping: cannot install solc {SOLC_VERSION}: {e}");
+                None
+            }
+            Err(e) => panic!("install solc {SOLC_VERSION}: {e}"),
+        }
+    }
+
+    #[test]
+    fn solidity_verifier_compiles_and_agrees_in_evm() {
+        let Some(solc) = pinned_solc() else { return };
+        let (vk, proof, _) = keccak_proof();
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("Verifier.sol");
+        std::fs::write(&src, generate_solidity(&vk)).unwrap();
+        let out = std::process::Command::new(solc)
+            .args(["--optimize", "--bin-runtime"])
+            .arg(&src)
+            .output()
+            .expect("run solc");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let stdout = String::from_utf8(out.stdout).unwrap();
+        let runtime = stdout
+            .lines()
+            .skip_while(|l| !l.starts_with("Binary of the runtime part"))
+            .nth(1)
+            .expect("runtime bytecode in solc output");
+        let creation = deploy_code(&hex::decode(runtime.trim()).unwrap());
+
+        let calldata = encode_calldata(&vk, &proof).unwrap();
+        assert_eq!(evm_call(&creation, &calldata), Some(true));
+        for (what, bad) in tampered(&vk, &calldata) {
+            // Solidity reverts on malformed ABI instead of returning false.
+            assert_ne!(evm_call(&creation, &bad), Some(true), "{what}");
+        }
+    }
+}
@@ -0,0 +1,695 @@
+//! Transparent FRI commitment backend (no trusted setup)
+//!
+//! This module implements [`PolynomialCommitment`] with **FRI** over
+//! Merkle-committed low-degree extensions, so proofs need no SRS at all.
+//! Everything is built from pieces the crate already has: the
+//! [`Transcript`] for Fiat–Shamir (hashed with the proof header's
+//! `fs_hash`, like the outer transcript) and the radix-2 NTT in `domain.rs`.
+//!
+//! ## Construction
+//! - **Commit.** A polynomial `f` with `deg f < n` (`n = max_degree + 1`,
+//!   rounded up to a power of two) is evaluated on the coset
+//!   `D₀ = g·⟨ω_M⟩` of size `M = n·2^blowup_log`, `g = F::GENERATOR`.
+//!   Leaf `j < M/2` of the Merkle tree holds the pair `(f(x_j), f(−x_j))`
+//!   (`−x_j = x_{j+M/2}`), so one opening serves one folding step.
+//!   The commitment is the 32-byte root.
+//! - **Open at ζ.** The prover runs FRI on `q(X) = (f(X) − f(ζ))/(X − ζ)`.
+//!   Layer 0 of `q` is never committed: the verifier derives `q(x)` from the
+//!   opened `f(x)` directly. Each fold halves the domain with
+//!   `q'(x²) = (q(x)+q(−x))/2 + β·(q(x)−q(−x))/(2x)`. Once the degree bound
+//!   drops to `final_len`, the last layer is sent as plain coefficients.
+//! - **Queries.** `num_queries` positions in `D₀` are drawn from the opening
+//!   transcript. Each is checked against every layer root and the final
+//!   polynomial.
+//!
+//! ## Memory
+//! Commitment ingests coefficient **tiles** like the KZG aggregator. But the
+//! LDE and its Merkle tree are inherently global, so FRI keeps
+//! **O(n·2^blowup_log)** field elements resident while committing or opening.
+//! This is the price of dropping the trusted setup. The rest of the
+//! scheduler keeps its O(b_blk) discipline.
+//!
+//! ## Header
+//! Proofs carry `PcsKind::Fri` in the header. The `srs_g2_digest` slot holds
+//! the FRI parameters in the clear (`u64be` blowup, query count and final
+//! length, zero-padded) and `srs_g1_digest` binds them, so a verifier rebuilds
+//! the prover's [`Fri`](crate::fri::Fri) with
+//! [`Fri::from_header`](crate::fri::Fri::from_header) and only has to enforce
+//! a security floor ([`Fri::security_bits`](crate::fri::Fri::security_bits)).
+//!
+//! [`Transcript`]: crate::transcript::Transcript
+
+#![forbid(unsafe_code)]
+
+use ark_ff::{batch_inversion, BigInteger, FftField, Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    domain::{self, Domain},
+    pcs::{
+        AggregatorError, CoeffStreamHiToLo, PcsKind, PcsParams, PolynomialCommitment,
+        VerifyError,
+    },
+    transcript::{FsHash, FsLabel, Transcript},
+    F,
+};
+
+/// 32-byte BLAKE3 digest (Merkle nodes and roots).
+pub type Digest = [u8; 32];
+
+/// FRI parameters. Conjectured security is roughly
+/// `num_queries · blowup_log` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fri {
+    /// log₂ of the LDE blowup factor ρ⁻¹.
+    pub blowup_log: u32,
+    /// Number of FRI query positions per opening.
+    pub num_queries: usize,
+    /// Stop folding once the degree bound is ≤ this (a power of two).
+    pub final_len: usize,
+}
+
+impl Default for Fri {
+    /// ρ = 1/4 with 64 queries (~128-bit conjectured security).
+    fn default() -> Self {
+        Self { blowup_log: 2, num_queries: 64, final_len: 8 }
+    }
+}
+
+/// Merkle root over the LDE of a committed polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FriCommitment(pub Digest);
+
+/// One opened Merkle leaf: the folding pair `(p(x), p(−x))` and its path.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FriLayerOpening {
+    /// Leaf values `[p(x), p(−x)]`.
+    pub values: [F; 2],
+    /// Sibling digests, leaf level first.
+    pub path: Vec<Digest>,
+}
+
+/// All layer openings for one query position.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FriQuery {
+    /// Layer 0 opens `f` itself; layer `i ≥ 1` opens the `i`-th fold of `q`.
+    pub layers: Vec<FriLayerOpening>,
+}
+
+/// FRI proof that a committed polynomial opens to `value` at `zeta`.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FriOpeningProof {
+    /// Evaluation point ζ.
+    pub zeta: F,
+    /// Claimed value f(ζ).
+    pub value: F,
+    /// Roots of the committed fold layers `1..r` (the last fold is sent in the clear).
+    pub layer_roots: Vec<Digest>,
+    /// Coefficients (low→high) of the final folded polynomial.
+    pub final_coeffs: Vec<F>,
+    /// Query openings, in transcript order.
+    pub queries: Vec<FriQuery>,
+}
+
+// ===========================================================================
+// Merkle tree over folding pairs
+// ===========================================================================
+
+fn hash_leaf(a: &F, b: &F) -> Digest {
+    let mut bytes = Vec::with_capacity(2 * a.compressed_size());
+    a.serialize_compressed(&mut bytes).expect("serialize field");
+    b.serialize_compressed(&mut bytes).expect("serialize field");
+    let mut h = blake3::Hasher::new();
+    h.update(b"SSZKP.FRI.leaf");
+    h.update(&bytes);
+    *h.finalize().as_bytes()
+}
+
+fn hash_node(l: &Digest, r: &Digest) -> Digest {
+    let mut h = blake3::Hasher::new();
+    h.update(b"SSZKP.FRI.node");
+    h.update(l);
+    h.update(r);
+    *h.finalize().as_bytes()
+}
+
+struct MerkleTree {
+    /// `levels[0]` are leaf digests; the last level holds the root.
+    levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    /// Build over the pairs `(evals[j], evals[j + len/2])`.
+    fn from_pairs(evals: &[F]) -> Self {
+        let h = evals.len() / 2;
+        let leaves: Vec<Digest> = (0..h).map(|j| hash_leaf(&evals[j], &evals[j + h])).collect();
+        let mut levels = vec![leaves];
+        while levels.last().map_or(0, |l| l.len()) > 1 {
+            let prev = levels.last().expect("non-empty");
+            let next = prev.chunks(2).map(|c| hash_node(&c[0], &c[1])).collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    fn root(&self) -> Digest {
+        self.levels.last().expect("non-empty")[0]
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<Digest> {
+        let mut out = Vec::with_capacity(self.levels.len() - 1);
+        for level in &self.levels[..self.levels.len() - 1] {
+            out.push(level[idx ^ 1]);
+            idx >>= 1;
+        }
+        out
+    }
+}
+
+fn merkle_verify(root: &Digest, mut idx: usize, leaf: Digest, path: &[Digest]) -> bool {
+    let mut acc = leaf;
+    for sib in path {
+        acc = if idx & 1 == 0 { hash_node(&acc, sib) } else { hash_node(sib, &acc) };
+        idx >>= 1;
+    }
+    idx == 0 && acc == *root
+}
+
+// ===========================================================================
+// Shape & arithmetic helpers
+// ===========================================================================
+
+/// Sizes shared by prover and verifier for one polynomial.
+#[derive(Debug, Clone, Copy)]
+struct Shape {
+    /// LDE size M = n·2^blowup_log.
+    m: usize,
+    /// Number of folds r.
+    rounds: usize,
+    /// Degree bound of the final polynomial.
+    final_len: usize,
+    /// Generator of ⟨ω_M⟩.
+    omega: F,
+    /// Coset shift g.
+    shift: F,
+}
+
+impl Fri {
+    /// Rebuild the parameters a proof was made with from its header.
+    ///
+    /// Returns `None` unless the header names FRI, the parameter slot is well
+    /// formed and the digest slot matches it. The result is only as strong as
+    /// the prover chose; check [`Fri::security_bits`] before trusting it.
+    pub fn from_header(header: &crate::ProofHeader) -> Option<Fri> {
+        if header.pcs != PcsKind::Fri {
+            return None;
+        }
+        let slot = &header.srs_g2_digest;
+        if slot[24..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        let word = |i: usize| u64::from_be_bytes(slot[8 * i..8 * i + 8].try_into().unwrap());
+        let fri = Fri {
+            blowup_log: u32::try_from(word(0)).ok().filter(|&b| b > 0 && b <= F::TWO_ADICITY)?,
+            num_queries: usize::try_from(word(1)).ok().filter(|&q| q > 0)?,
+            final_len: usize::try_from(word(2)).ok().filter(|&l| l > 0)?,
+        };
+        (fri.params_digest() == header.srs_g1_digest).then_some(fri)
+    }
+
+    /// Conjectured security in bits, `num_queries · blowup_log`.
+    pub fn security_bits(&self) -> usize {
+        self.num_queries.saturating_mul(self.blowup_log as usize)
+    }
+
+    fn shape(&self, pcs: &PcsParams) -> Option<Shape> {
+        let n = (pcs.max_degree + 1).next_power_of_two();
+        let m = n.checked_shl(self.blowup_log)?;
+        if m < 2 || m.trailing_zeros() > F::TWO_ADICITY {
+            return None;
+        }
+        let final_len = self.final_len.max(1).next_power_of_two().min(n);
+        let rounds = (n / final_len).trailing_zeros() as usize;
+        let omega = F::get_root_of_unity(m as u64)?;
+        Some(Shape { m, rounds, final_len, omega, shift: F::GENERATOR })
+    }
+
+    fn params_digest(&self) -> Digest {
+        let mut h = blake3::Hasher::new();
+        h.update(b"SSZKP.FRI.v1");
+        h.update(&(self.blowup_log as u64).to_be_bytes());
+        h.update(&(self.num_queries as u64).to_be_bytes());
+        h.update(&(self.final_len as u64).to_be_bytes());
+        *h.finalize().as_bytes()
+    }
+
+    /// The parameters in the clear, for the header's `srs_g2_digest` slot.
+    fn params_slot(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out[..8].copy_from_slice(&(self.blowup_log as u64).to_be_bytes());
+        out[8..16].copy_from_slice(&(self.num_queries as u64).to_be_bytes());
+        out[16..24].copy_from_slice(&(self.final_len as u64).to_be_bytes());
+        out
+    }
+}
+
+/// Evaluate `coeffs` (low→high, `len ≤ m`) on the coset `shift·⟨omega⟩` of size `m`.
+fn coset_lde(coeffs: &[F], m: usize, omega: F, shift: F) -> Vec<F> {
+    let mut scaled = Vec::with_capacity(m);
+    let mut s = F::one();
+    for c in coeffs {
+        scaled.push(*c * s);
+        s *= shift;
+    }
+    scaled.resize(m, F::zero());
+    let d = Domain { n: m, omega, zh_c: F::one() };
+    domain::ntt_block_coeffs_to_evals_r(&d, &scaled).expect("LDE size is a valid subgroup")
+}
+
+/// Coefficients (low→high) of the polynomial whose values on `shift·⟨omega⟩` are `evals`.
+fn coset_interpolate(evals: &[F], omega: F, shift: F) -> Vec<F> {
+    let d = Domain { n: evals.len(), omega, zh_c: F::one() };
+    let mut coeffs =
+        domain::ifft_block_evals_to_coeffs_r(&d, evals).expect("layer size is a valid subgroup");
+    let shift_inv = shift.inverse().expect("coset shift is non-zero");
+    let mut s = F::one();
+    for c in coeffs.iter_mut() {
+        *c *= s;
+        s *= shift_inv;
+    }
+    coeffs
+}
+
+/// One FRI fold of a full layer living on `shift·⟨omega⟩`.
+fn fold_layer(evals: &[F], omega: F, shift: F, beta: F) -> Vec<F> {
+    let h = evals.len() / 2;
+    let mut x_inv = Vec::with_capacity(h);
+    let mut x = shift;
+    for _ in 0..h {
+        x_inv.push(x);
+        x *= omega;
+    }
+    batch_inversion(&mut x_inv);
+    let two_inv = F::from(2u64).inverse().expect("char ≠ 2");
+    (0..h)
+        .map(|j| fold_pair(evals[j], evals[j + h], x_inv[j], beta, two_inv))
+        .collect()
+}
+
+#[inline]
+fn fold_pair(a: F, b: F, x_inv: F, beta: F, two_inv: F) -> F {
+    two_inv * ((a + b) + beta * (a - b) * x_inv)
+}
+
+#[inline]
+fn horner(coeffs: &[F], x: F) -> F {
+    coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c)
+}
+
+/// Map query challenges to positions in `[0, m)` (`m` a power of two).
+fn query_positions(fs: &mut Transcript, count: usize, m: usize) -> Vec<usize> {
+    fs.challenge_points_l(FsLabel::FriQuery, count)
+        .into_iter()
+        .map(|c| {
+            let limb = c.into_bigint().to_bytes_le();
+            let mut w = [0u8; 8];
+            w.copy_from_slice(&limb[..8]);
+            (u64::from_le_bytes(w) as usize) & (m - 1)
+        })
+        .collect()
+}
+
+/// Start the per-opening transcript (binds root, point and value), hashed
+/// with the proof's `fs_hash`.
+fn opening_transcript(fs_hash: FsHash, root: &Digest, zeta: &F, value: &F) -> Transcript {
+    let mut fs = Transcript::with_hash("sszkp.fri", fs_hash);
+    fs.absorb_bytes_l(FsLabel::FriRoot, root);
+    fs.absorb_scalar_l(FsLabel::FriOpenPoint, zeta);
+    fs.absorb_scalar_l(FsLabel::FriOpenPoint, value);
+    fs
+}
+
+fn absorb_final(fs: &mut Transcript, coeffs: &[F]) {
+    let mut bytes = Vec::new();
+    coeffs.serialize_compressed(&mut bytes).expect("serialize final coeffs");
+    fs.absorb_bytes_l(FsLabel::FriFinal, &bytes);
+}
+
+// ===========================================================================
+// Prover / verifier
+// ===========================================================================
+
+impl Fri {
+    fn collect_tiles<I>(&self, pcs: &PcsParams, tiles: I) -> Result<Vec<F>, AggregatorError>
+    where
+        I: IntoIterator<Item = Vec<F>>,
+    {
+        let limit = pcs.max_degree + 1;
+        let mut coeffs: Vec<F> = Vec::new();
+        for tile in tiles {
+            if coeffs.len() + tile.len() > limit {
+                return Err(AggregatorError::DegreeOverflow {
+                    cursor: coeffs.len(),
+                    adding: tile.len(),
+                    limit,
+                });
+            }
+            coeffs.extend_from_slice(&tile);
+        }
+        Ok(coeffs)
+    }
+
+    fn open_one(
+        &self,
+        sh: &Shape,
+        fs_hash: FsHash,
+        coeffs: &[F],
+        f_evals: &[F],
+        f_tree: &MerkleTree,
+        zeta: F,
+    ) -> FriOpeningProof {
+        let value = horner(coeffs, zeta);
+        let mut fs = opening_transcript(fs_hash, &f_tree.root(), &zeta, &value);
+
+        // Layer 0 of q from f's LDE: q(x) = (f(x) − v)/(x − ζ).
+        let mut denom = Vec::with_capacity(sh.m);
+        let mut x = sh.shift;
+        for _ in 0..sh.m {
+            denom.push(x - zeta);
+            x *= sh.omega;
+        }
+        batch_inversion(&mut denom);
+        let mut layer: Vec<F> =
+            f_evals.iter().zip(&denom).map(|(f, d)| (*f - value) * d).collect();
+
+        // Fold r times; commit every intermediate layer (kept for the queries).
+        let mut committed: Vec<(Vec<F>, MerkleTree)> = Vec::with_capacity(sh.rounds);
+        let mut layer_roots = Vec::with_capacity(sh.rounds.saturating_sub(1));
+        let (mut omega_i, mut shift_i) = (sh.omega, sh.shift);
+        for i in 0..sh.rounds {
+            let beta = fs.challenge_f_l(FsLabel::FriFold);
+            layer = fold_layer(&layer, omega_i, shift_i, beta);
+            omega_i.square_in_place();
+            shift_i.square_in_place();
+            if i + 1 < sh.rounds {
+                let t = MerkleTree::from_pairs(&layer);
+                fs.absorb_bytes_l(FsLabel::FriRoot, &t.root());
+                layer_roots.push(t.root());
+                committed.push((layer.clone(), t));
+            }
+        }
+        let mut final_coeffs = coset_interpolate(&layer, omega_i, shift_i);
+        final_coeffs.truncate(sh.final_len);
+        absorb_final(&mut fs, &final_coeffs);
+
+        let queries = query_positions(&mut fs, self.num_queries, sh.m)
+            .into_iter()
+            .map(|mut pos| {
+                let mut layers = Vec::with_capacity(sh.rounds.max(1));
+                for i in 0..sh.rounds.max(1) {
+                    let h = (sh.m >> i) / 2;
+                    let leaf = pos % h;
+                    let (evals, tree) = if i == 0 {
+                        (f_evals, f_tree)
+                    } else {
+                        let (e, t) = &committed[i - 1];
+                        (e.as_slice(), t)
+                    };
+                    layers.push(FriLayerOpening {
+                        values: [evals[leaf], evals[leaf + h]],
+                        path: tree.path(leaf),
+                    });
+                    pos = leaf;
+                }
+                FriQuery { layers }
+            })
+            .collect();
+
+        FriOpeningProof { zeta, value, layer_roots, final_coeffs, queries }
+    }
+
+    fn verify_one(
+        &self,
+        sh: &Shape,
+        fs_hash: FsHash,
+        commitment: &FriCommitment,
+        point: F,
+        claimed: F,
+        pr: &FriOpeningProof,
+    ) -> Result<(), VerifyError> {
+        if pr.zeta != point || pr.value != claimed {
+            return Err(VerifyError::Opening("FRI opening point/value mismatch"));
+        }
+        let expected_roots = sh.rounds.saturating_sub(1);
+        if pr.layer_roots.len() != expected_roots
+            || pr.final_coeffs.len() > sh.final_len
+            || pr.queries.len() != self.num_queries
+        {
+            return Err(VerifyError::Opening("FRI proof shape mismatch"));
+        }
+
+        // Replay the opening transcript.
+        let mut fs = opening_transcript(fs_hash, &commitment.0, &pr.zeta, &pr.value);
+        let mut betas = Vec::with_capacity(sh.rounds);
+        for i in 0..sh.rounds {
+            betas.push(fs.challenge_f_l(FsLabel::FriFold));
+            if i + 1 < sh.rounds {
+                fs.absorb_bytes_l(FsLabel::FriRoot, &pr.layer_roots[i]);
+            }
+        }
+        absorb_final(&mut fs, &pr.final_coeffs);
+        let positions = query_positions(&mut fs, self.num_queries, sh.m);
+
+        let two_inv = F::from(2u64).inverse().expect("char ≠ 2");
+        let opened_layers = sh.rounds.max(1);
+        for (q, &start) in pr.queries.iter().zip(&positions) {
+            if q.layers.len() != opened_layers {
+                return Err(VerifyError::Opening("FRI query shape mismatch"));
+            }
+            let mut pos = start;
+            let mut carried = F::zero();
+            for (i, op) in q.layers.iter().enumerate() {
+                let h = (sh.m >> i) / 2;
+                let (leaf, side) = (pos % h, pos / h);
+                let root = if i == 0 { &commitment.0 } else { &pr.layer_roots[i - 1] };
+                if !merkle_verify(root, leaf, hash_leaf(&op.values[0], &op.values[1]), &op.path) {
+                    return Err(VerifyError::Opening("FRI Merkle path invalid"));
+                }
+
+                let step = 1u64 << i;
+                let x = sh.shift.pow([step]) * sh.omega.pow([step * leaf as u64]);
+                let (a, b) = if i == 0 {
+                    let da = (x - pr.zeta).inverse();
+                    let db = (-x - pr.zeta).inverse();
+                    match (da, db) {
+                        (Some(da), Some(db)) => ((op.values[0] - pr.value) * da, (op.values[1] - pr.value) * db),
+                        _ => return Err(VerifyError::Opening("FRI query hit the opening point")),
+                    }
+                } else {
+                    if op.values[side] != carried {
+                        return Err(VerifyError::Opening("FRI fold inconsistency"));
+                    }
+                    (op.values[0], op.values[1])
+                };
+
+                if sh.rounds == 0 {
+                    if horner(&pr.final_coeffs, x) != a || horner(&pr.final_coeffs, -x) != b {
+                        return Err(VerifyError::Opening("FRI final polynomial mismatch"));
+                    }
+                    break;
+                }
+                let x_inv = x.inverse().expect("coset points are non-zero");
+                carried = fold_pair(a, b, x_inv, betas[i], two_inv);
+                pos = leaf;
+            }
+
+            if sh.rounds > 0 {
+                let step = 1u64 << sh.rounds;
+                let x = sh.shift.pow([step]) * sh.omega.pow([step * pos as u64]);
+                if horner(&pr.final_coeffs, x) != carried {
+                    return Err(VerifyError::Opening("FRI final polynomial mismatch"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PolynomialCommitment for Fri {
+    type Commitment = FriCommitment;
+    type Proof = FriOpeningProof;
+
+    const KIND: PcsKind = PcsKind::Fri;
+
+    fn srs_digests(&self, _pcs: &PcsParams) -> Result<([u8; 32], [u8; 32]), AggregatorError> {
+        Ok((self.params_digest(), self.params_slot()))
+    }
+
+    fn commit_coeff_tiles<I>(
+        &self,
+        pcs: &PcsParams,
+        _poly_id: &'static str,
+        tiles: I,
+    ) -> Result<FriCommitment, AggregatorError>
+    where
+        I: IntoIterator<Item = Vec<F>>,
+    {
+        let sh = self
+            .shape(pcs)
+            .ok_or(AggregatorError::Backend("FRI LDE exceeds the field's two-adicity"))?;
+        let coeffs = self.collect_tiles(pcs, tiles)?;
+        let evals = coset_lde(&coeffs, sh.m, sh.omega, sh.shift);
+        Ok(FriCommitment(MerkleTree::from_pairs(&evals).root()))
+    }
+
+    fn open_hi_to_lo(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        _commitment: &FriCommitment,
+        stream_coeff_hi_to_lo: &mut CoeffStreamHiToLo<'_>,
+        points: &[F],
+    ) -> Result<Vec<FriOpeningProof>, AggregatorError> {
+        let sh = self
+            .shape(pcs)
+            .ok_or(AggregatorError::Backend("FRI LDE exceeds the field's two-adicity"))?;
+
+        // Tiles start at degree `max_degree`; collect and flip to low→high.
+        let mut hi_to_lo: Vec<F> = Vec::with_capacity(pcs.max_degree + 1);
+        stream_coeff_hi_to_lo(&mut |tile: Vec<F>| hi_to_lo.extend(tile));
+        let mut coeffs = vec![F::zero(); pcs.max_degree + 1];
+        for (k, c) in hi_to_lo.into_iter().enumerate().take(pcs.max_degree + 1) {
+            coeffs[pcs.max_degree - k] = c;
+        }
+
+        let f_evals = coset_lde(&coeffs, sh.m, sh.omega, sh.shift);
+        let f_tree = MerkleTree::from_pairs(&f_evals);
+        Ok(points
+            .iter()
+            .map(|&zeta| self.open_one(&sh, fs_hash, &coeffs, &f_evals, &f_tree, zeta))
+            .collect())
+    }
+
+    #[inline]
+    fn opened_value(proof: &FriOpeningProof) -> F {
+        proof.value
+    }
+
+    fn verify(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        commitments: &[FriCommitment],
+        points: &[F],
+        claimed_evals: &[F],
+        proofs: &[FriOpeningProof],
+    ) -> Result<(), VerifyError> {
+        let expected = commitments.len().saturating_mul(points.len());
+        if proofs.len() != expected || claimed_evals.len() != expected {
+            return Err(VerifyError::Shape { expected, got: proofs.len().max(claimed_evals.len()) });
+        }
+        let sh = self.shape(pcs).ok_or(VerifyError::Opening("FRI shape unsupported"))?;
+        let mut idx = 0usize;
+        for c in commitments {
+            for &pt in points {
+                self.verify_one(&sh, fs_hash, c, pt, claimed_evals[idx], &proofs[idx])?;
+                idx += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcs::Basis;
+
+    fn pcs(n: usize) -> PcsParams {
+        PcsParams { max_degree: n - 1, basis: Basis::Coefficient, srs: None }
+    }
+
+    fn open(fri: &Fri, p: &PcsParams, cm: &FriCommitment, coeffs: &[F], z: F) -> FriOpeningProof {
+        let mut hi_to_lo: Vec<F> = coeffs.to_vec();
+        hi_to_lo.reverse();
+        let mut stream = |sink: &mut dyn FnMut(Vec<F>)| {
+            for tile in hi_to_lo.chunks(5) {
+                sink(tile.to_vec());
+            }
+        };
+        fri.open_hi_to_lo(p, FsHash::Blake3, cm, &mut stream, &[z]).unwrap().remove(0)
+    }
+
+    #[test]
+    fn commit_open_verify_roundtrip() {
+        let fri = Fri { num_queries: 16, ..Fri::default() };
+        for n in [4usize, 64] {
+            let p = pcs(n);
+            let coeffs: Vec<F> = (0..n as u64).map(|i| F::from(i * i + 3)).collect();
+            let tiles = coeffs.chunks(7).map(|c| c.to_vec());
+            let cm = fri.commit_coeff_tiles(&p, "t", tiles).unwrap();
+            let z = F::from(123_456u64);
+            let pr = open(&fri, &p, &cm, &coeffs, z);
+            assert_eq!(pr.value, horner(&coeffs, z));
+            fri.verify(&p, FsHash::Blake3, &[cm], &[z], &[pr.value], &[pr]).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_value_and_tampered_layers() {
+        let fri = Fri { num_queries: 16, ..Fri::default() };
+        let n = 64;
+        let p = pcs(n);
+        let coeffs: Vec<F> = (0..n as u64).map(|i| F::from(7 * i + 1)).collect();
+        let cm = fri.commit_coeff_tiles(&p, "t", [coeffs.clone()]).unwrap();
+        let z = F::from(99u64);
+        let pr = open(&fri, &p, &cm, &coeffs, z);
+        // Replaying with another hash than the prover's draws other queries.
+        assert!(fri.verify(&p, FsHash::Keccak256, &[cm], &[z], &[pr.value], std::slice::from_ref(&pr)).is_err());
+
+        let mut bad = pr.clone();
+        bad.value += F::one();
+        assert!(fri.verify(&p, FsHash::Blake3, &[cm], &[z], &[bad.value], &[bad]).is_err());
+
+        let mut bad = pr.clone();
+        bad.queries[0].layers[1].values[0] += F::one();
+        assert!(fri.verify(&p, FsHash::Blake3, &[cm], &[z], &[pr.value], &[bad]).is_err());
+
+        let mut bad = pr.clone();
+        bad.final_coeffs[0] += F::one();
+        assert!(fri.verify(&p, FsHash::Blake3, &[cm], &[z], &[pr.value], &[bad]).is_err());
+    }
+
+    #[test]
+    fn oversized_lde_is_an_error() {
+        let fri = Fri { blowup_log: F::TWO_ADICITY, ..Fri::default() };
+        let p = pcs(4);
+        assert!(fri.commit_coeff_tiles(&p, "t", [vec![F::one(); 4]]).is_err());
+        let mut stream = |sink: &mut dyn FnMut(Vec<F>)| sink(vec![F::one(); 4]);
+        let cm = FriCommitment([0u8; 32]);
+        let res = fri.open_hi_to_lo(&p, FsHash::Blake3, &cm, &mut stream, &[F::one()]);
+        assert!(matches!(res, Err(AggregatorError::Backend(_))), "{res:?}");
+    }
+
+    #[test]
+    fn scheduler_roundtrip_without_srs() {
+        use crate::{
+            scheduler::{Prover, Verifier},
+            test_util::tiny_prove_fixture,
+        };
+
+        let fx = tiny_prove_fixture(16);
+        let fri = Fri { num_queries: 8, ..Fri::default() };
+        let proof = Prover::with_pcs(&fx.air, &fx.pp, fri).prove_with_restreamer(&fx.rows).unwrap();
+        assert_eq!(proof.header.pcs, PcsKind::Fri);
+
+        let from_header = Fri::from_header(&proof.header).unwrap();
+        assert_eq!(from_header, fri);
+        assert_eq!(from_header.security_bits(), 16);
+        Verifier::with_pcs(&fx.vp, from_header).verify(&proof).unwrap();
+
+        let mut tampered = proof.header.clone();
+        tampered.srs_g2_digest[15] += 1;
+        assert_eq!(Fri::from_header(&tampered), None);
+    }
+}
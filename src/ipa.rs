@@ -0,0 +1,449 @@
+//! Transparent inner-product-argument (IPA) commitment backend
+//!
+//! A Bulletproofs/Halo-style polynomial commitment whose public parameters are
+//! **hashed to the curve**: no τ, no ceremony, no files. It sits between KZG
+//! (tiny proofs, trusted setup) and FRI (no setup, large proofs): openings are
+//! `2·log₂ n` group elements plus one scalar.
+//!
+//! ## Parameters
+//! - Generators `G_i` and the inner-product base `U` are derived by
+//!   try-and-increment hashing (BLAKE3, DST `"SSZKP.IPA.gen"`) onto G₁ of the build's curve.
+//!   They depend only on their index, so the set is **prefix-stable**. It is
+//!   cached process-wide and grown on demand, like the dev SRS in `pcs.rs`.
+//! - Nobody knows discrete-log relations between the generators, which is
+//!   the only assumption the scheme needs.
+//!
+//! ## Commit (streaming)
+//! [`IpaAggregator`] mirrors `pcs::Aggregator`. It keeps a cursor and a
+//! running `G1Projective` and absorbs coefficient tiles low→high as
+//! `C += Σ a_i·G_i`. So commitments keep the O(tile) memory profile.
+//!
+//! ## Open at ζ
+//! The standard halving argument is run on `⟨a, b⟩ = f(ζ)` with
+//! `b = (1, ζ, ζ², …)`. Each round sends `(L, R)` and folds `a`, `b` and `G`
+//! with a Fiat–Shamir challenge. The final scalar `a` closes the argument.
+//! The prover holds one polynomial's coefficients and generators
+//! (**O(n)**) while opening. The verifier does one MSM of size
+//! `n + 2·log₂ n`.
+//!
+//! Proofs carry `PcsKind::Ipa` in the header. The `srs_g1_digest` slot binds
+//! the generator derivation and `srs_g2_digest` is all-zero.
+//!
+//! [`IpaAggregator`]: crate::ipa::IpaAggregator
+
+#![forbid(unsafe_code)]
+
+use std::sync::{Mutex, OnceLock};
+
+use crate::curve::{Fq, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use blake3::Hasher;
+
+use crate::{
+    pcs::{
+        AggregatorError, Basis, CoeffStreamHiToLo, PcsKind, PcsParams, PolynomialCommitment,
+        VerifyError,
+    },
+    transcript::{FsHash, FsLabel, Transcript},
+    F,
+};
+
+/// Domain-separation tag for generator derivation.
+const GEN_DST: &[u8] = b"SSZKP.IPA.gen";
+
+/// IPA commitment backend (stateless; generators live in a global cache).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ipa;
+
+/// Pedersen-style vector commitment `C = Σ a_i·G_i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaCommitment(pub G1Affine);
+
+/// IPA opening proof for one point.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaOpeningProof {
+    /// Evaluation point ζ.
+    pub zeta: F,
+    /// Claimed value f(ζ).
+    pub value: F,
+    /// Left cross terms, one per round.
+    pub l: Vec<G1Affine>,
+    /// Right cross terms, one per round.
+    pub r: Vec<G1Affine>,
+    /// Fully folded coefficient.
+    pub a: F,
+}
+
+// ===========================================================================
+// Generators
+// ===========================================================================
+
+/// Try-and-increment hash onto G₁, cofactor cleared (a no-op on BN254).
+fn hash_to_g1(tag: &[u8], idx: u64) -> G1Affine {
+    for ctr in 0u32.. {
+        let mut h = Hasher::new();
+        h.update(GEN_DST);
+        h.update(tag);
+        h.update(&idx.to_be_bytes());
+        h.update(&ctr.to_be_bytes());
+        let mut buf = [0u8; 65];
+        h.finalize_xof().fill(&mut buf);
+        let x = Fq::from_le_bytes_mod_order(&buf[..64]);
+        if let Some(p) = G1Affine::get_point_from_x_unchecked(x, buf[64] & 1 == 1) {
+            let p = p.clear_cofactor();
+            if !p.is_zero() {
+                return p;
+            }
+        }
+    }
+    unreachable!("hash-to-curve exhausted its counter")
+}
+
+struct Generators {
+    g: Vec<G1Affine>,
+    u: G1Affine,
+}
+
+impl Generators {
+    fn ensure_len(&mut self, n: usize) {
+        let cur = self.g.len();
+        self.g.extend((cur..n).map(|i| hash_to_g1(b"G", i as u64)));
+    }
+}
+
+fn generators() -> &'static Mutex<Generators> {
+    static GENS: OnceLock<Mutex<Generators>> = OnceLock::new();
+    GENS.get_or_init(|| Mutex::new(Generators { g: Vec::new(), u: hash_to_g1(b"U", 0) }))
+}
+
+/// First `n` generators plus `U` (derived on first use, then cached).
+fn generators_prefix(n: usize) -> (Vec<G1Affine>, G1Affine) {
+    let mut guard = generators().lock().expect("IPA generator mutex poisoned");
+    guard.ensure_len(n);
+    (guard.g[..n].to_vec(), guard.u)
+}
+
+// ===========================================================================
+// Streaming commitment
+// ===========================================================================
+
+/// Streams coefficient tiles into `C = Σ a_i·G_i` (see `pcs::Aggregator`).
+pub struct IpaAggregator<'a> {
+    pub(crate) pcs: &'a PcsParams,
+    acc: G1Projective,
+    cursor: usize,
+}
+
+impl<'a> IpaAggregator<'a> {
+    /// Create a new aggregator. The initial `cursor` is 0 (constant term slot).
+    pub fn new(pcs: &'a PcsParams) -> Self {
+        Self { pcs, acc: G1Projective::zero(), cursor: 0 }
+    }
+
+    /// Current stream cursor (number of coefficients already absorbed).
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Add a block of **coefficients** provided in **low→high** order.
+    pub fn add_block_coeffs_r(&mut self, slice: &[F]) -> Result<(), AggregatorError> {
+        if !matches!(self.pcs.basis, Basis::Coefficient) {
+            return Err(AggregatorError::Basis {
+                expected: Basis::Coefficient,
+                got: self.pcs.basis,
+            });
+        }
+        let limit = self.pcs.max_degree + 1;
+        if self.cursor + slice.len() > limit {
+            return Err(AggregatorError::DegreeOverflow {
+                cursor: self.cursor,
+                adding: slice.len(),
+                limit,
+            });
+        }
+        let end = self.cursor + slice.len();
+        let mut guard = generators().lock().expect("IPA generator mutex poisoned");
+        guard.ensure_len(end);
+        self.acc += G1Projective::msm_unchecked(&guard.g[self.cursor..end], slice);
+        drop(guard);
+        self.cursor = end;
+        Ok(())
+    }
+
+    /// Finalize and return the commitment.
+    pub fn finalize(self) -> IpaCommitment {
+        IpaCommitment(self.acc.into_affine())
+    }
+}
+
+// ===========================================================================
+// Opening argument
+// ===========================================================================
+
+/// Vector length for a degree bound: `max_degree + 1` rounded up to a power of two.
+#[inline]
+fn vector_len(pcs: &PcsParams) -> usize {
+    (pcs.max_degree + 1).next_power_of_two()
+}
+
+#[inline]
+fn inner(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).map(|(x, y)| *x * y).sum()
+}
+
+/// Transcript for one opening, hashed with the proof's `fs_hash`; returns it
+/// with the challenge-scaled `U`.
+fn opening_transcript(
+    fs_hash: FsHash,
+    c: &IpaCommitment,
+    zeta: &F,
+    value: &F,
+    u: G1Affine,
+) -> (Transcript, G1Projective) {
+    let mut fs = Transcript::with_hash("sszkp.ipa", fs_hash);
+    fs.absorb_commitment_l(FsLabel::IpaOpenPoint, c);
+    fs.absorb_scalar_l(FsLabel::IpaOpenPoint, zeta);
+    fs.absorb_scalar_l(FsLabel::IpaOpenPoint, value);
+    let w = fs.challenge_f_l(FsLabel::IpaChallenge);
+    (fs, u * w)
+}
+
+fn absorb_round(fs: &mut Transcript, l: &G1Affine, r: &G1Affine) -> (F, F) {
+    fs.absorb_commitment_l(FsLabel::IpaRound, l);
+    fs.absorb_commitment_l(FsLabel::IpaRound, r);
+    let x = fs.challenge_f_l(FsLabel::IpaChallenge);
+    // A zero challenge happens with negligible probability; treat it as 1.
+    let x = if x.is_zero() { F::one() } else { x };
+    (x, x.inverse().expect("non-zero"))
+}
+
+impl Ipa {
+    fn open_one(
+        &self,
+        fs_hash: FsHash,
+        c: &IpaCommitment,
+        coeffs: &[F],
+        gens: &[G1Affine],
+        u: G1Affine,
+        zeta: F,
+    ) -> IpaOpeningProof {
+        let n = coeffs.len();
+        let mut b = Vec::with_capacity(n);
+        let mut p = F::one();
+        for _ in 0..n {
+            b.push(p);
+            p *= zeta;
+        }
+        let value = inner(coeffs, &b);
+        let (mut fs, u) = opening_transcript(fs_hash, c, &zeta, &value, u);
+
+        let mut a = coeffs.to_vec();
+        let mut g: Vec<G1Affine> = gens.to_vec();
+        let (mut ls, mut rs) = (Vec::new(), Vec::new());
+        while a.len() > 1 {
+            let h = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(h);
+            let (b_lo, b_hi) = b.split_at(h);
+            let (g_lo, g_hi) = g.split_at(h);
+            let l = (G1Projective::msm_unchecked(g_hi, a_lo) + u * inner(a_lo, b_hi)).into_affine();
+            let r = (G1Projective::msm_unchecked(g_lo, a_hi) + u * inner(a_hi, b_lo)).into_affine();
+            let (x, x_inv) = absorb_round(&mut fs, &l, &r);
+            ls.push(l);
+            rs.push(r);
+
+            let a_next: Vec<F> = (0..h).map(|i| a_lo[i] * x + a_hi[i] * x_inv).collect();
+            let b_next: Vec<F> = (0..h).map(|i| b_lo[i] * x_inv + b_hi[i] * x).collect();
+            let g_next: Vec<G1Projective> =
+                (0..h).map(|i| g_lo[i] * x_inv + g_hi[i] * x).collect();
+            a = a_next;
+            b = b_next;
+            g = G1Projective::normalize_batch(&g_next);
+        }
+
+        IpaOpeningProof { zeta, value, l: ls, r: rs, a: a[0] }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_one(
+        &self,
+        fs_hash: FsHash,
+        c: &IpaCommitment,
+        point: F,
+        claimed: F,
+        pr: &IpaOpeningProof,
+        gens: &[G1Affine],
+        u: G1Affine,
+    ) -> Result<(), VerifyError> {
+        let n = gens.len();
+        let rounds = n.trailing_zeros() as usize;
+        if pr.zeta != point || pr.value != claimed {
+            return Err(VerifyError::Opening("IPA opening point/value mismatch"));
+        }
+        if pr.l.len() != rounds || pr.r.len() != rounds {
+            return Err(VerifyError::Opening("IPA proof shape mismatch"));
+        }
+
+        let (mut fs, u) = opening_transcript(fs_hash, c, &pr.zeta, &pr.value, u);
+        let mut xs = Vec::with_capacity(rounds);
+        for (l, r) in pr.l.iter().zip(&pr.r) {
+            xs.push(absorb_round(&mut fs, l, r));
+        }
+
+        // s_j = Π_r x_r^{±1}: round r owns bit (rounds−1−r) of j.
+        let mut s = vec![F::one()];
+        for &(x, x_inv) in xs.iter().rev() {
+            let hi: Vec<F> = s.iter().map(|v| *v * x).collect();
+            s.iter_mut().for_each(|v| *v *= x_inv);
+            s.extend(hi);
+        }
+        let mut b_final = F::zero();
+        let mut p = F::one();
+        for sj in &s {
+            b_final += *sj * p;
+            p *= pr.zeta;
+        }
+
+        // a·⟨s,G⟩ + a·b·U − (C + v·U + Σ x²L + x⁻²R) == 0, as one MSM.
+        let mut bases: Vec<G1Affine> = gens.to_vec();
+        let mut scalars: Vec<F> = s.iter().map(|sj| *sj * pr.a).collect();
+        for ((x, x_inv), (l, r)) in xs.iter().zip(pr.l.iter().zip(&pr.r)) {
+            bases.push(*l);
+            scalars.push(-x.square());
+            bases.push(*r);
+            scalars.push(-x_inv.square());
+        }
+        let lhs = G1Projective::msm_unchecked(&bases, &scalars)
+            + u * (pr.a * b_final - pr.value)
+            - c.0.into_group();
+        if lhs.is_zero() {
+            Ok(())
+        } else {
+            Err(VerifyError::Opening("IPA inner-product check failed"))
+        }
+    }
+}
+
+impl PolynomialCommitment for Ipa {
+    type Commitment = IpaCommitment;
+    type Proof = IpaOpeningProof;
+
+    const KIND: PcsKind = PcsKind::Ipa;
+
+    fn srs_digests(&self, _pcs: &PcsParams) -> Result<([u8; 32], [u8; 32]), AggregatorError> {
+        let mut h = Hasher::new();
+        h.update(b"SSZKP.IPA.v1");
+        h.update(GEN_DST);
+        Ok((*h.finalize().as_bytes(), [0u8; 32]))
+    }
+
+    fn commit_coeff_tiles<I>(
+        &self,
+        pcs: &PcsParams,
+        _poly_id: &'static str,
+        tiles: I,
+    ) -> Result<IpaCommitment, AggregatorError>
+    where
+        I: IntoIterator<Item = Vec<F>>,
+    {
+        let coeff_pcs = PcsParams { basis: Basis::Coefficient, ..pcs.clone() };
+        let mut agg = IpaAggregator::new(&coeff_pcs);
+        for tile in tiles {
+            agg.add_block_coeffs_r(&tile)?;
+        }
+        Ok(agg.finalize())
+    }
+
+    fn open_hi_to_lo(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        commitment: &IpaCommitment,
+        stream_coeff_hi_to_lo: &mut CoeffStreamHiToLo<'_>,
+        points: &[F],
+    ) -> Result<Vec<IpaOpeningProof>, AggregatorError> {
+        let n = vector_len(pcs);
+        let mut hi_to_lo: Vec<F> = Vec::with_capacity(pcs.max_degree + 1);
+        stream_coeff_hi_to_lo(&mut |tile: Vec<F>| hi_to_lo.extend(tile));
+        let mut coeffs = vec![F::zero(); n];
+        for (k, c) in hi_to_lo.into_iter().enumerate().take(pcs.max_degree + 1) {
+            coeffs[pcs.max_degree - k] = c;
+        }
+
+        let (gens, u) = generators_prefix(n);
+        Ok(points
+            .iter()
+            .map(|&zeta| self.open_one(fs_hash, commitment, &coeffs, &gens, u, zeta))
+            .collect())
+    }
+
+    #[inline]
+    fn opened_value(proof: &IpaOpeningProof) -> F {
+        proof.value
+    }
+
+    fn verify(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        commitments: &[IpaCommitment],
+        points: &[F],
+        claimed_evals: &[F],
+        proofs: &[IpaOpeningProof],
+    ) -> Result<(), VerifyError> {
+        let expected = commitments.len().saturating_mul(points.len());
+        if proofs.len() != expected || claimed_evals.len() != expected {
+            return Err(VerifyError::Shape { expected, got: proofs.len().max(claimed_evals.len()) });
+        }
+        let (gens, u) = generators_prefix(vector_len(pcs));
+        let mut idx = 0usize;
+        for c in commitments {
+            for &pt in points {
+                self.verify_one(fs_hash, c, pt, claimed_evals[idx], &proofs[idx], &gens, u)?;
+                idx += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pcs(n: usize) -> PcsParams {
+        PcsParams { max_degree: n - 1, basis: Basis::Coefficient, srs: None }
+    }
+
+    #[test]
+    fn commit_open_verify_and_reject() {
+        let n = 32;
+        let p = pcs(n);
+        let coeffs: Vec<F> = (0..n as u64).map(|i| F::from(5 * i + 2)).collect();
+        let cm = Ipa.commit_coeff_tiles(&p, "t", coeffs.chunks(6).map(|c| c.to_vec())).unwrap();
+
+        let mut hi_to_lo = coeffs.clone();
+        hi_to_lo.reverse();
+        let mut stream = |sink: &mut dyn FnMut(Vec<F>)| {
+            for tile in hi_to_lo.chunks(4) {
+                sink(tile.to_vec());
+            }
+        };
+        let z = F::from(77u64);
+        let pr = Ipa.open_hi_to_lo(&p, FsHash::Blake3, &cm, &mut stream, &[z]).unwrap().remove(0);
+        assert_eq!(pr.l.len(), 5);
+        Ipa.verify(&p, FsHash::Blake3, &[cm], &[z], &[pr.value], std::slice::from_ref(&pr)).unwrap();
+        // The inner transcript follows the header's hash.
+        assert!(Ipa.verify(&p, FsHash::Keccak256, &[cm], &[z], &[pr.value], std::slice::from_ref(&pr)).is_err());
+
+        let mut bad = pr.clone();
+        bad.a += F::one();
+        assert!(Ipa.verify(&p, FsHash::Blake3, &[cm], &[z], &[pr.value], &[bad]).is_err());
+        let wrong = pr.value + F::one();
+        let mut bad = pr;
+        bad.value = wrong;
+        assert!(Ipa.verify(&p, FsHash::Blake3, &[cm], &[z], &[wrong], &[bad]).is_err());
+    }
+}
@@ -9,13 +9,17 @@
 //!
 //! - **Field & Curve.** Unless explicitly configured otherwise, the scalar field
 //!   is `ark_bn254::Fr` (`F` in this crate). Commitments use KZG on BN254
-//!   (`G1 = ark_bn254::G1Affine`). All arithmetic is constant-time as provided
-//!   by Arkworks; we **forbid unsafe** throughout the crate.
+//!   (`G1 = ark_bn254::G1Affine`). The `bls12-381` feature switches field,
+//!   groups and pairing to BLS12-381 (see `curve.rs`); the header records which.
+//!   All arithmetic is constant-time as provided by Arkworks; we **forbid
+//!   unsafe** throughout the crate (the opt-in `mmap` feature relaxes this to
+//!   two audited map calls, in `witness.rs` and `srs_file.rs`).
 //!
 //! - **Evaluation domain.** The vanishing polynomial is
-//!   `Z_H(X) = X^N − c` where `N` is a power of two and `ω` is a generator of
-//!   the size-`N` multiplicative subgroup (`ω^N = 1`, `ω^{N/2} ≠ 1` when
-//!   `N ≥ 2`). The constant `c = zh_c` is carried in the header and used by the
+//!   `Z_H(X) = X^N − c` where `N` is a power of two or, more generally, a
+//!   smooth divisor of `r − 1` (see `domain::next_smooth_size`), and `ω` is a
+//!   generator of the size-`N` multiplicative subgroup (`ω^N = 1`,
+//!   `ω^{N/p} ≠ 1` for every prime `p | N`). The constant `c = zh_c` is carried in the header and used by the
 //!   quotient construction and the algebraic check at `ζ`.
 //!
 //! - **Streaming discipline.** All core builders (wires, Z, Q) are wired so that
@@ -26,22 +30,32 @@
 //! - **Fiat–Shamir (FS).** We use BLAKE3 with **explicit domain separation**
 //!   tags, length-delimited absorbs, and an **XOF** to derive challenges. The
 //!   prover and verifier replay the exact same sequence of absorbs/challenges.
+//!   The hash is pluggable (`transcript::TranscriptHash`): Keccak-256 for
+//!   on-chain verification, Poseidon over BN254 for recursion. All of them
+//!   see the same byte schedule; the header records which one is used.
 //!
 //! These invariants are enforced by design across the submodules and are
 //! serialized into the `ProofHeader`. If any invariant is violated at runtime,
 //! the failure mode is a **precise error** (never UB).
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 #![deny(missing_docs, rust_2018_idioms)]
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+/// Compile-time curve selection (BN254 default, BLS12-381 via feature).
+pub mod curve;
 /// Domain & transforms (vanishing polynomial X^N − c, blocked IFFT/NTT).
 pub mod domain;
 /// Polynomial commitment scheme interface and linear aggregator (KZG by default).
 pub mod pcs;
+/// Pippenger multi-scalar multiplication used by the KZG commitment paths.
+pub mod msm;
 /// Fiat–Shamir transcript (domain-separated hashing, hash→field).
 pub mod transcript;
+/// Fiat–Shamir transcript recording (JSON traces) and trace diffing.
+pub mod transcript_trace;
 /// AIR template & block evaluator (local transitions / locals tuple).
 pub mod air;
 /// Permutation & lookup accumulators (multiplicative, time-ordered).
@@ -56,16 +70,37 @@ pub mod opening;
 pub mod scheduler;
 /// SRS setup and management (trusted ceremony integration)
 pub mod srs_setup;
+/// Fixed-stride SRS files: per-tile decoding, background validation, validation cache.
+pub mod srs_file;
+/// Native `.ptau` (snarkjs powers of tau) importer.
+pub mod ptau;
+/// Local multi-party powers-of-tau ceremony with verifiable update proofs.
+pub mod ceremony;
+/// Transparent FRI commitment backend (Merkle-committed LDE, no SRS).
+pub mod fri;
+/// Transparent IPA commitment backend (hashed generators, log-size openings).
+pub mod ipa;
+/// Binary witness files: columnar on-disk format, writer and random-access reader.
+pub mod witness;
+/// Compressed witness containers: independently compressed, block-aligned frames.
+pub mod witness_frames;
+/// Happy-path crate API: builders, witness adapters and v2 proof-file I/O.
+pub mod api;
+/// Solidity verifier generator and calldata encoder (BN254 KZG proofs).
+#[cfg(not(feature = "bls12-381"))]
+pub mod evm;
+#[cfg(test)]
+mod test_util;
 
 // ============================================================================
 // Canonical aliases and root-level re-exports (centralization)
 // ============================================================================
 
 /// Scalar field used across the crate (BN254 by default).
-pub type F = ark_bn254::Fr;
+pub type F = crate::curve::Fr;
 
 /// G1 affine group element used for commitments (KZG default).
-pub type G1 = ark_bn254::G1Affine;
+pub type G1 = crate::curve::G1Affine;
 
 /// Security parameter λ. In the manuscript, λ = Θ(log T) is implicit;
 /// we **do not** hardwire T here.
@@ -111,7 +146,10 @@ pub use scheduler::{Prover, Verifier};
 
 /// Re-export PCS surface types so downstream code uses the **single, canonical**
 /// definitions that already implement Arkworks serialization traits.
-pub use crate::pcs::{Basis, Commitment, OpeningProof, PcsParams, SrsLoadError, VerifyError};
+pub use crate::pcs::{
+    Basis, Commitment, Kzg, OpeningProof, PcsKind, PcsParams, PolynomialCommitment,
+    SrsLoadError, VerifyError,
+};
 
 // ============================================================================
 // Public parameter structs and proof types
@@ -134,6 +172,9 @@ pub struct ProveParams {
     /// **Invariant:** `b_blk > 0`. Many streaming helpers validate this and
     /// return `StreamError::BadBlockSize` or panic in legacy wrappers.
     pub b_blk: usize,
+    /// How blocked IFFTs run (in memory or spilled to disk, and where).
+    /// Defaults to in-memory.
+    pub transform: crate::domain::TransformConfig,
 }
 
 /// Parameters required by the verifier.
@@ -159,6 +200,8 @@ pub struct VerifyParams {
 pub struct ProofHeader {
     /// Header format / protocol version.
     pub version: u16,
+    /// Curve the proof was produced on (field, groups and pairing).
+    pub curve: crate::curve::CurveId,
     /// Domain size N.
     pub domain_n: u32,
     /// Subgroup generator ω.
@@ -169,7 +212,13 @@ pub struct ProofHeader {
     pub k: u16,
     /// Commitment basis for wires (Eval or Coeff).
     pub basis_wires: crate::pcs::Basis,
-    /// Digest of the loaded G1 SRS powers (compressed).
+    /// Commitment backend (KZG, FRI or IPA); selects the proof body encoding.
+    pub pcs: crate::pcs::PcsKind,
+    /// Hash driving the Fiat–Shamir transcript (BLAKE3, Keccak-256 or Poseidon).
+    pub fs_hash: crate::transcript::FsHash,
+    /// Digest of the first `domain_n` G1 SRS powers (compressed), so any large
+    /// enough SRS from the same ceremony matches. Transparent backends put a
+    /// digest of their parameters here.
     pub srs_g1_digest: [u8; 32],
     /// Digest of the loaded G2 SRS element(s) (compressed).
     pub srs_g2_digest: [u8; 32],
@@ -177,27 +226,28 @@ pub struct ProofHeader {
 
 /// The SSZKP proof object.
 ///
-/// Note: `Commitment` here is re-exported from `pcs` and already implements
-/// `CanonicalSerialize` / `CanonicalDeserialize`, so these derives work.
+/// Generic over the commitment backend `P`; the default (`Kzg`) uses the
+/// `Commitment` / `OpeningProof` types re-exported from `pcs`, which already
+/// implement `CanonicalSerialize` / `CanonicalDeserialize`.
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof {
+pub struct Proof<P: PolynomialCommitment = Kzg> {
     /// Protocol header bound into FS (domain / PCS basics + SRS digests).
     pub header: ProofHeader,
 
     /// Per-register wire commitments (aggregated across blocks; order `m = 0..k-1`).
     ///
     /// These are absorbed into the transcript **in order** before sampling `(β, γ)`.
-    pub wire_comms: Vec<Commitment>,
+    pub wire_comms: Vec<P::Commitment>,
 
     /// Optional permutation accumulator commitment `Z` (if committed by the scheme).
     ///
     /// If present, it is absorbed **after** sampling `(β, γ)` and **before** sampling `α`.
-    pub z_comm: Option<Commitment>,
+    pub z_comm: Option<P::Commitment>,
 
     /// Quotient commitment `Q` (coefficient-basis).
     ///
     /// This is absorbed **after** sampling `α` and **before** sampling the evaluation points.
-    pub q_comm: Commitment,
+    pub q_comm: P::Commitment,
 
     /// Evaluation points sampled via FS (e.g., `[ζ, …]`).
     ///
@@ -209,5 +259,5 @@ pub struct Proof {
     pub evals: Vec<F>,
 
     /// PCS opening proofs corresponding 1-to-1 with `evals` in the **same order**.
-    pub opening_proofs: Vec<OpeningProof>,
+    pub opening_proofs: Vec<P::Proof>,
 }
@@ -0,0 +1,233 @@
+//! Multi-scalar multiplication (Pippenger / bucket method) over G1
+//!
+//! Every commitment in this crate is an MSM `Σ a_i · [τ^i]G₁`. Doing one
+//! double-and-add per scalar costs ~1.5·|F| group operations per term; the
+//! bucket method brings that down to roughly `|F|/c · (1 + 2^c / n)` additions
+//! per term for a window of `c` bits.
+//!
+//! - [`msm`](crate::msm::msm) picks the window with [`window_bits_for`](crate::msm::window_bits_for) and, with the
+//!   `parallel` feature, spreads windows across threads for large inputs.
+//! - [`msm_with_window`](crate::msm::msm_with_window) / [`msm_parallel`](crate::msm::msm_parallel) are the explicit entry points
+//!   (benchmarks, `CommitStreamCfg::window_bits`).
+//!
+//! Streaming callers don't have all terms at once; they fill an [`MsmBatch`](crate::msm::MsmBatch)
+//! and it flushes every [`MSM_TILE`](crate::msm::MSM_TILE) terms. That keeps memory at O(1) in the
+//! polynomial degree while still giving each MSM enough terms to amortize the
+//! buckets.
+
+#![forbid(unsafe_code)]
+
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, Zero};
+
+use crate::curve::{G1Affine, G1Projective};
+use crate::F;
+
+/// Number of terms an [`MsmBatch`] gathers before running one MSM.
+pub const MSM_TILE: usize = 1 << 12;
+
+/// Below this many terms [`msm`] stays single-threaded even with `parallel`.
+pub const PARALLEL_THRESHOLD: usize = 1 << 10;
+
+/// Window size (bits) that minimizes additions for `n` terms.
+pub fn window_bits_for(n: usize) -> u32 {
+    if n < 32 {
+        3
+    } else {
+        // ≈ ln(n) + 2, without floats: log2(n) · ln 2.
+        (usize::BITS - n.leading_zeros()) * 69 / 100 + 2
+    }
+}
+
+/// `Σ scalars[i] · bases[i]` with an automatically chosen window.
+///
+/// Panics if the slices differ in length.
+pub fn msm(bases: &[G1Affine], scalars: &[F]) -> G1Projective {
+    let c = window_bits_for(bases.len());
+    #[cfg(feature = "parallel")]
+    if bases.len() >= PARALLEL_THRESHOLD {
+        let threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+        return msm_parallel(bases, scalars, c, threads);
+    }
+    msm_with_window(bases, scalars, c)
+}
+
+/// Single-threaded Pippenger with a `c`-bit window (clamped to `1..=20`).
+pub fn msm_with_window(bases: &[G1Affine], scalars: &[F], c: u32) -> G1Projective {
+    msm_parallel(bases, scalars, c, 1)
+}
+
+/// Pippenger with windows split across up to `threads` scoped threads.
+///
+/// Windows are independent, so each thread owns a contiguous range of them
+/// and the per-window sums are combined at the end.
+pub fn msm_parallel(bases: &[G1Affine], scalars: &[F], c: u32, threads: usize) -> G1Projective {
+    assert_eq!(bases.len(), scalars.len(), "msm: bases/scalars length mismatch");
+    let c = c.clamp(1, 20);
+    let (bases, digits): (Vec<G1Affine>, Vec<_>) = bases
+        .iter()
+        .zip(scalars)
+        .filter(|(_, s)| !s.is_zero())
+        .map(|(b, s)| (*b, s.into_bigint()))
+        .unzip();
+    if bases.is_empty() {
+        return G1Projective::zero();
+    }
+
+    let num_windows = F::MODULUS_BIT_SIZE.div_ceil(c) as usize;
+    let window_sum = |w: usize| -> G1Projective {
+        let mut buckets = vec![G1Projective::zero(); (1 << c) - 1];
+        for (base, s) in bases.iter().zip(&digits) {
+            let d = window_digit(s.as_ref(), w * c as usize, c);
+            if d != 0 {
+                buckets[d - 1] += base;
+            }
+        }
+        // Σ_j j·B_j via running suffix sums.
+        let mut running = G1Projective::zero();
+        let mut sum = G1Projective::zero();
+        for b in buckets.into_iter().rev() {
+            running += b;
+            sum += running;
+        }
+        sum
+    };
+
+    let threads = threads.clamp(1, num_windows);
+    let sums: Vec<G1Projective> = if threads == 1 {
+        (0..num_windows).map(window_sum).collect()
+    } else {
+        let per = num_windows.div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..num_windows)
+                .step_by(per)
+                .map(|lo| {
+                    let window_sum = &window_sum;
+                    scope.spawn(move || (lo..(lo + per).min(num_windows)).map(window_sum).collect::<Vec<_>>())
+                })
+                .collect();
+            handles.into_iter().flat_map(|h| h.join().expect("msm worker panicked")).collect()
+        })
+    };
+
+    sums.into_iter().rev().fold(G1Projective::zero(), |acc, s| {
+        (0..c).fold(acc, |a, _| a + a) + s
+    })
+}
+
+/// Bits `[start, start + c)` of a little-endian limb array.
+#[inline]
+fn window_digit(limbs: &[u64], start: usize, c: u32) -> usize {
+    let (idx, off) = (start / 64, start % 64);
+    if idx >= limbs.len() {
+        return 0;
+    }
+    let mut v = limbs[idx] >> off;
+    if off + c as usize > 64 && idx + 1 < limbs.len() {
+        v |= limbs[idx + 1] << (64 - off);
+    }
+    (v & ((1u64 << c) - 1)) as usize
+}
+
+/// Streaming MSM accumulator: gathers `(base, scalar)` terms in any index
+/// order and runs [`msm`] every [`MSM_TILE`] terms.
+#[derive(Debug)]
+pub struct MsmBatch {
+    acc: G1Projective,
+    bases: Vec<G1Affine>,
+    scalars: Vec<F>,
+    window_bits: Option<u32>,
+}
+
+impl Default for MsmBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MsmBatch {
+    /// Empty batch with an automatic window.
+    pub fn new() -> Self {
+        Self { acc: G1Projective::zero(), bases: Vec::new(), scalars: Vec::new(), window_bits: None }
+    }
+
+    /// Force a window size (`0` keeps the automatic choice).
+    pub fn with_window_bits(mut self, c: u32) -> Self {
+        self.window_bits = (c != 0).then_some(c);
+        self
+    }
+
+    /// Queue one term; zero scalars are dropped.
+    #[inline]
+    pub fn push(&mut self, base: G1Affine, scalar: F) {
+        if scalar.is_zero() {
+            return;
+        }
+        self.bases.push(base);
+        self.scalars.push(scalar);
+        if self.bases.len() >= MSM_TILE {
+            self.flush();
+        }
+    }
+
+    /// Run the MSM over the queued terms and fold it into the accumulator.
+    pub fn flush(&mut self) {
+        if self.bases.is_empty() {
+            return;
+        }
+        self.acc += match self.window_bits {
+            Some(c) => msm_with_window(&self.bases, &self.scalars, c),
+            None => msm(&self.bases, &self.scalars),
+        };
+        self.bases.clear();
+        self.scalars.clear();
+    }
+
+    /// Flush and return the total.
+    pub fn finish(mut self) -> G1Projective {
+        self.flush();
+        self.acc
+    }
+
+    /// Flush and return the total in affine form.
+    pub fn finish_affine(self) -> G1Affine {
+        self.finish().into_affine()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineRepr, Group};
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn naive(bases: &[G1Affine], scalars: &[F]) -> G1Projective {
+        bases.iter().zip(scalars).map(|(b, s)| b.into_group().mul_bigint(s.into_bigint())).sum()
+    }
+
+    #[test]
+    fn pippenger_matches_double_and_add() {
+        let mut rng = StdRng::seed_from_u64(33);
+        let n = 300;
+        let bases: Vec<G1Affine> =
+            (0..n).map(|_| (G1Projective::generator() * F::rand(&mut rng)).into_affine()).collect();
+        let mut scalars: Vec<F> = (0..n).map(|_| F::rand(&mut rng)).collect();
+        scalars[3] = F::zero();
+        scalars[4] = -F::from(1u64);
+        let want = naive(&bases, &scalars);
+
+        assert_eq!(msm(&bases, &scalars), want);
+        for c in [1, 4, 7, 13] {
+            assert_eq!(msm_with_window(&bases, &scalars, c), want, "c = {c}");
+        }
+        assert_eq!(msm_parallel(&bases, &scalars, 8, 4), want);
+        assert!(msm(&[], &[]).is_zero());
+
+        let mut batch = MsmBatch::new().with_window_bits(5);
+        for (b, s) in bases.iter().zip(&scalars).rev() {
+            batch.push(*b, *s);
+        }
+        assert_eq!(batch.finish(), want);
+    }
+}
@@ -101,7 +101,7 @@ where
         let mut bifft = crate::domain::BlockedIfft::new(self.domain, self.tile_len);
         (self.feed_evals)(&mut |blk: Vec<F>| {
             if !blk.is_empty() {
-                bifft.feed_eval_block(&blk);
+                bifft.feed_eval_block(&blk).expect("spill tape append");
             }
         });
         self.tiles_it = Some(Box::new(bifft.finish_low_to_high()));
@@ -201,7 +201,7 @@ pub fn coeff_tiles_hi_to_lo_from_eval_stream<'a>(
 ) -> impl Iterator<Item = Vec<F>> + 'a {
     // Feed time-ordered eval tiles into BlockedIfft, then finish **high→low**.
     let mut bifft = crate::domain::BlockedIfft::new(domain, b_blk);
-    stream_evals(&mut |blk: Vec<F>| bifft.feed_eval_block(&blk));
+    stream_evals(&mut |blk: Vec<F>| bifft.feed_eval_block(&blk).expect("spill tape append"));
     bifft.finish_high_to_low()
 }
 
@@ -216,13 +216,13 @@ pub fn open_eval_stream_at_points(
     domain: &crate::domain::Domain,
     mut stream_evals: impl FnMut(usize, &mut dyn FnMut(Vec<F>)),
     points: &[F],
-) -> Vec<crate::pcs::OpeningProof> {
+) -> Result<Vec<crate::pcs::OpeningProof>, crate::pcs::AggregatorError> {
     // Adapter: for each polynomial index `idx`, emit **hi→lo** coeff tiles.
     let mut as_coeff_hi_to_lo = |idx: usize, sink: &mut dyn FnMut(Vec<F>)| {
-        let mut tiles = coeff_tiles_hi_to_lo_from_eval_stream(domain, /*b_blk*/ 1 << 12, |push| {
+        let tiles = coeff_tiles_hi_to_lo_from_eval_stream(domain, /*b_blk*/ 1 << 12, |push| {
             stream_evals(idx, push);
         });
-        while let Some(tile) = tiles.next() {
+        for tile in tiles {
             sink(tile);
         }
     };
@@ -253,7 +253,7 @@ pub fn eval_and_open_one_point(
     b_blk: usize,
     mut stream_evals: impl FnMut(&mut dyn FnMut(Vec<F>)),
     zeta: F,
-) -> (F, crate::pcs::OpeningProof) {
+) -> Result<(F, crate::pcs::OpeningProof), crate::pcs::AggregatorError> {
     // Recreate the stream for each pass (cheap + stateless).
     let coeff_stream_eval = blocked_ifft_witness_stream(domain, b_blk, |push| stream_evals(push));
     let value = pcs::eval_at_stream(coeff_stream_eval, zeta);
@@ -265,8 +265,8 @@ pub fn eval_and_open_one_point(
         domain,
         |_idx, push| stream_evals(push),
         &[zeta],
-    );
-    (value, proofs.into_iter().next().unwrap())
+    )?;
+    Ok((value, proofs.into_iter().next().unwrap()))
 }
 
 // -----------------------------------------------------------------------------
@@ -276,7 +276,7 @@ pub fn eval_and_open_one_point(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_ff::{Field, One};
+    use ark_ff::{FftField, Field, One};
     use crate::domain;
 
     fn small_domain(n: usize) -> domain::Domain {
@@ -329,11 +329,11 @@ mod tests {
         let mut baseline: Vec<Vec<F>> = reversed.chunks(b_blk).map(|c| c.to_vec()).collect();
 
         // Streaming hi→lo tiles from eval stream
-        let mut tiles = coeff_tiles_hi_to_lo_from_eval_stream(&d, b_blk, |push| {
+        let tiles = coeff_tiles_hi_to_lo_from_eval_stream(&d, b_blk, |push| {
             for ch in evals.chunks(b_blk) { push(ch.to_vec()); }
         });
 
-        while let Some(tile) = tiles.next() {
+        for tile in tiles {
             let expect = baseline.remove(0);
             assert_eq!(tile, expect);
         }
@@ -13,14 +13,35 @@
 //!     - `commit_stream` — commit from a `CoeffTileStream` without ever owning
 //!       a full `Vec` of coefficients.
 //!     - `eval_at_stream` — Horner folding over tiles (wrapper).
+//! - **Bucketed MSM**: commits and KZG witnesses go through `crate::msm`
+//!   (Pippenger, batched per `MSM_TILE` terms) instead of one
+//!   double-and-add per coefficient.
+//! - **Explicit SRS**: [`Srs`](crate::pcs::Srs) (a
+//!   [`CommitterKey`](crate::pcs::CommitterKey) plus `[τ]G2`) rides in
+//!   [`PcsParams::srs`](crate::pcs::PcsParams::srs), so one process can hold
+//!   several SRSes and commits never lock. `srs: None` keeps the old global
+//!   (`load_srs_g1/g2`) path.
+//! - **Lagrange-basis keys**: [`LagrangeKey`](crate::pcs::LagrangeKey)
+//!   (`[L_i(τ)]G1`, derived once per domain size and attached with
+//!   `Srs::with_lagrange`) lets evaluation-basis wires commit as one MSM over
+//!   the streamed rows, skipping the blocked IFFT and its spill tape.
 //! - Kept SRS digest helpers and all public types intact (no API break).
+//! - **Prefix digests**: proof headers bind
+//!   [`Srs::g1_prefix_digest`](crate::pcs::Srs::g1_prefix_digest) — the
+//!   first `max_degree + 1` powers only — so a 1M-point server SRS and a
+//!   64K-point CLI copy of the same ceremony produce identical headers.
+//! - **Backend trait**: [`PolynomialCommitment`] captures what the scheduler
+//!   needs (commit from tiles, open from hi→lo tiles, verify, batch verify).
+//!   [`Kzg`] is the first implementation and batches all opening sets into a
+//!   single multi-Miller loop, weighting opening `j` by `νʲ` for a challenge
+//!   `ν` drawn after the claimed evaluations are fixed.
 //!
 //! ## Notes (whitepaper alignment)
 //! The PCS aggregator operates over **monomial coefficients** (tiles) and is
 //! deliberately **independent of the time/evaluation basis**. If callers have
-//! time-ordered evaluations on `H`, they must convert blockwise via IFFT and
-//! feed the resulting coefficient tiles (this is how our streaming scheduler
-//! maintains sublinear space).
+//! time-ordered evaluations on `H`, they either convert blockwise via IFFT
+//! and feed the resulting coefficient tiles, or commit them directly through
+//! a `LagrangeKey` for that domain.
 
 #![forbid(unsafe_code)]
 #![allow(dead_code)]
@@ -30,7 +51,7 @@
 #![allow(missing_docs)]
 #![allow(non_snake_case)]
 
-use ark_bn254::{Bn254, Fr as ScalarField, G1Affine, G1Projective, G2Affine, G2Projective};
+use crate::curve::{Engine, Fr as ScalarField, G1Affine, G1Projective, G2Affine, G2Projective};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
 use ark_serialize::{
@@ -39,8 +60,12 @@ use ark_serialize::{
 };
 use blake3::Hasher;
 use rand::{rngs::StdRng, SeedableRng};
-use std::sync::{Mutex, OnceLock};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
+use crate::msm::MsmBatch;
+use crate::transcript::FsHash;
 use crate::{domain, F};
 // Streaming tile trait + Horner fold
 use crate::stream::{horner_eval_stream, CoeffTileStream};
@@ -99,15 +124,90 @@ impl Valid for Basis {
     }
 }
 
+/// Which commitment backend produced a proof (recorded in the header).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcsKind {
+    /// KZG over the BN254 SRS (trusted setup).
+    Kzg,
+    /// FRI over Merkle-committed low-degree extensions (transparent).
+    Fri,
+    /// Bulletproofs-style inner-product argument over hashed generators (transparent).
+    Ipa,
+}
+
+impl PcsKind {
+    /// Stable lowercase name (CLI flags, API payloads).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PcsKind::Kzg => "kzg",
+            PcsKind::Fri => "fri",
+            PcsKind::Ipa => "ipa",
+        }
+    }
+}
+
+impl std::str::FromStr for PcsKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "kzg" => Ok(PcsKind::Kzg),
+            "fri" => Ok(PcsKind::Fri),
+            "ipa" => Ok(PcsKind::Ipa),
+            other => Err(format!("unknown PCS backend `{other}` (expected kzg|fri|ipa)")),
+        }
+    }
+}
+
+impl CanonicalSerialize for PcsKind {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut w: W,
+        _cm: Compress,
+    ) -> Result<(), SerializationError> {
+        let byte = match self {
+            PcsKind::Kzg => 0u8,
+            PcsKind::Fri => 1u8,
+            PcsKind::Ipa => 2u8,
+        };
+        w.write_all(&[byte])?;
+        Ok(())
+    }
+    fn serialized_size(&self, _cm: Compress) -> usize {
+        1
+    }
+}
+impl CanonicalDeserialize for PcsKind {
+    fn deserialize_with_mode<R: Read>(
+        mut r: R,
+        _cm: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut b = [0u8; 1];
+        r.read_exact(&mut b)?;
+        match b[0] {
+            0 => Ok(PcsKind::Kzg),
+            1 => Ok(PcsKind::Fri),
+            2 => Ok(PcsKind::Ipa),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+impl Valid for PcsKind {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
 /// Public parameters for the polynomial commitment scheme.
-#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Debug, Clone)]
 pub struct PcsParams {
     /// Maximum supported degree **d** (inclusive). Number of SRS powers is `d+1`.
     pub max_degree: usize,
     /// Basis expected by the commit-time interface for *the polynomial*.
     pub basis: Basis,
-    /// Placeholder to keep the type stable if we ever inline SRS metadata.
-    pub srs_placeholder: (),
+    /// KZG SRS to commit/verify against. `None` falls back to the
+    /// process-global SRS (`load_srs_g1` / `load_srs_g2`, or the dev SRS).
+    pub srs: Option<Srs>,
 }
 
 impl PcsParams {
@@ -120,6 +220,13 @@ impl PcsParams {
         self.basis = basis;
         self
     }
+
+    /// Return a copy of these parameters bound to an explicit SRS.
+    #[inline]
+    pub fn with_srs(mut self, srs: Srs) -> Self {
+        self.srs = Some(srs);
+        self
+    }
 }
 
 /// PCS commitment newtype (wrap **G1Affine** directly).
@@ -145,21 +252,462 @@ pub enum VerifyError {
     MissingG2,
     #[error("final pairing check failed")]
     Pairing,
+    #[error("opening proof rejected: {0}")]
+    Opening(&'static str),
+    #[error(transparent)]
+    Srs(#[from] AggregatorError),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum SrsLoadError {
     #[error("empty SRS provided")]
     Empty,
+    #[error("SRS has {have} G1 powers, need {need}")]
+    TooShort { need: usize, have: usize },
+    #[error("Lagrange key needs a power-of-two domain, got N={0}")]
+    DomainSize(usize),
+    #[error("Lagrange key has {got} points, domain has {expected}")]
+    LagrangeSize { expected: usize, got: usize },
+    #[error("SRS file holds {0:?}-basis points")]
+    FileBasis(Basis),
+    #[error("Lagrange key was derived from another SRS or domain")]
+    LagrangeBinding,
+    #[error(transparent)]
+    File(#[from] crate::srs_setup::SrsSetupError),
+    #[error(transparent)]
+    Read(#[from] AggregatorError),
+}
+
+// ===========================================================================
+// Explicit SRS context — CommitterKey / VerifierKey / Srs
+// ===========================================================================
+
+/// Prover half of a KZG SRS: the G1 powers `[τ^i]G1`.
+///
+/// The powers sit behind an `Arc` (in memory, or a fixed-stride file decoded
+/// per tile — see [`crate::srs_file`]), so clones are free and
+/// commits/openings read them without taking any lock.
+#[derive(Debug, Clone)]
+pub struct CommitterKey {
+    powers: G1Powers,
+    /// [`CommitterKey::g1_prefix_digest`] results, keyed by power count.
+    prefix_digests: Arc<Mutex<BTreeMap<usize, [u8; 32]>>>,
+}
+
+#[derive(Debug, Clone)]
+enum G1Powers {
+    Memory { powers: Arc<Vec<G1Affine>>, digest: Arc<OnceLock<[u8; 32]>> },
+    File(Arc<crate::srs_file::SrsFile>),
+}
+
+impl CommitterKey {
+    /// Wrap G1 powers (`powers[i] = [τ^i]G1`).
+    pub fn new(powers: Vec<G1Affine>) -> Result<Self, SrsLoadError> {
+        if powers.is_empty() {
+            return Err(SrsLoadError::Empty);
+        }
+        Ok(Self::from_shared(Arc::new(powers)))
+    }
+
+    /// Powers read on demand from an opened SRS file.
+    pub fn from_file(file: Arc<crate::srs_file::SrsFile>) -> Self {
+        Self { powers: G1Powers::File(file), prefix_digests: Arc::default() }
+    }
+
+    fn from_shared(powers: Arc<Vec<G1Affine>>) -> Self {
+        Self {
+            powers: G1Powers::Memory { powers, digest: Arc::default() },
+            prefix_digests: Arc::default(),
+        }
+    }
+
+    /// Number of G1 powers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.powers {
+            G1Powers::Memory { powers, .. } => powers.len(),
+            G1Powers::File(f) => f.len(),
+        }
+    }
+
+    /// Whether the key holds no powers (only the unloaded global SRS).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Largest committable degree (`len() − 1`).
+    #[inline]
+    pub fn max_degree(&self) -> usize {
+        self.len().saturating_sub(1)
+    }
+
+    /// Powers `start .. start + len`: borrowed when in memory, decoded with
+    /// one positioned read when file-backed. Fails if the range runs past the
+    /// key or the file can no longer be read.
+    pub fn powers(&self, start: usize, len: usize) -> Result<Cow<'_, [G1Affine]>, AggregatorError> {
+        let have = self.len();
+        if start.checked_add(len).is_none_or(|end| end > have) {
+            return Err(AggregatorError::DegreeOverflow { cursor: start, adding: len, limit: have });
+        }
+        match &self.powers {
+            G1Powers::Memory { powers, .. } => Ok(Cow::Borrowed(&powers[start..start + len])),
+            G1Powers::File(f) => Ok(Cow::Owned(f.read_range(start, len)?)),
+        }
+    }
+
+    #[inline]
+    fn power(&self, idx: usize) -> Result<G1Affine, AggregatorError> {
+        Ok(self.powers(idx, 1)?[0])
+    }
+
+    /// PCS digest of the powers (what [`srs_g1_digest`] reports), computed
+    /// once and cached. Fails only if a file-backed key can no longer be read.
+    pub fn g1_digest(&self) -> Result<[u8; 32], AggregatorError> {
+        match &self.powers {
+            G1Powers::Memory { powers, digest } => Ok(*digest.get_or_init(|| g1_digest_of(powers))),
+            G1Powers::File(f) => Ok(f.g1_digest()?),
+        }
+    }
+
+    /// Digest of the first `max_degree + 1` powers: the [`Self::g1_digest`]
+    /// hash over just that prefix, streamed a tile at a time and cached per
+    /// length. Any longer copy of the same SRS gives the same value. Covers
+    /// the whole key if it is shorter.
+    pub fn g1_prefix_digest(&self, max_degree: usize) -> Result<[u8; 32], AggregatorError> {
+        let count = max_degree.saturating_add(1);
+        if count >= self.len() {
+            return self.g1_digest();
+        }
+        let mut cache = self.prefix_digests.lock().expect("SRS digest cache poisoned");
+        if let Some(d) = cache.get(&count) {
+            return Ok(*d);
+        }
+        let mut d = G1DigestStream::new(count);
+        for start in (0..count).step_by(crate::msm::MSM_TILE) {
+            let len = crate::msm::MSM_TILE.min(count - start);
+            self.powers(start, len)?.iter().for_each(|p| d.push(p));
+        }
+        Ok(*cache.entry(count).or_insert(d.finish()))
+    }
+}
+
+/// Verifier half of a KZG SRS: `[1]G1` and `[τ]G2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierKey {
+    /// `[1]G1` (first G1 power).
+    pub g1: G1Affine,
+    /// `[τ]G2`.
+    pub tau_g2: G2Affine,
+}
+
+/// A KZG SRS held as a value rather than in process-global state.
+///
+/// Put it in [`PcsParams::srs`] (see [`Srs::pcs_params`] /
+/// [`PcsParams::with_srs`]) and every commit, opening and verification for
+/// those params uses it. One process can hold several (per curve, per size,
+/// dev and prod) and share them across threads; cloning is an `Arc` bump.
+/// The G1 digest is computed on first use and cached.
+#[derive(Debug, Clone)]
+pub struct Srs {
+    ck: CommitterKey,
+    tau_g2: Option<G2Affine>,
+    g2_digest: [u8; 32],
+    lagrange: Vec<LagrangeKey>,
+}
+
+impl Srs {
+    /// Build from G1 powers and (for verification) `[τ]G2`. A prover-only
+    /// SRS may omit `tau_g2`; its G2 digest then covers no bytes.
+    pub fn new(g1_powers: Vec<G1Affine>, tau_g2: Option<G2Affine>) -> Result<Self, SrsLoadError> {
+        Ok(Self::from_committer_key(CommitterKey::new(g1_powers)?, tau_g2))
+    }
+
+    /// Build over a fixed-stride SRS file of monomial powers (near-instant;
+    /// points are decoded per tile). See [`crate::srs_file`] for validation.
+    pub fn from_file(file: Arc<crate::srs_file::SrsFile>, tau_g2: Option<G2Affine>) -> Self {
+        Self::from_committer_key(CommitterKey::from_file(file), tau_g2)
+    }
+
+    fn from_committer_key(ck: CommitterKey, tau_g2: Option<G2Affine>) -> Self {
+        Self { ck, tau_g2, g2_digest: g2_digest_of(tau_g2), lagrange: Vec::new() }
+    }
+
+    /// Attach a Lagrange-basis key, replacing any for the same domain.
+    /// Evaluation-basis commits over that domain then skip the IFFT.
+    pub fn with_lagrange_key(mut self, key: LagrangeKey) -> Self {
+        self.lagrange.retain(|k| !(k.n == key.n && k.omega == key.omega));
+        self.lagrange.push(key);
+        self
+    }
+
+    /// Derive (see [`LagrangeKey::derive`]) and attach the key for `domain`.
+    pub fn with_lagrange(self, domain: &domain::Domain) -> Result<Self, SrsLoadError> {
+        let key = LagrangeKey::derive(&self.ck, domain)?;
+        Ok(self.with_lagrange_key(key))
+    }
+
+    /// Open a key saved with [`LagrangeKey::write`] (see
+    /// [`LagrangeKey::from_file`]) and attach it.
+    pub fn with_lagrange_file(
+        self,
+        path: impl AsRef<std::path::Path>,
+        domain: &domain::Domain,
+    ) -> Result<Self, SrsLoadError> {
+        let file = crate::srs_file::SrsFile::open(path)?;
+        let key = LagrangeKey::from_file(file, &self.ck, domain)?;
+        Ok(self.with_lagrange_key(key))
+    }
+
+    /// The attached Lagrange key for `domain`, if any.
+    pub fn lagrange_key(&self, domain: &domain::Domain) -> Option<&LagrangeKey> {
+        self.lagrange.iter().find(|k| k.matches(domain))
+    }
+
+    /// Prover key (G1 powers).
+    #[inline]
+    pub fn committer_key(&self) -> &CommitterKey {
+        &self.ck
+    }
+
+    /// Whether the G1 powers have been fully validated. In-memory powers were
+    /// checked as they were decoded; a file-backed SRS reports its
+    /// [`SrsFile::status`](crate::srs_file::SrsFile::status), which stays
+    /// `Pending` until a validation pass finishes.
+    pub fn validation_status(&self) -> crate::srs_file::ValidationStatus {
+        match &self.ck.powers {
+            G1Powers::Memory { .. } => crate::srs_file::ValidationStatus::Valid,
+            G1Powers::File(f) => f.status(),
+        }
+    }
+
+    /// Verifier key; fails if this SRS has no `[τ]G2`.
+    pub fn verifier_key(&self) -> Result<VerifierKey, VerifyError> {
+        let tau_g2 = self.tau_g2.ok_or(VerifyError::MissingG2)?;
+        Ok(VerifierKey { g1: self.ck.power(0)?, tau_g2 })
+    }
+
+    /// Largest committable degree.
+    #[inline]
+    pub fn max_degree(&self) -> usize {
+        self.ck.max_degree()
+    }
+
+    /// Same value as [`srs_g1_digest`] would give with these powers loaded.
+    #[inline]
+    pub fn g1_digest(&self) -> Result<[u8; 32], AggregatorError> {
+        self.ck.g1_digest()
+    }
+
+    /// Digest of the first `max_degree + 1` G1 powers (see
+    /// [`CommitterKey::g1_prefix_digest`]); what KZG proofs carry in their header.
+    #[inline]
+    pub fn g1_prefix_digest(&self, max_degree: usize) -> Result<[u8; 32], AggregatorError> {
+        self.ck.g1_prefix_digest(max_degree)
+    }
+
+    /// Same value as [`srs_g2_digest`] would give with this `[τ]G2` loaded.
+    #[inline]
+    pub fn g2_digest(&self) -> [u8; 32] {
+        self.g2_digest
+    }
+
+    /// Params covering the whole SRS, bound to it, in the given basis.
+    pub fn pcs_params(&self, basis: Basis) -> PcsParams {
+        PcsParams { max_degree: self.max_degree(), basis, srs: Some(self.clone()) }
+    }
+}
+
+/// `[L_i(τ)]G1` for the subgroup `{ω^i}` of one domain size.
+///
+/// With it, committing time values `v_i` is the MSM `Σ v_i·[L_i(τ)]G1`,
+/// which equals the monomial commitment of their interpolant — no IFFT.
+/// Deriving costs an `N log N` group FFT, so keys are built once per
+/// domain size and can be saved in the [`crate::srs_file`] layout.
+#[derive(Debug, Clone)]
+pub struct LagrangeKey {
+    n: usize,
+    omega: F,
+    bases: CommitterKey,
+    binding: [u8; 32],
+}
+
+/// What a saved key's header binds: the parent SRS's first `N` powers (its
+/// prefix digest), `ω` and `N`.
+fn lagrange_binding(ck: &CommitterKey, domain: &domain::Domain) -> Result<[u8; 32], AggregatorError> {
+    let mut omega = Vec::new();
+    domain.omega.serialize_compressed(&mut omega).expect("serialize ω");
+    let prefix = ck.g1_prefix_digest(domain.n - 1)?;
+    Ok(hash_bytes(&[b"lagrange", &prefix, &omega, &(domain.n as u64).to_be_bytes()]))
+}
+
+impl LagrangeKey {
+    /// Derive from the first `N` monomial powers:
+    /// `[L_i(τ)] = N⁻¹ · Σ_j ω^{-ij} [τ^j]`, i.e. an inverse FFT in G1.
+    pub fn derive(ck: &CommitterKey, domain: &domain::Domain) -> Result<Self, SrsLoadError> {
+        let n = domain.n;
+        if !n.is_power_of_two() {
+            return Err(SrsLoadError::DomainSize(n));
+        }
+        if ck.len() < n {
+            return Err(SrsLoadError::TooShort { need: n, have: ck.len() });
+        }
+        let mut a: Vec<G1Projective> = ck.powers(0, n)?.iter().map(|p| p.into_group()).collect();
+        g1_intt_in_place(&mut a, domain.omega);
+        let bases = CommitterKey::new(G1Projective::normalize_batch(&a))?;
+        Ok(Self { n, omega: domain.omega, bases, binding: lagrange_binding(ck, domain)? })
+    }
+
+    /// Key saved with [`Self::write`]. Its header must bind the first `N`
+    /// powers of `parent` and `domain`'s `ω`, so a key derived from another
+    /// SRS or root of unity is refused rather than committing to garbage.
+    pub fn from_file(
+        file: Arc<crate::srs_file::SrsFile>,
+        parent: &CommitterKey,
+        domain: &domain::Domain,
+    ) -> Result<Self, SrsLoadError> {
+        if file.basis() != Basis::Evaluation {
+            return Err(SrsLoadError::FileBasis(file.basis()));
+        }
+        if file.len() != domain.n {
+            return Err(SrsLoadError::LagrangeSize { expected: domain.n, got: file.len() });
+        }
+        if parent.len() < domain.n {
+            return Err(SrsLoadError::TooShort { need: domain.n, have: parent.len() });
+        }
+        let binding = lagrange_binding(parent, domain)?;
+        if file.binding() != binding {
+            return Err(SrsLoadError::LagrangeBinding);
+        }
+        Ok(Self { n: domain.n, omega: domain.omega, bases: CommitterKey::from_file(file), binding })
+    }
+
+    /// File name `srs_convert lagrange` gives the key for domain size `n`.
+    pub fn file_name(n: usize) -> String {
+        format!("lagrange-{n}.srs")
+    }
+
+    /// Save the bases in the fixed-stride [`crate::srs_file`] layout.
+    pub fn write(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), crate::srs_setup::SrsSetupError> {
+        let bases = self
+            .bases
+            .powers(0, self.n)
+            .map_err(|e| crate::srs_setup::SrsSetupError::Deserialize(e.to_string()))?;
+        crate::srs_file::write_g1_basis(path, &bases, Basis::Evaluation, self.binding)
+    }
+
+    /// Domain size `N`.
+    #[inline]
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Whether this key was built for `domain`.
+    #[inline]
+    pub fn matches(&self, domain: &domain::Domain) -> bool {
+        self.n == domain.n && self.omega == domain.omega
+    }
+
+    /// Commit time-ordered evaluations (`≤ N`; missing ones are zero).
+    pub fn commit_evals<I>(&self, evals: I) -> Result<Commitment, AggregatorError>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let mut acc = MsmBatch::new();
+        let mut evals = evals.into_iter().peekable();
+        let mut cursor = 0;
+        while evals.peek().is_some() {
+            let tile: Vec<F> = evals.by_ref().take(crate::msm::MSM_TILE).collect();
+            if cursor + tile.len() > self.n {
+                return Err(AggregatorError::DegreeOverflow {
+                    cursor,
+                    adding: tile.len(),
+                    limit: self.n,
+                });
+            }
+            let bases = self.bases.powers(cursor, tile.len())?;
+            for (b, v) in bases.iter().zip(&tile) {
+                acc.push(*b, *v);
+            }
+            cursor += tile.len();
+        }
+        Ok(Commitment(acc.finish_affine()))
+    }
+}
+
+/// Radix-2 inverse FFT over G1 (same butterfly layout as `domain`'s NTT).
+fn g1_intt_in_place(a: &mut [G1Projective], omega: F) {
+    let n = a.len();
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let inv_root = omega.inverse().expect("ω non-zero");
+    let mut len = 2;
+    while len <= n {
+        let w_len = inv_root.pow([(n / len) as u64]);
+        let half = len / 2;
+        for start in (0..n).step_by(len) {
+            let mut w = F::one();
+            for i in 0..half {
+                let u = a[start + i];
+                let v = if w.is_one() { a[start + i + half] } else { a[start + i + half] * w };
+                a[start + i] = u + v;
+                a[start + i + half] = u - v;
+                w *= w_len;
+            }
+        }
+        len <<= 1;
+    }
+    let inv_n = F::from(n as u64).inverse().expect("N non-zero");
+    for x in a.iter_mut() {
+        *x *= inv_n;
+    }
+}
+
+/// Back-compat name for the SRS handle taken by [`commit_stream`].
+pub type SRS = Srs;
+
+/// Committer key for `pcs`: its explicit SRS, or a snapshot of the global
+/// one grown (dev SRS) to `need` powers. The global mutex is held only for
+/// the snapshot, never while committing.
+fn committer_key(pcs: &PcsParams, need: usize) -> CommitterKey {
+    match &pcs.srs {
+        Some(srs) => srs.ck.clone(),
+        None => {
+            let mut guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
+            guard.ensure_len(need);
+            CommitterKey::from_shared(Arc::clone(&guard.powers))
+        }
+    }
+}
+
+/// Verifier key for `pcs`: its explicit SRS, or the global one.
+fn verifier_key(pcs: &PcsParams) -> Result<VerifierKey, VerifyError> {
+    match &pcs.srs {
+        Some(srs) => srs.verifier_key(),
+        None => global_verifier_key(),
+    }
 }
 
 // ===========================================================================
-// Internal SRS (BN254) — G1 powers of τ and a single G2 element [τ]G2
+// Global SRS (legacy) — G1 powers of τ and a single G2 element [τ]G2
 // ===========================================================================
 
 #[derive(Debug)]
 struct SrsG1 {
-    powers: Vec<G1Affine>,
+    powers: Arc<Vec<G1Affine>>,
     #[cfg(feature = "dev-srs")]
     tau: ScalarField,
 }
@@ -169,7 +717,7 @@ impl SrsG1 {
     fn new_dev() -> Self {
         let mut rng = StdRng::from_seed([42u8; 32]);
         let tau = ScalarField::rand(&mut rng);
-        let mut s = SrsG1 { powers: Vec::new(), tau };
+        let mut s = SrsG1 { powers: Arc::default(), tau };
         s.ensure_len(1);
         s
     }
@@ -180,11 +728,13 @@ impl SrsG1 {
         }
         #[cfg(feature = "dev-srs")]
         {
+            // Copies only if a committer key still holds the old snapshot.
+            let powers = Arc::make_mut(&mut self.powers);
             let gen = G1Projective::generator();
-            let current = self.powers.len();
+            let current = powers.len();
             for idx in current..new_len {
                 let gi = gen.mul_bigint(self.tau.pow([idx as u64]).into_bigint());
-                self.powers.push(gi.into_affine());
+                powers.push(gi.into_affine());
             }
         }
         #[cfg(not(feature = "dev-srs"))]
@@ -211,24 +761,26 @@ fn srs_g1() -> &'static Mutex<SrsG1> {
     }
     #[cfg(not(feature = "dev-srs"))]
     {
-        SRS.get_or_init(|| Mutex::new(SrsG1 { powers: Vec::new() }))
+        SRS.get_or_init(|| Mutex::new(SrsG1 { powers: Arc::default() }))
     }
 }
 
 /// Load a trusted **G1** SRS and return a template (Result).
+///
+/// This replaces the process-global SRS used by params with `srs: None`;
+/// prefer [`Srs::new`] + [`Srs::pcs_params`] for new code.
 pub fn try_load_srs_g1(powers: &[G1Affine]) -> Result<PcsParams, SrsLoadError> {
     if powers.is_empty() {
         return Err(SrsLoadError::Empty);
     }
     let mut guard = srs_g1().lock().expect("SRS mutex poisoned");
-    guard.powers.clear();
-    guard.powers.extend_from_slice(powers);
+    guard.powers = Arc::new(powers.to_vec());
     drop(guard);
 
     Ok(PcsParams {
         max_degree: powers.len() - 1,
         basis: Basis::Coefficient,
-        srs_placeholder: (),
+        srs: None,
     })
 }
 
@@ -246,7 +798,7 @@ impl SrsG2 {
     #[cfg(feature = "dev-srs")]
     fn new_dev() -> Self {
         let tau = srs_g1().lock().expect("SRS mutex poisoned").tau;
-        let g2_gen = <Bn254 as Pairing>::G2::generator();
+        let g2_gen = <Engine as Pairing>::G2::generator();
         let tau_g2 = (G2Projective::from(g2_gen) * tau).into_affine();
         Self { tau_g2: Some(tau_g2) }
     }
@@ -293,22 +845,43 @@ fn hash_bytes(parts: &[&[u8]]) -> [u8; 32] {
     *h.finalize().as_bytes()
 }
 
-pub fn srs_g1_digest() -> [u8; 32] {
-    let guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
-    let mut bytes = Vec::new();
-    bytes.extend_from_slice(&(guard.powers.len() as u64).to_be_bytes());
-    for p in &guard.powers {
-        let mut tmp = Vec::with_capacity(48);
-        p.serialize_compressed(&mut tmp).expect("serialize G1");
-        bytes.extend_from_slice(&tmp);
+/// Incremental form of the G1 digest: same bytes as [`hash_bytes`] over
+/// `u64be(count) ‖ compressed(p_0) ‖ …`, without buffering them.
+pub(crate) struct G1DigestStream {
+    h: Hasher,
+    buf: Vec<u8>,
+}
+
+impl G1DigestStream {
+    pub(crate) fn new(count: usize) -> Self {
+        let point_len = G1Affine::zero().compressed_size();
+        let mut h = Hasher::new();
+        h.update(b"SSZKP.SRS.v1");
+        h.update(&((8 + count * point_len) as u64).to_be_bytes());
+        h.update(&(count as u64).to_be_bytes());
+        Self { h, buf: Vec::with_capacity(point_len) }
+    }
+
+    pub(crate) fn push(&mut self, p: &G1Affine) {
+        self.buf.clear();
+        p.serialize_compressed(&mut self.buf).expect("serialize G1");
+        self.h.update(&self.buf);
+    }
+
+    pub(crate) fn finish(self) -> [u8; 32] {
+        *self.h.finalize().as_bytes()
     }
-    hash_bytes(&[&bytes])
 }
 
-pub fn srs_g2_digest() -> [u8; 32] {
-    let guard = srs_g2().lock().expect("SRS G2 mutex poisoned");
+fn g1_digest_of(powers: &[G1Affine]) -> [u8; 32] {
+    let mut d = G1DigestStream::new(powers.len());
+    powers.iter().for_each(|p| d.push(p));
+    d.finish()
+}
+
+fn g2_digest_of(tau_g2: Option<G2Affine>) -> [u8; 32] {
     let mut bytes = Vec::new();
-    if let Some(tau_g2) = guard.tau_g2 {
+    if let Some(tau_g2) = tau_g2 {
         let mut tmp = Vec::with_capacity(96);
         tau_g2.serialize_compressed(&mut tmp).expect("serialize G2");
         bytes.extend_from_slice(&tmp);
@@ -316,6 +889,31 @@ pub fn srs_g2_digest() -> [u8; 32] {
     hash_bytes(&[&bytes])
 }
 
+/// Digest of the global G1 SRS.
+pub fn srs_g1_digest() -> [u8; 32] {
+    let powers = Arc::clone(&srs_g1().lock().expect("SRS G1 mutex poisoned").powers);
+    g1_digest_of(&powers)
+}
+
+/// Digest of the first `max_degree + 1` powers of the global G1 SRS (see
+/// [`CommitterKey::g1_prefix_digest`]).
+pub fn srs_g1_prefix_digest(max_degree: usize) -> [u8; 32] {
+    let count = max_degree.saturating_add(1);
+    let powers = {
+        #[allow(unused_mut)]
+        let mut guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
+        #[cfg(feature = "dev-srs")]
+        guard.ensure_len(count);
+        Arc::clone(&guard.powers)
+    };
+    g1_digest_of(&powers[..count.min(powers.len())])
+}
+
+/// Digest of the global G2 SRS element.
+pub fn srs_g2_digest() -> [u8; 32] {
+    g2_digest_of(srs_g2().lock().expect("SRS G2 mutex poisoned").tau_g2)
+}
+
 // ===========================================================================
 // Aggregator — streaming-friendly, tile-aware coefficient ingestion
 // ===========================================================================
@@ -327,6 +925,10 @@ pub enum AggregatorError {
     DegreeOverflow { cursor: usize, adding: usize, limit: usize },
     #[error("PCS basis mismatch (expected {expected:?}, got {got:?})")]
     Basis { expected: Basis, got: Basis },
+    #[error("commitment backend cannot commit: {0}")]
+    Backend(&'static str),
+    #[error("SRS read failed: {0}")]
+    SrsRead(#[from] crate::srs_setup::SrsSetupError),
 }
 
 /// Aggregates contributions `a_i · [τ^i]G₁` as tiles of **coefficients** arrive.
@@ -337,7 +939,8 @@ pub enum AggregatorError {
 pub struct Aggregator<'a> {
     pub(crate) pcs: &'a PcsParams,
     pub(crate) poly_id: &'static str,
-    acc: G1Projective,
+    ck: CommitterKey,
+    acc: MsmBatch,
     cursor: usize,
     // --- diagnostics (opt-in via env) ---
     memlog: bool,
@@ -348,12 +951,16 @@ pub struct Aggregator<'a> {
 
 impl<'a> Aggregator<'a> {
     /// Create a new aggregator. The initial `cursor` is 0 (constant term slot).
+    ///
+    /// The committer key is resolved once here (see [`PcsParams::srs`]);
+    /// ingesting tiles never locks.
     pub fn new(pcs: &'a PcsParams, poly_id: &'static str) -> Self {
         let memlog = std::env::var("SSZKP_MEMLOG").ok().as_deref() == Some("1");
         Self {
             pcs,
             poly_id,
-            acc: G1Projective::zero(),
+            ck: committer_key(pcs, 0),
+            acc: MsmBatch::new(),
             cursor: 0,
             memlog,
             peak_inflight_coeffs: 0,
@@ -374,11 +981,13 @@ impl<'a> Aggregator<'a> {
         self.pcs.max_degree + 1 - self.cursor
     }
 
-    /// Pre-reserve SRS powers to reduce mutex traffic (optional micro-optim).
+    /// Make sure the committer key covers `count` more coefficients (only
+    /// matters for the lazily grown global dev SRS).
     pub fn reserve_coeffs(&mut self, count: usize) {
         let need = self.cursor.saturating_add(count);
-        let mut guard = srs_g1().lock().expect("SRS mutex poisoned");
-        guard.ensure_len(need);
+        if self.ck.len() < need {
+            self.ck = committer_key(self.pcs, need);
+        }
     }
 
     // ----------------- High-level ingestion (legacy behavior kept) -----------------
@@ -433,7 +1042,7 @@ impl<'a> Aggregator<'a> {
                 self.poly_id, self.peak_inflight_coeffs, self.total_blocks, self.peak_buffered_blocks
             );
         }
-        Commitment(self.acc.into_affine())
+        Commitment(self.acc.finish_affine())
     }
 
     // ----------------- Tile-oriented ingestion (new) -----------------
@@ -469,7 +1078,7 @@ impl<'a> Aggregator<'a> {
     where
         I: Iterator<Item = &'static [F]>,
     {
-        while let Some(t) = tiles.next() {
+        for t in tiles {
             self.add_coeff_tile(t, order)?;
         }
         Ok(())
@@ -488,23 +1097,13 @@ impl<'a> Aggregator<'a> {
             });
         }
 
-        {
-            let mut guard = srs_g1().lock().expect("SRS mutex poisoned");
-            guard.ensure_len(self.cursor + add);
-            // If you stage blocks internally, update peak_buffered_blocks here.
-            // (We stream directly; keep at zero.)
-        }
-
-        let guard = srs_g1().lock().expect("SRS mutex poisoned");
-        for (i, c) in coeffs.iter().enumerate() {
-            if c.is_zero() {
-                continue;
-            }
-            let base = guard.get_power(self.cursor + i);
-            let term = base.into_group().mul_bigint(c.into_bigint());
-            self.acc += term;
+        self.reserve_coeffs(add);
+        // If you stage blocks internally, update peak_buffered_blocks here.
+        // (We stream directly; keep at zero.)
+        let bases = self.ck.powers(self.cursor, add)?;
+        for (b, c) in bases.iter().zip(coeffs) {
+            self.acc.push(*b, *c);
         }
-        drop(guard);
 
         self.cursor += add;
         Ok(())
@@ -521,15 +1120,11 @@ impl<'a> Aggregator<'a> {
 // NEW: Streaming PCS entry points
 // ===========================================================================
 
-/// Opaque SRS handle for streaming APIs. We delegate to this module’s
-/// internal global SRS; the handle exists to match the requested signature.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct SRS;
-
 /// MSM/window configuration for streaming commits.
 #[derive(Debug, Clone, Copy)]
 pub struct CommitStreamCfg {
-    /// MSM fixed-window size (bits). Kept here for future bucketed MSM.
+    /// Pippenger window size in bits; `0` picks it from the batch size
+    /// (see [`crate::msm::window_bits_for`]).
     pub window_bits: u32,
     /// Preferred tile length (aka `b_blk`). Not strictly required here, but
     /// recorded for diagnostics and future tuning.
@@ -552,21 +1147,22 @@ impl Default for StreamingHandle {
 
 /// Commit from a **coefficient tile stream** without materializing the full `Vec`.
 ///
-/// Conceptually this could maintain MSM buckets per window; for now we
-/// accumulate directly into a single `G1Projective` using the global SRS
-/// powers, which already keeps memory at **O(tile_len)**.
+/// Terms are gathered into an [`MsmBatch`] and flushed through a bucketed
+/// MSM every [`crate::msm::MSM_TILE`] coefficients, so memory stays at
+/// **O(tile_len + MSM_TILE)** regardless of degree.
 ///
 /// The returned `StreamingHandle` captures the polynomial degree/basis.
+/// Fails if the stream outruns the SRS or a file-backed SRS cannot be read.
 pub fn commit_stream<TS>(
     mut tiles: TS,
-    _srs: &SRS,
+    srs: &SRS,
     cfg: &CommitStreamCfg,
-) -> (Commitment, StreamingHandle)
+) -> Result<(Commitment, StreamingHandle), AggregatorError>
 where
     TS: CoeffTileStream,
 {
     let memlog = std::env::var("SSZKP_MEMLOG").ok().as_deref() == Some("1");
-    let mut acc = G1Projective::zero();
+    let mut acc = MsmBatch::new().with_window_bits(cfg.window_bits);
     let mut cursor = 0usize;
     let mut peak_inflight = 0usize;
     let mut total_tiles = 0usize;
@@ -577,19 +1173,10 @@ where
             peak_inflight = tile.len();
         }
 
-        {
-            let mut guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
-            guard.ensure_len(cursor + tile.len());
+        let bases = srs.ck.powers(cursor, tile.len())?;
+        for (b, c) in bases.iter().zip(tile.iter()) {
+            acc.push(*b, *c);
         }
-        let guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
-        for (i, c) in tile.iter().enumerate() {
-            if c.is_zero() {
-                continue;
-            }
-            let base = guard.get_power(cursor + i);
-            acc += base.into_group().mul_bigint(c.into_bigint());
-        }
-        drop(guard);
 
         cursor += tile.len();
     }
@@ -601,9 +1188,9 @@ where
         );
     }
 
-    let c = Commitment(acc.into_affine());
+    let c = Commitment(acc.finish_affine());
     let handle = StreamingHandle { degree: cursor.saturating_sub(1), basis: Basis::Coefficient };
-    (c, handle)
+    Ok((c, handle))
 }
 
 /// Streaming Horner evaluation wrapper (used by openings).
@@ -641,51 +1228,56 @@ pub fn open_at_points(
 /// Single-pass, no buffering of the witness, no replay of the source:
 /// - Uses Horner to accumulate f(ζ).
 /// - Computes quotient coefficients on the fly (synthetic division).
-/// - Adds each quotient coefficient directly into the MSM at absolute index j=i−1,
+/// - Queues each quotient coefficient into a batched MSM at absolute index j=i−1,
 ///   where `i` counts down from `pcs_for_poly.max_degree`.
+///
+/// Fails if the tiles outrun the SRS or a file-backed SRS cannot be read.
 pub fn open_at_points_with_coeffs(
     pcs_for_poly: &PcsParams,
     commitments: &[Commitment],
     _stream_eval: impl Fn(usize, F) -> F,
     mut stream_coeff_hi_to_lo: impl FnMut(usize, &mut dyn FnMut(Vec<F>)),
     points: &[F],
-) -> Vec<OpeningProof> {
+) -> Result<Vec<OpeningProof>, AggregatorError> {
     let mut proofs = Vec::with_capacity(commitments.len().saturating_mul(points.len()));
     let memlog = std::env::var("SSZKP_MEMLOG").ok().as_deref() == Some("1");
 
-    // Upper-bound SRS reservation once (we'll also check inside the loop defensively).
-    {
-        let mut g = srs_g1().lock().expect("SRS G1 mutex poisoned");
-        g.ensure_len(pcs_for_poly.max_degree + 1);
-    }
+    // Resolve the committer key once for the upper bound; tiles never lock.
+    let ck = committer_key(pcs_for_poly, pcs_for_poly.max_degree + 1);
 
     for (pi, _c) in commitments.iter().enumerate() {
         for &zeta in points {
             let mut eval_acc = F::zero();          // Horner accumulator for f(ζ)
-            let mut w_acc = G1Projective::zero();  // MSM accumulator for W(X)
+            let mut w_acc = MsmBatch::new();       // batched MSM for W(X)
             let mut i_abs: isize = pcs_for_poly.max_degree as isize; // absolute coefficient index (a_i), high→low
 
             let mut peak_inflight = 0usize;
             let mut total_blocks = 0usize;
+            // The sink can't return an error: keep the first and drop later blocks.
+            let mut failed: Option<AggregatorError> = None;
 
             let mut consume_block = |mut blk_hi_to_lo: Vec<F>| {
+                if failed.is_some() {
+                    return;
+                }
                 total_blocks += 1;
                 if memlog && blk_hi_to_lo.len() > peak_inflight { peak_inflight = blk_hi_to_lo.len(); }
 
-                // Make sure SRS has enough powers for upcoming indices (defensive).
-                {
-                    let mut g = srs_g1().lock().expect("SRS G1 mutex poisoned");
-                    // We may touch up to (i_abs as usize) next, but guard for zero/negative below.
-                    let need = (i_abs.max(0) as usize) + 1;
-                    g.ensure_len(need);
-                }
-
                 // Drain the block (already high→low). For each incoming a_i:
                 //   b_{i-1} = a_i + z * b_i, with b_{deg} := 0.
                 //   f_acc    = a_i + z * f_acc  (standard Horner)
                 //
-                // We add b_{i-1} into MSM at index (i-1) immediately.
-                let g = srs_g1().lock().expect("SRS G1 mutex poisoned");
+                // We queue b_{i-1} into the MSM at index (i-1) immediately.
+                // The block touches indices [lo, top), fetched in one go.
+                let top = i_abs.max(0) as usize;
+                let lo = top.saturating_sub(blk_hi_to_lo.len());
+                let bases = match ck.powers(lo, top - lo) {
+                    Ok(bases) => bases,
+                    Err(e) => {
+                        failed = Some(e);
+                        return;
+                    }
+                };
                 for a_i in blk_hi_to_lo.drain(..) {
                     // quotient recurrence (synthetic division)
                     let b_im1 = a_i + zeta * eval_acc;
@@ -694,18 +1286,19 @@ pub fn open_at_points_with_coeffs(
                     eval_acc = b_im1;
 
                     // Absolute MSM index for b_{i-1} is (i_abs - 1)
-                    if i_abs > 0 && !b_im1.is_zero() {
-                        let base = g.get_power((i_abs - 1) as usize);
-                        w_acc += base.into_group().mul_bigint(b_im1.into_bigint());
+                    if i_abs > 0 {
+                        w_acc.push(bases[i_abs as usize - 1 - lo], b_im1);
                     }
 
                     // Move to next coefficient (downwards)
                     i_abs -= 1;
                 }
-                // drop(g) by leaving scope
             };
 
             stream_coeff_hi_to_lo(pi, &mut consume_block);
+            if let Some(e) = failed {
+                return Err(e);
+            }
 
             if memlog {
                 eprintln!(
@@ -718,12 +1311,12 @@ pub fn open_at_points_with_coeffs(
             proofs.push(OpeningProof {
                 zeta,
                 value: eval_acc,
-                witness_comm: Commitment(w_acc.into_affine()),
+                witness_comm: Commitment(w_acc.finish_affine()),
             });
         }
     }
 
-    proofs
+    Ok(proofs)
 }
 
 /// Open at points from **evaluation streams** by converting each eval-tile to
@@ -735,7 +1328,7 @@ pub fn open_eval_stream_at_points(
     domain: &crate::domain::Domain,
     mut stream_evals: impl FnMut(usize, &mut dyn FnMut(Vec<F>)),
     points: &[F],
-) -> Vec<OpeningProof> {
+) -> Result<Vec<OpeningProof>, AggregatorError> {
     let mut as_coeff_hi_to_lo = |idx: usize, sink: &mut dyn FnMut(Vec<F>)| {
         // Convert each eval block to coeffs, then flip to high→low and forward.
         let mut coeff_blocks: Vec<Vec<F>> = Vec::new();
@@ -765,22 +1358,42 @@ pub fn open_eval_stream_at_points(
 // ===========================================================================
 
 pub fn verify_openings(
-    _pcs: &PcsParams,
+    pcs: &PcsParams,
     commitments: &[Commitment],
     points: &[F],
     claimed_evals: &[F],
     proofs: &[OpeningProof],
 ) -> Result<(), VerifyError> {
-    let expected = commitments.len().saturating_mul(points.len());
-    if proofs.len() != expected || claimed_evals.len() != expected {
-        return Err(VerifyError::Shape { expected, got: proofs.len().max(claimed_evals.len()) });
-    }
+    let vk = verifier_key(pcs)?;
+    // No transcript here: the verifier's own randomness weights the openings.
+    let nu = ScalarField::rand(&mut rand::thread_rng());
+    let mut a_all: Vec<<Engine as Pairing>::G1Prepared> = Vec::new();
+    let mut b_all: Vec<<Engine as Pairing>::G2Prepared> = Vec::new();
+    push_opening_pairs(
+        vk.g1,
+        vk.tau_g2,
+        commitments,
+        points,
+        claimed_evals,
+        proofs,
+        (nu, &mut ScalarField::one()),
+        &mut a_all,
+        &mut b_all,
+    )?;
+    final_pairing_check(a_all, b_all)
+}
+
+/// `[1]G1` and `[τ]G2` from the loaded SRS (what an external verifier embeds).
+pub fn loaded_verifier_key() -> Result<(G1Affine, G2Affine), VerifyError> {
+    global_verifier_key().map(|vk| (vk.g1, vk.tau_g2))
+}
 
+/// Fetch `[1]G1` and `[τ]G2` from the global SRS.
+fn global_verifier_key() -> Result<VerifierKey, VerifyError> {
     let g1_gen = {
         let guard = srs_g1().lock().expect("SRS G1 mutex poisoned");
         guard.get_power(0)
     };
-    let g2_gen = <Bn254 as Pairing>::G2::generator().into_affine();
     let g2_tau = {
         let guard = srs_g2().lock().expect("SRS G2 mutex poisoned");
         match guard.tau_g2 {
@@ -788,50 +1401,475 @@ pub fn verify_openings(
             None => return Err(VerifyError::MissingG2),
         }
     };
+    Ok(VerifierKey { g1: g1_gen, tau_g2: g2_tau })
+}
 
-    let mut a_all: Vec<<Bn254 as Pairing>::G1Prepared> = Vec::with_capacity(expected * 3);
-    let mut b_all: Vec<<Bn254 as Pairing>::G2Prepared> = Vec::with_capacity(expected * 3);
+/// Append the Miller-loop inputs for one `(commitments × points)` opening set.
+///
+/// Opening `j` (counting across every set sharing `weight.1`) is scaled by
+/// `ρ_j = νʲ`, and the set collapses into two pairs:
+/// `e(Σ ρ_j·(C_j − v_j·G1 + ζ_j·W_j), G2) · e(−Σ ρ_j·W_j, [τ]G2)`.
+/// Without the weights a prover could shift `δ` between two claimed values
+/// and still satisfy the summed equation.
+#[allow(clippy::too_many_arguments)]
+fn push_opening_pairs(
+    g1_gen: G1Affine,
+    g2_tau: G2Affine,
+    commitments: &[Commitment],
+    points: &[F],
+    claimed_evals: &[F],
+    proofs: &[OpeningProof],
+    weight: (F, &mut F),
+    a_all: &mut Vec<<Engine as Pairing>::G1Prepared>,
+    b_all: &mut Vec<<Engine as Pairing>::G2Prepared>,
+) -> Result<(), VerifyError> {
+    let expected = commitments.len().saturating_mul(points.len());
+    if proofs.len() != expected || claimed_evals.len() != expected {
+        return Err(VerifyError::Shape { expected, got: proofs.len().max(claimed_evals.len()) });
+    }
+    let (nu, rho) = weight;
 
+    let mut lhs = G1Projective::zero();
+    let mut sum_w = G1Projective::zero();
+    let mut sum_v = F::zero();
     let mut idx = 0usize;
     for cmt in commitments.iter() {
-        let c_aff = cmt.0;
         for &pt in points.iter() {
             let pr = &proofs[idx];
             let val = claimed_evals[idx];
-
             if pr.value != val || pr.zeta != pt {
                 return Err(VerifyError::Pairing);
             }
 
-            // e(C, G2)
-            a_all.push(<Bn254 as Pairing>::G1Prepared::from(c_aff));
-            b_all.push(<Bn254 as Pairing>::G2Prepared::from(g2_gen));
-
-            // e(−f(ζ)·G1, G2)
-            let minus_f_g1 = (-g1_gen.into_group().mul_bigint(val.into_bigint())).into_affine();
-            a_all.push(<Bn254 as Pairing>::G1Prepared::from(minus_f_g1));
-            b_all.push(<Bn254 as Pairing>::G2Prepared::from(g2_gen));
-
-            // e(−W, [τ]G2 − ζ·G2)
-            let right_g2 =
-                (g2_tau.into_group() - g2_gen.into_group().mul_bigint(pt.into_bigint())).into_affine();
-            let minus_w = (-pr.witness_comm.0).into_group().into_affine();
-            a_all.push(<Bn254 as Pairing>::G1Prepared::from(minus_w));
-            b_all.push(<Bn254 as Pairing>::G2Prepared::from(right_g2));
-
+            let w = pr.witness_comm.0.into_group() * *rho;
+            lhs += cmt.0.into_group() * *rho + w * pt;
+            sum_w += w;
+            sum_v += *rho * val;
+            *rho *= nu;
             idx += 1;
         }
     }
+    if idx == 0 {
+        return Ok(());
+    }
+    lhs -= g1_gen.into_group() * sum_v;
 
+    let g2_gen = <Engine as Pairing>::G2::generator().into_affine();
+    a_all.push(<Engine as Pairing>::G1Prepared::from(lhs.into_affine()));
+    b_all.push(<Engine as Pairing>::G2Prepared::from(g2_gen));
+    a_all.push(<Engine as Pairing>::G1Prepared::from((-sum_w).into_affine()));
+    b_all.push(<Engine as Pairing>::G2Prepared::from(g2_tau));
+    Ok(())
+}
+
+/// Run one multi-Miller loop + final exponentiation and require `1 ∈ GT`.
+fn final_pairing_check(
+    a_all: Vec<<Engine as Pairing>::G1Prepared>,
+    b_all: Vec<<Engine as Pairing>::G2Prepared>,
+) -> Result<(), VerifyError> {
     if a_all.is_empty() {
         return Ok(());
     }
 
-    let mlo = <Bn254 as Pairing>::multi_miller_loop(a_all, b_all);
-    if let Some(fe) = <Bn254 as Pairing>::final_exponentiation(mlo) {
+    let mlo = <Engine as Pairing>::multi_miller_loop(a_all, b_all);
+    if let Some(fe) = <Engine as Pairing>::final_exponentiation(mlo) {
         if fe.0.is_one() {
             return Ok(());
         }
     }
     Err(VerifyError::Pairing)
 }
+
+// ===========================================================================
+// Backend trait — what the five-phase scheduler needs from a PCS
+// ===========================================================================
+
+/// One `(commitments × points)` opening set handed to
+/// [`PolynomialCommitment::batch_verify`].
+///
+/// `evals`/`proofs` are flattened **poly-major, point-minor**, exactly as the
+/// scheduler appends them to the proof.
+pub struct OpeningBatch<'b, P: PolynomialCommitment + ?Sized> {
+    /// Degree/basis context of the committed polynomials.
+    pub pcs: &'b PcsParams,
+    /// Commitments opened in this set.
+    pub commitments: &'b [P::Commitment],
+    /// Evaluation points shared by every commitment in the set.
+    pub points: &'b [F],
+    /// Claimed evaluations (`commitments.len() * points.len()` items).
+    pub evals: &'b [F],
+    /// Opening proofs, 1-to-1 with `evals`.
+    pub proofs: &'b [P::Proof],
+}
+
+/// Producer of a polynomial's **high→low** coefficient tiles: called with a
+/// sink, it pushes every tile into it.
+pub type CoeffStreamHiToLo<'s> = dyn FnMut(&mut dyn FnMut(Vec<F>)) + 's;
+
+/// A polynomial commitment backend usable by [`crate::scheduler`].
+///
+/// The scheduler only ever hands a backend **coefficient tiles**: low→high
+/// when committing and high→low when opening. Backends decide how much of a
+/// polynomial they keep resident; KZG stays at O(tile) throughout.
+pub trait PolynomialCommitment {
+    /// Commitment to a single polynomial (absorbed into the transcript).
+    type Commitment: Clone + std::fmt::Debug + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+    /// Proof that a committed polynomial opens to a value at one point.
+    type Proof: Clone + std::fmt::Debug + CanonicalSerialize + CanonicalDeserialize;
+
+    /// Header tag identifying this backend.
+    const KIND: PcsKind;
+
+    /// `(G1, G2)` setup digests bound into the proof header for params
+    /// `pcs`. KZG digests only the first `pcs.max_degree + 1` powers, so any
+    /// large enough SRS from the same ceremony matches. Transparent backends
+    /// put a digest of their parameters in the first slot. Fails if a
+    /// file-backed SRS can no longer be read.
+    fn srs_digests(&self, pcs: &PcsParams) -> Result<([u8; 32], [u8; 32]), AggregatorError>;
+
+    /// Commit from **low→high** coefficient tiles, contiguous from degree 0.
+    fn commit_coeff_tiles<I>(
+        &self,
+        pcs: &PcsParams,
+        poly_id: &'static str,
+        tiles: I,
+    ) -> Result<Self::Commitment, AggregatorError>
+    where
+        I: IntoIterator<Item = Vec<F>>;
+
+    /// Whether [`Self::commit_evals`] can commit time values over `domain`
+    /// directly. When it can't, the scheduler interpolates (blocked IFFT)
+    /// and calls [`Self::commit_coeff_tiles`].
+    fn can_commit_evals(&self, _pcs: &PcsParams, _domain: &domain::Domain) -> bool {
+        false
+    }
+
+    /// Commit time-ordered evaluations over `domain` (zero-padded to `N`)
+    /// without interpolating them first.
+    fn commit_evals<I>(
+        &self,
+        _pcs: &PcsParams,
+        _domain: &domain::Domain,
+        _poly_id: &'static str,
+        _evals: I,
+    ) -> Result<Self::Commitment, AggregatorError>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        Err(AggregatorError::Backend("no evaluation-basis commitment key"))
+    }
+
+    /// Open one commitment at `points`.
+    ///
+    /// `stream_coeff_hi_to_lo` pushes the polynomial's coefficient tiles,
+    /// **high→low**, starting from degree `pcs.max_degree`, into the sink.
+    /// Returns one proof per point. Backends with their own Fiat–Shamir
+    /// transcript build it with `fs_hash` (the proof header's choice).
+    fn open_hi_to_lo(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        commitment: &Self::Commitment,
+        stream_coeff_hi_to_lo: &mut CoeffStreamHiToLo<'_>,
+        points: &[F],
+    ) -> Result<Vec<Self::Proof>, AggregatorError>;
+
+    /// The evaluation a proof attests to.
+    fn opened_value(proof: &Self::Proof) -> F;
+
+    /// Verify one `(commitments × points)` opening set made with `fs_hash`.
+    fn verify(
+        &self,
+        pcs: &PcsParams,
+        fs_hash: FsHash,
+        commitments: &[Self::Commitment],
+        points: &[F],
+        claimed_evals: &[F],
+        proofs: &[Self::Proof],
+    ) -> Result<(), VerifyError>;
+
+    /// Verify several opening sets at once. `nu` is a Fiat–Shamir challenge
+    /// drawn after every claimed evaluation was absorbed; a backend that merges
+    /// openings into one equation must weight opening `j` (across all sets, in
+    /// order) by `νʲ`. The default checks the sets in turn and ignores `nu`.
+    fn batch_verify(
+        &self,
+        batches: &[OpeningBatch<'_, Self>],
+        nu: F,
+        fs_hash: FsHash,
+    ) -> Result<(), VerifyError> {
+        let _ = nu;
+        for b in batches {
+            self.verify(b.pcs, fs_hash, b.commitments, b.points, b.evals, b.proofs)?;
+        }
+        Ok(())
+    }
+}
+
+/// KZG backed by each call's [`PcsParams::srs`] (or the global SRS).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kzg;
+
+impl PolynomialCommitment for Kzg {
+    type Commitment = Commitment;
+    type Proof = OpeningProof;
+
+    const KIND: PcsKind = PcsKind::Kzg;
+
+    fn srs_digests(&self, pcs: &PcsParams) -> Result<([u8; 32], [u8; 32]), AggregatorError> {
+        Ok(match &pcs.srs {
+            Some(srs) => (srs.g1_prefix_digest(pcs.max_degree)?, srs.g2_digest()),
+            None => (srs_g1_prefix_digest(pcs.max_degree), srs_g2_digest()),
+        })
+    }
+
+    fn commit_coeff_tiles<I>(
+        &self,
+        pcs: &PcsParams,
+        poly_id: &'static str,
+        tiles: I,
+    ) -> Result<Commitment, AggregatorError>
+    where
+        I: IntoIterator<Item = Vec<F>>,
+    {
+        let pcs_for_commit = PcsParams { basis: Basis::Coefficient, ..pcs.clone() };
+        let mut agg = Aggregator::new(&pcs_for_commit, poly_id);
+        for tile in tiles {
+            agg.add_block_coeffs_r(&tile)?;
+        }
+        Ok(agg.finalize())
+    }
+
+    /// Evaluation-basis params whose SRS carries a [`LagrangeKey`] for `domain`.
+    fn can_commit_evals(&self, pcs: &PcsParams, domain: &domain::Domain) -> bool {
+        pcs.basis == Basis::Evaluation
+            && pcs.srs.as_ref().is_some_and(|srs| srs.lagrange_key(domain).is_some())
+    }
+
+    fn commit_evals<I>(
+        &self,
+        pcs: &PcsParams,
+        domain: &domain::Domain,
+        _poly_id: &'static str,
+        evals: I,
+    ) -> Result<Commitment, AggregatorError>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let key = pcs
+            .srs
+            .as_ref()
+            .and_then(|srs| srs.lagrange_key(domain))
+            .ok_or(AggregatorError::Backend("no Lagrange key for this domain"))?;
+        // Same bound the interpolated path hits with its N coefficients.
+        if key.n() > pcs.max_degree + 1 {
+            return Err(AggregatorError::DegreeOverflow {
+                cursor: 0,
+                adding: key.n(),
+                limit: pcs.max_degree + 1,
+            });
+        }
+        key.commit_evals(evals)
+    }
+
+    fn open_hi_to_lo(
+        &self,
+        pcs: &PcsParams,
+        _fs_hash: FsHash,
+        commitment: &Commitment,
+        stream_coeff_hi_to_lo: &mut CoeffStreamHiToLo<'_>,
+        points: &[F],
+    ) -> Result<Vec<OpeningProof>, AggregatorError> {
+        open_at_points_with_coeffs(
+            pcs,
+            std::slice::from_ref(commitment),
+            |_idx, _z| F::zero(),
+            |_idx: usize, sink: &mut dyn FnMut(Vec<F>)| stream_coeff_hi_to_lo(sink),
+            points,
+        )
+    }
+
+    #[inline]
+    fn opened_value(proof: &OpeningProof) -> F {
+        proof.value
+    }
+
+    fn verify(
+        &self,
+        pcs: &PcsParams,
+        _fs_hash: FsHash,
+        commitments: &[Commitment],
+        points: &[F],
+        claimed_evals: &[F],
+        proofs: &[OpeningProof],
+    ) -> Result<(), VerifyError> {
+        verify_openings(pcs, commitments, points, claimed_evals, proofs)
+    }
+
+    /// All sets share one multi-Miller loop and a single final exponentiation;
+    /// opening `j` is weighted by `νʲ` so claimed values cannot be traded.
+    fn batch_verify(
+        &self,
+        batches: &[OpeningBatch<'_, Self>],
+        nu: F,
+        _fs_hash: FsHash,
+    ) -> Result<(), VerifyError> {
+        let mut a_all: Vec<<Engine as Pairing>::G1Prepared> = Vec::new();
+        let mut b_all: Vec<<Engine as Pairing>::G2Prepared> = Vec::new();
+        let mut rho = F::one();
+        for b in batches {
+            let vk = verifier_key(b.pcs)?;
+            push_opening_pairs(
+                vk.g1,
+                vk.tau_g2,
+                b.commitments,
+                b.points,
+                b.evals,
+                b.proofs,
+                (nu, &mut rho),
+                &mut a_all,
+                &mut b_all,
+            )?;
+        }
+        final_pairing_check(a_all, b_all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn srs_with_tau(tau: u64, len: usize) -> Srs {
+        let tau = ScalarField::from(tau);
+        let g = G1Projective::generator();
+        let powers = (0..len as u64).map(|i| (g * tau.pow([i])).into_affine()).collect();
+        Srs::new(powers, Some((G2Projective::generator() * tau).into_affine())).unwrap()
+    }
+
+    #[test]
+    fn explicit_srses_coexist_without_global_state() {
+        let (a, b) = (srs_with_tau(11, 8), srs_with_tau(13, 8));
+        assert_ne!(a.g1_digest().unwrap(), b.g1_digest().unwrap());
+        let (pa, pb) = (a.pcs_params(Basis::Coefficient), b.pcs_params(Basis::Coefficient));
+        assert_eq!(Kzg.srs_digests(&pa).unwrap(), (a.g1_digest().unwrap(), a.g2_digest()));
+
+        let coeffs: Vec<F> = (1..=8u64).map(F::from).collect();
+        let ca = Kzg.commit_coeff_tiles(&pa, "f", vec![coeffs[..4].to_vec(), coeffs[4..].to_vec()]).unwrap();
+        let cb = Kzg.commit_coeff_tiles(&pb, "f", vec![coeffs.clone()]).unwrap();
+        assert_ne!(ca, cb);
+
+        let zeta = F::from(5u64);
+        let mut hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| sink(coeffs.iter().rev().copied().collect());
+        let proofs = Kzg.open_hi_to_lo(&pa, FsHash::Blake3, &ca, &mut hi_to_lo, &[zeta]).unwrap();
+        let evals = [proofs[0].value];
+        Kzg.verify(&pa, FsHash::Blake3, &[ca], &[zeta], &evals, &proofs).unwrap();
+        assert!(Kzg.verify(&pb, FsHash::Blake3, &[ca], &[zeta], &evals, &proofs).is_err());
+    }
+
+    #[test]
+    fn batch_rejects_values_traded_between_openings() {
+        let pcs = srs_with_tau(11, 8).pcs_params(Basis::Coefficient);
+        let zeta = F::from(5u64);
+        let mut comms = Vec::new();
+        let mut proofs = Vec::new();
+        for seed in [1u64, 9] {
+            let coeffs: Vec<F> = (seed..seed + 8).map(F::from).collect();
+            let c = Kzg.commit_coeff_tiles(&pcs, "f", vec![coeffs.clone()]).unwrap();
+            let mut hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| sink(coeffs.iter().rev().copied().collect());
+            proofs.extend(Kzg.open_hi_to_lo(&pcs, FsHash::Blake3, &c, &mut hi_to_lo, &[zeta]).unwrap());
+            comms.push(c);
+        }
+        let batch = |proofs: &[OpeningProof], evals: &[F]| {
+            let b = OpeningBatch { pcs: &pcs, commitments: &comms, points: &[zeta], evals, proofs };
+            Kzg.batch_verify(&[b], F::from(3u64), FsHash::Blake3)
+        };
+        let evals: Vec<F> = proofs.iter().map(|p| p.value).collect();
+        batch(&proofs, &evals).unwrap();
+
+        // Shifting δ from one claimed value to the other keeps the unweighted
+        // sum intact; the ν weights must catch it.
+        let delta = F::from(1234u64);
+        let mut forged = proofs.clone();
+        forged[0].value += delta;
+        forged[1].value -= delta;
+        let evals: Vec<F> = forged.iter().map(|p| p.value).collect();
+        assert!(batch(&forged, &evals).is_err());
+        assert!(Kzg.verify(&pcs, FsHash::Blake3, &comms, &[zeta], &evals, &forged).is_err());
+    }
+
+    #[test]
+    fn prefix_digest_lets_a_larger_srs_verify() {
+        use crate::scheduler::{Prover, Verifier};
+        use crate::test_util::tiny_prove_fixture;
+        use ark_ff::FftField;
+
+        let n = 8;
+        let domain = domain::Domain { n, omega: F::get_root_of_unity(n as u64).unwrap(), zh_c: F::one() };
+        let (short, long) = (srs_with_tau(7, n), srs_with_tau(7, 4 * n + 3));
+        assert_ne!(short.g1_digest().unwrap(), long.g1_digest().unwrap());
+        assert_eq!(long.g1_prefix_digest(n - 1).unwrap(), short.g1_digest().unwrap());
+        assert_eq!(short.g1_prefix_digest(4 * n).unwrap(), short.g1_digest().unwrap());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("long.srs");
+        crate::srs_file::write_g1(&path, &long.committer_key().powers(0, 4 * n + 3).unwrap()).unwrap();
+        let file = crate::srs_file::SrsFile::open_with_cache(&path, None).unwrap();
+        let from_file = Srs::from_file(file, Some(long.verifier_key().unwrap().tau_g2));
+        assert_eq!(from_file.g1_prefix_digest(n - 1).unwrap(), short.g1_digest().unwrap());
+
+        // Prove with the file-backed large SRS, verify with the exact-size one.
+        let fx = tiny_prove_fixture(n).with_rows(6).with_srs(&from_file);
+        let proof = Prover::new(&fx.air, &fx.pp).prove_with_restreamer(&fx.rows).unwrap();
+        assert_eq!(proof.header.srs_g1_digest, short.g1_digest().unwrap());
+        Verifier::new(&tiny_prove_fixture(n).with_srs(&short).vp).verify(&proof).unwrap();
+    }
+
+    #[test]
+    fn lagrange_key_commits_match_interpolated_commits() {
+        use crate::scheduler::{Prover, Verifier};
+        use crate::test_util::tiny_prove_fixture;
+        use ark_ff::FftField;
+
+        let n = 8;
+        let domain = domain::Domain { n, omega: F::get_root_of_unity(n as u64).unwrap(), zh_c: F::one() };
+        let srs = srs_with_tau(7, n);
+        let keyed = srs.clone().with_lagrange(&domain).unwrap();
+        let key = keyed.lagrange_key(&domain).unwrap();
+
+        // Fewer values than N: the rest are zero, as in the IFFT path.
+        let evals: Vec<F> = (1..=6u64).map(F::from).collect();
+        let coeffs = domain::ifft_time_stream_to_coeff_tiles(&domain, n, evals.iter().copied());
+        let want = Kzg.commit_coeff_tiles(&srs.pcs_params(Basis::Coefficient), "f", coeffs).unwrap();
+        assert_eq!(key.commit_evals(evals.iter().copied()).unwrap(), want);
+        assert!(key.commit_evals((0..=n as u64).map(F::from)).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lagrange.srs");
+        key.write(&path).unwrap();
+        let file = crate::srs_file::SrsFile::open_with_cache(&path, None).unwrap();
+        let from_file = LagrangeKey::from_file(file.clone(), srs.committer_key(), &domain).unwrap();
+        assert_eq!(from_file.commit_evals(evals.iter().copied()).unwrap(), want);
+        // The header binds the parent SRS and ω.
+        let other = srs_with_tau(9, n);
+        assert!(matches!(
+            LagrangeKey::from_file(file.clone(), other.committer_key(), &domain),
+            Err(SrsLoadError::LagrangeBinding)
+        ));
+        let twisted = domain::Domain { omega: domain.omega.pow([3u64]), ..domain.clone() };
+        assert!(matches!(
+            LagrangeKey::from_file(file, srs.committer_key(), &twisted),
+            Err(SrsLoadError::LagrangeBinding)
+        ));
+
+        // Whole prover: same proof with and without the key.
+        let prove = |srs: &Srs| {
+            let fx = tiny_prove_fixture(n).with_rows(6).with_srs(srs);
+            let proof = Prover::new(&fx.air, &fx.pp).prove_with_restreamer(&fx.rows).unwrap();
+            Verifier::new(&fx.vp).verify(&proof).unwrap();
+            proof
+        };
+        let (plain, fast) = (prove(&srs), prove(&keyed));
+        assert_eq!(plain.wire_comms, fast.wire_comms);
+        assert_eq!(plain.evals, fast.evals);
+    }
+}
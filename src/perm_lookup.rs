@@ -61,6 +61,12 @@ impl PermAcc {
     }
 }
 
+impl Default for PermAcc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Compute the per-row permutation multiplicand φ_perm for a given row.
 ///
 /// φ_perm(i) = Π_c (w_c + β·id_c + γ) / Π_c (w_c + β·σ_c + γ)
@@ -151,6 +157,12 @@ impl LookupAcc {
     }
 }
 
+impl Default for LookupAcc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A **generic compressed multiplicand** builder for lookup-style accumulators.
 ///
 /// Given *left* and *right* slices for a row (caller-defined), compress them
@@ -288,6 +300,7 @@ pub fn emit_lookup_column_block(
 ///
 /// This compiles and runs identically with or without `--features lookups`.
 /// With the feature disabled, the column is constant-1 (still well-formed).
+#[allow(clippy::too_many_arguments)]
 pub fn commit_lookup_acc_stream<R: Restreamer<Item = air::Row>>(
     air: &air::AirSpec,
     rs: &R,
@@ -321,7 +334,7 @@ pub fn commit_lookup_acc_stream<R: Restreamer<Item = air::Row>>(
         };
         z_start = carry;
 
-        bifft.feed_eval_block(&z_l_block);
+        bifft.feed_eval_block(&z_l_block).expect("spill tape append");
         boundary = br.boundary_out;
     }
 
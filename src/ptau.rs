@@ -0,0 +1,823 @@
+//! Native `.ptau` (snarkjs powers of tau) importer
+//!
+//! The Hermez / Perpetual Powers of Tau ceremonies publish their output in
+//! the snarkjs binary container:
+//!
+//! ```text
+//! "ptau" ‖ u32 version ‖ u32 nSections ‖ { u32 type ‖ u64 size ‖ data }*
+//!
+//!  1  header         u32 n8 ‖ q (n8 bytes LE) ‖ u32 power ‖ u32 ceremonyPower
+//!  2  tauG1          [τ^i]G1,  i < 2^(power+1) − 1
+//!  3  tauG2          [τ^i]G2,  i < 2^power
+//!  4  alphaTauG1     [ατ^i]G1, i < 2^power
+//!  5  betaTauG1      [βτ^i]G1, i < 2^power
+//!  6  betaG2         [β]G2
+//!  7  contributions  u32 count ‖ records (points, keys, hashes, params)
+//! ```
+//!
+//! Points are affine `x ‖ y` with every base-field element stored little
+//! endian in **Montgomery form** (`x·R`, `R = 2^(8·n8)`); `Fq2` is `c0 ‖ c1`
+//! and the point at infinity is all zeros.
+//!
+//! [`import`](crate::ptau::import) extracts the G1 powers and `[τ]G2`, checks
+//! them (curve + subgroup, a randomized pairing check that the G1 powers and
+//! `[τ]G2` share one τ, and that they match the last contribution's record)
+//! and writes `G1.bin` / `G2.bin` for
+//! [`load_and_validate_g1_srs`](crate::srs_setup::load_and_validate_g1_srs).
+//!
+//! When the file is untruncated (`power == ceremonyPower`) it also checks the
+//! ceremony's embedded hash: the last contribution's `nextChallenge` must be
+//! the BLAKE2b-512 of its response hash followed by sections 2–6 in the
+//! challenge encoding (uncompressed, big endian, `Fq2` as `c1 ‖ c0`). The
+//! response hash is resumed from the record's `partialHash` (a RFC 7693
+//! BLAKE2b state), as snarkjs does. Truncated files (`hez_final_NN` with
+//! `NN < 28`) cannot carry a valid challenge hash; snarkjs skips it for them
+//! and so do we.
+
+#![forbid(unsafe_code)]
+
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField};
+
+use crate::curve::{Fq, G1Affine, G2Affine};
+use crate::srs_setup::{self, SrsSetupError};
+
+type Fq2 = <G2Affine as AffineRepr>::BaseField;
+
+const MAGIC: [u8; 4] = *b"ptau";
+const SECTION_HEADER: u32 = 1;
+const SECTION_TAU_G1: u32 = 2;
+const SECTION_TAU_G2: u32 = 3;
+const SECTION_ALPHA_TAU_G1: u32 = 4;
+const SECTION_BETA_TAU_G1: u32 = 5;
+const SECTION_BETA_G2: u32 = 6;
+const SECTION_CONTRIBUTIONS: u32 = 7;
+
+/// Points decoded per read when streaming a section.
+const CHUNK: usize = 1 << 14;
+
+/// Ceremony parameters from section 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtauHeader {
+    /// Base-field element size in bytes (32 for BN254).
+    pub n8: usize,
+    /// `log2` of the number of τ powers in G2 held by this file.
+    pub power: u32,
+    /// `power` of the ceremony the file was produced (or truncated) from.
+    pub ceremony_power: u32,
+}
+
+impl PtauHeader {
+    /// Number of `[τ^i]G1` points (`2^(power+1) − 1`).
+    pub fn g1_count(&self) -> usize {
+        (1usize << (self.power + 1)) - 1
+    }
+
+    /// Number of `[τ^i]G2` (and alpha/beta G1) points (`2^power`).
+    pub fn g2_count(&self) -> usize {
+        1usize << self.power
+    }
+}
+
+/// One record of the contributions section.
+#[derive(Debug, Clone)]
+pub struct Contribution {
+    /// `[τ]G1` after this contribution.
+    pub tau_g1: G1Affine,
+    /// `[τ]G2` after this contribution.
+    pub tau_g2: G2Affine,
+    /// Participant name, if recorded.
+    pub name: Option<String>,
+    /// Whether this was a random-beacon contribution.
+    pub beacon: bool,
+    /// BLAKE2b-512 of the contributor's response.
+    pub response_hash: [u8; 64],
+    /// BLAKE2b-512 the next participant's challenge must hash to.
+    pub next_challenge: [u8; 64],
+}
+
+/// What [`import`] checked and wrote.
+#[derive(Debug, Clone)]
+pub struct PtauImport {
+    /// Ceremony parameters.
+    pub header: PtauHeader,
+    /// Contribution records, oldest first.
+    pub contributions: Vec<Contribution>,
+    /// G1 powers written to `G1.bin`.
+    pub g1_written: usize,
+    /// Whether the last `nextChallenge` hash was recomputed and matched
+    /// (false for truncated files or when disabled).
+    pub challenge_hash_checked: bool,
+    /// `G1.bin` path.
+    pub g1_path: PathBuf,
+    /// `G2.bin` path.
+    pub g2_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Section {
+    offset: u64,
+    size: u64,
+}
+
+/// An opened `.ptau` file: section table and header, read lazily otherwise.
+#[derive(Debug)]
+pub struct PtauFile {
+    r: BufReader<File>,
+    header: PtauHeader,
+    sections: Vec<(u32, Section)>,
+    r_inv: Fq,
+}
+
+fn bad(msg: impl Into<String>) -> SrsSetupError {
+    SrsSetupError::Deserialize(format!("ptau: {}", msg.into()))
+}
+
+fn u32_le(b: &[u8]) -> u32 {
+    u32::from_le_bytes(b[..4].try_into().expect("4 bytes"))
+}
+
+impl PtauFile {
+    /// Open and check the container, section table and header (field size
+    /// and modulus must match this build's curve).
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SrsSetupError> {
+        let file = File::open(path.as_ref())?;
+        let len = file.metadata()?.len();
+        let mut r = BufReader::new(file);
+
+        let mut head = [0u8; 12];
+        r.read_exact(&mut head).map_err(|_| bad("file shorter than its header"))?;
+        if head[..4] != MAGIC {
+            return Err(bad("bad magic (not a .ptau file; an HTML/XML error page saved as .ptau?)"));
+        }
+        if u32_le(&head[4..]) != 1 {
+            return Err(bad(format!("unsupported version {}", u32_le(&head[4..]))));
+        }
+        let n_sections = u32_le(&head[8..]);
+
+        let mut sections = Vec::with_capacity(n_sections as usize);
+        let mut pos = 12u64;
+        for _ in 0..n_sections {
+            let mut sh = [0u8; 12];
+            r.read_exact(&mut sh).map_err(|_| bad("truncated section table"))?;
+            let size = u64::from_le_bytes(sh[4..].try_into().expect("8 bytes"));
+            let offset = pos + 12;
+            if offset.checked_add(size).is_none_or(|end| end > len) {
+                return Err(bad(format!("section {} runs past end of file", u32_le(&sh))));
+            }
+            sections.push((u32_le(&sh), Section { offset, size }));
+            pos = offset + size;
+            r.seek(SeekFrom::Start(pos))?;
+        }
+
+        let n8 = Fq::MODULUS.to_bytes_le().len();
+        let r_inv = Fq::from(2u64).pow([8 * n8 as u64]).inverse().expect("R invertible");
+        let mut me = Self {
+            r,
+            header: PtauHeader { n8, power: 0, ceremony_power: 0 },
+            sections,
+            r_inv,
+        };
+
+        let h = me.read_section(SECTION_HEADER, 0, None)?;
+        if h.len() != 12 + n8 {
+            return Err(bad(format!("header section is {} bytes, expected {}", h.len(), 12 + n8)));
+        }
+        if u32_le(&h) as usize != n8 {
+            return Err(bad(format!("field size {} bytes, this build expects {n8}", u32_le(&h))));
+        }
+        if h[4..4 + n8] != Fq::MODULUS.to_bytes_le()[..] {
+            return Err(bad("base-field modulus does not match this build's curve"));
+        }
+        me.header.power = u32_le(&h[4 + n8..]);
+        me.header.ceremony_power = u32_le(&h[8 + n8..]);
+        if me.header.power == 0 || me.header.power > 30 {
+            return Err(bad(format!("implausible power {}", me.header.power)));
+        }
+
+        for (id, count, size) in [
+            (SECTION_TAU_G1, me.header.g1_count(), 2 * n8),
+            (SECTION_TAU_G2, me.header.g2_count(), 4 * n8),
+        ] {
+            let s = me.section(id)?;
+            if s.size != (count * size) as u64 {
+                return Err(bad(format!("section {id} is {} bytes, expected {}", s.size, count * size)));
+            }
+        }
+        Ok(me)
+    }
+
+    /// Ceremony parameters.
+    pub fn header(&self) -> PtauHeader {
+        self.header
+    }
+
+    fn section(&self, id: u32) -> Result<Section, SrsSetupError> {
+        let mut it = self.sections.iter().filter(|(t, _)| *t == id);
+        match (it.next(), it.next()) {
+            (Some((_, s)), None) => Ok(*s),
+            (None, _) => Err(bad(format!("missing section {id}"))),
+            (Some(_), Some(_)) => Err(bad(format!("duplicate section {id}"))),
+        }
+    }
+
+    /// Bytes `start .. start + len` of a section (`None` = to its end).
+    fn read_section(&mut self, id: u32, start: u64, len: Option<u64>) -> Result<Vec<u8>, SrsSetupError> {
+        let s = self.section(id)?;
+        let len = len.unwrap_or(s.size.saturating_sub(start));
+        if start + len > s.size {
+            return Err(bad(format!("read past end of section {id}")));
+        }
+        self.r.seek(SeekFrom::Start(s.offset + start))?;
+        let mut buf = vec![0u8; len as usize];
+        self.r.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// The first `count` `[τ^i]G1`, curve- and subgroup-checked.
+    pub fn read_g1_powers(&mut self, count: usize) -> Result<Vec<G1Affine>, SrsSetupError> {
+        if count > self.header.g1_count() {
+            return Err(SrsSetupError::Validation(format!(
+                "ptau holds {} G1 powers, {count} requested",
+                self.header.g1_count()
+            )));
+        }
+        let sz = 2 * self.header.n8;
+        let mut out = Vec::with_capacity(count);
+        for start in (0..count).step_by(CHUNK) {
+            let n = CHUNK.min(count - start);
+            let buf = self.read_section(SECTION_TAU_G1, (start * sz) as u64, Some((n * sz) as u64))?;
+            for (i, raw) in buf.chunks_exact(sz).enumerate() {
+                let p = self.g1(raw)?;
+                if !(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve()) {
+                    return Err(SrsSetupError::Validation(format!("tauG1[{}] not in G1", start + i)));
+                }
+                out.push(p);
+            }
+        }
+        Ok(out)
+    }
+
+    /// `[τ]G2`, curve- and subgroup-checked; `[τ^0]G2` must be the generator.
+    pub fn read_tau_g2(&mut self) -> Result<G2Affine, SrsSetupError> {
+        let sz = 4 * self.header.n8;
+        let buf = self.read_section(SECTION_TAU_G2, 0, Some(2 * sz as u64))?;
+        let (g, tau) = (self.g2(&buf[..sz])?, self.g2(&buf[sz..])?);
+        if g != G2Affine::generator() {
+            return Err(SrsSetupError::Validation("tauG2[0] is not the G2 generator".into()));
+        }
+        if tau.is_zero() || !(tau.is_on_curve() && tau.is_in_correct_subgroup_assuming_on_curve()) {
+            return Err(SrsSetupError::Validation("tauG2[1] not in G2".into()));
+        }
+        Ok(tau)
+    }
+
+    /// Parse the contributions section.
+    pub fn contributions(&mut self) -> Result<Vec<Contribution>, SrsSetupError> {
+        let buf = self.read_section(SECTION_CONTRIBUTIONS, 0, None)?;
+        let mut c = Cursor { buf: &buf, pos: 0 };
+        let (g1, g2) = (2 * self.header.n8, 4 * self.header.n8);
+        let count = u32_le(c.take(4)?);
+        let mut out = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let tau_g1 = self.g1(c.take(g1)?)?;
+            let tau_g2 = self.g2(c.take(g2)?)?;
+            c.take(g1 + g1 + g2)?; // alphaG1, betaG1, betaG2
+            let key = c.take(6 * g1 + 3 * g2)?;
+            let partial: [u8; 216] = c.take(216)?.try_into().expect("216 bytes");
+            let next_challenge: [u8; 64] = c.take(64)?.try_into().expect("64 bytes");
+            let beacon = u32_le(c.take(4)?) == 1;
+
+            let mut key_u = Vec::with_capacity(key.len());
+            for raw in key[..6 * g1].chunks_exact(g1) {
+                self.g1_to_challenge(raw, &mut key_u)?;
+            }
+            for raw in key[6 * g1..].chunks_exact(g2) {
+                self.g2_to_challenge(raw, &mut key_u)?;
+            }
+            let mut h = Blake2b::from_partial(&partial)?;
+            h.update(&key_u);
+            let response_hash = h.finalize();
+
+            let params_len = u32_le(c.take(4)?) as usize;
+            let mut p = Cursor { buf: c.take(params_len)?, pos: 0 };
+            let mut name = None;
+            while p.pos < p.buf.len() {
+                match p.take(1)?[0] {
+                    1 => {
+                        let n = p.take(1)?[0] as usize;
+                        name = Some(String::from_utf8_lossy(p.take(n)?).into_owned());
+                    }
+                    2 => {
+                        p.take(1)?;
+                    }
+                    3 => {
+                        let n = p.take(1)?[0] as usize;
+                        p.take(n)?;
+                    }
+                    t => return Err(bad(format!("unknown contribution parameter {t}"))),
+                }
+            }
+            out.push(Contribution { tau_g1, tau_g2, name, beacon, response_hash, next_challenge });
+        }
+        Ok(out)
+    }
+
+    /// Recompute the hash over sections 2–6 seeded with `last`'s response
+    /// hash and compare it with `last.next_challenge`.
+    pub fn check_challenge_hash(&mut self, last: &Contribution) -> Result<(), SrsSetupError> {
+        let hd = self.header;
+        let mut h = Blake2b::new(64);
+        h.update(&last.response_hash);
+        let mut u = Vec::new();
+        for (id, count, g2) in [
+            (SECTION_TAU_G1, hd.g1_count(), false),
+            (SECTION_TAU_G2, hd.g2_count(), true),
+            (SECTION_ALPHA_TAU_G1, hd.g2_count(), false),
+            (SECTION_BETA_TAU_G1, hd.g2_count(), false),
+            (SECTION_BETA_G2, 1, true),
+        ] {
+            let sz = if g2 { 4 * hd.n8 } else { 2 * hd.n8 };
+            for start in (0..count).step_by(CHUNK) {
+                let n = CHUNK.min(count - start);
+                let buf = self.read_section(id, (start * sz) as u64, Some((n * sz) as u64))?;
+                u.clear();
+                for raw in buf.chunks_exact(sz) {
+                    if g2 {
+                        self.g2_to_challenge(raw, &mut u)?;
+                    } else {
+                        self.g1_to_challenge(raw, &mut u)?;
+                    }
+                }
+                h.update(&u);
+            }
+        }
+        if h.finalize() != last.next_challenge {
+            return Err(SrsSetupError::CeremonyHash(
+                "points do not hash to the last contribution's nextChallenge".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    // ---- encoding ----
+
+    fn fq(&self, raw: &[u8]) -> Result<Fq, SrsSetupError> {
+        let x = Fq::from_le_bytes_mod_order(raw);
+        if x.into_bigint().to_bytes_le() != raw {
+            return Err(bad("field element not reduced"));
+        }
+        Ok(x * self.r_inv)
+    }
+
+    fn g1(&self, raw: &[u8]) -> Result<G1Affine, SrsSetupError> {
+        if raw.iter().all(|b| *b == 0) {
+            return Ok(G1Affine::zero());
+        }
+        let n8 = self.header.n8;
+        Ok(G1Affine::new_unchecked(self.fq(&raw[..n8])?, self.fq(&raw[n8..])?))
+    }
+
+    fn fq2(&self, raw: &[u8]) -> Result<Fq2, SrsSetupError> {
+        let n8 = self.header.n8;
+        Ok(Fq2::new(self.fq(&raw[..n8])?, self.fq(&raw[n8..])?))
+    }
+
+    fn g2(&self, raw: &[u8]) -> Result<G2Affine, SrsSetupError> {
+        if raw.iter().all(|b| *b == 0) {
+            return Ok(G2Affine::zero());
+        }
+        let n8 = self.header.n8;
+        Ok(G2Affine::new_unchecked(self.fq2(&raw[..2 * n8])?, self.fq2(&raw[2 * n8..])?))
+    }
+
+    /// Challenge encoding: big endian, infinity = `0x40 ‖ 0…`.
+    fn g1_to_challenge(&self, raw: &[u8], out: &mut Vec<u8>) -> Result<(), SrsSetupError> {
+        if raw.iter().all(|b| *b == 0) {
+            out.push(0x40);
+            out.extend(std::iter::repeat_n(0, raw.len() - 1));
+            return Ok(());
+        }
+        let n8 = self.header.n8;
+        for half in raw.chunks_exact(n8) {
+            out.extend(self.fq(half)?.into_bigint().to_bytes_be());
+        }
+        Ok(())
+    }
+
+    /// As [`Self::g1_to_challenge`], with each `Fq2` written `c1 ‖ c0`.
+    fn g2_to_challenge(&self, raw: &[u8], out: &mut Vec<u8>) -> Result<(), SrsSetupError> {
+        if raw.iter().all(|b| *b == 0) {
+            out.push(0x40);
+            out.extend(std::iter::repeat_n(0, raw.len() - 1));
+            return Ok(());
+        }
+        let n8 = self.header.n8;
+        for coord in raw.chunks_exact(2 * n8) {
+            out.extend(self.fq(&coord[n8..])?.into_bigint().to_bytes_be());
+            out.extend(self.fq(&coord[..n8])?.into_bigint().to_bytes_be());
+        }
+        Ok(())
+    }
+}
+
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SrsSetupError> {
+        let out = self.buf.get(self.pos..self.pos + n).ok_or_else(|| bad("truncated contribution record"))?;
+        self.pos += n;
+        Ok(out)
+    }
+}
+
+/// Convert `ptau_path` into `out_dir/G1.bin` + `out_dir/G2.bin`.
+///
+/// Writes `max_degree + 1` G1 powers (all of them if `None`). With
+/// `check_hash`, untruncated files also get the `nextChallenge` check
+/// described in the module docs (one pass over the whole file).
+pub fn import(
+    ptau_path: impl AsRef<Path>,
+    out_dir: impl AsRef<Path>,
+    max_degree: Option<usize>,
+    check_hash: bool,
+) -> Result<PtauImport, SrsSetupError> {
+    let mut f = PtauFile::open(ptau_path)?;
+    let header = f.header();
+    let count = max_degree.map_or(header.g1_count(), |d| d + 1);
+
+    let powers = f.read_g1_powers(count)?;
+    let tau_g2 = f.read_tau_g2()?;
+    srs_setup::validate_g1_powers(&powers, tau_g2)?;
+
+    let contributions = f.contributions()?;
+    let last = contributions
+        .last()
+        .ok_or_else(|| SrsSetupError::Validation("ptau has no contributions".into()))?;
+    if (powers.len() > 1 && last.tau_g1 != powers[1]) || last.tau_g2 != tau_g2 {
+        return Err(SrsSetupError::Validation(
+            "last contribution's [τ] does not match the tau sections".into(),
+        ));
+    }
+    let challenge_hash_checked = check_hash && header.power == header.ceremony_power;
+    if challenge_hash_checked {
+        f.check_challenge_hash(last)?;
+    }
+
+    let out_dir = out_dir.as_ref();
+    std::fs::create_dir_all(out_dir)?;
+    let (g1_path, g2_path) = (out_dir.join("G1.bin"), out_dir.join("G2.bin"));
+    srs_setup::save_g1_srs(&g1_path, &powers)?;
+    srs_setup::save_g2_srs(&g2_path, tau_g2)?;
+
+    Ok(PtauImport {
+        header,
+        contributions,
+        g1_written: powers.len(),
+        challenge_hash_checked,
+        g1_path,
+        g2_path,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// BLAKE2b (RFC 7693), with the reference context layout so snarkjs'
+// `partialHash` (blake2b-wasm's 216-byte state dump) can be resumed:
+//   b[128] ‖ h[8] u64 LE ‖ t[2] u64 LE ‖ c u32 LE ‖ outlen u32 LE
+// ---------------------------------------------------------------------------
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+struct Blake2b {
+    b: [u8; 128],
+    h: [u64; 8],
+    t: [u64; 2],
+    c: usize,
+    outlen: usize,
+}
+
+impl Blake2b {
+    fn new(outlen: usize) -> Self {
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ outlen as u64;
+        Self { b: [0; 128], h, t: [0; 2], c: 0, outlen }
+    }
+
+    fn from_partial(s: &[u8; 216]) -> Result<Self, SrsSetupError> {
+        let u64_at = |o: usize| u64::from_le_bytes(s[o..o + 8].try_into().expect("8 bytes"));
+        let (c, outlen) = (u32_le(&s[208..]) as usize, u32_le(&s[212..]) as usize);
+        if c > 128 || outlen == 0 || outlen > 64 {
+            return Err(bad("malformed partialHash"));
+        }
+        Ok(Self {
+            b: s[..128].try_into().expect("128 bytes"),
+            h: std::array::from_fn(|i| u64_at(128 + 8 * i)),
+            t: [u64_at(192), u64_at(200)],
+            c,
+            outlen,
+        })
+    }
+
+    #[cfg(test)]
+    fn to_partial(&self) -> [u8; 216] {
+        let mut s = [0u8; 216];
+        s[..128].copy_from_slice(&self.b);
+        for (i, w) in self.h.iter().chain(&self.t).enumerate() {
+            s[128 + 8 * i..136 + 8 * i].copy_from_slice(&w.to_le_bytes());
+        }
+        s[208..212].copy_from_slice(&(self.c as u32).to_le_bytes());
+        s[212..216].copy_from_slice(&(self.outlen as u32).to_le_bytes());
+        s
+    }
+
+    fn compress(&mut self, last: bool) {
+        let m: [u64; 16] =
+            std::array::from_fn(|i| u64::from_le_bytes(self.b[8 * i..8 * i + 8].try_into().expect("8 bytes")));
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.h);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= self.t[0];
+        v[13] ^= self.t[1];
+        if last {
+            v[14] = !v[14];
+        }
+        fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+            v[d] = (v[d] ^ v[a]).rotate_right(32);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(24);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(63);
+        }
+        for s in &SIGMA {
+            g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+        for i in 0..8 {
+            self.h[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+
+    fn add_counter(&mut self, n: usize) {
+        let (t0, carry) = self.t[0].overflowing_add(n as u64);
+        self.t[0] = t0;
+        self.t[1] = self.t[1].wrapping_add(carry as u64);
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // Like the reference code, a full buffer is only compressed once
+            // more input arrives (the final block is compressed by finalize).
+            if self.c == 128 {
+                self.add_counter(128);
+                self.compress(false);
+                self.c = 0;
+            }
+            let n = (128 - self.c).min(data.len());
+            self.b[self.c..self.c + n].copy_from_slice(&data[..n]);
+            self.c += n;
+            data = &data[n..];
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 64] {
+        self.add_counter(self.c);
+        self.b[self.c..].fill(0);
+        self.compress(true);
+        let mut out = [0u8; 64];
+        for (i, w) in self.h.iter().enumerate() {
+            out[8 * i..8 * i + 8].copy_from_slice(&w.to_le_bytes());
+        }
+        out[self.outlen..].fill(0);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{Engine, G1Projective};
+    use crate::F;
+    use ark_ec::{pairing::Pairing, CurveGroup, Group};
+    use blake2::Digest;
+
+    #[test]
+    fn blake2b_matches_reference_and_resumes_from_partial_state() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in [0, 1, 127, 128, 129, 256, 1000] {
+            let mut h = Blake2b::new(64);
+            h.update(&data[..len]);
+            let want: [u8; 64] = blake2::Blake2b512::digest(&data[..len]).into();
+            assert_eq!(h.finalize(), want, "len {len}");
+        }
+        let mut a = Blake2b::new(64);
+        a.update(&data[..300]);
+        let mut b = Blake2b::from_partial(&a.to_partial()).unwrap();
+        b.update(&data[300..]);
+        assert_eq!(b.finalize(), <[u8; 64]>::from(blake2::Blake2b512::digest(&data)));
+    }
+
+    /// Minimal snarkjs-layout writer for a known τ (test fixture only).
+    struct Fixture {
+        n8: usize,
+        r: Fq,
+    }
+
+    impl Fixture {
+        fn fq(&self, x: Fq, out: &mut Vec<u8>) {
+            out.extend((x * self.r).into_bigint().to_bytes_le());
+        }
+        fn g1(&self, p: G1Affine, out: &mut Vec<u8>) {
+            match p.xy() {
+                Some((x, y)) => {
+                    self.fq(*x, out);
+                    self.fq(*y, out);
+                }
+                None => out.extend(vec![0u8; 2 * self.n8]),
+            }
+        }
+        fn g2(&self, p: G2Affine, out: &mut Vec<u8>) {
+            let (x, y) = p.xy().expect("finite");
+            for c in [x.c0, x.c1, y.c0, y.c1] {
+                self.fq(c, out);
+            }
+        }
+
+        fn build(&self, power: u32, tau: F, tamper: bool) -> Vec<u8> {
+            let hd = PtauHeader { n8: self.n8, power, ceremony_power: power };
+            let (g1, g2) = (G1Projective::generator(), <Engine as Pairing>::G2::generator());
+            let pow = |i: usize| tau.pow([i as u64]);
+            let mut sections: Vec<(u32, Vec<u8>)> = Vec::new();
+
+            let mut s = Vec::new();
+            s.extend((self.n8 as u32).to_le_bytes());
+            s.extend(Fq::MODULUS.to_bytes_le());
+            s.extend(power.to_le_bytes());
+            s.extend(power.to_le_bytes());
+            sections.push((SECTION_HEADER, s));
+
+            let mut s = Vec::new();
+            for i in 0..hd.g1_count() {
+                self.g1((g1 * pow(i)).into_affine(), &mut s);
+            }
+            sections.push((SECTION_TAU_G1, s));
+            let mut s = Vec::new();
+            for i in 0..hd.g2_count() {
+                self.g2((g2 * pow(i)).into_affine(), &mut s);
+            }
+            sections.push((SECTION_TAU_G2, s));
+            for (id, k) in [(SECTION_ALPHA_TAU_G1, 3u64), (SECTION_BETA_TAU_G1, 4)] {
+                let mut s = Vec::new();
+                for i in 0..hd.g2_count() {
+                    self.g1((g1 * (pow(i) * F::from(k))).into_affine(), &mut s);
+                }
+                sections.push((id, s));
+            }
+            let mut s = Vec::new();
+            self.g2((g2 * F::from(4u64)).into_affine(), &mut s);
+            sections.push((SECTION_BETA_G2, s));
+
+            // One contribution; its nextChallenge covers the points as
+            // written before any tampering.
+            let mut prior = Blake2b::new(64);
+            prior.update(b"challenge transcript so far");
+            let partial = prior.to_partial();
+            let mut key = Vec::new();
+            for _ in 0..6 {
+                self.g1(g1.into_affine(), &mut key);
+            }
+            for _ in 0..3 {
+                self.g2(g2.into_affine(), &mut key);
+            }
+
+            let mut c = Vec::new();
+            c.extend(1u32.to_le_bytes());
+            self.g1((g1 * tau).into_affine(), &mut c);
+            self.g2((g2 * tau).into_affine(), &mut c);
+            self.g1((g1 * F::from(3u64)).into_affine(), &mut c);
+            self.g1((g1 * F::from(4u64)).into_affine(), &mut c);
+            self.g2((g2 * F::from(4u64)).into_affine(), &mut c);
+            c.extend(&key);
+            c.extend(partial);
+            let next_at = c.len();
+            c.extend([0u8; 64]);
+            c.extend(0u32.to_le_bytes());
+            let params = [&[1u8, 5][..], b"alice"].concat();
+            c.extend((params.len() as u32).to_le_bytes());
+            c.extend(params);
+            sections.push((SECTION_CONTRIBUTIONS, c));
+
+            let bytes = |sections: &[(u32, Vec<u8>)]| {
+                let mut out = Vec::new();
+                out.extend(MAGIC);
+                out.extend(1u32.to_le_bytes());
+                out.extend((sections.len() as u32).to_le_bytes());
+                for (id, data) in sections {
+                    out.extend(id.to_le_bytes());
+                    out.extend((data.len() as u64).to_le_bytes());
+                    out.extend(data);
+                }
+                out
+            };
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("x.ptau");
+            std::fs::write(&path, bytes(&sections)).unwrap();
+            let mut f = PtauFile::open(&path).unwrap();
+            let contr = f.contributions().unwrap().pop().unwrap();
+            let mut h = Blake2b::new(64);
+            h.update(&contr.response_hash);
+            let mut u = Vec::new();
+            for (id, data) in &sections[1..6] {
+                let g2 = matches!(*id, SECTION_TAU_G2 | SECTION_BETA_G2);
+                let sz = if g2 { 4 * self.n8 } else { 2 * self.n8 };
+                for raw in data.chunks_exact(sz) {
+                    if g2 {
+                        f.g2_to_challenge(raw, &mut u).unwrap();
+                    } else {
+                        f.g1_to_challenge(raw, &mut u).unwrap();
+                    }
+                }
+            }
+            h.update(&u);
+            let next = h.finalize();
+            sections[6].1[next_at..next_at + 64].copy_from_slice(&next);
+            if tamper {
+                let s = &mut sections[1].1;
+                s.truncate(s.len() - 2 * self.n8);
+                self.g1((g1 * F::from(5u64)).into_affine(), s);
+            }
+            bytes(&sections)
+        }
+    }
+
+    #[test]
+    fn imports_ptau_and_checks_powers_and_challenge_hash() {
+        let n8 = Fq::MODULUS.to_bytes_le().len();
+        let fx = Fixture { n8, r: Fq::from(2u64).pow([8 * n8 as u64]) };
+        let tau = F::from(1234567u64);
+        let dir = tempfile::tempdir().unwrap();
+        let ptau = dir.path().join("pot.ptau");
+        std::fs::write(&ptau, fx.build(3, tau, false)).unwrap();
+
+        let out = dir.path().join("srs");
+        let rep = import(&ptau, &out, Some(9), true).unwrap();
+        assert_eq!((rep.header.power, rep.g1_written), (3, 10));
+        assert!(rep.challenge_hash_checked);
+        assert_eq!(rep.contributions[0].name.as_deref(), Some("alice"));
+        let g1 = srs_setup::load_and_validate_g1_srs(&rep.g1_path, 9).unwrap();
+        let tau_g2 = srs_setup::load_and_validate_g2_srs(&rep.g2_path).unwrap();
+        assert_eq!(g1[7], (G1Projective::generator() * tau.pow([7u64])).into_affine());
+        assert_eq!(tau_g2, (<Engine as Pairing>::G2::generator() * tau).into_affine());
+
+        // Last tauG1 point replaced: both the pairing check (when exported)
+        // and the challenge hash (when not) catch it.
+        std::fs::write(&ptau, fx.build(3, tau, true)).unwrap();
+        assert!(matches!(import(&ptau, &out, None, false), Err(SrsSetupError::PairingCheck(_))));
+        assert!(matches!(import(&ptau, &out, Some(9), true), Err(SrsSetupError::CeremonyHash(_))));
+        assert!(import(&ptau, &out, Some(9), false).is_ok());
+
+        std::fs::write(&ptau, b"<?xml version=\"1.0\"?><Error>AccessDenied</Error>").unwrap();
+        assert!(matches!(PtauFile::open(&ptau), Err(SrsSetupError::Deserialize(_))));
+    }
+}
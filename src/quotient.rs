@@ -17,12 +17,16 @@
 //!
 //! - The builders’ **accumulators** (Q tile buffer, MSM temporary storage,
 //!   and any per-tile scratch) are **O(b_blk)**.
-//! - The legacy path that materializes `R`’s coefficients keeps **O(N)** live
-//!   field elements. If you compile/run with the optional blocked-IFFT path
-//!   (`SSZKP_BLOCKED_IFFT=1`), the transform itself is out-of-core; the
-//!   remaining O(N) comes only from holding `R` coefficients for the final
-//!   long-division. (A tape-based in-place long-division can eliminate this;
-//!   its façade remains future-work without changing these APIs.)
+//! - The streamed builders never hold `R` or `Q` as a `Vec`: `R`’s
+//!   coefficients stay in a [`crate::domain::CoeffTape`] and
+//!   [`crate::quotient::QuotientTiles`]
+//!   folds each `Q` tile straight off it (`q_j = Σ_{k≥1} c^{k−1}·r_{j+kN}`),
+//!   reading `O(b_blk)` coefficients at a time.
+//! - When the [`crate::domain::TransformConfig`] spills, that tape is a file,
+//!   so transform, fold-down and commitment are all out-of-core. Without it the
+//!   legacy transform keeps `R`’s **O(N)** coefficients in memory.
+//! - [`crate::quotient::build_and_commit_quotient_r`] is the in-memory
+//!   reference and stays O(N).
 //!
 //! All public behaviors are unchanged; these additions are strictly additive.
 
@@ -31,13 +35,18 @@
 
 use ark_ff::Zero;
 
-use crate::{domain, pcs, F};
+use crate::{
+    domain::{self, CoeffTape, CoeffTileOrder, DomainError, TransformConfig},
+    pcs, F,
+};
 
 /// Errors surfaced by the quotient builder.
 #[derive(Debug, thiserror::Error)]
 pub enum QuotientError {
     #[error(transparent)]
     Domain(#[from] crate::domain::DomainError),
+    #[error(transparent)]
+    Commit(#[from] crate::pcs::AggregatorError),
 }
 
 /// Long-division by X^N − c on **low→high** coefficients.
@@ -63,7 +72,7 @@ pub fn long_divide_xn_minus_c_lo_to_hi(r_lo_to_hi: &[F], n: usize, c: F) -> Vec<
     // Process from high degree down.
     let mut i = r.len().saturating_sub(1);
     loop {
-        if i + 1 <= n {
+        if i < n {
             break; // degree < N → done
         }
         let coeff = r[i];
@@ -80,7 +89,7 @@ pub fn long_divide_xn_minus_c_lo_to_hi(r_lo_to_hi: &[F], n: usize, c: F) -> Vec<
         i -= 1;
     }
 
-    while q.last().map_or(false, |x| x.is_zero()) {
+    while q.last().is_some_and(|x| x.is_zero()) {
         q.pop();
     }
     q
@@ -91,9 +100,9 @@ pub fn long_divide_xn_minus_c_lo_to_hi(r_lo_to_hi: &[F], n: usize, c: F) -> Vec<
 /// This collects `R`’s evaluations into a single `Vec`, performs an IFFT to get
 /// `R`’s coefficients (low→high), runs the long-division by `X^N−c`, and streams
 /// `Q` to the PCS in moderately sized tiles.
-pub fn build_and_commit_quotient_r<'a>(
+pub fn build_and_commit_quotient_r(
     domain: &domain::Domain,
-    pcs: &'a pcs::PcsParams,
+    pcs: &pcs::PcsParams,
     _alpha: F,
     _beta: F,
     _gamma: F,
@@ -121,9 +130,9 @@ pub fn build_and_commit_quotient_r<'a>(
 }
 
 /// Back-compat wrapper (panics on error).
-pub fn build_and_commit_quotient<'a>(
+pub fn build_and_commit_quotient(
     domain: &domain::Domain,
-    pcs: &'a pcs::PcsParams,
+    pcs: &pcs::PcsParams,
     _alpha: F,
     _beta: F,
     _gamma: F,
@@ -133,203 +142,426 @@ pub fn build_and_commit_quotient<'a>(
         .expect("quotient build failed")
 }
 
-/// **Streamed** builder (Result-returning; public API is stable).
+/// `Q = R div (X^N − c)` as coefficient tiles folded directly off `R`’s
+/// [`CoeffTape`], never holding more than one tile of `Q` and one of `R`.
 ///
-/// Uses the [`domain::BlockedIfft`] façade to produce `R`’s coefficients in
-/// tiles, then performs the same long-division and streams `Q` to the PCS.
-/// Accumulator memory is **O(b_blk)**.
-pub fn build_and_commit_quotient_streamed_r<'a>(
+/// Coefficient `q_j` is `Σ_{k≥1} c^{k−1}·r_{j+kN}`, so the tile `[j, j+b)` is a
+/// Horner sum over the `R` tiles `[j+kN, j+kN+b)`. High zero coefficients are
+/// trimmed exactly as [`long_divide_xn_minus_c_lo_to_hi`] does, so the tiles
+/// concatenate (in `order`) to its output.
+///
+/// A failed read of `R`'s tape ends the iteration early; call
+/// [`QuotientTiles::check`] after draining to tell that from the end of `Q`.
+pub struct QuotientTiles {
+    r: CoeffTape,
+    n: usize,
+    c: F,
+    tile: usize,
+    order: CoeffTileOrder,
+    /// Trimmed length of `Q`, found on the first call to `next`.
+    q_len: Option<usize>,
+    /// Low→high: next low index. High→low: current high end (exclusive).
+    cursor: usize,
+    /// First tape read error; iteration stops once it is set.
+    error: Option<DomainError>,
+}
+
+impl QuotientTiles {
+    /// Divide the polynomial on `r` by `X^n − c`, emitting tiles of at most
+    /// `tile` coefficients in `order`.
+    pub fn new(r: CoeffTape, n: usize, c: F, tile: usize, order: CoeffTileOrder) -> Self {
+        assert!(n > 0 && tile > 0, "n and tile must be positive");
+        Self { r, n, c, tile, order, q_len: None, cursor: 0, error: None }
+    }
+
+    /// The tape read error that cut the tiles short, if any.
+    pub fn check(&mut self) -> Result<(), DomainError> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+
+    /// `q_lo .. q_hi` (low→high) by Horner over the stride-`N` slices of `R`.
+    fn fold_range(&mut self, lo: usize, hi: usize) -> Result<Vec<F>, DomainError> {
+        let len = hi - lo;
+        let mut acc = vec![F::zero(); len];
+        let kmax = (self.r.len() - 1 - lo) / self.n;
+        for k in (1..=kmax).rev() {
+            for a in acc.iter_mut() {
+                *a *= self.c;
+            }
+            let r = self
+                .r
+                .read_range(lo + k * self.n, len)
+                .map_err(|e| DomainError::Spill(e.to_string()))?;
+            for (a, x) in acc.iter_mut().zip(&r) {
+                *a += x;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Scan down from the top for the highest non-zero `q_j`.
+    fn trimmed_len(&mut self) -> Result<usize, DomainError> {
+        let mut hi = self.r.len().saturating_sub(self.n);
+        while hi > 0 {
+            let lo = hi.saturating_sub(self.tile);
+            let q = self.fold_range(lo, hi)?;
+            if let Some(p) = q.iter().rposition(|x| !x.is_zero()) {
+                return Ok(lo + p + 1);
+            }
+            hi = lo;
+        }
+        Ok(0)
+    }
+
+    /// Stash `e` and end the iteration.
+    fn fail(&mut self, e: DomainError) -> Option<Vec<F>> {
+        self.error = Some(e);
+        self.q_len = Some(0);
+        self.cursor = 0;
+        None
+    }
+}
+
+impl Iterator for QuotientTiles {
+    type Item = Vec<F>;
+
+    fn next(&mut self) -> Option<Vec<F>> {
+        if self.error.is_some() {
+            return None;
+        }
+        let q_len = match self.q_len {
+            Some(l) => l,
+            None => {
+                let l = match self.trimmed_len() {
+                    Ok(l) => l,
+                    Err(e) => return self.fail(e),
+                };
+                self.q_len = Some(l);
+                if self.order == CoeffTileOrder::HighToLow {
+                    self.cursor = l;
+                }
+                l
+            }
+        };
+        match self.order {
+            CoeffTileOrder::LowToHigh => {
+                if self.cursor >= q_len {
+                    return None;
+                }
+                let lo = self.cursor;
+                let hi = (lo + self.tile).min(q_len);
+                self.cursor = hi;
+                match self.fold_range(lo, hi) {
+                    Ok(q) => Some(q),
+                    Err(e) => self.fail(e),
+                }
+            }
+            CoeffTileOrder::HighToLow => {
+                if self.cursor == 0 {
+                    return None;
+                }
+                let hi = self.cursor;
+                let lo = hi.saturating_sub(self.tile);
+                self.cursor = lo;
+                match self.fold_range(lo, hi) {
+                    Ok(mut q) => {
+                        q.reverse();
+                        Some(q)
+                    }
+                    Err(e) => self.fail(e),
+                }
+            }
+        }
+    }
+}
+
+/// Run the residual stream (truncated/padded to `N`) through
+/// [`domain::BlockedIfft`] in `b_blk` chunks and keep `R`’s coefficients on
+/// its tape.
+fn residual_coeffs(
     domain: &domain::Domain,
-    pcs: &'a pcs::PcsParams,
-    _alpha: F,
-    _beta: F,
-    _gamma: F,
     b_blk: usize,
+    transform: &TransformConfig,
     stream_r_rows: impl Iterator<Item = F>,
-) -> Result<pcs::Commitment, QuotientError> {
-    // 1) Produce R coefficients (low→high) using BlockedIfft façade.
-    let mut bifft = domain::BlockedIfft::new(domain, b_blk);
-
-    // Feed time-ordered residual evaluations into the façade in b_blk-sized chunks.
+) -> Result<CoeffTape, DomainError> {
+    let mut bifft = domain::BlockedIfft::try_with_config(domain, b_blk, transform)?;
     let mut buf: Vec<F> = Vec::with_capacity(b_blk);
-    for r in stream_r_rows {
+    for r in stream_r_rows.take(domain.n) {
         buf.push(r);
         if buf.len() == b_blk {
-            bifft.feed_eval_block(&buf);
+            bifft.feed_eval_block(&buf)?;
             buf.clear();
         }
     }
     if !buf.is_empty() {
-        bifft.feed_eval_block(&buf);
-    }
-
-    // Collect the **low→high** coefficient tiles into a single vector (behavior-preserving).
-    let mut r_coeffs_lo_to_hi: Vec<F> = Vec::with_capacity(domain.n);
-    for tile in bifft.finish_low_to_high() {
-        r_coeffs_lo_to_hi.extend_from_slice(&tile);
-    }
-    if r_coeffs_lo_to_hi.len() < domain.n {
-        r_coeffs_lo_to_hi.resize(domain.n, F::zero());
-    } else if r_coeffs_lo_to_hi.len() > domain.n {
-        r_coeffs_lo_to_hi.truncate(domain.n);
+        bifft.feed_eval_block(&buf)?;
     }
+    bifft.finish_coeffs()
+}
 
-    // 2) Fold-down by X^N − c to obtain Q (low→high).
-    let q_coeffs_lo_to_hi = long_divide_xn_minus_c_lo_to_hi(&r_coeffs_lo_to_hi, domain.n, domain.zh_c);
+/// **Streamed** builder (Result-returning; public API is stable).
+///
+/// Uses the [`domain::BlockedIfft`] façade to produce `R`’s coefficients,
+/// folds `Q` off them tile by tile and streams it to the PCS.
+/// Accumulator memory is **O(b_blk)**.
+pub fn build_and_commit_quotient_streamed_r(
+    domain: &domain::Domain,
+    pcs: &pcs::PcsParams,
+    _alpha: F,
+    _beta: F,
+    _gamma: F,
+    b_blk: usize,
+    stream_r_rows: impl Iterator<Item = F>,
+) -> Result<pcs::Commitment, QuotientError> {
+    let transform = TransformConfig::from_env();
+    build_and_commit_quotient_streamed_with(&pcs::Kzg, domain, pcs, b_blk, &transform, stream_r_rows)
+}
 
-    // 3) Stream Q coefficients to PCS as tiles (low→high).
-    let mut agg = pcs::Aggregator::new(pcs, "Q");
-    const TILE: usize = 1 << 12;
-    for chunk in q_coeffs_lo_to_hi.chunks(TILE) {
-        agg.add_block_coeffs(chunk);
-    }
-    Ok(agg.finalize())
+/// Backend-generic form of [`build_and_commit_quotient_streamed_r`]: `Q` is
+/// committed through `backend` as **low→high** tiles of `b_blk`, with the
+/// IFFT of `R` run as `transform` says.
+pub fn build_and_commit_quotient_streamed_with<P: pcs::PolynomialCommitment>(
+    backend: &P,
+    domain: &domain::Domain,
+    pcs: &pcs::PcsParams,
+    b_blk: usize,
+    transform: &TransformConfig,
+    stream_r_rows: impl Iterator<Item = F>,
+) -> Result<P::Commitment, QuotientError> {
+    let r = residual_coeffs(domain, b_blk, transform, stream_r_rows)?;
+    let mut tiles = QuotientTiles::new(r, domain.n, domain.zh_c, b_blk, CoeffTileOrder::LowToHigh);
+    let cm = backend.commit_coeff_tiles(pcs, "Q", tiles.by_ref())?;
+    tiles.check()?;
+    Ok(cm)
 }
 
 /// **Tile-native** builder over the residual stream (high→low emission).
 ///
-/// This variant avoids allocating a `Vec<Q>` by performing the long-division
-/// *in place* on a local `R` buffer and emitting **high→low tiles** directly
-/// into the PCS aggregator. The PCS ingests these tiles with
+/// `Q` tiles are folded off `R`’s coefficient tape and emitted **high→low**
+/// directly into the PCS aggregator, which ingests them with
 /// `CoeffTileOrder::HighToLow`.
 ///
-/// Memory:
-/// - `R` coefficients: O(N) (legacy in-mem path).
-/// - Q tile buffer + MSM scratch: **O(b_blk)**.
-///
-/// Note: when `SSZKP_BLOCKED_IFFT=1`, the IFFT itself is out-of-core; this
-/// function still holds `R` locally to perform the fold-down. A future taped
-/// fold-down would remove that O(N) as well without changing this API.
-pub fn build_and_commit_quotient_streamed_tile_native_r<'a>(
+/// Memory: Q tile buffer + MSM scratch are **O(b_blk)**; `R` is O(N) only on
+/// the legacy in-memory IFFT path (see the module docs).
+pub fn build_and_commit_quotient_streamed_tile_native_r(
     domain: &domain::Domain,
-    pcs: &'a pcs::PcsParams,
+    pcs: &pcs::PcsParams,
     _alpha: F,
     _beta: F,
     _gamma: F,
     b_blk: usize,
     stream_r_rows: impl Iterator<Item = F>,
 ) -> Result<pcs::Commitment, QuotientError> {
-    // IFFT to get R coefficients (low→high), as in the streamed builder.
-    let mut bifft = domain::BlockedIfft::new(domain, b_blk);
-    let mut buf: Vec<F> = Vec::with_capacity(b_blk);
-    for r in stream_r_rows {
-        buf.push(r);
-        if buf.len() == b_blk {
-            bifft.feed_eval_block(&buf);
-            buf.clear();
-        }
-    }
-    if !buf.is_empty() {
-        bifft.feed_eval_block(&buf);
-    }
-    let mut r_coeffs: Vec<F> = Vec::with_capacity(domain.n);
-    for tile in bifft.finish_low_to_high() {
-        r_coeffs.extend_from_slice(&tile);
+    let r = residual_coeffs(domain, b_blk, &TransformConfig::from_env(), stream_r_rows)?;
+    let mut agg = pcs::Aggregator::new(pcs, "Q");
+    let mut tiles = QuotientTiles::new(r, domain.n, domain.zh_c, b_blk, CoeffTileOrder::HighToLow);
+    for tile in tiles.by_ref() {
+        agg.add_coeff_tile(&tile, CoeffTileOrder::HighToLow)?;
     }
-    if r_coeffs.len() < domain.n { r_coeffs.resize(domain.n, F::zero()); }
-    else if r_coeffs.len() > domain.n { r_coeffs.truncate(domain.n); }
+    tiles.check()?;
+    Ok(agg.finalize())
+}
 
-    // Tile-native emission: iterate i from high→low and emit q_{i-N} on the fly.
-    use crate::domain::CoeffTileOrder;
-    let mut agg = pcs::Aggregator::new(pcs, "Q");
-    let mut q_tile_hi_to_lo: Vec<F> = Vec::with_capacity(b_blk);
+/// Helper for openings: return **Q coefficient tiles high→low** from a residual
+/// time stream. The iterator yields contiguous **high→low** tiles, each of size
+/// at most `b_blk`, folded lazily off `R`’s coefficient tape (produced as
+/// `transform` says). Call [`QuotientTiles::check`] once it is drained.
+pub fn stream_q_coeff_tiles_hi_to_lo_from_r_stream(
+    domain: &domain::Domain,
+    b_blk: usize,
+    transform: &TransformConfig,
+    stream_r_rows: impl Iterator<Item = F>,
+) -> Result<QuotientTiles, QuotientError> {
+    let r = residual_coeffs(domain, b_blk, transform, stream_r_rows)?;
+    Ok(QuotientTiles::new(r, domain.n, domain.zh_c, b_blk, CoeffTileOrder::HighToLow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
 
-    if r_coeffs.len() > domain.n {
-        r_coeffs.truncate(domain.n);
+    fn tape_of(coeffs: &[F], tile: usize) -> CoeffTape {
+        CoeffTape::from_tiles(coeffs.chunks(tile).map(|c| c.to_vec())).unwrap()
     }
-    // i runs [len-1 .. N]
-    let mut i = r_coeffs.len().saturating_sub(1);
-    loop {
-        if i + 1 <= domain.n { break; } // done when degree < N
 
-        let coeff = r_coeffs[i];
-        if !coeff.is_zero() {
-            // Emit q_{i-N} (in high→low order).
-            q_tile_hi_to_lo.push(coeff);
-            // Apply fold-down effect to r_{i-N}.
-            let qi = i - domain.n;
-            r_coeffs[qi] += domain.zh_c * coeff;
-            // clear (not strictly required since we won’t touch r[i] again)
-            // r_coeffs[i] = F::zero();
-        }
+    #[test]
+    fn taped_fold_down_matches_long_division() {
+        let mut rng = StdRng::seed_from_u64(41);
+        let (n, tile) = (16, 5);
+        let c = F::rand(&mut rng);
+        for len in [n - 3, n, 2 * n + 5, 3 * n + 1] {
+            let mut r: Vec<F> = (0..len).map(|_| F::rand(&mut rng)).collect();
+            // Zero the top so the high-end trim is exercised.
+            if len > n + 2 {
+                r[len - 1] = F::zero();
+                r[len - 2] = F::zero();
+            }
+            let want = long_divide_xn_minus_c_lo_to_hi(&r, n, c);
 
-        // Flush tile if full.
-        if q_tile_hi_to_lo.len() == b_blk {
-            // Aggregator can ingest **high→low** tiles.
-            agg.add_coeff_tile(&q_tile_hi_to_lo, CoeffTileOrder::HighToLow)
-                .expect("aggregator add tile");
-            q_tile_hi_to_lo.clear();
+            let lo: Vec<F> =
+                QuotientTiles::new(tape_of(&r, 7), n, c, tile, CoeffTileOrder::LowToHigh)
+                    .flatten()
+                    .collect();
+            assert_eq!(lo, want, "low→high, len {len}");
+
+            let tiles: Vec<Vec<F>> =
+                QuotientTiles::new(tape_of(&r, 7), n, c, tile, CoeffTileOrder::HighToLow).collect();
+            assert!(tiles.iter().all(|t| t.len() <= tile));
+            let mut hi: Vec<F> = tiles.concat();
+            hi.reverse();
+            assert_eq!(hi, want, "high→low, len {len}");
         }
+    }
 
-        if i == 0 { break; }
-        i -= 1;
+    #[test]
+    fn spill_failure_is_an_error() {
+        let n = 16;
+        let domain = domain::Domain::new_with_c(n, domain::root_of_unity(n).unwrap(), F::from(3u64));
+        let params = pcs::PcsParams { max_degree: 2 * n, basis: pcs::Basis::Coefficient, srs: None };
+        // A spill dir below a regular file can be neither created nor used.
+        let file = std::env::temp_dir().join(format!("sszkp-not-a-dir-{}", std::process::id()));
+        std::fs::write(&file, b"").unwrap();
+        let transform = TransformConfig::default()
+            .with_backend(domain::TransformBackend::Tape)
+            .with_spill_dir(file.join("spill"));
+        let rows = (0..n).map(|i| F::from(i as u64));
+        let res = build_and_commit_quotient_streamed_with(&TileTally::default(), &domain, &params, 4, &transform, rows);
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(res, Err(QuotientError::Domain(DomainError::Spill(_)))), "{res:?}");
     }
 
-    // Flush any remaining Q coeffs in the last (short) tile.
-    if !q_tile_hi_to_lo.is_empty() {
-        agg.add_coeff_tile(&q_tile_hi_to_lo, CoeffTileOrder::HighToLow)
-            .expect("aggregator add tail tile");
+    /// Test backend that sums and counts the `Q` tiles and coefficients it
+    /// is handed, keeping only O(tile) of them resident.
+    #[derive(Default)]
+    struct TileTally {
+        tiles: std::cell::Cell<usize>,
+        coeffs: std::cell::Cell<usize>,
     }
 
-    Ok(agg.finalize())
-}
+    impl pcs::PolynomialCommitment for TileTally {
+        type Commitment = F;
+        type Proof = F;
+        const KIND: pcs::PcsKind = pcs::PcsKind::Kzg;
 
-/// Helper for openings: return **Q coefficient tiles high→low** from a residual
-/// time stream. The iterator yields contiguous **high→low** tiles, each of size
-/// at most `b_blk`.
-pub fn stream_q_coeff_tiles_hi_to_lo_from_r_stream<'a>(
-    domain: &domain::Domain,
-    b_blk: usize,
-    stream_r_rows: impl Iterator<Item = F> + 'a,
-) -> impl Iterator<Item = Vec<F>> + 'a {
-    // 1) Use BlockedIfft to derive **low→high** coefficient tiles for R.
-    let mut bifft = domain::BlockedIfft::new(domain, b_blk);
+        fn srs_digests(&self, _pcs: &pcs::PcsParams) -> Result<([u8; 32], [u8; 32]), pcs::AggregatorError> {
+            Ok(([0; 32], [0; 32]))
+        }
 
-    // Feed time-ordered residual evaluations (chunked).
-    let mut buf: Vec<F> = Vec::with_capacity(b_blk);
-    let mut it = stream_r_rows;
-    while let Some(x) = it.next() {
-        buf.push(x);
-        if buf.len() == b_blk {
-            bifft.feed_eval_block(&buf);
-            buf.clear();
+        fn commit_coeff_tiles<I>(
+            &self,
+            _pcs: &pcs::PcsParams,
+            _poly_id: &'static str,
+            tiles: I,
+        ) -> Result<F, pcs::AggregatorError>
+        where
+            I: IntoIterator<Item = Vec<F>>,
+        {
+            let mut sum = F::zero();
+            for t in tiles {
+                self.tiles.set(self.tiles.get() + 1);
+                self.coeffs.set(self.coeffs.get() + t.len());
+                sum += t.iter().sum::<F>();
+            }
+            Ok(sum)
         }
-    }
-    if !buf.is_empty() {
-        bifft.feed_eval_block(&buf);
-    }
 
-    // Gather R coeffs (low→high) exactly to length N.
-    let mut r_coeffs_lo_to_hi: Vec<F> = Vec::with_capacity(domain.n);
-    for tile in bifft.finish_low_to_high() {
-        r_coeffs_lo_to_hi.extend_from_slice(&tile);
-    }
-    if r_coeffs_lo_to_hi.len() < domain.n {
-        r_coeffs_lo_to_hi.resize(domain.n, F::zero());
-    } else if r_coeffs_lo_to_hi.len() > domain.n {
-        r_coeffs_lo_to_hi.truncate(domain.n);
+        fn open_hi_to_lo(
+            &self,
+            _pcs: &pcs::PcsParams,
+            _fs_hash: crate::transcript::FsHash,
+            _commitment: &F,
+            _stream_coeff_hi_to_lo: &mut pcs::CoeffStreamHiToLo<'_>,
+            _points: &[F],
+        ) -> Result<Vec<F>, pcs::AggregatorError> {
+            unreachable!("commit-only test backend")
+        }
+
+        fn opened_value(proof: &F) -> F {
+            *proof
+        }
+
+        fn verify(
+            &self,
+            _pcs: &pcs::PcsParams,
+            _fs_hash: crate::transcript::FsHash,
+            _commitments: &[F],
+            _points: &[F],
+            _claimed_evals: &[F],
+            _proofs: &[F],
+        ) -> Result<(), pcs::VerifyError> {
+            unreachable!("commit-only test backend")
+        }
     }
 
-    // 2) Fold-down → Q (low→high), then reverse to high→low for emission.
-    let mut q_hi_to_lo: Vec<F> =
-        long_divide_xn_minus_c_lo_to_hi(&r_coeffs_lo_to_hi, domain.n, domain.zh_c);
-    q_hi_to_lo.reverse();
+    const RSS_PROBE_ENV: &str = "SSZKP_TEST_QUOTIENT_RSS_N";
 
-    // 3) Yield high→low tiles of size ≤ b_blk.
-    struct QRevIter {
-        coeffs_hi_to_lo: Vec<F>,
-        idx: usize,
-        tile: usize,
+    /// Child half of `taped_fold_down_has_bounded_peak_rss`: streams a
+    /// lazily generated residual over `4N` points through the `Tape`
+    /// transform (the public builders take only `N` evaluations, so their
+    /// `R` has degree `< N` and `Q` is empty), folds `Q = R div (Xᴺ − c)` off
+    /// the tape into a commitment, and prints `VmHWM`.
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore = "spawned by taped_fold_down_has_bounded_peak_rss"]
+    fn rss_probe() {
+        let n: usize = std::env::var(RSS_PROBE_ENV).unwrap().parse().unwrap();
+        let tile = 1usize << (n.trailing_zeros() / 2);
+        let c = F::from(3u64);
+
+        let m = 4 * n;
+        let r_domain = domain::Domain::new_with_c(m, domain::root_of_unity(m).unwrap(), F::from(1u64));
+        let transform = TransformConfig::default().with_backend(domain::TransformBackend::Tape);
+        let rows = (0..m).map(|i| F::from(i as u64 + 1));
+        let r = residual_coeffs(&r_domain, tile, &transform, rows).unwrap();
+
+        let params = pcs::PcsParams { max_degree: 3 * n, basis: pcs::Basis::Coefficient, srs: None };
+        let tally = TileTally::default();
+        let mut tiles = QuotientTiles::new(r, n, c, tile, CoeffTileOrder::LowToHigh);
+        let sum = pcs::PolynomialCommitment::commit_coeff_tiles(&tally, &params, "Q", tiles.by_ref()).unwrap();
+        tiles.check().unwrap();
+        // deg R = 4N − 1 (the ramp's interpolant has a non-zero top
+        // coefficient), so Q has 3N coefficients in full tiles.
+        assert_eq!(tally.coeffs.get(), 3 * n);
+        assert_eq!(tally.tiles.get(), 3 * n / tile);
+        assert!(!sum.is_zero());
+
+        let status = std::fs::read_to_string("/proc/self/status").unwrap();
+        let hwm = status.lines().find(|l| l.starts_with("VmHWM:")).unwrap();
+        println!("RSS_PROBE {}", hwm.split_whitespace().nth(1).unwrap());
     }
-    impl Iterator for QRevIter {
-        type Item = Vec<F>;
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.idx >= self.coeffs_hi_to_lo.len() { return None; }
-            let end = (self.idx + self.tile).min(self.coeffs_hi_to_lo.len());
-            let out = self.coeffs_hi_to_lo[self.idx..end].to_vec();
-            self.idx = end;
-            Some(out)
-        }
+
+    #[cfg(target_os = "linux")]
+    fn probe_peak_kib(n: usize) -> u64 {
+        let out = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["quotient::tests::rss_probe", "--exact", "--ignored", "--nocapture"])
+            .env(RSS_PROBE_ENV, n.to_string())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(out.status.success(), "probe failed: {stdout}");
+        stdout
+            .lines()
+            .find_map(|l| l.split_once("RSS_PROBE ").map(|(_, v)| v))
+            .expect("probe output")
+            .trim()
+            .parse()
+            .unwrap()
+    }
+
+    /// Growing `N` 32× must not grow peak RSS by anything close to the size of
+    /// a materialized `R` (`4N` field elements = 16 MiB at `N = 2^17`) through
+    /// the taped transform, fold-down and commitment.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn taped_fold_down_has_bounded_peak_rss() {
+        let small = probe_peak_kib(1 << 12);
+        let large = probe_peak_kib(1 << 17);
+        let materialized_kib = (4 * (1u64 << 17) * 32) / 1024;
+        assert!(
+            large.saturating_sub(small) < materialized_kib / 4,
+            "peak RSS grew {small} → {large} KiB (materialized R ≈ {materialized_kib} KiB)"
+        );
     }
-    QRevIter { coeffs_hi_to_lo: q_hi_to_lo, idx: 0, tile: b_blk }
 }
@@ -15,6 +15,9 @@
 //!   Blocked-IFFT-powered coefficient tiles (no global materialization).
 //! - **Algebra check is enforced:** A non-zero residual triggers
 //!   `VerifySchedError::Algebra` (hard error).
+//! - **Backend-agnostic:** `Prover`/`Verifier` are generic over
+//!   [`PolynomialCommitment`] (KZG by default). The scheduler only hands the
+//!   backend coefficient tiles and never names a concrete PCS function.
 //!
 //! ## Opening order (test-invariant)
 //! We preserve the opening order required by the existing tests and by the
@@ -56,23 +59,25 @@
 use ark_ff::{Field, One, Zero};
 
 use crate::{
-    air::{self, BlockResult, ResidualCfg},
-    pcs::{self, Aggregator, Basis, PcsParams, VerifyError as PcsVerifyError},
+    air::{self, AirError, BlockResult, ResidualCfg},
+    pcs::{
+        self, AggregatorError, Basis, Kzg, OpeningBatch, PcsParams, PolynomialCommitment,
+        VerifyError as PcsVerifyError,
+    },
     perm_lookup::{
         absorb_block_lookup, absorb_block_lookup_with_challenges, absorb_block_perm,
         emit_z_column_block, LookupAcc, PermAcc,
     },
     quotient::{
-        build_and_commit_quotient_streamed_r, stream_q_coeff_tiles_hi_to_lo_from_r_stream,
+        build_and_commit_quotient_streamed_with, stream_q_coeff_tiles_hi_to_lo_from_r_stream,
         QuotientError,
     },
-    stream::{blocks, BlockIdx, RegIdx, RowIdx, Restreamer},
-    transcript::{FsLabel, Transcript},
+    stream::{blocks, BlockIdx, LatchedRestreamer, RegIdx, RowIdx, Restreamer, TryRestreamer, WitnessError},
+    transcript::{FsHash, FsLabel, Transcript},
+    transcript_trace::TranscriptTrace,
     F, Proof, ProofHeader, ProveParams, VerifyParams,
 };
 
-type PcsCommit = pcs::Commitment;
-
 // ============================================================================
 // Two-tile prefetch helper (ping–pong buffers)
 // ============================================================================
@@ -108,39 +113,103 @@ impl<T> TwoTileBuf<T> {
     }
 }
 
-/// Consume an iterator of coefficient **tiles** with a one-tile **prefetch**.
-/// This keeps `current` and `next` tiles alive simultaneously.
-fn consume_tiles_with_prefetch(
-    mut tiles: impl Iterator<Item = Vec<F>>,
-    mut consume: impl FnMut(&[F]),
-) {
-    // Prime the pipeline with one tile.
-    let mut current = match tiles.next() {
-        Some(t) => t,
-        None => return,
-    };
-    loop {
-        // Prefetch the next tile *before* we MSM the current tile.
+/// [`air::eval_block_r`] over witness rows starting at trace row `start`. A
+/// row of the wrong width is a [`WitnessError::Width`] at its trace index.
+fn eval_rows(
+    air: &air::AirSpec,
+    m: RegIdx,
+    t: BlockIdx,
+    boundary: &[F],
+    start: RowIdx,
+    rows: impl Iterator<Item = air::Row>,
+) -> Result<BlockResult, WitnessError> {
+    air::eval_block_r(air, m, t, boundary, rows).map_err(|e| row_error(e, start.0))
+}
+
+/// Rebase an AIR row error found `start` rows into the trace.
+fn row_error(e: AirError, start: usize) -> WitnessError {
+    match e {
+        AirError::BadRowLen { row, expected, got } => WitnessError::Width { row: start + row, expected, got },
+        // The prover always seeds `k` boundary registers and asks for `m < k`.
+        e => unreachable!("prover AIR invariant broken: {e}"),
+    }
+}
+
+/// Iterator adapter over coefficient **tiles** with a one-tile **prefetch**.
+/// Handing out tile `t` first pulls tile `t+1` from the producer, so `current`
+/// and `next` tiles are alive simultaneously while the PCS consumes `t`.
+struct PrefetchTiles<I: Iterator<Item = Vec<F>>> {
+    tiles: I,
+    next: Option<Vec<F>>,
+}
+
+impl<I: Iterator<Item = Vec<F>>> PrefetchTiles<I> {
+    fn new(mut tiles: I) -> Self {
+        // Prime the pipeline with one tile.
         let next = tiles.next();
-        // MSM / consume tile `current`.
-        consume(&current);
-        // Move to the next tile, or finish.
-        match next {
-            Some(n) => current = n,
-            None => break,
-        }
+        Self { tiles, next }
+    }
+}
+
+impl<I: Iterator<Item = Vec<F>>> Iterator for PrefetchTiles<I> {
+    type Item = Vec<F>;
+    fn next(&mut self) -> Option<Vec<F>> {
+        let current = self.next.take()?;
+        // Prefetch the next tile *before* the caller MSMs the current tile.
+        self.next = self.tiles.next();
+        Some(current)
     }
 }
 
 // ============================================================================
 
-pub struct Prover<'a> {
+/// Streaming prover, generic over the commitment backend (KZG by default).
+pub struct Prover<'a, P: PolynomialCommitment = Kzg> {
     pub air: &'a air::AirSpec,
     pub params: &'a ProveParams,
+    pub pcs: P,
+    /// Transcript hash recorded in the header (BLAKE3 unless overridden).
+    pub fs_hash: FsHash,
 }
 
-pub struct Verifier<'a> {
+/// Verifier counterpart of [`Prover`]; must use the same backend.
+pub struct Verifier<'a, P: PolynomialCommitment = Kzg> {
     pub params: &'a VerifyParams,
+    pub pcs: P,
+}
+
+impl<'a> Prover<'a, Kzg> {
+    /// KZG prover over the loaded global SRS.
+    pub fn new(air: &'a air::AirSpec, params: &'a ProveParams) -> Self {
+        Self { air, params, pcs: Kzg, fs_hash: FsHash::Blake3 }
+    }
+}
+
+impl<'a, P: PolynomialCommitment> Prover<'a, P> {
+    /// Prover over an explicit commitment backend.
+    pub fn with_pcs(air: &'a air::AirSpec, params: &'a ProveParams, pcs: P) -> Self {
+        Self { air, params, pcs, fs_hash: FsHash::Blake3 }
+    }
+
+    /// Drive the transcript with `fs_hash` (e.g. Keccak-256 for EVM verification).
+    pub fn with_fs_hash(mut self, fs_hash: FsHash) -> Self {
+        self.fs_hash = fs_hash;
+        self
+    }
+}
+
+impl<'a> Verifier<'a, Kzg> {
+    /// KZG verifier over the loaded global SRS.
+    pub fn new(params: &'a VerifyParams) -> Self {
+        Self { params, pcs: Kzg }
+    }
+}
+
+impl<'a, P: PolynomialCommitment> Verifier<'a, P> {
+    /// Verifier over an explicit commitment backend.
+    pub fn with_pcs(params: &'a VerifyParams, pcs: P) -> Self {
+        Self { params, pcs }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -149,6 +218,12 @@ pub enum ProveError {
     Params(&'static str),
     #[error(transparent)]
     Quotient(#[from] QuotientError),
+    #[error(transparent)]
+    Commit(#[from] AggregatorError),
+    #[error(transparent)]
+    Transform(#[from] crate::domain::DomainError),
+    #[error("witness source failed: {0}")]
+    Witness(#[from] WitnessError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -157,60 +232,108 @@ pub enum VerifySchedError {
     TranscriptMismatch,
     #[error("algebraic residual check failed at ζ")]
     Algebra,
+    #[error("proof uses the {got:?} commitment backend, verifier expects {expected:?}")]
+    Backend { expected: pcs::PcsKind, got: pcs::PcsKind },
+    #[error("proof was produced on curve {got:?}, verifier is built for {expected:?}")]
+    Curve { expected: crate::curve::CurveId, got: crate::curve::CurveId },
+    #[error("transcript hash {0:?} is not available in this build")]
+    TranscriptHash(FsHash),
     #[error(transparent)]
     Pcs(#[from] PcsVerifyError),
 }
 
-impl<'a> Prover<'a> {
-    /// Commit to a time-streamed polynomial by converting to **coefficient tiles**
-    /// (via blocked IFFT under the hood) and aggregating directly in the PCS.
+impl<'a, P: PolynomialCommitment> Prover<'a, P> {
+    /// Commit to a time-streamed polynomial: directly when the backend has an
+    /// evaluation-basis key for the domain (KZG + [`pcs::LagrangeKey`]),
+    /// otherwise by converting to **coefficient tiles** (blocked IFFT) and
+    /// aggregating in the PCS.
     #[inline]
     fn commit_from_time_stream<I: Iterator<Item = F>>(
         &self,
         poly_id: &'static str,
         time_vals: I,
         pcs_degree_ctx: &PcsParams,
-    ) -> PcsCommit {
+    ) -> Result<P::Commitment, ProveError> {
+        let domain = &self.params.domain;
+        if self.pcs.can_commit_evals(pcs_degree_ctx, domain) {
+            return Ok(self.pcs.commit_evals(pcs_degree_ctx, domain, poly_id, time_vals)?);
+        }
         // Commit from **coefficient** tiles regardless of the time/eval basis at the API level.
         let pcs_for_commit = PcsParams { basis: Basis::Coefficient, ..pcs_degree_ctx.clone() };
-        let tiles = crate::domain::ifft_time_stream_to_coeff_tiles(
+        let mut tiles = crate::domain::ifft_time_stream_to_coeff_tiles_with(
             &self.params.domain,
             self.params.b_blk,
+            &self.params.transform,
             time_vals,
-        );
+        )?;
 
-        let mut agg = Aggregator::new(&pcs_for_commit, poly_id);
-        consume_tiles_with_prefetch(tiles, |tile| agg.add_block_coeffs(tile));
-        agg.finalize()
+        let cm = self.pcs.commit_coeff_tiles(&pcs_for_commit, poly_id, PrefetchTiles::new(tiles.by_ref()));
+        tiles.check()?;
+        Ok(cm?)
     }
 
-    fn build_header(&self) -> ProofHeader {
-        ProofHeader {
-            version: 1,
+    fn build_header(&self) -> Result<ProofHeader, ProveError> {
+        let (srs_g1_digest, srs_g2_digest) = self.pcs.srs_digests(&self.params.pcs_wires)?;
+        Ok(ProofHeader {
+            version: 2,
+            curve: crate::curve::CURVE_ID,
             domain_n: self.params.domain.n as u32,
             domain_omega: self.params.domain.omega,
             zh_c: self.params.domain.zh_c,
             k: self.air.k as u16,
             basis_wires: self.params.pcs_wires.basis,
-            srs_g1_digest: pcs::srs_g1_digest(),
-            srs_g2_digest: pcs::srs_g2_digest(),
-        }
+            pcs: P::KIND,
+            fs_hash: self.fs_hash,
+            srs_g1_digest,
+            srs_g2_digest,
+        })
     }
 
+    /// Prove from any (possibly fallible) row source. Source errors abort the
+    /// proof as [`ProveError::Witness`] at the end of the pass that hit them.
     pub fn prove_with_restreamer(
         &self,
-        rs: &impl Restreamer<Item = air::Row>,
-    ) -> Result<Proof, ProveError> {
-        let t_rows = rs.len_rows();
+        rs: &impl TryRestreamer<Item = air::Row>,
+    ) -> Result<Proof<P>, ProveError> {
+        self.prove_recording(rs, false).map(|(proof, _)| proof)
+    }
+
+    /// Like [`Self::prove_with_restreamer`], also returning the Fiat–Shamir trace.
+    pub fn prove_with_trace(
+        &self,
+        rs: &impl TryRestreamer<Item = air::Row>,
+    ) -> Result<(Proof<P>, TranscriptTrace), ProveError> {
+        self.prove_recording(rs, true)
+            .map(|(proof, trace)| (proof, trace.expect("recording was enabled")))
+    }
+
+    fn prove_recording(
+        &self,
+        rs: &impl TryRestreamer<Item = air::Row>,
+        record: bool,
+    ) -> Result<(Proof<P>, Option<TranscriptTrace>), ProveError> {
+        // Every pass below reads through the latch; check it before using a
+        // pass's output so a failed read never reaches the transcript.
+        let rows = LatchedRestreamer::new(rs);
+        let rs = &rows;
+        let t_rows = Restreamer::len_rows(rs);
         if self.air.k == 0 {
             return Err(ProveError::Params("AIR must define at least one register (k > 0)"));
         }
         if self.params.b_blk == 0 {
             return Err(ProveError::Params("block size b_blk must be positive"));
         }
+        // Fail up front (not mid-proof) if the transform config cannot spill this domain.
+        self.params.transform.spills(self.params.domain.n)?;
+        if !self.fs_hash.is_available() {
+            return Err(ProveError::Params("transcript hash not available on this curve"));
+        }
 
-        let mut fs = Transcript::new("sszkp.proof");
-        let header = self.build_header();
+        let header = self.build_header()?;
+        let mut fs = Transcript::with_hash("sszkp.proof", header.fs_hash);
+        if record {
+            fs.start_recording();
+        }
         fs.absorb_protocol_header(&header);
 
         let pcs_wires: &PcsParams = &self.params.pcs_wires;
@@ -218,24 +341,25 @@ impl<'a> Prover<'a> {
         let b_blk = self.params.b_blk;
 
         // A — selectors (public-fixed in this repo; omitted from transcript)
-        if false && !self.air.selectors.is_empty() {
+        const COMMIT_SELECTORS: bool = false;
+        if COMMIT_SELECTORS && !self.air.selectors.is_empty() {
             let n = self.params.domain.n;
             for col in &self.air.selectors {
                 let time_stream =
                     (0..n).map(|i| if col.is_empty() { F::zero() } else { col[i % col.len()] });
-                let cm = self.commit_from_time_stream("selector", time_stream, pcs_wires);
+                let cm = self.commit_from_time_stream("selector", time_stream, pcs_wires)?;
                 fs.absorb_commitment_l(FsLabel::SelectorCommit, &cm);
             }
         }
 
         // B — wires (stream per register)
-        let mut wire_commits: Vec<PcsCommit> = Vec::with_capacity(self.air.k);
+        let mut wire_commits: Vec<P::Commitment> = Vec::with_capacity(self.air.k);
         let boundary_seed = vec![F::zero(); self.air.k].into_boxed_slice();
 
         // Iterator that restreams a target register `m` in time order by blocks.
-        struct WireTime<'r, R: Restreamer<Item = air::Row>> {
+        struct WireTime<'r, R: TryRestreamer<Item = air::Row>> {
             air: &'r air::AirSpec,
-            rs: &'r R,
+            rs: &'r LatchedRestreamer<'r, R>,
             boundary: Box<[F]>,
             t_rows: usize,
             b_blk: usize,
@@ -243,7 +367,7 @@ impl<'a> Prover<'a> {
             cur_block: Option<(Vec<F>, usize)>,
             reg_idx: usize,
         }
-        impl<'r, R: Restreamer<Item = air::Row>> Iterator for WireTime<'r, R> {
+        impl<'r, R: TryRestreamer<Item = air::Row>> Iterator for WireTime<'r, R> {
             type Item = F;
             fn next(&mut self) -> Option<F> {
                 loop {
@@ -262,13 +386,17 @@ impl<'a> Prover<'a> {
                     }
                     let end_idx = (start_idx + self.b_blk).min(self.t_rows);
                     let it = self.rs.stream_rows(RowIdx(start_idx), RowIdx(end_idx));
-                    let br = air::eval_block(
+                    // A bad row ends the stream; the caller's `rows.check()` reports it.
+                    let br = eval_rows(
                         self.air,
                         RegIdx(self.reg_idx),
                         BlockIdx(self.next_block),
                         &self.boundary,
+                        RowIdx(start_idx),
                         it,
-                    );
+                    )
+                    .map_err(|e| self.rs.latch(e))
+                    .ok()?;
                     self.boundary = br.boundary_out;
                     self.cur_block = Some((br.reg_m_vals, 0));
                     self.next_block += 1;
@@ -286,7 +414,8 @@ impl<'a> Prover<'a> {
                 cur_block: None,
                 reg_idx: m,
             };
-            let cm = self.commit_from_time_stream("wire", time_stream, pcs_wires);
+            let cm = self.commit_from_time_stream("wire", time_stream, pcs_wires)?;
+            rows.check()?;
             fs.absorb_commitment_l(FsLabel::WireCommit, &cm);
             wire_commits.push(cm);
         }
@@ -299,14 +428,13 @@ impl<'a> Prover<'a> {
         let mut perm_acc = PermAcc { z: F::one() };
         let mut _lookup_acc = LookupAcc { z: F::one() };
 
-        let mut bifft_z = crate::domain::BlockedIfft::new(&self.params.domain, b_blk);
+        let mut bifft_z = crate::domain::BlockedIfft::try_with_config(&self.params.domain, b_blk, &self.params.transform)?;
         let mut boundary = boundary_seed.clone();
         let mut z_start = F::one();
 
         for (BlockIdx(t), start, end) in blocks(t_rows, b_blk) {
             let block_it = rs.stream_rows(start, end);
-            let br: BlockResult =
-                air::eval_block(self.air, RegIdx(0), BlockIdx(t), &boundary, block_it);
+            let br: BlockResult = eval_rows(self.air, RegIdx(0), BlockIdx(t), &boundary, start, block_it)?;
 
             absorb_block_perm(&mut perm_acc, &br.locals, beta, gamma);
 
@@ -315,7 +443,7 @@ impl<'a> Prover<'a> {
             if let Some(last) = z_block.last() {
                 z_start = *last;
             }
-            bifft_z.feed_eval_block(&z_block);
+            bifft_z.feed_eval_block(&z_block)?;
 
             #[cfg(feature = "lookups")]
             {
@@ -328,17 +456,17 @@ impl<'a> Prover<'a> {
 
             boundary = br.boundary_out;
         }
+        rows.check()?;
 
         // Finalize Z commitment: drain coeff tiles with a one-tile prefetch
-        let mut cm_z_opt: Option<PcsCommit> = None;
+        let mut cm_z_opt: Option<P::Commitment> = None;
         {
             let pcs_for_z = PcsParams { basis: Basis::Coefficient, ..pcs_wires.clone() };
-            let mut agg_z = Aggregator::new(&pcs_for_z, "perm_Z");
-
-            let tiles = bifft_z.finish_low_to_high();
-            consume_tiles_with_prefetch(tiles, |tile| agg_z.add_block_coeffs(tile));
-
-            let cm_z = agg_z.finalize();
+            let mut tiles = bifft_z.finish_low_to_high_checked()?;
+            let cm_z =
+                self.pcs.commit_coeff_tiles(&pcs_for_z, "perm_Z", PrefetchTiles::new(tiles.by_ref()));
+            tiles.check()?;
+            let cm_z = cm_z?;
             fs.absorb_commitment_l(FsLabel::PermZCommit, &cm_z);
             cm_z_opt = Some(cm_z);
         }
@@ -348,16 +476,17 @@ impl<'a> Prover<'a> {
 
         // D — Quotient Q (fully streamed builder)
         let r_cfg = ResidualCfg { alpha, beta, gamma };
-        let r_stream = air::residual_stream(self.air, r_cfg.clone(), rs, b_blk);
-        let q_commit: PcsCommit = build_and_commit_quotient_streamed_r(
+        let r_stream = air::residual_stream_r(self.air, r_cfg, rs, b_blk)
+            .map_while(|r| r.map_err(|e| rows.latch(row_error(e, 0))).ok());
+        let q_commit: P::Commitment = build_and_commit_quotient_streamed_with(
+            &self.pcs,
             &self.params.domain,
             &self.params.pcs_coeff,
-            alpha,
-            beta,
-            gamma,
             b_blk,
+            &self.params.transform,
             r_stream,
         )?;
+        rows.check()?;
         fs.absorb_commitment_l(FsLabel::QuotientCommit, &q_commit);
 
         // Points: keep `[ζ]` for compatibility.
@@ -365,187 +494,263 @@ impl<'a> Prover<'a> {
         let zeta = eval_points[0];
 
         // E — Openings (tile-streamed throughout)
-        let mut open_set: Vec<PcsCommit> = Vec::new();
-        open_set.extend_from_slice(&wire_commits);
-        if let Some(zc) = cm_z_opt {
-            open_set.push(zc);
-        }
-        let q_index = open_set.len();
-        open_set.push(q_commit);
-
         let k_regs = self.air.k;
 
         // Wires @ ζ — open each wire with PCS helper using **coeff tiles** hi→lo
-        let mut proofs_wires: Vec<pcs::OpeningProof> = Vec::with_capacity(k_regs);
-        for m in 0..k_regs {
+        let mut proofs_wires: Vec<P::Proof> = Vec::with_capacity(k_regs);
+        for (m, wire_commit) in wire_commits.iter().enumerate() {
             // Build the time-stream for register m (recompute is OK; see note above).
             let mut boundary = vec![F::zero(); k_regs].into_boxed_slice();
             let time_vals_iter = blocks(t_rows, b_blk).flat_map(move |(BlockIdx(t), start, end)| {
                 let it = rs.stream_rows(start, end);
-                let br = air::eval_block(self.air, RegIdx(m), BlockIdx(t), &boundary, it);
-                boundary = br.boundary_out;
-                br.reg_m_vals.into_iter()
+                // A bad row ends the stream; `rows.check()` below reports it.
+                let vals = match eval_rows(self.air, RegIdx(m), BlockIdx(t), &boundary, start, it) {
+                    Ok(br) => {
+                        boundary = br.boundary_out;
+                        br.reg_m_vals
+                    }
+                    Err(e) => {
+                        rs.latch(e);
+                        Vec::new()
+                    }
+                };
+                vals.into_iter()
             });
 
             // Coeff tiles, high→low, streamed into the PCS opener.
-            let mut tiles_it = crate::domain::ifft_time_stream_to_coeff_tiles_hi_to_lo(
+            let mut tiles_it = crate::domain::ifft_time_stream_to_coeff_tiles_hi_to_lo_with(
                 &self.params.domain,
                 b_blk,
+                &self.params.transform,
                 time_vals_iter,
-            );
+            )?;
 
-            let mut stream_coeff_hi_to_lo =
-                |_idx: usize, sink: &mut dyn FnMut(Vec<F>)| while let Some(tile) = tiles_it.next()
-                {
+            let mut stream_coeff_hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| {
+                for tile in tiles_it.by_ref() {
                     sink(tile);
-                };
+                }
+            };
 
-            let pr = pcs::open_at_points_with_coeffs(
+            let pr = self.pcs.open_hi_to_lo(
                 &self.params.pcs_wires,
-                &[wire_commits[m]],
-                |_idx, _z| F::zero(),
+                self.fs_hash,
+                wire_commit,
                 &mut stream_coeff_hi_to_lo,
                 &eval_points,
             );
-            proofs_wires.extend(pr);
+            tiles_it.check()?;
+            proofs_wires.extend(pr?);
+            rows.check()?;
         }
 
         // Z @ ζ — recompute Z evals and open from coeff tiles (hi→lo)
-        let proofs_z_at_zeta: Vec<pcs::OpeningProof> = if let Some(zc) = cm_z_opt {
+        let proofs_z_at_zeta: Vec<P::Proof> = if let Some(zc) = &cm_z_opt {
             let mut boundary = boundary_seed.clone();
             let mut z_run = F::one();
-            let mut bifft = crate::domain::BlockedIfft::new(&self.params.domain, b_blk);
+            let mut bifft = crate::domain::BlockedIfft::try_with_config(&self.params.domain, b_blk, &self.params.transform)?;
             for (BlockIdx(t), start, end) in blocks(t_rows, b_blk) {
                 let it = rs.stream_rows(start, end);
-                let br = air::eval_block(self.air, RegIdx(0), BlockIdx(t), &boundary, it);
+                let br = eval_rows(self.air, RegIdx(0), BlockIdx(t), &boundary, start, it)?;
                 let zb = emit_z_column_block(z_run, &br.locals, beta, gamma);
                 if let Some(last) = zb.last() {
                     z_run = *last;
                 }
-                bifft.feed_eval_block(&zb);
+                bifft.feed_eval_block(&zb)?;
                 boundary = br.boundary_out;
             }
 
-            let mut tiles = bifft.finish_high_to_low();
-            let mut stream_coeff_hi_to_lo =
-                |_idx: usize, sink: &mut dyn FnMut(Vec<F>)| while let Some(block) = tiles.next() {
+            let mut tiles = bifft.finish_high_to_low_checked()?;
+            let mut stream_coeff_hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| {
+                for block in tiles.by_ref() {
                     sink(block);
-                };
+                }
+            };
 
-            pcs::open_at_points_with_coeffs(
+            let pr = self.pcs.open_hi_to_lo(
                 &self.params.pcs_wires,
-                &[zc],
-                |_idx, _z| F::zero(),
+                self.fs_hash,
+                zc,
                 &mut stream_coeff_hi_to_lo,
                 &eval_points,
-            )
+            );
+            tiles.check()?;
+            pr?
         } else {
             Vec::new()
         };
-
-        // Q @ ζ — stream tiles (hi→lo) directly from residual stream
-        let mut stream_q_coeff_hi_to_lo = |_idx: usize, sink: &mut dyn FnMut(Vec<F>)| {
-            let r_stream_all = air::residual_stream(self.air, r_cfg.clone(), rs, b_blk);
-            let mut tiles = stream_q_coeff_tiles_hi_to_lo_from_r_stream(
+        rows.check()?;
+
+        // Q @ ζ — stream tiles (hi→lo) directly from residual stream. The
+        // sink can't fail, so a transform/spill error is kept and checked
+        // after the opening.
+        let mut q_failed: Option<QuotientError> = None;
+        let mut stream_q_coeff_hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| {
+            let r_stream_all = air::residual_stream_r(self.air, r_cfg, rs, b_blk)
+                .map_while(|r| r.map_err(|e| rows.latch(row_error(e, 0))).ok());
+            let streamed = stream_q_coeff_tiles_hi_to_lo_from_r_stream(
                 &self.params.domain,
                 b_blk,
+                &self.params.transform,
                 r_stream_all,
-            );
-            while let Some(block) = tiles.next() {
-                sink(block);
+            )
+            .and_then(|mut tiles| {
+                for block in tiles.by_ref() {
+                    sink(block);
+                }
+                Ok(tiles.check()?)
+            });
+            if let Err(e) = streamed {
+                q_failed.get_or_insert(e);
             }
         };
-        let proofs_q_at_zeta = pcs::open_at_points_with_coeffs(
+        let proofs_q_at_zeta = self.pcs.open_hi_to_lo(
             &self.params.pcs_coeff,
-            &[open_set[q_index]],
-            |_idx, _z| F::zero(),
+            self.fs_hash,
+            &q_commit,
             &mut stream_q_coeff_hi_to_lo,
             &eval_points,
         );
+        if let Some(e) = q_failed {
+            return Err(e.into());
+        }
+        let proofs_q_at_zeta = proofs_q_at_zeta?;
+        rows.check()?;
 
         // (Feature) Z @ ω·ζ — recompute and open from coeff tiles (hi→lo)
         #[cfg(feature = "zeta-shift")]
-        let proofs_z_at_omega_zeta: Vec<pcs::OpeningProof> = if let Some(zc) = cm_z_opt {
+        let proofs_z_at_omega_zeta: Vec<P::Proof> = if let Some(zc) = &cm_z_opt {
             let omega_zeta = self.params.domain.omega * zeta;
             let pts = vec![omega_zeta];
 
             let mut boundary = boundary_seed.clone();
             let mut z_run = F::one();
-            let mut bifft = crate::domain::BlockedIfft::new(&self.params.domain, b_blk);
+            let mut bifft = crate::domain::BlockedIfft::try_with_config(&self.params.domain, b_blk, &self.params.transform)?;
             for (BlockIdx(t), start, end) in blocks(t_rows, b_blk) {
                 let it = rs.stream_rows(start, end);
-                let br = air::eval_block(self.air, RegIdx(0), BlockIdx(t), &boundary, it);
+                let br = eval_rows(self.air, RegIdx(0), BlockIdx(t), &boundary, start, it)?;
                 let zb = emit_z_column_block(z_run, &br.locals, beta, gamma);
                 if let Some(last) = zb.last() {
                     z_run = *last;
                 }
-                bifft.feed_eval_block(&zb);
+                bifft.feed_eval_block(&zb)?;
                 boundary = br.boundary_out;
             }
-            let mut tiles = bifft.finish_high_to_low();
-            let mut stream_coeff_hi_to_lo =
-                |_idx: usize, sink: &mut dyn FnMut(Vec<F>)| while let Some(block) = tiles.next() {
+            let mut tiles = bifft.finish_high_to_low_checked()?;
+            let mut stream_coeff_hi_to_lo = |sink: &mut dyn FnMut(Vec<F>)| {
+                for block in tiles.by_ref() {
                     sink(block);
-                };
+                }
+            };
 
-            pcs::open_at_points_with_coeffs(
+            let pr = self.pcs.open_hi_to_lo(
                 &self.params.pcs_wires,
-                &[zc],
-                |_idx, _z| F::zero(),
+                self.fs_hash,
+                zc,
                 &mut stream_coeff_hi_to_lo,
                 &pts,
-            )
+            );
+            tiles.check()?;
+            pr?
         } else {
             Vec::new()
         };
+        #[cfg(feature = "zeta-shift")]
+        rows.check()?;
 
         #[cfg(not(feature = "zeta-shift"))]
-        let proofs_z_at_omega_zeta: Vec<pcs::OpeningProof> = Vec::new();
+        let proofs_z_at_omega_zeta: Vec<P::Proof> = Vec::new();
 
         // Merge proofs in required order:
         // [wires@ζ] [Z@ζ?] [Q@ζ] [Z@ω·ζ?]
         let mut opening_proofs = Vec::new();
         opening_proofs.extend(proofs_wires);
-        opening_proofs.extend(proofs_z_at_zeta.iter().cloned());
-        opening_proofs.extend(proofs_q_at_zeta.iter().cloned());
-        opening_proofs.extend(proofs_z_at_omega_zeta.iter().cloned());
+        opening_proofs.extend(proofs_z_at_zeta);
+        opening_proofs.extend(proofs_q_at_zeta);
+        opening_proofs.extend(proofs_z_at_omega_zeta);
 
         // Claimed evals follow the same order.
-        let evals: Vec<F> = opening_proofs.iter().map(|p| p.value).collect();
+        let evals: Vec<F> = opening_proofs.iter().map(P::opened_value).collect();
 
-        // Wrap -> Proof
-        let wire_comms_wrapped: Vec<crate::Commitment> =
-            wire_commits.iter().copied().map(|c| crate::Commitment(c.0)).collect();
-        let z_comm_wrapped: Option<crate::Commitment> = cm_z_opt.map(|c| crate::Commitment(c.0));
-        let q_comm_wrapped: crate::Commitment = crate::Commitment(open_set[q_index].0);
+        // Bind the claimed values before the verifier's batching weight ν; the
+        // prover draws ν only to keep the transcript in step.
+        fs.absorb_scalars_l(FsLabel::OpeningEvals, &evals);
+        let _nu: F = fs.challenge_f_l(FsLabel::OpeningBatch);
 
-        Ok(Proof {
+        let proof = Proof {
             header,
-            wire_comms: wire_comms_wrapped,
-            z_comm: z_comm_wrapped,
-            q_comm: q_comm_wrapped,
+            wire_comms: wire_commits,
+            z_comm: cm_z_opt,
+            q_comm: q_commit,
             eval_points, // still [ζ] only (deterministic by label)
             evals,
             opening_proofs,
-        })
+        };
+        Ok((proof, fs.take_trace()))
     }
 
-    pub fn prove(&self, witness_rows: impl Iterator<Item = air::Row>) -> Result<Proof, ProveError> {
+    pub fn prove(
+        &self,
+        witness_rows: impl Iterator<Item = air::Row>,
+    ) -> Result<Proof<P>, ProveError> {
         let rows: Vec<air::Row> = witness_rows.collect();
         self.prove_with_restreamer(&rows)
     }
 }
 
-impl<'a> Verifier<'a> {
-    pub fn verify(&self, proof: &crate::Proof) -> Result<(), VerifySchedError> {
-        let mut fs = Transcript::new("sszkp.proof");
+impl<'a, P: PolynomialCommitment> Verifier<'a, P> {
+    pub fn verify(&self, proof: &Proof<P>) -> Result<(), VerifySchedError> {
+        self.check_header(proof)?;
+        let mut fs = Transcript::with_hash("sszkp.proof", proof.header.fs_hash);
+        self.verify_transcript(proof, &mut fs)
+    }
+
+    /// Like [`Self::verify`], also returning the Fiat–Shamir trace replayed so
+    /// far. The trace is returned on failure too (with no events if the
+    /// header was rejected before the transcript started).
+    pub fn verify_with_trace(
+        &self,
+        proof: &Proof<P>,
+    ) -> (Result<(), VerifySchedError>, TranscriptTrace) {
+        let hash = proof.header.fs_hash;
+        let mut fs = if hash.is_available() {
+            Transcript::with_hash("sszkp.proof", hash)
+        } else {
+            Transcript::new("sszkp.proof")
+        };
+        fs.start_recording();
+        let res = self.check_header(proof).and_then(|()| self.verify_transcript(proof, &mut fs));
+        (res, fs.take_trace().expect("recording was enabled"))
+    }
+
+    /// Backend, curve and transcript-hash checks that precede the transcript.
+    fn check_header(&self, proof: &Proof<P>) -> Result<(), VerifySchedError> {
+        if proof.header.pcs != P::KIND {
+            return Err(VerifySchedError::Backend { expected: P::KIND, got: proof.header.pcs });
+        }
+        if proof.header.curve != crate::curve::CURVE_ID {
+            return Err(VerifySchedError::Curve {
+                expected: crate::curve::CURVE_ID,
+                got: proof.header.curve,
+            });
+        }
+        if !proof.header.fs_hash.is_available() {
+            return Err(VerifySchedError::TranscriptHash(proof.header.fs_hash));
+        }
+        Ok(())
+    }
+
+    fn verify_transcript(
+        &self,
+        proof: &Proof<P>,
+        fs: &mut Transcript,
+    ) -> Result<(), VerifySchedError> {
         fs.absorb_protocol_header(&proof.header);
 
         // A — selectors (public-fixed; intentionally omitted)
 
         // B — wires
         for cm in &proof.wire_comms {
-            fs.absorb_commitment_l(FsLabel::WireCommit, &pcs::Commitment(cm.0));
+            fs.absorb_commitment_l(FsLabel::WireCommit, cm);
         }
 
         // (β, γ)
@@ -554,7 +759,7 @@ impl<'a> Verifier<'a> {
 
         // C — Z (if present)
         let has_z = if let Some(zc) = &proof.z_comm {
-            fs.absorb_commitment_l(FsLabel::PermZCommit, &pcs::Commitment(zc.0));
+            fs.absorb_commitment_l(FsLabel::PermZCommit, zc);
             true
         } else {
             false
@@ -564,7 +769,7 @@ impl<'a> Verifier<'a> {
         let alpha: F = fs.challenge_f_l(FsLabel::Alpha);
 
         // D — Q
-        fs.absorb_commitment_l(FsLabel::QuotientCommit, &pcs::Commitment(proof.q_comm.0));
+        fs.absorb_commitment_l(FsLabel::QuotientCommit, &proof.q_comm);
 
         // Eval points (ζ only)
         let expect_points: Vec<F> =
@@ -577,86 +782,70 @@ impl<'a> Verifier<'a> {
         #[cfg(feature = "zeta-shift")]
         let omega_zeta = omega * zeta;
 
-        // Build commitments list for verification routing
-        let mut open_set_all: Vec<PcsCommit> = Vec::new();
-        open_set_all.extend(proof.wire_comms.iter().map(|c| pcs::Commitment(c.0)));
-        if has_z {
-            if let Some(zc) = &proof.z_comm {
-                open_set_all.push(pcs::Commitment(zc.0));
-            }
-        }
-        open_set_all.push(pcs::Commitment(proof.q_comm.0));
-
         let k = proof.wire_comms.len();
         let s = proof.eval_points.len(); // =1
 
         // Partition evals/proofs in the order the prover appended them:
-        let m_wz = k + if has_z { 1 } else { 0 };
         let count_wires = k * s; // k
         let count_z_at_zeta = if has_z { s } else { 0 }; // 0 or 1
         let count_q = s; // 1
 
         let mut cursor = 0usize;
+        let mut batches: Vec<OpeningBatch<'_, P>> = Vec::with_capacity(4);
 
         // Wires @ ζ
-        let evals_wires = &proof.evals[cursor..cursor + count_wires];
-        let proofs_wires = &proof.opening_proofs[cursor..cursor + count_wires];
-        let open_set_wires: Vec<PcsCommit> = open_set_all[0..k].to_vec();
-        pcs::verify_openings(
-            &self.params.pcs_wires,
-            &open_set_wires,
-            &proof.eval_points,
-            evals_wires,
-            proofs_wires,
-        )?;
+        batches.push(OpeningBatch {
+            pcs: &self.params.pcs_wires,
+            commitments: &proof.wire_comms,
+            points: &proof.eval_points,
+            evals: &proof.evals[cursor..cursor + count_wires],
+            proofs: &proof.opening_proofs[cursor..cursor + count_wires],
+        });
         cursor += count_wires;
 
         // Z @ ζ (if any)
-        if has_z {
-            let evals_z = &proof.evals[cursor..cursor + count_z_at_zeta];
-            let proofs_z = &proof.opening_proofs[cursor..cursor + count_z_at_zeta];
-            let open_set_z: [PcsCommit; 1] = [open_set_all[k]];
-            pcs::verify_openings(
-                &self.params.pcs_wires,
-                &open_set_z,
-                &proof.eval_points,
-                evals_z,
-                proofs_z,
-            )?;
+        if let Some(zc) = &proof.z_comm {
+            batches.push(OpeningBatch {
+                pcs: &self.params.pcs_wires,
+                commitments: std::slice::from_ref(zc),
+                points: &proof.eval_points,
+                evals: &proof.evals[cursor..cursor + count_z_at_zeta],
+                proofs: &proof.opening_proofs[cursor..cursor + count_z_at_zeta],
+            });
             cursor += count_z_at_zeta;
         }
 
         // Q @ ζ
-        {
-            let evals_q = &proof.evals[cursor..cursor + count_q];
-            let proofs_q = &proof.opening_proofs[cursor..cursor + count_q];
-            let open_set_q: [PcsCommit; 1] = [open_set_all[m_wz]];
-            pcs::verify_openings(
-                &self.params.pcs_coeff,
-                &open_set_q,
-                &proof.eval_points,
-                evals_q,
-                proofs_q,
-            )?;
-            cursor += count_q;
-        }
+        batches.push(OpeningBatch {
+            pcs: &self.params.pcs_coeff,
+            commitments: std::slice::from_ref(&proof.q_comm),
+            points: &proof.eval_points,
+            evals: &proof.evals[cursor..cursor + count_q],
+            proofs: &proof.opening_proofs[cursor..cursor + count_q],
+        });
+        cursor += count_q;
 
         // (Feature) Z @ ω·ζ
         #[cfg(feature = "zeta-shift")]
-        if has_z {
-            let evals_z_omega = &proof.evals[cursor..cursor + 1];
-            let proofs_z_omega = &proof.opening_proofs[cursor..cursor + 1];
-            let open_set_z: [PcsCommit; 1] = [open_set_all[k]];
-            pcs::verify_openings(
-                &self.params.pcs_wires,
-                &open_set_z,
-                &[omega_zeta],
-                evals_z_omega,
-                proofs_z_omega,
-            )?;
+        let omega_zeta_pts = [omega_zeta];
+        #[cfg(feature = "zeta-shift")]
+        if let Some(zc) = &proof.z_comm {
+            batches.push(OpeningBatch {
+                pcs: &self.params.pcs_wires,
+                commitments: std::slice::from_ref(zc),
+                points: &omega_zeta_pts,
+                evals: &proof.evals[cursor..cursor + 1],
+                proofs: &proof.opening_proofs[cursor..cursor + 1],
+            });
             cursor += 1;
         }
 
+        // ν weights the openings inside a merged pairing check; it is drawn
+        // only after every claimed value is fixed.
+        fs.absorb_scalars_l(FsLabel::OpeningEvals, &proof.evals);
+        let nu: F = fs.challenge_f_l(FsLabel::OpeningBatch);
+        self.pcs.batch_verify(&batches, nu, proof.header.fs_hash)?;
+
         // Algebraic check at ζ (hard error if violated)
         let n_u32 = proof.header.domain_n;
         let zh_c = proof.header.zh_c;
@@ -665,13 +854,13 @@ impl<'a> Verifier<'a> {
         // Gather wires@ζ in eval order.
         let mut wires_at_zeta: Vec<F> = Vec::with_capacity(k_regs);
         for m in 0..k_regs {
-            wires_at_zeta.push(proof.evals[m * s + 0]);
+            wires_at_zeta.push(proof.evals[m * s]);
         }
-        let z_at_zeta = if has_z { proof.evals[k_regs * s + 0] } else { F::one() };
+        let z_at_zeta = if has_z { proof.evals[k_regs * s] } else { F::one() };
 
         // Pass Q(ζ) to the residual helper unless strict mode is enabled.
         let q_at_zeta = {
-            let idx = (k_regs + if has_z { 1 } else { 0 }) * s + 0;
+            let idx = (k_regs + if has_z { 1 } else { 0 }) * s;
             proof.evals[idx]
         };
         let q_arg = if cfg!(feature = "strict-recompute-r") {
@@ -0,0 +1,670 @@
+//! Fixed-stride SRS files: lazy decoding and background validation
+//!
+//! `srs_setup::load_and_validate_g1_srs` reads a compressed `Vec<G1Affine>`
+//! and decompresses + subgroup-checks every point before the first commit.
+//! For a 2²⁰–2²² SRS that is minutes of cold start and gigabytes of RSS. This
+//! module defines a file layout that avoids both:
+//!
+//! ```text
+//! offset 0   magic      b"SSZKPSRS"
+//!        8   version    u32 LE (= 1)
+//!       12   curve      u32 LE (0 = bn254, 1 = bls12-381)
+//!       16   stride     u32 LE (uncompressed G1 size: 64 / 96)
+//!       20   basis      u32 LE (0 = powers [τ^i]G1, 1 = Lagrange [L_i(τ)]G1)
+//!       24   count      u64 LE
+//!       32   binding    [u8; 32] (Lagrange keys; zero for powers)
+//!       64   powers     count × stride bytes, arkworks *uncompressed*
+//! ```
+//!
+//! A Lagrange key's binding ties it to the SRS and domain it was derived
+//! from (see [`LagrangeKey::from_file`](crate::pcs::LagrangeKey::from_file)).
+//!
+//! Point `i` lives at `64 + i·stride`, so any tile can be fetched with one
+//! positioned read and decoded without square roots. With the `mmap` feature
+//! [`SrsFile`](crate::srs_file::SrsFile) maps the file read-only and decodes
+//! straight from the map; otherwise (or if mapping fails) it uses positioned
+//! reads and lets the OS page cache do the rest.
+//!
+//! Opening a file only checks the header and `[1]G1`. Full validation
+//! (on-curve + subgroup for every point), the PCS digest and the file's
+//! BLAKE3 run in one background pass ([`SrsFile::spawn_validation`](crate::srs_file::SrsFile::spawn_validation));
+//! the result is persisted in a small JSON cache keyed by the file digest,
+//! next to a stat entry (device, inode, size, mtime, ctime) that maps the
+//! unchanged file back to its digest, so the next start reads nothing past
+//! the first point. A stat entry recorded within a couple of seconds of the
+//! file's ctime is re-checked by hashing, since a rewrite in the same clock
+//! tick keeps its timestamps. A cache entry vouches for a file, so
+//! the cache lives in a per-user directory ([`default_cache_dir`](crate::srs_file::default_cache_dir))
+//! and is ignored whenever another user could write to it. Until validation finishes, points
+//! are used unchecked: callers that must not commit against an unvalidated
+//! SRS call [`SrsFile::validate`](crate::srs_file::SrsFile::validate) instead.
+
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use ark_ec::{pairing::Pairing, AffineRepr, Group};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::curve::{CurveId, Engine, G1Affine, CURVE_ID};
+use crate::pcs::Basis;
+use crate::srs_setup::SrsSetupError;
+
+/// File magic.
+pub const MAGIC: [u8; 8] = *b"SSZKPSRS";
+/// Layout version written by [`write_g1`].
+pub const FORMAT_VERSION: u32 = 1;
+/// Bytes before the first point.
+pub const HEADER_LEN: u64 = 64;
+
+/// Environment variable overriding [`default_cache_dir`].
+pub const CACHE_DIR_ENV: &str = "SSZKP_SRS_CACHE_DIR";
+
+/// Uncompressed size of one G1 point for this build's curve.
+pub fn g1_stride() -> usize {
+    G1Affine::zero().uncompressed_size()
+}
+
+fn curve_code(c: CurveId) -> u32 {
+    match c {
+        CurveId::Bn254 => 0,
+        CurveId::Bls12_381 => 1,
+    }
+}
+
+fn basis_code(b: Basis) -> u32 {
+    match b {
+        Basis::Coefficient => 0,
+        Basis::Evaluation => 1,
+    }
+}
+
+/// Write monomial `powers` (`[τ^i]G1`) in the fixed-stride layout.
+pub fn write_g1(path: impl AsRef<Path>, powers: &[G1Affine]) -> Result<(), SrsSetupError> {
+    write_g1_basis(path, powers, Basis::Coefficient, [0u8; 32])
+}
+
+/// Write G1 points tagged with the basis they commit in
+/// (`Evaluation` for a `pcs::LagrangeKey`) and a 32-byte `binding` for the
+/// header's last slot.
+pub fn write_g1_basis(
+    path: impl AsRef<Path>,
+    powers: &[G1Affine],
+    basis: Basis,
+    binding: [u8; 32],
+) -> Result<(), SrsSetupError> {
+    let stride = g1_stride();
+    let mut w = BufWriter::new(File::create(path.as_ref())?);
+    let mut header = [0u8; HEADER_LEN as usize];
+    header[..8].copy_from_slice(&MAGIC);
+    header[8..12].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+    header[12..16].copy_from_slice(&curve_code(CURVE_ID).to_le_bytes());
+    header[16..20].copy_from_slice(&(stride as u32).to_le_bytes());
+    header[20..24].copy_from_slice(&basis_code(basis).to_le_bytes());
+    header[24..32].copy_from_slice(&(powers.len() as u64).to_le_bytes());
+    header[32..64].copy_from_slice(&binding);
+    w.write_all(&header)?;
+    let mut buf = Vec::with_capacity(stride);
+    for p in powers {
+        buf.clear();
+        p.serialize_uncompressed(&mut buf)
+            .map_err(|e| SrsSetupError::Validation(format!("G1 serialize: {e}")))?;
+        w.write_all(&buf)?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// Whether `path` starts with [`MAGIC`] (vs. a legacy compressed `G1.bin`).
+pub fn is_srs_file(path: impl AsRef<Path>) -> bool {
+    let mut magic = [0u8; 8];
+    File::open(path).and_then(|mut f| f.read_exact(&mut magic)).is_ok() && magic == MAGIC
+}
+
+/// Outcome of full point validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationStatus {
+    /// Not validated yet (or the background pass is still running).
+    Pending,
+    /// Every point is on the curve and in the prime-order subgroup.
+    Valid,
+    /// Point `index` failed (off-curve, wrong subgroup or undecodable).
+    Invalid {
+        /// Index of the first bad point.
+        index: usize,
+    },
+}
+
+/// Cache entry written after a successful validation pass.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    file_blake3: String,
+    count: u64,
+    g1_digest: String,
+}
+
+/// Stat index entry: the file digest last seen for one `(device, inode)`.
+#[derive(Debug, Serialize, Deserialize)]
+struct StatEntry {
+    /// Size, mtime and ctime when `file_blake3` was computed.
+    stat: String,
+    file_blake3: String,
+    /// Unix seconds when the entry was written.
+    recorded: u64,
+}
+
+/// Slack for coarse file timestamps: a file whose ctime is this close to
+/// its stat entry may have been rewritten unnoticed.
+const RACY_SECS: u64 = 2;
+
+/// Stat identity of an open file: the index file name, the size/time
+/// signature and the ctime in Unix seconds. Only on Unix, where ctime cannot
+/// be set by the file's owner.
+#[cfg(unix)]
+fn stat_of(meta: &std::fs::Metadata) -> Option<(String, String, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((
+        format!("stat-{}-{}.json", meta.dev(), meta.ino()),
+        format!(
+            "{} {}.{:09} {}.{:09}",
+            meta.size(),
+            meta.mtime(),
+            meta.mtime_nsec(),
+            meta.ctime(),
+            meta.ctime_nsec()
+        ),
+        u64::try_from(meta.ctime()).ok()?,
+    ))
+}
+
+#[cfg(not(unix))]
+fn stat_of(_meta: &std::fs::Metadata) -> Option<(String, String, u64)> {
+    None
+}
+
+/// Where the points are read from.
+#[derive(Debug)]
+enum Backing {
+    /// Positioned reads.
+    File(File),
+    /// Read-only map of the whole file.
+    #[cfg(feature = "mmap")]
+    Map(memmap2::Mmap),
+}
+
+impl Backing {
+    #[cfg(not(feature = "mmap"))]
+    fn new(file: File) -> Self {
+        Backing::File(file)
+    }
+
+    /// Map `file`, falling back to positioned reads if that fails.
+    #[cfg(feature = "mmap")]
+    #[allow(unsafe_code)]
+    fn new(file: File) -> Self {
+        // SAFETY: the map is read-only and only ever copied or decoded out
+        // of; a concurrent writer truncating the file is outside the
+        // contract (as for any mapped input), and the length was validated
+        // against the header.
+        match unsafe { memmap2::Mmap::map(&file) } {
+            Ok(map) => Backing::Map(map),
+            Err(_) => Backing::File(file),
+        }
+    }
+
+    /// `len` bytes at `offset`: borrowed from the map, or read.
+    fn bytes(&self, offset: u64, len: usize) -> std::io::Result<Cow<'_, [u8]>> {
+        match self {
+            Backing::File(f) => {
+                let mut buf = vec![0u8; len];
+                read_exact_at(f, &mut buf, offset)?;
+                Ok(Cow::Owned(buf))
+            }
+            #[cfg(feature = "mmap")]
+            Backing::Map(m) => usize::try_from(offset)
+                .ok()
+                .and_then(|o| m.get(o..o.checked_add(len)?))
+                .map(Cow::Borrowed)
+                .ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+/// Directory for validation cache entries: `$SSZKP_SRS_CACHE_DIR`, else
+/// `$XDG_CACHE_HOME/sszkp/srs`, else `$HOME/.cache/sszkp/srs`; `None` (no
+/// cache) if none of these is set. Never the shared temp dir: anyone who can
+/// write an entry there could mark an unvalidated file as valid.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let non_empty = |k: &str| std::env::var_os(k).filter(|v| !v.is_empty()).map(PathBuf::from);
+    non_empty(CACHE_DIR_ENV)
+        .or_else(|| non_empty("XDG_CACHE_HOME").map(|d| d.join("sszkp").join("srs")))
+        .or_else(|| non_empty("HOME").map(|d| d.join(".cache").join("sszkp").join("srs")))
+}
+
+/// Create `dir` owner-only if missing and report whether it is private: on
+/// Unix, a real directory (not a symlink) with no group/other write bits.
+fn private_dir(dir: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        if !dir.exists() {
+            let _ = std::fs::DirBuilder::new().recursive(true).mode(0o700).create(dir);
+        }
+        std::fs::symlink_metadata(dir).is_ok_and(|m| m.is_dir() && m.permissions().mode() & 0o022 == 0)
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::create_dir_all(dir).is_ok()
+    }
+}
+
+/// An open fixed-stride G1 SRS file.
+#[derive(Debug)]
+pub struct SrsFile {
+    backing: Backing,
+    path: PathBuf,
+    count: usize,
+    stride: usize,
+    basis: Basis,
+    binding: [u8; 32],
+    file_digest: OnceLock<[u8; 32]>,
+    stat: Option<(String, String, u64)>,
+    cache_dir: Option<PathBuf>,
+    status: Mutex<ValidationStatus>,
+    g1_digest: OnceLock<[u8; 32]>,
+}
+
+impl SrsFile {
+    /// Open with the [`default_cache_dir`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Arc<Self>, SrsSetupError> {
+        Self::open_with_cache(path, default_cache_dir())
+    }
+
+    /// Open, checking the header and `[1]G1`. A cache hit in `cache_dir`
+    /// marks the file [`ValidationStatus::Valid`] and restores its digests;
+    /// `None` disables the cache, and so does a directory other users can
+    /// write to. The file is only hashed here if its stat entry is too
+    /// recent to trust.
+    pub fn open_with_cache(
+        path: impl AsRef<Path>,
+        cache_dir: Option<PathBuf>,
+    ) -> Result<Arc<Self>, SrsSetupError> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = File::open(&path)?;
+        let mut header = [0u8; HEADER_LEN as usize];
+        file.read_exact(&mut header)
+            .map_err(|_| SrsSetupError::Deserialize("SRS file shorter than its header".into()))?;
+        let u32_at = |o: usize| u32::from_le_bytes(header[o..o + 4].try_into().unwrap());
+        if header[..8] != MAGIC {
+            return Err(SrsSetupError::Deserialize("not a fixed-stride SRS file (bad magic)".into()));
+        }
+        if u32_at(8) != FORMAT_VERSION {
+            return Err(SrsSetupError::Deserialize(format!("unsupported SRS file version {}", u32_at(8))));
+        }
+        if u32_at(12) != curve_code(CURVE_ID) {
+            return Err(SrsSetupError::Validation(format!(
+                "SRS file is for another curve (this build is {})",
+                CURVE_ID.as_str()
+            )));
+        }
+        let stride = u32_at(16) as usize;
+        if stride != g1_stride() {
+            return Err(SrsSetupError::Deserialize(format!("SRS stride {stride}, expected {}", g1_stride())));
+        }
+        let basis = match u32_at(20) {
+            0 => Basis::Coefficient,
+            1 => Basis::Evaluation,
+            b => return Err(SrsSetupError::Deserialize(format!("unknown SRS basis tag {b}"))),
+        };
+        let count = u64::from_le_bytes(header[24..32].try_into().unwrap()) as usize;
+        let expected_len = HEADER_LEN + (count as u64) * stride as u64;
+        let meta = file.metadata()?;
+        let actual_len = meta.len();
+        if count == 0 || actual_len != expected_len {
+            return Err(SrsSetupError::Deserialize(format!(
+                "SRS file is {actual_len} bytes, header implies {expected_len}"
+            )));
+        }
+
+        let srs = SrsFile {
+            backing: Backing::new(file),
+            path,
+            count,
+            stride,
+            basis,
+            binding: header[32..64].try_into().unwrap(),
+            file_digest: OnceLock::new(),
+            stat: stat_of(&meta),
+            cache_dir,
+            status: Mutex::new(ValidationStatus::Pending),
+            g1_digest: OnceLock::new(),
+        };
+
+        let first = srs.read_range(0, 1)?[0];
+        if basis == Basis::Coefficient && first != G1Affine::from(<Engine as Pairing>::G1::generator()) {
+            return Err(SrsSetupError::Validation(
+                "G1 SRS first element is not the generator (possible corruption or wrong curve)".into(),
+            ));
+        }
+        if let Some(entry) = srs.known_digest()?.and_then(|d| srs.read_cache(d)) {
+            if let Ok(Ok(d)) = hex::decode(&entry.g1_digest).map(<[u8; 32]>::try_from) {
+                let _ = srs.g1_digest.set(d);
+                *srs.status.lock().expect("status mutex poisoned") = ValidationStatus::Valid;
+            }
+        }
+        Ok(Arc::new(srs))
+    }
+
+    /// Number of G1 powers.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Always `false` for an opened file (empty files are rejected).
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// `Coefficient` for monomial powers, `Evaluation` for a Lagrange key.
+    pub fn basis(&self) -> Basis {
+        self.basis
+    }
+
+    /// The header's binding slot (see [`write_g1_basis`]).
+    pub fn binding(&self) -> [u8; 32] {
+        self.binding
+    }
+
+    /// Path the file was opened from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// BLAKE3 of the whole file (the validation cache key). Cached; hashes
+    /// the file if neither the stat index nor a validation pass produced it.
+    pub fn file_digest(&self) -> Result<[u8; 32], SrsSetupError> {
+        if let Some(d) = self.file_digest.get() {
+            return Ok(*d);
+        }
+        let mut h = blake3::Hasher::new();
+        let len = HEADER_LEN + (self.count * self.stride) as u64;
+        let mut offset = 0u64;
+        while offset < len {
+            let n = (1u64 << 20).min(len - offset) as usize;
+            h.update(&self.backing.bytes(offset, n)?);
+            offset += n as u64;
+        }
+        Ok(*self.file_digest.get_or_init(|| *h.finalize().as_bytes()))
+    }
+
+    /// Current validation status.
+    pub fn status(&self) -> ValidationStatus {
+        *self.status.lock().expect("status mutex poisoned")
+    }
+
+    /// Decode powers `start .. start + len` without curve/subgroup checks.
+    pub fn read_range(&self, start: usize, len: usize) -> Result<Vec<G1Affine>, SrsSetupError> {
+        if start.checked_add(len).is_none_or(|end| end > self.count) {
+            return Err(SrsSetupError::Validation(format!(
+                "SRS range {start}..{} out of bounds ({} powers)",
+                start.saturating_add(len),
+                self.count
+            )));
+        }
+        self.decode(&self.backing.bytes(HEADER_LEN + (start * self.stride) as u64, len * self.stride)?)
+    }
+
+    fn decode(&self, raw: &[u8]) -> Result<Vec<G1Affine>, SrsSetupError> {
+        raw.chunks_exact(self.stride)
+            .map(|c| {
+                G1Affine::deserialize_uncompressed_unchecked(c)
+                    .map_err(|e| SrsSetupError::Deserialize(format!("G1 point: {e}")))
+            })
+            .collect()
+    }
+
+    /// PCS digest of the powers (same as `pcs::srs_g1_digest` with them
+    /// loaded). Cached; computed by streaming the file if validation has not
+    /// produced it yet, which fails if the file can no longer be read.
+    pub fn g1_digest(&self) -> Result<[u8; 32], SrsSetupError> {
+        if let Some(d) = self.g1_digest.get() {
+            return Ok(*d);
+        }
+        let (_, digest) = self.scan(|_, _| true)?;
+        Ok(*self.g1_digest.get_or_init(|| digest))
+    }
+
+    /// Blocking full validation + digest; persists the cache on success.
+    pub fn validate(&self) -> ValidationStatus {
+        if self.status() == ValidationStatus::Valid {
+            return ValidationStatus::Valid;
+        }
+        let status = match self.scan(|_, p| p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve()) {
+            Ok((None, digest)) => {
+                let _ = self.g1_digest.set(digest);
+                if let Some(&file_digest) = self.file_digest.get() {
+                    self.write_cache(file_digest, digest);
+                }
+                ValidationStatus::Valid
+            }
+            Ok((Some(index), _)) => ValidationStatus::Invalid { index },
+            Err(_) => ValidationStatus::Invalid { index: 0 },
+        };
+        *self.status.lock().expect("status mutex poisoned") = status;
+        status
+    }
+
+    /// Run [`validate`](Self::validate) on a background thread.
+    pub fn spawn_validation(self: &Arc<Self>) -> std::thread::JoinHandle<ValidationStatus> {
+        let me = Arc::clone(self);
+        std::thread::spawn(move || me.validate())
+    }
+
+    /// One pass over all points in tiles: returns the first index failing
+    /// `check` (if any) and the PCS digest. The file digest is hashed from
+    /// the same bytes and cached.
+    fn scan(
+        &self,
+        check: impl Fn(usize, &G1Affine) -> bool,
+    ) -> Result<(Option<usize>, [u8; 32]), SrsSetupError> {
+        const TILE: usize = 1 << 14;
+        let mut digest = crate::pcs::G1DigestStream::new(self.count);
+        let mut file_hash = blake3::Hasher::new();
+        file_hash.update(&self.backing.bytes(0, HEADER_LEN as usize)?);
+        let mut bad = None;
+        for start in (0..self.count).step_by(TILE) {
+            let len = TILE.min(self.count - start);
+            let raw = self.backing.bytes(HEADER_LEN + (start * self.stride) as u64, len * self.stride)?;
+            file_hash.update(&raw);
+            let tile = self.decode(&raw)?;
+            for (i, p) in tile.iter().enumerate() {
+                if bad.is_none() && !check(start + i, p) {
+                    bad = Some(start + i);
+                }
+                digest.push(p);
+            }
+        }
+        let _ = self.file_digest.set(*file_hash.finalize().as_bytes());
+        Ok((bad, digest.finish()))
+    }
+
+    fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref().filter(|d| private_dir(d))
+    }
+
+    /// The file digest if the cache can name it: from the stat index when
+    /// the entry is old enough to trust, by hashing when it is too recent;
+    /// `None` (left to the validation pass) without an entry.
+    fn known_digest(&self) -> Result<Option<[u8; 32]>, SrsSetupError> {
+        let (Some(dir), Some((name, stat, ctime))) = (self.cache_dir(), &self.stat) else {
+            return Ok(None);
+        };
+        let Some(entry) = std::fs::read(dir.join(name))
+            .ok()
+            .and_then(|b| serde_json::from_slice::<StatEntry>(&b).ok())
+            .filter(|e| &e.stat == stat)
+        else {
+            return Ok(None);
+        };
+        if entry.recorded < ctime.saturating_add(RACY_SECS) {
+            return self.file_digest().map(Some);
+        }
+        let Ok(Ok(d)) = hex::decode(&entry.file_blake3).map(<[u8; 32]>::try_from) else {
+            return Ok(None);
+        };
+        Ok(Some(*self.file_digest.get_or_init(|| d)))
+    }
+
+    fn read_cache(&self, file_digest: [u8; 32]) -> Option<CacheEntry> {
+        let path = self.cache_dir()?.join(format!("{}.json", hex::encode(file_digest)));
+        let entry: CacheEntry = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+        (entry.version == FORMAT_VERSION
+            && entry.file_blake3 == hex::encode(file_digest)
+            && entry.count == self.count as u64)
+            .then_some(entry)
+    }
+
+    /// Best effort: a read-only cache dir only costs a re-validation.
+    fn write_cache(&self, file_digest: [u8; 32], g1_digest: [u8; 32]) {
+        let Some(dir) = self.cache_dir() else { return };
+        let entry = CacheEntry {
+            version: FORMAT_VERSION,
+            file_blake3: hex::encode(file_digest),
+            count: self.count as u64,
+            g1_digest: hex::encode(g1_digest),
+        };
+        let path = dir.join(format!("{}.json", entry.file_blake3));
+        let _ = std::fs::write(path, serde_json::to_vec_pretty(&entry).expect("cache entry serializes"));
+        if let Some((name, stat, _)) = &self.stat {
+            let recorded = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            let entry = StatEntry { stat: stat.clone(), file_blake3: entry.file_blake3, recorded };
+            let _ = std::fs::write(dir.join(name), serde_json::to_vec_pretty(&entry).expect("stat entry serializes"));
+        }
+    }
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    while !buf.is_empty() {
+        match std::os::windows::fs::FileExt::seek_read(file, buf, offset)? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            n => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// No positioned reads on this target: seek and read under one lock, since
+/// every reader of an [`SrsFile`] shares its handle's cursor.
+#[cfg(not(any(unix, windows)))]
+fn read_exact_at(mut file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    use std::io::{Seek, SeekFrom};
+    static CURSOR: Mutex<()> = Mutex::new(());
+    let _guard = CURSOR.lock().unwrap_or_else(|p| p.into_inner());
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcs::{self, Basis, PolynomialCommitment};
+    use crate::F;
+    use ark_ec::CurveGroup;
+    use ark_ff::Field;
+
+    #[test]
+    fn file_backed_srs_matches_in_memory_and_caches_validation() {
+        let tau = F::from(17u64);
+        let g = crate::curve::G1Projective::generator();
+        let powers: Vec<G1Affine> = (0..40u64).map(|i| (g * tau.pow([i])).into_affine()).collect();
+        let dir = tempfile::tempdir().unwrap();
+        let (path, cache) = (dir.path().join("G1.srs"), dir.path().join("cache"));
+        write_g1(&path, &powers).unwrap();
+        assert!(is_srs_file(&path));
+
+        let file = SrsFile::open_with_cache(&path, Some(cache.clone())).unwrap();
+        assert_eq!(file.status(), ValidationStatus::Pending);
+        assert!(file.file_digest.get().is_none(), "a cold open does not hash the file");
+        assert_eq!(file.read_range(5, 3).unwrap(), powers[5..8]);
+        assert_eq!(file.spawn_validation().join().unwrap(), ValidationStatus::Valid);
+        let digest = *blake3::hash(&std::fs::read(&path).unwrap()).as_bytes();
+        assert_eq!(file.file_digest().unwrap(), digest);
+        #[cfg(unix)]
+        let stat = file.stat.clone();
+
+        let mem = pcs::Srs::new(powers.clone(), None).unwrap();
+        let mapped = pcs::Srs::from_file(file, None);
+        assert_eq!(mapped.g1_digest().unwrap(), mem.g1_digest().unwrap());
+        let coeffs: Vec<F> = (0..40u64).map(|i| F::from(i * i + 1)).collect();
+        let tiles = || coeffs.chunks(7).map(<[F]>::to_vec).collect::<Vec<_>>();
+        assert_eq!(
+            pcs::Kzg.commit_coeff_tiles(&mapped.pcs_params(Basis::Coefficient), "f", tiles()).unwrap(),
+            pcs::Kzg.commit_coeff_tiles(&mem.pcs_params(Basis::Coefficient), "f", tiles()).unwrap(),
+        );
+
+        // Second open hits the cache; a modified file does not.
+        assert_eq!(SrsFile::open_with_cache(&path, Some(cache.clone())).unwrap().status(), ValidationStatus::Valid);
+        #[cfg(unix)]
+        {
+            // An entry recorded well after the file's ctime is trusted
+            // without hashing: point it at a digest with no cache entry.
+            let (name, _, _) = stat.unwrap();
+            let stat_path = cache.join(name);
+            let mut entry: StatEntry = serde_json::from_slice(&std::fs::read(&stat_path).unwrap()).unwrap();
+            let real = std::mem::replace(&mut entry.file_blake3, hex::encode([7u8; 32]));
+            entry.recorded = u64::MAX;
+            std::fs::write(&stat_path, serde_json::to_vec(&entry).unwrap()).unwrap();
+            let reopened = SrsFile::open_with_cache(&path, Some(cache.clone())).unwrap();
+            assert_eq!(reopened.status(), ValidationStatus::Pending);
+            assert_eq!(reopened.file_digest().unwrap(), [7u8; 32]);
+            entry.file_blake3 = real;
+            std::fs::write(&stat_path, serde_json::to_vec(&entry).unwrap()).unwrap();
+            assert_eq!(SrsFile::open_with_cache(&path, Some(cache.clone())).unwrap().status(), ValidationStatus::Valid);
+            // Racy again, so the same-tick rewrite below is caught by hashing.
+            entry.recorded = 0;
+            std::fs::write(&stat_path, serde_json::to_vec(&entry).unwrap()).unwrap();
+        }
+        let mut bad = powers;
+        bad[9] = (g * F::from(3u64)).into_affine();
+        write_g1(&path, &bad).unwrap();
+        assert_eq!(SrsFile::open_with_cache(&path, Some(cache)).unwrap().status(), ValidationStatus::Pending);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shared_cache_dir_is_ignored() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let g = crate::curve::G1Projective::generator();
+        let powers: Vec<G1Affine> = (0..4u64).map(|i| (g * F::from(5u64).pow([i])).into_affine()).collect();
+        let dir = tempfile::tempdir().unwrap();
+        let (path, cache) = (dir.path().join("G1.srs"), dir.path().join("cache"));
+        write_g1(&path, &powers).unwrap();
+        std::fs::create_dir(&cache).unwrap();
+        std::fs::set_permissions(&cache, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        let file = SrsFile::open_with_cache(&path, Some(cache.clone())).unwrap();
+        assert_eq!(file.validate(), ValidationStatus::Valid);
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+        assert_eq!(SrsFile::open_with_cache(&path, Some(cache)).unwrap().status(), ValidationStatus::Pending);
+    }
+}
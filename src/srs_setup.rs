@@ -21,12 +21,12 @@
 //!
 //! 1. **Perpetual Powers of Tau** (Ethereum Foundation)
 //!    - URL: <https://github.com/privacy-scaling-explorations/perpetualpowersoftau>
-//!    - Format: `.ptau` files (needs conversion)
+//!    - Format: `.ptau` files (import with `srs_convert ptau`, see [`crate::ptau`])
 //!    - Security: High (100+ participants)
 //!
 //! 2. **Hermez/Polygon Ceremony**
 //!    - URL: `https://hermez.s3-eu-west-1.amazonaws.com/powersOfTau28_hez_final_NN.ptau`
-//!    - Format: Direct download
+//!    - Format: `.ptau`, same importer
 //!    - Security: High (trusted by Polygon zkEVM)
 //!
 //! 3. **Aztec Ignition Ceremony**
@@ -34,6 +34,23 @@
 //!    - Format: `.dat` files
 //!    - Security: High (176 participants)
 //!
+//! 4. **Your own ceremony**
+//!    - `srs_ceremony init/contribute/verify` (see [`crate::ceremony`]): participants
+//!      pass SRS directories along; every update carries a pairing-checkable proof
+//!    - Security: one honest participant among those you invite
+//!
+//! ## Recommended Sources for BLS12-381 (`--features bls12-381`)
+//!
+//! 1. **Ethereum KZG Ceremony** (EIP-4844)
+//!    - URL: <https://github.com/ethereum/kzg-ceremony-sequencer>
+//!    - Format: `transcript.json` (load with [`load_ethereum_ceremony_json`])
+//!    - Security: Very high (140k+ participants)
+//!    - Sizes: 2¹², 2¹³, 2¹⁴, 2¹⁵ G1 powers with 65 G2 powers each
+//!
+//! The curve is fixed at compile time (see `curve.rs`), so every loader here
+//! reads points of the build's curve. A BN254 file fed to a BLS12-381 build
+//! fails at deserialization.
+//!
 //! # Security Model
 //!
 //! ```text
@@ -63,6 +80,10 @@
 //! [G2Affine; 1 or 2]    // Either [τ·G₂] or [G₂, τ·G₂]
 //! ```
 //!
+//! For large SRSes, convert `G1.bin` once with `srs_convert fixed-stride` into the
+//! uncompressed fixed-stride layout of [`crate::srs_file`]; the server detects
+//! it by its magic and opens it without decoding every point up front.
+//!
 //! # Usage
 //!
 //! ## Production (with trusted SRS)
@@ -109,21 +130,27 @@
 //! 2. **Structural validation**: First element is generator, sufficient degree
 //! 3. **Cryptographic validation**: Optional pairing checks (expensive)
 //! 4. **Digest verification**: Compare against known-good ceremony outputs
+//!    ([`crate::srs_setup::KNOWN_CEREMONIES`] plus an optional `SSZKP_CEREMONY_REGISTRY` file);
+//!    once the registry has any entry, production prover, verifier and API
+//!    server refuse an unknown SRS unless overridden (see
+//!    [`crate::srs_setup::require_known_ceremony`]). The compiled-in registry
+//!    is currently empty, so out of the box nothing is enforced.
 //!
 //! # Performance Notes
 //!
 //! - **Loading**: O(degree) deserialization, typically <1s for degree=16384
 //! - **Validation**: Optional pairing checks add ~0.1s per check
 //! - **Memory**: Loaded SRS persists in global state (≈1MB per 16384 powers)
+//! - **Large SRS**: use [`crate::srs_file`]; cold start is one hash of the file
 
 #![forbid(unsafe_code)]
 #![allow(unused_imports)]
 
-use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use crate::curve::{Engine, Fr, G1Affine, G1Projective, G2Affine};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use std::path::Path;
+use std::{borrow::Cow, path::Path};
 
 // ============================================================================
 // Error Types
@@ -151,6 +178,24 @@ pub enum SrsSetupError {
     /// Pairing check failed (indicates corrupted or malicious SRS)
     #[error("pairing check failed: {0}")]
     PairingCheck(String),
+
+    /// Ceremony transcript hash did not match (tampered or corrupted file)
+    #[error("ceremony hash mismatch: {0}")]
+    CeremonyHash(String),
+
+    /// SRS digests match no entry of the ceremony registry
+    #[error(
+        "SRS matches no known ceremony (G1 digest {g1_digest}, G2 digest {g2_digest}); \
+         register it via ${} or pass --allow-unknown-srs / {}=1",
+        CEREMONY_REGISTRY_ENV,
+        ALLOW_UNKNOWN_SRS_ENV
+    )]
+    UnknownCeremony {
+        /// Hex G1 digest of the loaded SRS
+        g1_digest: String,
+        /// Hex G2 digest of the loaded SRS
+        g2_digest: String,
+    },
 }
 
 // ============================================================================
@@ -167,7 +212,7 @@ pub enum SrsSetupError {
 ///
 /// 1. **Deserialization**: Ensures binary data is well-formed
 /// 2. **Degree check**: Verifies `powers.len() >= expected_degree + 1`
-/// 3. **Generator check**: Confirms `powers[0]` equals the BN254 G1 generator
+/// 3. **Generator check**: Confirms `powers[0]` equals the G1 generator of the build's curve
 /// 4. **Point validity**: All points are on-curve (automatic via Arkworks)
 ///
 /// # Security Note
@@ -182,7 +227,7 @@ pub enum SrsSetupError {
 /// pcs::load_srs_g1(&powers);
 ///
 /// let digest = pcs::srs_g1_digest();
-/// const EXPECTED: [u8; 32] = /* from ceremony transcript */;
+/// const EXPECTED: [u8; 32] = [0u8; 32]; // from ceremony transcript
 /// assert_eq!(digest, EXPECTED, "SRS digest mismatch - possible corruption or wrong file");
 /// # Ok::<(), srs_setup::SrsSetupError>(())
 /// ```
@@ -216,7 +261,7 @@ pub fn load_and_validate_g1_srs(
 
     // Layer 3: Generator validation
     // The first element MUST be [1]G₁ = G₁ (the identity scalar times the generator)
-    let g1_gen = <Bn254 as Pairing>::G1::generator();
+    let g1_gen = <Engine as Pairing>::G1::generator();
     if powers[0] != g1_gen {
         return Err(SrsSetupError::Validation(
             "G1 SRS first element is not the generator (possible corruption or wrong curve)".into(),
@@ -254,12 +299,12 @@ pub fn validate_g1_pairing(
         ));
     }
 
-    let g1_gen = <Bn254 as Pairing>::G1::generator();
-    let g2_gen = <Bn254 as Pairing>::G2::generator();
+    let g1_gen = <Engine as Pairing>::G1::generator();
+    let g2_gen = <Engine as Pairing>::G2::generator();
 
     // Check: e(τ·G₁, G₂) =?= e(G₁, τ·G₂)
-    let lhs = Bn254::pairing(g1_powers[1], g2_gen);
-    let rhs = Bn254::pairing(g1_gen, tau_g2);
+    let lhs = Engine::pairing(g1_powers[1], g2_gen);
+    let rhs = Engine::pairing(g1_gen, tau_g2);
 
     if lhs != rhs {
         return Err(SrsSetupError::PairingCheck(
@@ -270,6 +315,38 @@ pub fn validate_g1_pairing(
     Ok(())
 }
 
+/// Check that *every* G1 power matches `[τ]G₂`, not just the first.
+///
+/// Randomized same-ratio check: with random `r_i`,
+/// `e(Σ r_i·P_i, [τ]G₂) = e(Σ r_i·P_{i+1}, G₂)` iff (w.h.p.) every
+/// `P_{i+1} = τ·P_i`. Also pins `P_0` to the generator. Costs two MSMs over
+/// the powers plus two pairings; ceremony tooling runs it on every file it reads.
+///
+/// # Errors
+///
+/// - [`SrsSetupError::Validation`] if `P_0` is not the generator
+/// - [`SrsSetupError::PairingCheck`] if the ratio check fails
+pub fn validate_g1_powers(powers: &[G1Affine], tau_g2: G2Affine) -> Result<(), SrsSetupError> {
+    if powers.first() != Some(&G1Affine::generator()) {
+        return Err(SrsSetupError::Validation("tauG1[0] is not the G1 generator".into()));
+    }
+    if powers.len() < 2 {
+        return Ok(());
+    }
+    let mut rng = rand::thread_rng();
+    let r: Vec<Fr> = (0..powers.len() - 1).map(|_| Fr::rand(&mut rng)).collect();
+    let lo: G1Projective = crate::msm::msm(&powers[..powers.len() - 1], &r);
+    let hi: G1Projective = crate::msm::msm(&powers[1..], &r);
+    let lhs = Engine::pairing(lo.into_affine(), tau_g2);
+    let rhs = Engine::pairing(hi.into_affine(), G2Affine::generator());
+    if lhs != rhs {
+        return Err(SrsSetupError::PairingCheck(
+            "tauG1 powers are not consecutive powers of the τ in tauG2[1]".into(),
+        ));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // G2 SRS Loading and Validation
 // ============================================================================
@@ -317,7 +394,7 @@ pub fn load_and_validate_g2_srs(path: impl AsRef<Path>) -> Result<G2Affine, SrsS
     let tau_g2 = if elements.len() >= 2 {
         // Format: [G₂, τ·G₂]
         // Verify first element is generator
-        let g2_gen = <Bn254 as Pairing>::G2::generator();
+        let g2_gen = <Engine as Pairing>::G2::generator();
         if elements[0] != g2_gen {
             return Err(SrsSetupError::Validation(
                 "G2 SRS first element is not the generator (expected [G₂, τ·G₂] format)".into(),
@@ -340,6 +417,116 @@ pub fn load_and_validate_g2_srs(path: impl AsRef<Path>) -> Result<G2Affine, SrsS
     Ok(tau_g2)
 }
 
+// ============================================================================
+// Ethereum KZG ceremony (BLS12-381)
+// ============================================================================
+
+#[cfg(feature = "bls12-381")]
+#[derive(serde::Deserialize)]
+struct EthCeremonyFile {
+    transcripts: Vec<EthTranscript>,
+}
+
+#[cfg(feature = "bls12-381")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EthTranscript {
+    num_g1_powers: usize,
+    powers_of_tau: EthPowersOfTau,
+}
+
+#[cfg(feature = "bls12-381")]
+#[derive(serde::Deserialize)]
+struct EthPowersOfTau {
+    #[serde(rename = "G1Powers")]
+    g1_powers: Vec<String>,
+    #[serde(rename = "G2Powers")]
+    g2_powers: Vec<String>,
+}
+
+#[cfg(feature = "bls12-381")]
+fn decode_hex_point<P: CanonicalDeserialize>(s: &str, what: &str, idx: usize) -> Result<P, SrsSetupError> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))
+        .map_err(|e| SrsSetupError::Deserialize(format!("{what}[{idx}]: bad hex: {e}")))?;
+    P::deserialize_compressed(bytes.as_slice())
+        .map_err(|e| SrsSetupError::Deserialize(format!("{what}[{idx}]: {e}")))
+}
+
+/// Load the Ethereum KZG ceremony output (`transcript.json`, BLS12-381).
+///
+/// # Format
+///
+/// The sequencer's final transcript holds several sub-ceremonies
+/// (`transcripts[i]`). Each has `powersOfTau.G1Powers` / `G2Powers` as
+/// `0x`-prefixed hex of **compressed Zcash-format** points, which is the
+/// encoding Arkworks uses for BLS12-381. The smallest sub-ceremony with at
+/// least `expected_degree + 1` G1 powers is selected.
+///
+/// # Validation Performed
+///
+/// 1. **Deserialization**: every point decodes and is on-curve / in-subgroup
+/// 2. **Degree check**: some sub-ceremony is large enough
+/// 3. **Generator checks**: `G1Powers[0] = G₁`, `G2Powers[0] = G₂`
+/// 4. **Non-identity check**: `τ·G₂` is not the point at infinity
+///
+/// Returns the first `expected_degree + 1` G1 powers and `τ·G₂`, ready for
+/// `pcs::load_srs_g1` / `pcs::load_srs_g2`.
+///
+/// # Errors
+///
+/// - [`SrsSetupError::Io`] if file cannot be read
+/// - [`SrsSetupError::Deserialize`] if the JSON or a point is malformed
+/// - [`SrsSetupError::Validation`] if structural checks fail
+#[cfg(feature = "bls12-381")]
+pub fn load_ethereum_ceremony_json(
+    path: impl AsRef<Path>,
+    expected_degree: usize,
+) -> Result<(Vec<G1Affine>, G2Affine), SrsSetupError> {
+    let text = std::fs::read_to_string(path.as_ref())?;
+    let file: EthCeremonyFile = serde_json::from_str(&text)
+        .map_err(|e| SrsSetupError::Deserialize(format!("ceremony JSON: {e}")))?;
+
+    let need = expected_degree + 1;
+    let tr = file
+        .transcripts
+        .iter()
+        .filter(|t| t.num_g1_powers >= need && t.powers_of_tau.g1_powers.len() >= need)
+        .min_by_key(|t| t.num_g1_powers)
+        .ok_or_else(|| {
+            SrsSetupError::Validation(format!(
+                "no sub-ceremony has {need} G1 powers (largest: {})",
+                file.transcripts.iter().map(|t| t.num_g1_powers).max().unwrap_or(0)
+            ))
+        })?;
+    if tr.powers_of_tau.g2_powers.len() < 2 {
+        return Err(SrsSetupError::Validation("ceremony transcript lacks [τ]G₂".into()));
+    }
+
+    let g1_powers = tr.powers_of_tau.g1_powers[..need]
+        .iter()
+        .enumerate()
+        .map(|(i, h)| decode_hex_point::<G1Affine>(h, "G1Powers", i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let g2_0: G2Affine = decode_hex_point(&tr.powers_of_tau.g2_powers[0], "G2Powers", 0)?;
+    let tau_g2: G2Affine = decode_hex_point(&tr.powers_of_tau.g2_powers[1], "G2Powers", 1)?;
+
+    if g1_powers[0] != <Engine as Pairing>::G1::generator() {
+        return Err(SrsSetupError::Validation(
+            "G1Powers[0] is not the generator (possible corruption or wrong curve)".into(),
+        ));
+    }
+    if g2_0 != <Engine as Pairing>::G2::generator() {
+        return Err(SrsSetupError::Validation("G2Powers[0] is not the generator".into()));
+    }
+    if tau_g2.is_zero() {
+        return Err(SrsSetupError::Validation(
+            "τ·G₂ is the point at infinity (invalid SRS)".into(),
+        ));
+    }
+
+    Ok((g1_powers, tau_g2))
+}
+
 // ============================================================================
 // Development SRS Generation (NOT FOR PRODUCTION)
 // ============================================================================
@@ -399,8 +586,8 @@ pub fn generate_dev_srs(degree: usize) -> (Vec<G1Affine>, G2Affine) {
 
     eprintln!("Generating SRS: degree={}, tau=<deterministic>", degree);
 
-    let g1_gen = <Bn254 as Pairing>::G1::generator();
-    let g2_gen = <Bn254 as Pairing>::G2::generator();
+    let g1_gen = <Engine as Pairing>::G1::generator();
+    let g2_gen = <Engine as Pairing>::G2::generator();
 
     // Compute [τ⁰·G₁, τ¹·G₁, ..., τᵈ·G₁]
     let mut g1_powers = Vec::with_capacity(degree + 1);
@@ -447,7 +634,7 @@ pub fn save_g1_srs(path: impl AsRef<Path>, powers: &[G1Affine]) -> Result<(), Sr
 ///
 /// Saves in `[G₂, τ·G₂]` format for maximum compatibility.
 pub fn save_g2_srs(path: impl AsRef<Path>, tau_g2: G2Affine) -> Result<(), SrsSetupError> {
-    let g2_gen = <Bn254 as Pairing>::G2::generator();
+    let g2_gen = <Engine as Pairing>::G2::generator();
     let elements = vec![g2_gen, tau_g2.into()];
 
     let mut bytes = Vec::new();
@@ -472,9 +659,8 @@ pub fn save_g2_srs(path: impl AsRef<Path>, tau_g2: G2Affine) -> Result<(), SrsSe
 ///
 /// 1. Determine required `.ptau` file from `max_degree`
 /// 2. Download from Hermez/Aztec/Ethereum S3 bucket
-/// 3. Parse `.ptau` format (ceremony-specific)
-/// 4. Convert to Arkworks binary format
-/// 5. Save to `output_dir/{G1,G2}.bin`
+/// 3. Parse `.ptau` format and save `output_dir/{G1,G2}.bin`
+///    (implemented by [`crate::ptau::import`]; only the download is missing)
 ///
 /// # Security
 ///
@@ -489,26 +675,26 @@ pub fn download_perpetual_powers(
     let degree_log2 = (max_degree as f64).log2().ceil() as usize;
 
     // Hermez ceremony URL pattern (example)
-    let url_base = "https://hermez.s3-eu-west-1.amazonaws.com/powersOfTau28_hez_final";
-    let ptau_file = format!("{}_{}.ptau", url_base, degree_log2);
+    let url_base = "https://hermez.s3-eu-west-1.amazonaws.com";
+    let ptau_name = format!("powersOfTau28_hez_final_{}.ptau", degree_log2);
 
     Err(SrsSetupError::Download(format!(
         "Auto-download not yet implemented. Manual download instructions:\n\
          \n\
          1. Download the .ptau file for your degree:\n\
-            {}\n\
+            {}/{}\n\
          \n\
-         2. Convert to Arkworks format using `snarkjs` or our conversion script:\n\
-            scripts/convert_ptau.py {} {}/G1.bin {}/G2.bin\n\
+         2. Import it (checks the points and the ceremony hash):\n\
+            srs_convert ptau {} {}\n\
          \n\
          3. Verify digests match ceremony transcript:\n\
             sha256sum {}/G1.bin {}/G2.bin\n\
          \n\
          See https://github.com/privacy-scaling-explorations/perpetualpowersoftau\n\
          for ceremony details and verification procedures.",
-        ptau_file,
-        ptau_file,
-        output_dir.as_ref().display(),
+        url_base,
+        ptau_name,
+        ptau_name,
         output_dir.as_ref().display(),
         output_dir.as_ref().display(),
         output_dir.as_ref().display(),
@@ -521,31 +707,195 @@ pub fn download_perpetual_powers(
 
 /// Known-good SRS digests from trusted ceremonies.
 ///
-/// Use these to verify loaded SRS matches a specific ceremony. Add entries
-/// as you verify new ceremony outputs.
-#[derive(Debug, Clone)]
+/// An entry is keyed on `(max_degree, g1_digest)`: `g1_digest` is the
+/// prefix digest (`pcs::Srs::g1_prefix_digest(max_degree)`) of the ceremony's
+/// first `max_degree + 1` powers. Any SRS that starts with those powers and
+/// carries the same `[τ]G2` matches, however long it is, and is vouched for
+/// up to `max_degree`. One ceremony can be registered at several degrees.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CeremonyDigests {
     /// Human-readable ceremony name
-    pub name: &'static str,
-    /// Maximum degree supported
+    pub name: Cow<'static, str>,
+    /// Degree covered: the entry vouches for powers `0..=max_degree`
     pub max_degree: usize,
-    /// Expected G1 digest (from `pcs::srs_g1_digest()`)
+    /// Prefix digest of those powers (`pcs::Srs::g1_prefix_digest(max_degree)`)
     pub g1_digest: [u8; 32],
     /// Expected G2 digest (from `pcs::srs_g2_digest()`)
     pub g2_digest: [u8; 32],
 }
 
-/// Registry of known ceremony digests (extend as needed).
-pub const KNOWN_CEREMONIES: &[CeremonyDigests] = &[
-    // Add digests here after verifying ceremony outputs
-    // Example:
-    // CeremonyDigests {
-    //     name: "Hermez Powers of Tau 28 (degree 2^20)",
-    //     max_degree: 1_048_576,
-    //     g1_digest: [0xAB, 0xCD, ...],
-    //     g2_digest: [0x12, 0x34, ...],
-    // },
-];
+/// One registry entry as written in a [`CEREMONY_REGISTRY_ENV`] file.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RegistryEntry {
+    name: String,
+    max_degree: usize,
+    g1_digest: String,
+    g2_digest: String,
+}
+
+impl CeremonyDigests {
+    /// This entry as a JSON object for a [`CEREMONY_REGISTRY_ENV`] file.
+    pub fn to_registry_json(&self) -> String {
+        let entry = RegistryEntry {
+            name: self.name.to_string(),
+            max_degree: self.max_degree,
+            g1_digest: hex::encode(self.g1_digest),
+            g2_digest: hex::encode(self.g2_digest),
+        };
+        serde_json::to_string_pretty(&entry).expect("registry entry serializes")
+    }
+
+    fn from_entry(entry: RegistryEntry) -> Result<Self, SrsSetupError> {
+        let digest = |s: &str, what: &str| -> Result<[u8; 32], SrsSetupError> {
+            let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| {
+                SrsSetupError::Deserialize(format!("{}: bad {} hex: {}", entry.name, what, e))
+            })?;
+            bytes.try_into().map_err(|_| {
+                SrsSetupError::Deserialize(format!("{}: {} must be 32 bytes", entry.name, what))
+            })
+        };
+        Ok(Self {
+            g1_digest: digest(&entry.g1_digest, "g1_digest")?,
+            g2_digest: digest(&entry.g2_digest, "g2_digest")?,
+            max_degree: entry.max_degree,
+            name: Cow::Owned(entry.name),
+        })
+    }
+}
+
+/// Registry of known ceremony digests compiled into the binary.
+///
+/// Entries come from importing the published Hermez files
+/// (`powersOfTau28_hez_final_NN.ptau`) with `srs_convert ptau` and running
+/// `srs_convert digest --max-degree D` on the result for each degree `D` to
+/// vouch for; the file's own challenge hash is checked during import.
+/// Deployments can register further files without a rebuild through
+/// [`CEREMONY_REGISTRY_ENV`].
+///
+/// **Known gap:** no Hermez digests ship yet. They have to be derived from
+/// the real ceremony files, which are not vendored in this repository. Until
+/// entries are added here or through [`CEREMONY_REGISTRY_ENV`],
+/// [`require_known_ceremony`] has nothing to check against and accepts any
+/// SRS (callers report its ceremony as unknown).
+pub const KNOWN_CEREMONIES: &[CeremonyDigests] = &[];
+
+/// Environment variable naming a JSON file of extra registry entries.
+///
+/// The file holds an array of objects shaped like the output of
+/// `srs_convert digest`: `{"name", "max_degree", "g1_digest", "g2_digest"}`
+/// with hex digests.
+pub const CEREMONY_REGISTRY_ENV: &str = "SSZKP_CEREMONY_REGISTRY";
+
+/// Environment variable that lets production builds run with an SRS that is
+/// not in the registry (`1`/`true`). CLIs also accept `--allow-unknown-srs`.
+pub const ALLOW_UNKNOWN_SRS_ENV: &str = "SSZKP_ALLOW_UNKNOWN_SRS";
+
+/// Read a registry file (see [`CEREMONY_REGISTRY_ENV`]).
+pub fn load_ceremony_registry(path: impl AsRef<Path>) -> Result<Vec<CeremonyDigests>, SrsSetupError> {
+    let bytes = std::fs::read(path.as_ref())?;
+    let entries: Vec<RegistryEntry> = serde_json::from_slice(&bytes).map_err(|e| {
+        SrsSetupError::Deserialize(format!("ceremony registry {}: {}", path.as_ref().display(), e))
+    })?;
+    entries.into_iter().map(CeremonyDigests::from_entry).collect()
+}
+
+/// [`KNOWN_CEREMONIES`] plus the entries of the [`CEREMONY_REGISTRY_ENV`] file, if set.
+pub fn ceremony_registry() -> Result<Vec<CeremonyDigests>, SrsSetupError> {
+    let mut all = KNOWN_CEREMONIES.to_vec();
+    if let Some(path) = std::env::var_os(CEREMONY_REGISTRY_ENV) {
+        all.extend(load_ceremony_registry(path)?);
+    }
+    Ok(all)
+}
+
+/// Whether [`ALLOW_UNKNOWN_SRS_ENV`] is set to a true value.
+pub fn allow_unknown_srs_from_env() -> bool {
+    std::env::var(ALLOW_UNKNOWN_SRS_ENV)
+        .map(|s| matches!(s.as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Look up an exact `(g1_digest, g2_digest)` entry in [`ceremony_registry`].
+pub fn identify_ceremony(
+    g1_digest: [u8; 32],
+    g2_digest: [u8; 32],
+) -> Result<Option<CeremonyDigests>, SrsSetupError> {
+    Ok(ceremony_registry()?
+        .into_iter()
+        .find(|c| c.g1_digest == g1_digest && c.g2_digest == g2_digest))
+}
+
+/// The registry entry vouching for `srs` up to `degree`: same `[τ]G2`,
+/// `degree ≤ max_degree ≤ srs.max_degree()`, and `srs` starts with the
+/// entry's powers. Only those prefixes are hashed (smallest first), never the
+/// whole SRS, so a large file-backed SRS is identified in time proportional
+/// to the registered degree.
+pub fn identify_srs(
+    srs: &crate::pcs::Srs,
+    degree: usize,
+) -> Result<Option<CeremonyDigests>, SrsSetupError> {
+    find_srs(ceremony_registry()?, srs, degree)
+}
+
+/// [`crate::pcs::Srs::g1_prefix_digest`], with a file-backed SRS's read
+/// error passed through as is.
+fn prefix_digest(srs: &crate::pcs::Srs, max_degree: usize) -> Result<[u8; 32], SrsSetupError> {
+    srs.g1_prefix_digest(max_degree).map_err(|e| match e {
+        crate::pcs::AggregatorError::SrsRead(e) => e,
+        e => SrsSetupError::Validation(e.to_string()),
+    })
+}
+
+fn find_srs(
+    registry: Vec<CeremonyDigests>,
+    srs: &crate::pcs::Srs,
+    degree: usize,
+) -> Result<Option<CeremonyDigests>, SrsSetupError> {
+    let g2_digest = srs.g2_digest();
+    let mut candidates: Vec<CeremonyDigests> = registry
+        .into_iter()
+        .filter(|c| c.g2_digest == g2_digest && (degree..=srs.max_degree()).contains(&c.max_degree))
+        .collect();
+    candidates.sort_by_key(|c| c.max_degree);
+    for c in candidates {
+        if prefix_digest(srs, c.max_degree)? == c.g1_digest {
+            return Ok(Some(c));
+        }
+    }
+    Ok(None)
+}
+
+/// Like [`identify_srs`], but an unknown SRS is an error unless
+/// `allow_unknown` is set or the registry is empty (there is nothing to
+/// enforce until some ceremony is registered, see [`KNOWN_CEREMONIES`]).
+///
+/// Production entry points (prover, verifier, API server) call this after
+/// loading SRS files with the degree they will use; `allow_unknown` comes
+/// from `--allow-unknown-srs` or [`ALLOW_UNKNOWN_SRS_ENV`].
+pub fn require_known_ceremony(
+    srs: &crate::pcs::Srs,
+    degree: usize,
+    allow_unknown: bool,
+) -> Result<Option<CeremonyDigests>, SrsSetupError> {
+    check_srs(ceremony_registry()?, srs, degree, allow_unknown)
+}
+
+fn check_srs(
+    registry: Vec<CeremonyDigests>,
+    srs: &crate::pcs::Srs,
+    degree: usize,
+    allow_unknown: bool,
+) -> Result<Option<CeremonyDigests>, SrsSetupError> {
+    let enforced = !registry.is_empty();
+    match find_srs(registry, srs, degree)? {
+        Some(c) => Ok(Some(c)),
+        None if allow_unknown || !enforced => Ok(None),
+        None => Err(SrsSetupError::UnknownCeremony {
+            g1_digest: hex::encode(prefix_digest(srs, degree)?),
+            g2_digest: hex::encode(srs.g2_digest()),
+        }),
+    }
+}
 
 /// Verify loaded SRS matches a known ceremony.
 ///
@@ -603,6 +953,51 @@ pub fn verify_ceremony_digests(
 mod tests {
     use super::*;
 
+    #[test]
+    fn registry_file_round_trips_and_gates_unknown_srs() {
+        let known = CeremonyDigests {
+            name: Cow::Borrowed("test ceremony"),
+            max_degree: 15,
+            g1_digest: [0x11; 32],
+            g2_digest: [0x22; 32],
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registry.json");
+        std::fs::write(&path, format!("[{}]", known.to_registry_json())).unwrap();
+        assert_eq!(load_ceremony_registry(&path).unwrap(), vec![known]);
+
+        std::fs::write(&path, r#"[{"name":"x","max_degree":1,"g1_digest":"00","g2_digest":"00"}]"#).unwrap();
+        assert!(load_ceremony_registry(&path).is_err());
+
+        // Entries match by prefix: a longer SRS from the same ceremony is
+        // vouched for up to the registered degree only.
+        let g = <Engine as Pairing>::G1::generator();
+        let tau = Fr::from(5u64);
+        let powers: Vec<G1Affine> = (0..16u64).map(|i| (g * tau.pow([i])).into_affine()).collect();
+        let tau_g2 = (<Engine as Pairing>::G2::generator() * tau).into_affine();
+        let srs = crate::pcs::Srs::new(powers, Some(tau_g2)).unwrap();
+        let entry = CeremonyDigests {
+            name: Cow::Borrowed("prefix"),
+            max_degree: 7,
+            g1_digest: srs.g1_prefix_digest(7).unwrap(),
+            g2_digest: srs.g2_digest(),
+        };
+        assert_eq!(find_srs(vec![entry.clone()], &srs, 3).unwrap(), Some(entry.clone()));
+        assert_eq!(find_srs(vec![entry.clone()], &srs, 8).unwrap(), None);
+        let other = crate::pcs::Srs::new(vec![G1Affine::from(g); 16], Some(tau_g2)).unwrap();
+        assert_eq!(find_srs(vec![entry.clone()], &other, 3).unwrap(), None);
+
+        // A non-empty registry refuses an unknown SRS unless overridden; an
+        // empty one has nothing to enforce.
+        assert_eq!(check_srs(vec![entry.clone()], &srs, 3, false).unwrap(), Some(entry.clone()));
+        assert!(matches!(
+            check_srs(vec![entry.clone()], &srs, 8, false),
+            Err(SrsSetupError::UnknownCeremony { .. })
+        ));
+        assert!(check_srs(vec![entry], &srs, 8, true).unwrap().is_none());
+        assert!(check_srs(Vec::new(), &srs, 8, false).unwrap().is_none());
+    }
+
     #[test]
     #[cfg(feature = "dev-srs")]
     fn dev_srs_generation_produces_valid_structure() {
@@ -611,7 +1006,7 @@ mod tests {
 
         // Structural checks
         assert_eq!(g1_powers.len(), degree + 1);
-        assert_eq!(g1_powers[0], <Bn254 as Pairing>::G1::generator());
+        assert_eq!(g1_powers[0], <Engine as Pairing>::G1::generator());
         assert!(!tau_g2.is_zero());
     }
 
@@ -689,4 +1084,41 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("empty"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[cfg(all(feature = "bls12-381", feature = "dev-srs"))]
+    fn loads_ethereum_ceremony_transcript() {
+        use tempfile::NamedTempFile;
+
+        let hex_of = |p: &dyn Fn(&mut Vec<u8>)| {
+            let mut v = Vec::new();
+            p(&mut v);
+            format!("0x{}", hex::encode(v))
+        };
+        let (g1_powers, tau_g2) = generate_dev_srs(31);
+        let g2_gen = <Engine as Pairing>::G2::generator().into_affine();
+        let sub = |n: usize| {
+            let g1: Vec<String> = g1_powers[..n]
+                .iter()
+                .map(|p| hex_of(&|v| p.serialize_compressed(v).unwrap()))
+                .collect();
+            let g2: Vec<String> = [g2_gen, tau_g2]
+                .iter()
+                .map(|p| hex_of(&|v| p.serialize_compressed(v).unwrap()))
+                .collect();
+            serde_json::json!({
+                "numG1Powers": n,
+                "numG2Powers": 2,
+                "powersOfTau": { "G1Powers": g1, "G2Powers": g2 },
+            })
+        };
+        let doc = serde_json::json!({ "transcripts": [sub(8), sub(32)] });
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), doc.to_string()).unwrap();
+
+        let (g1, g2) = load_ethereum_ceremony_json(file.path(), 16).unwrap();
+        assert_eq!(g1, g1_powers[..17].to_vec());
+        assert_eq!(g2, tau_g2);
+        assert!(load_ethereum_ceremony_json(file.path(), 32).is_err());
+    }
+}
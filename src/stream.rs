@@ -13,6 +13,9 @@
 //! - NEW: `SliceTileStream` — a zero-copy adapter to turn a slice into tiles.
 //! - NEW: `BorrowingRestreamer` — an optional, reference-based restreaming API
 //!   to avoid cloning when the source can yield `&Row` (keeps `Restreamer` intact).
+//! - NEW: `TryRestreamer` — fallible restreaming for sources that can fail
+//!   mid-stream (rows are `Result<Row, WitnessError>`), with
+//!   `LatchedRestreamer` adapting it to the block evaluators.
 //!
 //! # Rationale
 //! The whitepaper’s sublinear-space design relies on *time-ordered* blocks
@@ -76,7 +79,7 @@ pub fn block_count_r(n_rows: usize, b_blk: usize) -> Result<usize, StreamError>
     if b_blk == 0 {
         return Err(StreamError::BadBlockSize(b_blk));
     }
-    Ok((n_rows + b_blk - 1) / b_blk)
+    Ok(n_rows.div_ceil(b_blk))
 }
 
 /// Back-compat wrapper (panics on error).
@@ -164,6 +167,7 @@ pub fn traverse_blocks(_t: Traversal, b_cnt: usize) -> impl Iterator<Item = Bloc
 }
 
 /// A tiny guard to help enforce **strictly increasing** block order.
+#[derive(Default)]
 pub struct MonotoneBlockGuard {
     prev: Option<BlockIdx>,
 }
@@ -330,12 +334,133 @@ impl BorrowingRestreamer for Vec<crate::air::Row> {
     }
 }
 
+// ============================================================================
+// Fallible restreaming — witness sources that can fail mid-stream
+// ============================================================================
+
+/// A witness source failed to produce a row. `src` names the source (e.g. a
+/// file path) and `row` is the global row index.
+#[derive(Debug, thiserror::Error)]
+pub enum WitnessError {
+    #[error("{src}: row {row}: {err}")]
+    Io {
+        src: String,
+        row: usize,
+        #[source]
+        err: std::io::Error,
+    },
+    #[error("{src}: row {row}: {msg}")]
+    Parse { src: String, row: usize, msg: String },
+    #[error("row {row}: {got} registers, expected k={expected}")]
+    Width { row: usize, expected: usize, got: usize },
+}
+
+impl WitnessError {
+    /// Global index of the row that failed.
+    pub fn row(&self) -> usize {
+        match self {
+            Self::Io { row, .. } | Self::Parse { row, .. } | Self::Width { row, .. } => *row,
+        }
+    }
+}
+
+/// A source that can re-stream rows but may fail while doing so (file I/O,
+/// malformed input). Every [`Restreamer`] is a `TryRestreamer` that never fails.
+pub trait TryRestreamer {
+    type Item;
+
+    /// Total number of rows `T` available from this source.
+    fn len_rows(&self) -> usize;
+
+    /// Produce a fresh iterator over rows in `[start, end)`. The stream should
+    /// end after yielding an `Err`.
+    fn try_stream_rows(
+        &self,
+        start: RowIdx,
+        end: RowIdx,
+    ) -> Box<dyn Iterator<Item = Result<Self::Item, WitnessError>> + '_>;
+}
+
+impl<R: Restreamer + ?Sized> TryRestreamer for R {
+    type Item = R::Item;
+
+    #[inline]
+    fn len_rows(&self) -> usize {
+        Restreamer::len_rows(self)
+    }
+
+    fn try_stream_rows(
+        &self,
+        start: RowIdx,
+        end: RowIdx,
+    ) -> Box<dyn Iterator<Item = Result<Self::Item, WitnessError>> + '_> {
+        Box::new(self.stream_rows(start, end).map(Ok))
+    }
+}
+
+/// Adapts a [`TryRestreamer`] to the infallible [`Restreamer`] interface the
+/// block evaluators consume: the first error ends the stream and is latched,
+/// and every later `stream_rows` call yields nothing. Callers check
+/// [`Self::check`] after each pass so a short stream is never mistaken for a
+/// short trace.
+pub struct LatchedRestreamer<'a, R: ?Sized> {
+    inner: &'a R,
+    err: std::cell::RefCell<Option<WitnessError>>,
+}
+
+impl<'a, R: TryRestreamer + ?Sized> LatchedRestreamer<'a, R> {
+    /// Wrap `inner` with no error latched.
+    pub fn new(inner: &'a R) -> Self {
+        Self { inner, err: std::cell::RefCell::new(None) }
+    }
+
+    /// Latch an error found while consuming a pass (a row the source yielded
+    /// but the caller rejects). The first latched error wins.
+    pub fn latch(&self, e: WitnessError) {
+        self.err.borrow_mut().get_or_insert(e);
+    }
+
+    /// Take the latched error, if any pass hit one.
+    pub fn check(&self) -> Result<(), WitnessError> {
+        match self.err.borrow_mut().take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, R: TryRestreamer + ?Sized> Restreamer for LatchedRestreamer<'a, R> {
+    type Item = R::Item;
+
+    #[inline]
+    fn len_rows(&self) -> usize {
+        self.inner.len_rows()
+    }
+
+    fn stream_rows(
+        &self,
+        start: RowIdx,
+        end: RowIdx,
+    ) -> Box<dyn Iterator<Item = Self::Item> + '_> {
+        if self.err.borrow().is_some() {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(self.inner.try_stream_rows(start, end).map_while(move |r| match r {
+            Ok(row) => Some(row),
+            Err(e) => {
+                self.err.borrow_mut().get_or_insert(e);
+                None
+            }
+        }))
+    }
+}
+
 // ============================================================================
 // Generic tiling helpers (evaluation/coefficients, safe chunking)
 // ============================================================================
 
 /// Chunk a slice into **low→high** tiles of (at most) `tile` elements.
-pub fn chunks_low_to_high<'a, T>(v: &'a [T], tile: usize) -> impl Iterator<Item = &'a [T]> {
+pub fn chunks_low_to_high<T>(v: &[T], tile: usize) -> impl Iterator<Item = &[T]> {
     assert!(tile > 0, "tile size must be positive");
     let n = v.len();
     let mut start = 0usize;
@@ -351,7 +476,7 @@ pub fn chunks_low_to_high<'a, T>(v: &'a [T], tile: usize) -> impl Iterator<Item
 }
 
 /// Chunk a slice into **high→low** tiles of (at most) `tile` elements.
-pub fn chunks_high_to_low<'a, T>(v: &'a [T], tile: usize) -> impl Iterator<Item = &'a [T]> {
+pub fn chunks_high_to_low<T>(v: &[T], tile: usize) -> impl Iterator<Item = &[T]> {
     assert!(tile > 0, "tile size must be positive");
     let n = v.len();
     let mut end = n;
@@ -442,7 +567,7 @@ pub(crate) fn pow_usize(base: F, mut exp: usize) -> F {
     let mut b = base;
     while exp > 0 {
         if (exp & 1) == 1 {
-            acc = acc * b;
+            acc *= b;
         }
         b = b * b;
         exp >>= 1;
@@ -467,9 +592,9 @@ pub fn horner_eval_stream<S: CoeffTileStream>(mut s: S, z: F) -> F {
         for &ai in tile.iter().rev() {
             local = local * z + ai;
         }
-        acc = acc + pow * local;
+        acc += pow * local;
         // Advance pow by tile length: pow *= z^{tile.len()}
-        pow = pow * pow_usize(z, tile.len());
+        pow *= pow_usize(z, tile.len());
     }
     acc
 }
@@ -0,0 +1,71 @@
+//! Shared test fixtures
+//!
+//! Most end-to-end tests prove the same tiny witness: `k` registers, row `i`
+//! holding `i + 1, 3i + 2, 5i + 3, …`, on a length-`n` domain with `b_blk = 4`.
+//! [`tiny_prove_fixture`] builds it once; tests override only what they vary.
+
+use ark_ff::One;
+
+use crate::{
+    air::{AirSpec, Row},
+    domain::{root_of_unity, Domain, TransformConfig},
+    pcs::{Basis, PcsParams, Srs},
+    ProveParams, VerifyParams, F,
+};
+
+/// AIR, witness rows and matching prover/verifier parameters.
+pub(crate) struct TinyFixture {
+    pub air: AirSpec,
+    pub rows: Vec<Row>,
+    pub pp: ProveParams,
+    pub vp: VerifyParams,
+}
+
+/// Two registers, `n` rows, no SRS (transparent backends), default transforms.
+pub(crate) fn tiny_prove_fixture(n: usize) -> TinyFixture {
+    let domain = Domain::new_with_c(n, root_of_unity(n).expect("test domain size"), F::one());
+    let pcs_wires = PcsParams { max_degree: n - 1, basis: Basis::Evaluation, srs: None };
+    let pcs_coeff = pcs_wires.clone().with_basis(Basis::Coefficient);
+    let pp = ProveParams {
+        domain: domain.clone(),
+        pcs_wires: pcs_wires.clone(),
+        pcs_coeff: pcs_coeff.clone(),
+        b_blk: 4,
+        transform: Default::default(),
+    };
+    let vp = VerifyParams { domain, pcs_wires, pcs_coeff };
+    TinyFixture { air: AirSpec::with_cyclic_sigma(2), rows: Vec::new(), pp, vp }.with_rows(n)
+}
+
+impl TinyFixture {
+    /// `t` witness rows (fewer than `n` are zero-padded by the prover).
+    pub fn with_rows(mut self, t: usize) -> Self {
+        let k = self.air.k as u64;
+        self.rows = (0..t as u64)
+            .map(|i| Row { regs: (0..k).map(|j| F::from((2 * j + 1) * i + j + 1)).collect() })
+            .collect();
+        self
+    }
+
+    /// `k` registers per row.
+    #[cfg_attr(feature = "bls12-381", allow(dead_code))] // only BN254 tests vary `k`
+    pub fn with_k(mut self, k: usize) -> Self {
+        self.air = AirSpec::with_cyclic_sigma(k);
+        let t = self.rows.len();
+        self.with_rows(t)
+    }
+
+    /// Commit (and verify) against `srs` with both bases.
+    pub fn with_srs(mut self, srs: &Srs) -> Self {
+        for p in [&mut self.pp.pcs_wires, &mut self.pp.pcs_coeff, &mut self.vp.pcs_wires, &mut self.vp.pcs_coeff] {
+            p.srs = Some(srs.clone());
+        }
+        self
+    }
+
+    /// Run the prover's blocked transforms with `transform`.
+    pub fn with_transform(mut self, transform: TransformConfig) -> Self {
+        self.pp.transform = transform;
+        self
+    }
+}
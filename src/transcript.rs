@@ -19,6 +19,11 @@
 //!   counters or sizes (encoded big-endian).
 //! - `absorb_vec_commitments[_l]`: helper to bind a *sequence* of PCS
 //!   commitments in a single, length-delimited item.
+//! - [`TranscriptHash`]: the hash is pluggable. [`Blake3Hash`] (default),
+//!   [`Keccak256Hash`] (EVM) and `PoseidonHash` (BN254 circuits) all see
+//!   the **identical** byte schedule; only compression and the challenge
+//!   squeeze differ. The choice is recorded in `ProofHeader::fs_hash` and
+//!   verifiers rebuild the transcript with [`Transcript::with_hash`].
 //!
 //! ### Rustdoc examples
 //! The FS labels are **deterministic**: changing the label changes the
@@ -54,16 +59,28 @@
 //!
 //! assert_eq!(a, b);
 //! ```
+//!
+//! [`TranscriptHash`]: crate::transcript::TranscriptHash
+//! [`Blake3Hash`]: crate::transcript::Blake3Hash
+//! [`Keccak256Hash`]: crate::transcript::Keccak256Hash
+//! [`Transcript::with_hash`]: crate::transcript::Transcript::with_hash
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // This module is heavily documented but kept permissive for internal items.
 
 use ark_ff::PrimeField; // needed for from_le_bytes_mod_order
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use blake3::Hasher;
+use sha3::{Digest, Keccak256};
 use std::io::Read; // needed for OutputReader::read
 
-use crate::{pcs, F, ProofHeader};
+use crate::{
+    pcs,
+    transcript_trace::{TraceEvent, TranscriptTrace},
+    F, ProofHeader,
+};
 
 /// Canonical labels to avoid typos across prover/verifier.
 ///
@@ -81,6 +98,16 @@ pub enum FsLabel {
     Gamma,
     Alpha,
     EvalPoints,
+    FriRoot,
+    FriOpenPoint,
+    FriFold,
+    FriFinal,
+    FriQuery,
+    IpaOpenPoint,
+    IpaRound,
+    IpaChallenge,
+    OpeningEvals,
+    OpeningBatch,
 }
 
 impl FsLabel {
@@ -96,18 +123,276 @@ impl FsLabel {
             FsLabel::Gamma => "gamma",
             FsLabel::Alpha => "alpha",
             FsLabel::EvalPoints => "eval_points",
+            FsLabel::FriRoot => "fri_root",
+            FsLabel::FriOpenPoint => "fri_open_point",
+            FsLabel::FriFold => "fri_fold",
+            FsLabel::FriFinal => "fri_final",
+            FsLabel::FriQuery => "fri_query",
+            FsLabel::IpaOpenPoint => "ipa_open_point",
+            FsLabel::IpaRound => "ipa_round",
+            FsLabel::IpaChallenge => "ipa_challenge",
+            FsLabel::OpeningEvals => "opening_evals",
+            FsLabel::OpeningBatch => "opening_batch",
+        }
+    }
+}
+
+/// Hash function driving the transcript (recorded in the proof header).
+///
+/// Each variant has a [`TranscriptHash`] implementation in this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FsHash {
+    /// BLAKE3 with XOF challenge expansion (default; fastest natively).
+    #[default]
+    Blake3,
+    /// Keccak-256 (cheap on the EVM; used by the Solidity verifier).
+    Keccak256,
+    /// Poseidon over BN254 (cheap inside circuits, for recursion).
+    Poseidon,
+}
+
+impl FsHash {
+    /// Stable lowercase name (CLI flags, API payloads).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FsHash::Blake3 => "blake3",
+            FsHash::Keccak256 => "keccak256",
+            FsHash::Poseidon => "poseidon",
+        }
+    }
+
+    /// Whether this build can run the hash (Poseidon is BN254-only).
+    pub fn is_available(&self) -> bool {
+        !(cfg!(feature = "bls12-381") && *self == FsHash::Poseidon)
+    }
+}
+
+impl std::str::FromStr for FsHash {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "blake3" => Ok(FsHash::Blake3),
+            "keccak" | "keccak256" => Ok(FsHash::Keccak256),
+            "poseidon" => Ok(FsHash::Poseidon),
+            other => Err(format!(
+                "unknown transcript hash `{other}` (expected blake3|keccak256|poseidon)"
+            )),
+        }
+    }
+}
+
+impl CanonicalSerialize for FsHash {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut w: W,
+        _cm: Compress,
+    ) -> Result<(), SerializationError> {
+        let byte = match self {
+            FsHash::Blake3 => 0u8,
+            FsHash::Keccak256 => 1u8,
+            FsHash::Poseidon => 2u8,
+        };
+        w.write_all(&[byte])?;
+        Ok(())
+    }
+    fn serialized_size(&self, _cm: Compress) -> usize {
+        1
+    }
+}
+impl CanonicalDeserialize for FsHash {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut r: R,
+        _cm: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut b = [0u8; 1];
+        r.read_exact(&mut b)?;
+        match b[0] {
+            0 => Ok(FsHash::Blake3),
+            1 => Ok(FsHash::Keccak256),
+            2 => Ok(FsHash::Poseidon),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+impl Valid for FsHash {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+// ------------------------ Hash backends ------------------------
+
+/// Hash function behind a [`Transcript`].
+///
+/// The transcript feeds every backend the **same** byte stream (preamble,
+/// `item:` records, challenge DSTs); a backend only decides how bytes are
+/// compressed and how a (cloned) state is squeezed into field elements.
+pub trait TranscriptHash: Clone + Send + 'static {
+    /// Identifier recorded in `ProofHeader::fs_hash`.
+    const KIND: FsHash;
+    /// Absorb raw bytes.
+    fn update(&mut self, bytes: &[u8]);
+    /// Consume the state and derive `k` field elements.
+    fn squeeze(self, k: usize) -> Vec<F>;
+}
+
+/// BLAKE3 with XOF expansion (the original transcript hash).
+#[derive(Clone, Default)]
+pub struct Blake3Hash(Hasher);
+
+impl TranscriptHash for Blake3Hash {
+    const KIND: FsHash = FsHash::Blake3;
+
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// XOF → `k * 64` bytes, each chunk reduced little-endian.
+    fn squeeze(self, k: usize) -> Vec<F> {
+        let mut xof = self.0.finalize_xof();
+        let mut out = Vec::with_capacity(k);
+        let mut buf = [0u8; 64];
+        for _ in 0..k {
+            let _ = xof.read(&mut buf);
+            out.push(F::from_le_bytes_mod_order(&buf));
+        }
+        out
+    }
+}
+
+/// Keccak-256 (cheap on the EVM).
+#[derive(Clone, Default)]
+pub struct Keccak256Hash(Keccak256);
+
+impl TranscriptHash for Keccak256Hash {
+    const KIND: FsHash = FsHash::Keccak256;
+
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        Digest::update(&mut self.0, bytes);
+    }
+
+    /// Keccak has no XOF, so each element takes two 32-byte blocks
+    /// `keccak(state ‖ u64be(i))`, `i = 2e, 2e+1`, reduced big-endian.
+    ///
+    /// This matches what a Solidity verifier computes with
+    /// `keccak256(abi.encodePacked(buf, dst, uint64(i)))` and
+    /// `addmod(mulmod(h0, 2^256 mod r, r), h1, r)`.
+    fn squeeze(self, k: usize) -> Vec<F> {
+        let mut out = Vec::with_capacity(k);
+        let mut buf = [0u8; 64];
+        for e in 0..k {
+            for half in 0..2 {
+                let mut hi = self.0.clone();
+                Digest::update(&mut hi, ((2 * e + half) as u64).to_be_bytes());
+                buf[32 * half..32 * (half + 1)].copy_from_slice(&hi.finalize());
+            }
+            out.push(F::from_be_bytes_mod_order(&buf));
+        }
+        out
+    }
+}
+
+/// Poseidon over BN254 (circom parameters, `x⁵`, width 3) as a 2→1
+/// compression function, for transcripts replayed inside circuits.
+///
+/// Bytes are packed big-endian into 31-byte chunks (always `< r`) and chained
+/// as `s ← H(s, chunk)` from `s = 0`. Squeezing closes the stream with the
+/// final partial chunk (possibly empty) and the total byte length, then
+/// outputs `H(s, i)` for `i = 0..k`. Outputs are field elements already, so
+/// there is no modular-reduction bias.
+#[cfg(not(feature = "bls12-381"))]
+#[derive(Clone, Default)]
+pub struct PoseidonHash {
+    state: F,
+    pending: Vec<u8>,
+    total: u64,
+}
+
+#[cfg(not(feature = "bls12-381"))]
+impl PoseidonHash {
+    /// Bytes per absorbed field element.
+    const CHUNK: usize = 31;
+
+    fn compress(a: F, b: F) -> F {
+        use light_poseidon::{parameters::bn254_x5, Poseidon, PoseidonHasher, PoseidonParameters};
+        use std::sync::OnceLock;
+
+        // Parsing the circom constants dominates a fresh `new_circom(2)`, so
+        // parse them once; the per-call copy is a few hundred field elements.
+        static PARAMS: OnceLock<PoseidonParameters<F>> = OnceLock::new();
+        let p = PARAMS.get_or_init(|| {
+            bn254_x5::get_poseidon_parameters::<F>(3).expect("circom Poseidon(2) parameters")
+        });
+        let params = PoseidonParameters::new(
+            p.ark.clone(),
+            p.mds.clone(),
+            p.full_rounds,
+            p.partial_rounds,
+            p.width,
+            p.alpha,
+        );
+        Poseidon::new(params).hash(&[a, b]).expect("two inputs for width 3")
+    }
+}
+
+#[cfg(not(feature = "bls12-381"))]
+impl TranscriptHash for PoseidonHash {
+    const KIND: FsHash = FsHash::Poseidon;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.total += bytes.len() as u64;
+        self.pending.extend_from_slice(bytes);
+        let full = self.pending.len() / Self::CHUNK * Self::CHUNK;
+        for chunk in self.pending[..full].chunks_exact(Self::CHUNK) {
+            self.state = Self::compress(self.state, F::from_be_bytes_mod_order(chunk));
+        }
+        self.pending.drain(..full);
+    }
+
+    fn squeeze(self, k: usize) -> Vec<F> {
+        let s = Self::compress(self.state, F::from_be_bytes_mod_order(&self.pending));
+        let s = Self::compress(s, F::from(self.total));
+        (0..k as u64).map(|i| Self::compress(s, F::from(i))).collect()
+    }
+}
+
+/// Object-safe view of a [`TranscriptHash`] so the transcript can pick its
+/// hash at runtime (from a proof header) without becoming generic.
+trait DynHash: Send {
+    fn update(&mut self, bytes: &[u8]);
+    fn squeeze_after(&self, suffix: &[&[u8]], k: usize) -> Vec<F>;
+}
+
+impl<H: TranscriptHash> DynHash for H {
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        TranscriptHash::update(self, bytes)
+    }
+
+    fn squeeze_after(&self, suffix: &[&[u8]], k: usize) -> Vec<F> {
+        let mut h = self.clone();
+        for part in suffix {
+            TranscriptHash::update(&mut h, part);
         }
+        h.squeeze(k)
     }
 }
 
-/// Fiat–Shamir transcript with domain separation (BLAKE3-based).
+/// Fiat–Shamir transcript with domain separation over a pluggable hash.
 pub struct Transcript {
     /// Domain-separation label for this transcript instance.
     label: &'static str,
-    /// Running hash state (BLAKE3).
-    hasher: Hasher,
+    /// Running hash state.
+    hasher: Box<dyn DynHash>,
+    /// Which hash `hasher` is.
+    kind: FsHash,
     /// Monotone counter for challenge derivations.
     ctr: u64,
+    /// Recorded absorbs/challenges, when recording is enabled.
+    trace: Option<TranscriptTrace>,
 }
 
 impl Transcript {
@@ -115,25 +400,75 @@ impl Transcript {
     ///
     /// The label distinguishes independent FS domains (e.g., proof types).
     pub fn new(label: &'static str) -> Self {
-        let mut hasher = Hasher::new();
+        Self::with_hasher(label, Blake3Hash::default())
+    }
+
+    /// Create a new transcript driven by the hash named in a header.
+    ///
+    /// Panics if `hash` is not available in this build (see [`FsHash::is_available`]).
+    pub fn with_hash(label: &'static str, hash: FsHash) -> Self {
+        match hash {
+            FsHash::Blake3 => Self::with_hasher(label, Blake3Hash::default()),
+            FsHash::Keccak256 => Self::with_hasher(label, Keccak256Hash::default()),
+            #[cfg(not(feature = "bls12-381"))]
+            FsHash::Poseidon => Self::with_hasher(label, PoseidonHash::default()),
+            #[cfg(feature = "bls12-381")]
+            FsHash::Poseidon => panic!("Poseidon transcript is only available on BN254"),
+        }
+    }
+
+    /// Create a new transcript over an explicit hash backend.
+    pub fn with_hasher<H: TranscriptHash>(label: &'static str, hasher: H) -> Self {
+        let mut hasher: Box<dyn DynHash> = Box::new(hasher);
         // Domain separation preamble: fixed prefix + label.
         hasher.update(b"SSZKP.transcript.v1");
         hasher.update(label.as_bytes());
-        Self { label, hasher, ctr: 0 }
+        Self { label, hasher, kind: H::KIND, ctr: 0, trace: None }
+    }
+
+    /// Hash driving this transcript.
+    #[inline]
+    pub fn hash_kind(&self) -> FsHash {
+        self.kind
+    }
+
+    // ---------------------------- Recording -----------------------------------
+
+    /// Start logging absorbs and challenges (see `transcript_trace`).
+    ///
+    /// Call right after construction to capture the full schedule; events
+    /// before this call are not recorded.
+    pub fn start_recording(&mut self) {
+        if self.trace.is_none() {
+            self.trace = Some(TranscriptTrace::new(self.label, self.kind.as_str()));
+        }
+    }
+
+    /// The trace recorded so far, if recording is enabled.
+    pub fn trace(&self) -> Option<&TranscriptTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Stop recording and return the trace.
+    pub fn take_trace(&mut self) -> Option<TranscriptTrace> {
+        self.trace.take()
     }
 
     // ---------------------------- Absorb (public) -----------------------------
 
-    /// Absorb a PCS commitment using **compressed G1** encoding (enum label).
+    /// Absorb a PCS commitment using its **compressed canonical** encoding (enum label).
+    ///
+    /// For KZG this is the compressed G1 point; other backends absorb their own
+    /// commitment encoding under the same label.
     #[inline]
-    pub fn absorb_commitment_l(&mut self, label: FsLabel, c: &pcs::Commitment) {
+    pub fn absorb_commitment_l<C: CanonicalSerialize>(&mut self, label: FsLabel, c: &C) {
         self.absorb_commitment(label.as_str(), c)
     }
 
-    /// Absorb a PCS commitment using **compressed G1** encoding (legacy string).
-    pub fn absorb_commitment(&mut self, label: &'static str, c: &pcs::Commitment) {
-        let mut bytes = Vec::with_capacity(48); // BN254 compressed G1 is ~48 bytes
-        c.0.serialize_compressed(&mut bytes).expect("serialize G1");
+    /// Absorb a PCS commitment using its **compressed canonical** encoding (legacy string).
+    pub fn absorb_commitment<C: CanonicalSerialize>(&mut self, label: &'static str, c: &C) {
+        let mut bytes = Vec::with_capacity(c.compressed_size());
+        c.serialize_compressed(&mut bytes).expect("serialize commitment");
         self.absorb_bytes(label, &bytes);
     }
 
@@ -174,6 +509,9 @@ impl Transcript {
         self.hasher.update(&(bytes.len() as u64).to_be_bytes());
         self.hasher.update(b":data:");
         self.hasher.update(bytes);
+        if let Some(t) = &mut self.trace {
+            t.events.push(TraceEvent::absorb(label, bytes));
+        }
     }
 
     /// Absorb a field element `F` using compressed canonical serialization.
@@ -184,6 +522,16 @@ impl Transcript {
         self.absorb_bytes_l(label, &bytes);
     }
 
+    /// Absorb a slice of field elements as one item: the concatenation of
+    /// their compressed canonical encodings.
+    pub fn absorb_scalars_l(&mut self, label: FsLabel, fs: &[F]) {
+        let mut bytes = Vec::with_capacity(fs.len() * 32);
+        for f in fs {
+            f.serialize_compressed(&mut bytes).expect("serialize field");
+        }
+        self.absorb_bytes_l(label, &bytes);
+    }
+
     /// Absorb a big-endian counter (e.g., sizes, indices) with the given label.
     ///
     /// This is a small convenience wrapper to avoid ad-hoc endian/width choices
@@ -220,7 +568,8 @@ impl Transcript {
 
     /// Derive a single field challenge `F` (legacy string).
     pub fn challenge_f(&mut self, label: &'static str) -> F {
-        let out = hash_to_field(&self.hasher, self.label, label, self.ctr, 1);
+        let out = hash_to_field(&*self.hasher, self.label, label, self.ctr, 1);
+        self.record_challenge(label, &out);
         self.ctr = self.ctr.wrapping_add(1);
         out[0]
     }
@@ -233,10 +582,18 @@ impl Transcript {
 
     /// Derive `k` field challenges (legacy string).
     pub fn challenge_points(&mut self, label: &'static str, k: usize) -> Vec<F> {
-        let out = hash_to_field(&self.hasher, self.label, label, self.ctr, k);
+        let out = hash_to_field(&*self.hasher, self.label, label, self.ctr, k);
+        self.record_challenge(label, &out);
         self.ctr = self.ctr.wrapping_add(1);
         out
     }
+
+    #[inline]
+    fn record_challenge(&mut self, label: &str, out: &[F]) {
+        if let Some(t) = &mut self.trace {
+            t.events.push(TraceEvent::challenge(label, self.ctr, out));
+        }
+    }
 }
 
 // ------------------------ Internals ------------------------
@@ -247,30 +604,88 @@ impl Transcript {
 /// challenge derivation is a *pure function* of the absorb schedule and the
 /// (label, counter) tuple.
 fn hash_to_field(
-    base: &Hasher,
+    base: &dyn DynHash,
     tlabel: &'static str,
     label: &'static str,
     ctr: u64,
     k: usize,
 ) -> Vec<F> {
-    let mut h = base.clone();
     // Challenge DST (stable and explicit).
-    h.update(b"challenge:");
-    h.update(b"SSZKP.v1");
-    h.update(b":tlabel:");
-    h.update(tlabel.as_bytes());
-    h.update(b":label:");
-    h.update(label.as_bytes());
-    h.update(b":ctr:");
-    h.update(&ctr.to_be_bytes());
-
-    // XOF → k * 64 bytes, then reduce to field (little-endian).
-    let mut xof = h.finalize_xof();
-    let mut out = Vec::with_capacity(k);
-    let mut buf = [0u8; 64];
-    for _ in 0..k {
-        let _ = xof.read(&mut buf);
-        out.push(F::from_le_bytes_mod_order(&buf));
-    }
-    out
+    base.squeeze_after(
+        &[
+            b"challenge:",
+            b"SSZKP.v1",
+            b":tlabel:",
+            tlabel.as_bytes(),
+            b":label:",
+            label.as_bytes(),
+            b":ctr:",
+            &ctr.to_be_bytes(),
+        ],
+        k,
+    )
+}
+
+#[cfg(all(test, not(feature = "bls12-381")))]
+mod tests {
+    use super::*;
+    use crate::{
+        ipa::Ipa,
+        scheduler::{Prover, Verifier, VerifySchedError},
+        test_util::{tiny_prove_fixture, TinyFixture},
+    };
+
+    fn run(hash: FsHash) -> Vec<F> {
+        let mut t = Transcript::with_hash("test", hash);
+        t.absorb_bytes_l(FsLabel::ProtocolHeader, &[7u8; 100]);
+        t.absorb_counter_l(FsLabel::WireCommit, 3);
+        let mut out = vec![t.challenge_f_l(FsLabel::Beta)];
+        out.extend(t.challenge_points_l(FsLabel::EvalPoints, 2));
+        out
+    }
+
+    #[test]
+    fn hashes_are_deterministic_and_distinct() {
+        let all = [FsHash::Blake3, FsHash::Keccak256, FsHash::Poseidon];
+        for h in all {
+            assert_eq!(run(h), run(h));
+            assert_eq!(Transcript::with_hash("x", h).hash_kind(), h);
+        }
+        assert_ne!(run(FsHash::Blake3), run(FsHash::Keccak256));
+        assert_ne!(run(FsHash::Keccak256), run(FsHash::Poseidon));
+
+        // Poseidon chunking must not depend on how bytes are split.
+        let data: Vec<u8> = (0..97u8).collect();
+        let mut whole = PoseidonHash::default();
+        TranscriptHash::update(&mut whole, &data);
+        let mut split = PoseidonHash::default();
+        for piece in data.chunks(5) {
+            TranscriptHash::update(&mut split, piece);
+        }
+        assert_eq!(whole.squeeze(2), split.squeeze(2));
+
+        // The cached parameters are circom's Poseidon(2).
+        use light_poseidon::{Poseidon, PoseidonHasher};
+        let (a, b) = (F::from(1u64), F::from(2u64));
+        let fresh = Poseidon::<F>::new_circom(2).unwrap().hash(&[a, b]).unwrap();
+        assert_eq!(PoseidonHash::compress(a, b), fresh);
+    }
+
+    #[test]
+    fn scheduler_roundtrip_per_hash() {
+        let TinyFixture { air, rows, pp, vp } = tiny_prove_fixture(16).with_k(1);
+
+        for h in [FsHash::Keccak256, FsHash::Poseidon] {
+            let proof = Prover::with_pcs(&air, &pp, Ipa).with_fs_hash(h).prove_with_restreamer(&rows).unwrap();
+            assert_eq!(proof.header.fs_hash, h);
+            Verifier::with_pcs(&vp, Ipa).verify(&proof).unwrap();
+
+            let mut relabeled = proof.clone();
+            relabeled.header.fs_hash = FsHash::Blake3;
+            assert!(matches!(
+                Verifier::with_pcs(&vp, Ipa).verify(&relabeled),
+                Err(VerifySchedError::TranscriptMismatch)
+            ));
+        }
+    }
 }
@@ -0,0 +1,210 @@
+//! Fiat–Shamir transcript traces (recording, JSON, diff)
+//!
+//! When another implementation (e.g. a TypeScript verifier) disagrees with
+//! `Verifier::verify`, the quickest way to find the cause is to compare the
+//! two Fiat–Shamir schedules step by step. A [`Transcript`] with recording
+//! enabled logs:
+//!
+//! - every **absorb**: label, data length and SHA-256 of the data bytes (the
+//!   `item:` framing is implied by the label and length);
+//! - every **challenge**: label, counter and the derived field elements as
+//!   `0x`-prefixed big-endian hex.
+//!
+//! Traces serialize to a small JSON document:
+//!
+//! ```json
+//! { "version": 1, "label": "sszkp.proof", "hash": "blake3",
+//!   "events": [
+//!     { "kind": "absorb", "label": "protocol_header", "len": 141, "sha256": "…" },
+//!     { "kind": "challenge", "label": "beta", "ctr": 0, "outputs": ["0x…"] } ] }
+//! ```
+//!
+//! [`diff`] reports the first step where two traces diverge. The
+//! `transcript_diff` binary wraps it, and the prover/verifier CLIs write
+//! traces with `--trace <file>`. Only the top-level proof transcript is
+//! recorded; transcripts that FRI/IPA run internally are not.
+//!
+//! [`Transcript`]: crate::transcript::Transcript
+//! [`diff`]: crate::transcript_trace::diff
+
+#![forbid(unsafe_code)]
+
+use std::fmt;
+
+use ark_ff::{BigInteger, PrimeField};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::F;
+
+/// Trace format version (bump on incompatible changes).
+pub const TRACE_VERSION: u32 = 1;
+
+/// One recorded transcript operation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A length-delimited absorb.
+    Absorb {
+        /// Item label (e.g. `wire_commit`).
+        label: String,
+        /// Data length in bytes.
+        len: u64,
+        /// SHA-256 of the data bytes, lowercase hex.
+        sha256: String,
+    },
+    /// A challenge derivation.
+    Challenge {
+        /// Challenge label (e.g. `beta`).
+        label: String,
+        /// Derivation counter used for this challenge.
+        ctr: u64,
+        /// Derived field elements, `0x`-prefixed big-endian hex.
+        outputs: Vec<String>,
+    },
+}
+
+impl TraceEvent {
+    pub(crate) fn absorb(label: &str, bytes: &[u8]) -> Self {
+        TraceEvent::Absorb {
+            label: label.to_string(),
+            len: bytes.len() as u64,
+            sha256: hex::encode(Sha256::digest(bytes)),
+        }
+    }
+
+    pub(crate) fn challenge(label: &str, ctr: u64, outputs: &[F]) -> Self {
+        TraceEvent::Challenge { label: label.to_string(), ctr, outputs: outputs.iter().map(fe_hex).collect() }
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceEvent::Absorb { label, len, sha256 } => {
+                write!(f, "absorb {label} ({len} bytes, sha256 {sha256})")
+            }
+            TraceEvent::Challenge { label, ctr, outputs } => {
+                write!(f, "challenge {label} #{ctr} -> [{}]", outputs.join(", "))
+            }
+        }
+    }
+}
+
+/// A recorded transcript: its setup plus the ordered events.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptTrace {
+    /// Format version ([`TRACE_VERSION`]).
+    pub version: u32,
+    /// Transcript domain-separation label.
+    pub label: String,
+    /// Transcript hash (`FsHash::as_str`).
+    pub hash: String,
+    /// Absorbs and challenges in order.
+    pub events: Vec<TraceEvent>,
+}
+
+impl TranscriptTrace {
+    pub(crate) fn new(label: &str, hash: &str) -> Self {
+        Self { version: TRACE_VERSION, label: label.to_string(), hash: hash.to_string(), events: Vec::new() }
+    }
+
+    /// Pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("trace serializes")
+    }
+
+    /// Parse a JSON trace (from this crate or another implementation).
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+/// First point where two traces disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceDivergence {
+    /// Different transcript label or hash: every later step differs anyway.
+    Setup {
+        /// `label/hash` of the left trace.
+        left: String,
+        /// `label/hash` of the right trace.
+        right: String,
+    },
+    /// Events differ at `step` (`None` means that trace ended early).
+    Step {
+        /// Zero-based event index.
+        step: usize,
+        /// Left event, if any.
+        left: Option<TraceEvent>,
+        /// Right event, if any.
+        right: Option<TraceEvent>,
+    },
+}
+
+impl fmt::Display for TraceDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |e: &Option<TraceEvent>| match e {
+            Some(e) => e.to_string(),
+            None => "<end of trace>".to_string(),
+        };
+        match self {
+            TraceDivergence::Setup { left, right } => {
+                write!(f, "transcript setup differs: {left} vs {right}")
+            }
+            TraceDivergence::Step { step, left, right } => {
+                write!(f, "first divergence at step {step}:\n  left:  {}\n  right: {}", side(left), side(right))
+            }
+        }
+    }
+}
+
+/// Compare two traces; `None` if they are identical.
+pub fn diff(left: &TranscriptTrace, right: &TranscriptTrace) -> Option<TraceDivergence> {
+    if left.label != right.label || left.hash != right.hash {
+        return Some(TraceDivergence::Setup {
+            left: format!("{}/{}", left.label, left.hash),
+            right: format!("{}/{}", right.label, right.hash),
+        });
+    }
+    let steps = left.events.len().max(right.events.len());
+    (0..steps).find_map(|step| {
+        let (l, r) = (left.events.get(step), right.events.get(step));
+        (l != r).then(|| TraceDivergence::Step { step, left: l.cloned(), right: r.cloned() })
+    })
+}
+
+fn fe_hex(x: &F) -> String {
+    format!("0x{}", hex::encode(x.into_bigint().to_bytes_be()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ipa::Ipa,
+        scheduler::{Prover, Verifier},
+        test_util::{tiny_prove_fixture, TinyFixture},
+    };
+
+    #[test]
+    fn prover_and_verifier_traces_match_and_diff_locates_tampering() {
+        let TinyFixture { air, rows, pp, vp } = tiny_prove_fixture(16);
+
+        let (proof, p_trace) = Prover::with_pcs(&air, &pp, Ipa).prove_with_trace(&rows).unwrap();
+        let (res, v_trace) = Verifier::with_pcs(&vp, Ipa).verify_with_trace(&proof);
+        res.unwrap();
+        // header, 2 wires, β, γ, Z, α, Q, ζ, opening evals, ν
+        assert_eq!(p_trace.events.len(), 11);
+        assert_eq!(diff(&p_trace, &v_trace), None);
+        assert_eq!(TranscriptTrace::from_json(&p_trace.to_json()).unwrap(), p_trace);
+
+        let mut bad = proof;
+        bad.wire_comms.swap(0, 1);
+        let (res, bad_trace) = Verifier::with_pcs(&vp, Ipa).verify_with_trace(&bad);
+        assert!(res.is_err());
+        match diff(&p_trace, &bad_trace) {
+            Some(TraceDivergence::Step { step: 1, left: Some(_), right: Some(_) }) => {}
+            other => panic!("unexpected divergence: {other:?}"),
+        }
+    }
+}
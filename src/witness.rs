@@ -0,0 +1,620 @@
+//! Binary witness files
+//!
+//! A fixed-width on-disk witness format that serves any `[start, end)` block
+//! with a single offset computation: no tokenizing, no index, no scan.
+//! Values are stored as canonical little-endian field elements, so a file is
+//! ~`32·k` bytes per row (CSV of full-width scalars is 3–4× that).
+//!
+//! ## Layout (version 1, all integers little-endian)
+//!
+//! | offset | size | field                                                    |
+//! |-------:|-----:|----------------------------------------------------------|
+//! | 0      | 8    | magic `b"SSZKPwt1"`                                      |
+//! | 8      | 2    | format version (`1`)                                     |
+//! | 10     | 1    | layout: `0` row-major, `1` column-major blocks           |
+//! | 11     | 1    | field id ([`crate::curve::CurveId`]: `0` BN254, `1` BLS12-381) |
+//! | 12     | 4    | bytes per element (`32`)                                 |
+//! | 16     | 4    | `k` (registers per row)                                  |
+//! | 20     | 4    | reserved (`0`)                                           |
+//! | 24     | 8    | row count `T`                                            |
+//! | 32     | 8    | rows per block (column-major; `0` for row-major)         |
+//! | 40     | 8    | byte length `L` of the column-name section (`0` = none)  |
+//! | 48     | 32   | BLAKE3 of the data section                               |
+//! | 80     | `L`  | `k` names, each `u16` length + UTF-8 bytes               |
+//! | 80+`L` | …    | data                                                     |
+//!
+//! **Row-major** data is `T` rows of `k` elements. **Column-major blocks**
+//! split the rows into blocks of `B` (the last may be shorter); block `t`
+//! stores its `k` column segments back to back, so one register over a block
+//! is one contiguous range. Elements are the arkworks uncompressed encoding
+//! and must be canonical (`< r`); the reader rejects anything else.
+//!
+//! [`WitnessWriter`](crate::witness::WitnessWriter) streams rows into a file
+//! (buffering one block for the column-major layout) and patches the row
+//! count and checksum on `finish`. [`WitnessFile`](crate::witness::WitnessFile) implements
+//! [`crate::stream::TryRestreamer`]; it reads with positioned I/O by default
+//! and memory-maps the file with the `mmap` feature.
+
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "mmap"))]
+use std::sync::Mutex;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    air::Row,
+    curve::{CurveId, CURVE_ID},
+    stream::{RowIdx, TryRestreamer, WitnessError},
+    F,
+};
+
+/// File magic of a binary witness.
+pub const WITNESS_MAGIC: &[u8; 8] = b"SSZKPwt1";
+/// Current format version.
+pub const WITNESS_VERSION: u16 = 1;
+/// Fixed header length before the column-name section.
+const HEADER_LEN: usize = 80;
+/// Bytes per stored field element.
+pub(crate) const ELEM_BYTES: usize = 32;
+
+/// Column-name section: each name as a `u16` length + UTF-8 bytes.
+pub(crate) fn encode_names(names: &[String]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for n in names {
+        out.extend_from_slice(&(n.len() as u16).to_le_bytes());
+        out.extend_from_slice(n.as_bytes());
+    }
+    out
+}
+
+/// Inverse of [`encode_names`]; the result is empty or has exactly `k` names.
+pub(crate) fn decode_names(mut rest: &[u8], k: usize) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    while !rest.is_empty() {
+        let (len, tail) = rest.split_at_checked(2).ok_or("truncated column name")?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        let (name, tail) = tail.split_at_checked(len).ok_or("truncated column name")?;
+        names.push(String::from_utf8(name.to_vec()).map_err(|_| "column name is not UTF-8")?);
+        rest = tail;
+    }
+    if !names.is_empty() && names.len() != k {
+        return Err(format!("{} column names for k={k}", names.len()));
+    }
+    Ok(names)
+}
+
+/// Read a `names_len`-byte name section from `r`, refusing lengths beyond
+/// the `avail` bytes left in the file before allocating for them.
+pub(crate) fn read_names(mut r: impl Read, names_len: u64, avail: u64, k: usize) -> Result<Vec<String>, String> {
+    if names_len > avail {
+        return Err(format!("column-name section of {names_len} bytes exceeds the {avail} bytes after the header"));
+    }
+    let mut names = vec![0u8; names_len as usize];
+    r.read_exact(&mut names).map_err(|e| format!("truncated column names: {e}"))?;
+    decode_names(&names, k)
+}
+
+/// Check a column-name list for the writers.
+pub(crate) fn check_names(names: &[String], k: usize) -> std::io::Result<()> {
+    if names.len() != k || names.iter().any(|n| n.len() > u16::MAX as usize) {
+        return Err(invalid_input("need exactly k column names of at most 65535 bytes"));
+    }
+    Ok(())
+}
+
+/// How rows are laid out in the data section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessLayout {
+    /// `T` rows of `k` elements.
+    RowMajor,
+    /// Blocks of `block_rows` rows, each stored column by column.
+    ColumnBlocks {
+        /// Rows per block (the last block may be shorter).
+        block_rows: usize,
+    },
+}
+
+impl WitnessLayout {
+    /// Block size for `columns` without an explicit `:B`.
+    pub const DEFAULT_BLOCK_ROWS: usize = 1 << 10;
+}
+
+impl std::str::FromStr for WitnessLayout {
+    type Err = String;
+
+    /// `rows`, `columns` or `columns:B`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "rows" => Ok(Self::RowMajor),
+            None if s == "columns" => {
+                Ok(Self::ColumnBlocks { block_rows: Self::DEFAULT_BLOCK_ROWS })
+            }
+            Some(("columns", b)) => match b.parse::<usize>() {
+                Ok(block_rows) if block_rows > 0 => Ok(Self::ColumnBlocks { block_rows }),
+                _ => Err(format!("bad block size `{b}` (expected a positive integer)")),
+            },
+            _ => Err(format!("unknown witness layout `{s}` (expected rows|columns[:B])")),
+        }
+    }
+}
+
+/// Decoded header of a binary witness file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessHeader {
+    /// Data layout.
+    pub layout: WitnessLayout,
+    /// Field the elements belong to.
+    pub curve: CurveId,
+    /// Registers per row.
+    pub k: usize,
+    /// Number of rows `T`.
+    pub rows: usize,
+    /// Optional column names (`k` of them, or empty).
+    pub column_names: Vec<String>,
+    /// BLAKE3 of the data section.
+    pub checksum: [u8; 32],
+}
+
+impl WitnessHeader {
+    fn encode(&self) -> Vec<u8> {
+        let names = encode_names(&self.column_names);
+        let (layout, block_rows) = match self.layout {
+            WitnessLayout::RowMajor => (0u8, 0u64),
+            WitnessLayout::ColumnBlocks { block_rows } => (1u8, block_rows as u64),
+        };
+        let mut out = Vec::with_capacity(HEADER_LEN + names.len());
+        out.extend_from_slice(WITNESS_MAGIC);
+        out.extend_from_slice(&WITNESS_VERSION.to_le_bytes());
+        out.push(layout);
+        self.curve.serialize_compressed(&mut out).expect("curve id");
+        out.extend_from_slice(&(ELEM_BYTES as u32).to_le_bytes());
+        out.extend_from_slice(&(self.k as u32).to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&(self.rows as u64).to_le_bytes());
+        out.extend_from_slice(&block_rows.to_le_bytes());
+        out.extend_from_slice(&(names.len() as u64).to_le_bytes());
+        out.extend_from_slice(&self.checksum);
+        out.extend_from_slice(&names);
+        out
+    }
+
+    /// Parse a header from the start of `r` (a file of `len` bytes); returns
+    /// it with the data offset.
+    fn decode(mut r: impl Read, len: u64) -> Result<(Self, u64), String> {
+        let mut h = [0u8; HEADER_LEN];
+        r.read_exact(&mut h).map_err(|e| format!("truncated header: {e}"))?;
+        let u32_at = |o: usize| u32::from_le_bytes(h[o..o + 4].try_into().expect("4 bytes"));
+        let u64_at = |o: usize| u64::from_le_bytes(h[o..o + 8].try_into().expect("8 bytes"));
+        if &h[..8] != WITNESS_MAGIC {
+            return Err("not a binary witness file (bad magic)".into());
+        }
+        let version = u16::from_le_bytes([h[8], h[9]]);
+        if version != WITNESS_VERSION {
+            return Err(format!("unsupported witness format version {version}"));
+        }
+        let layout = match (h[10], u64_at(32)) {
+            (0, _) => WitnessLayout::RowMajor,
+            (1, b) if b > 0 => WitnessLayout::ColumnBlocks { block_rows: b as usize },
+            (l, b) => return Err(format!("bad layout {l} (block rows {b})")),
+        };
+        let curve = CurveId::deserialize_compressed(&h[11..12])
+            .map_err(|_| format!("unknown field id {}", h[11]))?;
+        if u32_at(12) as usize != ELEM_BYTES {
+            return Err(format!("unsupported element width {} bytes", u32_at(12)));
+        }
+        let (k, rows) = (u32_at(16) as usize, u64_at(24));
+        if k == 0 {
+            return Err("witness rows have k=0 registers".into());
+        }
+        if rows.checked_mul(k as u64).and_then(|n| n.checked_mul(ELEM_BYTES as u64)).is_none() {
+            return Err(format!("{rows} rows of k={k} overflow the data size"));
+        }
+        let names_len = u64_at(40);
+        let column_names = read_names(&mut r, names_len, len.saturating_sub(HEADER_LEN as u64), k)?;
+        let header = Self {
+            layout,
+            curve,
+            k,
+            rows: rows as usize,
+            column_names,
+            checksum: h[48..80].try_into().expect("32 bytes"),
+        };
+        Ok((header, HEADER_LEN as u64 + names_len))
+    }
+
+    /// Size of the data section in bytes (validated not to overflow on open).
+    pub fn data_len(&self) -> u64 {
+        (self.rows as u64) * (self.k as u64) * ELEM_BYTES as u64
+    }
+}
+
+// ------------------------- Writer -------------------------
+
+/// Streams rows into a binary witness. The header is written up front with a
+/// zero row count and checksum and patched by [`Self::finish`].
+pub struct WitnessWriter<W: Write + Seek> {
+    out: W,
+    header: WitnessHeader,
+    started: bool,
+    block: Vec<F>, // column-major staging: up to `block_rows` rows, row-major
+    hasher: blake3::Hasher,
+    elem: Vec<u8>,
+}
+
+impl WitnessWriter<BufWriter<File>> {
+    /// Create (truncate) `path` and write rows of `k` registers into it.
+    pub fn create(path: impl AsRef<Path>, k: usize, layout: WitnessLayout) -> std::io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), k, layout))
+    }
+}
+
+impl<W: Write + Seek> WitnessWriter<W> {
+    /// Write rows of `k` registers into `out` (positioned at the file start).
+    pub fn new(out: W, k: usize, layout: WitnessLayout) -> Self {
+        let header = WitnessHeader {
+            layout,
+            curve: CURVE_ID,
+            k,
+            rows: 0,
+            column_names: Vec::new(),
+            checksum: [0u8; 32],
+        };
+        Self {
+            out,
+            header,
+            started: false,
+            block: Vec::new(),
+            hasher: blake3::Hasher::new(),
+            elem: Vec::with_capacity(ELEM_BYTES),
+        }
+    }
+
+    /// Record one name per register (must be called before the first row).
+    pub fn with_column_names(mut self, names: Vec<String>) -> std::io::Result<Self> {
+        if self.started {
+            return Err(invalid_input("column names must be set before any row"));
+        }
+        check_names(&names, self.header.k)?;
+        self.header.column_names = names;
+        Ok(self)
+    }
+
+    /// Append one row of exactly `k` registers.
+    pub fn push_row(&mut self, regs: &[F]) -> std::io::Result<()> {
+        if regs.len() != self.header.k {
+            return Err(invalid_input("row width does not match k"));
+        }
+        if !self.started {
+            self.out.write_all(&self.header.encode())?;
+            self.started = true;
+        }
+        self.header.rows += 1;
+        match self.header.layout {
+            WitnessLayout::RowMajor => regs.iter().try_for_each(|x| self.write_elem(x)),
+            WitnessLayout::ColumnBlocks { block_rows } => {
+                self.block.extend_from_slice(regs);
+                if self.block.len() == block_rows * self.header.k {
+                    self.flush_block()?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Flush the last block, patch the header and return it.
+    pub fn finish(mut self) -> std::io::Result<WitnessHeader> {
+        if !self.started {
+            self.out.write_all(&self.header.encode())?;
+        }
+        self.flush_block()?;
+        self.header.checksum = *self.hasher.finalize().as_bytes();
+        self.out.seek(SeekFrom::Start(0))?;
+        self.out.write_all(&self.header.encode())?;
+        self.out.flush()?;
+        Ok(self.header)
+    }
+
+    fn flush_block(&mut self) -> std::io::Result<()> {
+        let k = self.header.k;
+        let block = std::mem::take(&mut self.block);
+        let rows = block.len().checked_div(k).unwrap_or(0);
+        for j in 0..k {
+            for i in 0..rows {
+                self.write_elem(&block[i * k + j])?;
+            }
+        }
+        self.block = block;
+        self.block.clear();
+        Ok(())
+    }
+
+    fn write_elem(&mut self, x: &F) -> std::io::Result<()> {
+        self.elem.clear();
+        x.serialize_uncompressed(&mut self.elem).expect("field serialize");
+        self.hasher.update(&self.elem);
+        self.out.write_all(&self.elem)
+    }
+}
+
+pub(crate) fn invalid_input(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.to_string())
+}
+
+// ------------------------- Reader -------------------------
+
+enum Backing {
+    #[cfg(not(feature = "mmap"))]
+    File(Mutex<File>),
+    #[cfg(feature = "mmap")]
+    Map(memmap2::Mmap),
+}
+
+/// Random-access binary witness. Each block request becomes one read per
+/// contiguous range (one for row-major, `k` per stored block for
+/// column-major) and a fixed-width decode.
+pub struct WitnessFile {
+    path: PathBuf,
+    header: WitnessHeader,
+    data_offset: u64,
+    backing: Backing,
+}
+
+impl WitnessFile {
+    /// Open and validate the header and file size (not the checksum; see
+    /// [`Self::verify_checksum`]). With the `mmap` feature the file is mapped.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, WitnessError> {
+        let path = path.into();
+        let src = path.display().to_string();
+        let io = |err| WitnessError::Io { src: src.clone(), row: 0, err };
+        let bad = |msg| WitnessError::Parse { src: src.clone(), row: 0, msg };
+        let mut f = File::open(&path).map_err(io)?;
+        let len = f.metadata().map_err(io)?.len();
+        let (header, data_offset) = WitnessHeader::decode(&mut f, len).map_err(bad)?;
+        if header.curve != CURVE_ID {
+            return Err(bad(format!(
+                "witness is over {}, this build uses {}",
+                header.curve.as_str(),
+                CURVE_ID.as_str()
+            )));
+        }
+        if len != data_offset + header.data_len() {
+            return Err(bad(format!(
+                "file is {len} bytes, header describes {}",
+                data_offset + header.data_len()
+            )));
+        }
+        Ok(Self { backing: Self::backing(f).map_err(io)?, path, header, data_offset })
+    }
+
+    /// [`Self::open`] for proving `air`: also rejects a file whose rows do
+    /// not have `air.k` registers.
+    pub fn open_for(path: impl Into<PathBuf>, air: &crate::air::AirSpec) -> Result<Self, WitnessError> {
+        let f = Self::open(path)?;
+        if f.header.k != air.k {
+            return Err(WitnessError::Parse {
+                src: f.path.display().to_string(),
+                row: 0,
+                msg: format!("witness has k={}, air.k is {}", f.header.k, air.k),
+            });
+        }
+        Ok(f)
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    fn backing(f: File) -> std::io::Result<Backing> {
+        Ok(Backing::File(Mutex::new(f)))
+    }
+
+    #[cfg(feature = "mmap")]
+    #[allow(unsafe_code)]
+    fn backing(f: File) -> std::io::Result<Backing> {
+        // SAFETY: the map is read-only and only ever copied out of; a
+        // concurrent writer truncating the file is outside the contract
+        // (as for any mapped input), and lengths were validated above.
+        Ok(Backing::Map(unsafe { memmap2::Mmap::map(&f)? }))
+    }
+
+    /// The file's header.
+    pub fn header(&self) -> &WitnessHeader {
+        &self.header
+    }
+
+    /// Recompute the BLAKE3 of the data section and compare it to the header.
+    pub fn verify_checksum(&self) -> Result<(), WitnessError> {
+        const CHUNK: u64 = 1 << 20;
+        let mut h = blake3::Hasher::new();
+        let mut off = 0u64;
+        while off < self.header.data_len() {
+            let len = CHUNK.min(self.header.data_len() - off) as usize;
+            h.update(&self.read_data(off, len).map_err(|e| self.io_err(0, e))?);
+            off += len as u64;
+        }
+        if *h.finalize().as_bytes() != self.header.checksum {
+            return Err(self.parse_err(0, "data checksum mismatch".into()));
+        }
+        Ok(())
+    }
+
+    fn read_data(&self, off: u64, len: usize) -> std::io::Result<Cow<'_, [u8]>> {
+        let off = self.data_offset + off;
+        match &self.backing {
+            #[cfg(not(feature = "mmap"))]
+            Backing::File(f) => {
+                let mut f = f.lock().unwrap_or_else(|p| p.into_inner());
+                let mut buf = vec![0u8; len];
+                f.seek(SeekFrom::Start(off))?;
+                f.read_exact(&mut buf)?;
+                Ok(Cow::Owned(buf))
+            }
+            #[cfg(feature = "mmap")]
+            Backing::Map(m) => Ok(Cow::Borrowed(&m[off as usize..off as usize + len])),
+        }
+    }
+
+    fn io_err(&self, row: usize, err: std::io::Error) -> WitnessError {
+        WitnessError::Io { src: self.path.display().to_string(), row, err }
+    }
+
+    fn parse_err(&self, row: usize, msg: String) -> WitnessError {
+        WitnessError::Parse { src: self.path.display().to_string(), row, msg }
+    }
+
+    /// Decode the element for `(row, col)` from `bytes`.
+    fn decode(&self, bytes: &[u8], row: usize, col: usize) -> Result<F, WitnessError> {
+        F::deserialize_uncompressed(bytes)
+            .map_err(|_| self.parse_err(row, format!("column {col}: element is not a canonical field element")))
+    }
+
+    /// Rows `[start, end)` (already clamped to `T`).
+    fn read_rows(&self, start: usize, end: usize) -> Result<Vec<Row>, WitnessError> {
+        let k = self.header.k;
+        match self.header.layout {
+            WitnessLayout::RowMajor => {
+                let row_bytes = k * ELEM_BYTES;
+                let bytes = self
+                    .read_data((start * row_bytes) as u64, (end - start) * row_bytes)
+                    .map_err(|e| self.io_err(start, e))?;
+                bytes
+                    .chunks_exact(row_bytes)
+                    .enumerate()
+                    .map(|(i, r)| {
+                        let regs = r
+                            .chunks_exact(ELEM_BYTES)
+                            .enumerate()
+                            .map(|(j, x)| self.decode(x, start + i, j))
+                            .collect::<Result<Vec<F>, _>>()?;
+                        Ok(Row { regs: regs.into_boxed_slice() })
+                    })
+                    .collect()
+            }
+            WitnessLayout::ColumnBlocks { block_rows } => {
+                let mut regs = vec![Vec::with_capacity(k); end - start];
+                let mut row = start;
+                while row < end {
+                    let t = row / block_rows;
+                    let b0 = t * block_rows;
+                    let b_len = block_rows.min(self.header.rows - b0);
+                    let stop = end.min(b0 + b_len);
+                    let base = (b0 * k * ELEM_BYTES) as u64;
+                    for j in 0..k {
+                        let off = base + ((j * b_len + (row - b0)) * ELEM_BYTES) as u64;
+                        let bytes = self
+                            .read_data(off, (stop - row) * ELEM_BYTES)
+                            .map_err(|e| self.io_err(row, e))?;
+                        for (i, x) in bytes.chunks_exact(ELEM_BYTES).enumerate() {
+                            regs[row - start + i].push(self.decode(x, row + i, j)?);
+                        }
+                    }
+                    row = stop;
+                }
+                Ok(regs.into_iter().map(|r| Row { regs: r.into_boxed_slice() }).collect())
+            }
+        }
+    }
+}
+
+impl TryRestreamer for WitnessFile {
+    type Item = Row;
+
+    fn len_rows(&self) -> usize {
+        self.header.rows
+    }
+
+    fn try_stream_rows(
+        &self,
+        start: RowIdx,
+        end: RowIdx,
+    ) -> Box<dyn Iterator<Item = Result<Row, WitnessError>> + '_> {
+        let end = end.0.min(self.header.rows);
+        let start = start.0.min(end);
+        match self.read_rows(start, end) {
+            Ok(rows) => Box::new(rows.into_iter().map(Ok)),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{BigInteger, PrimeField, UniformRand};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn read(w: &WitnessFile, s: usize, e: usize) -> Vec<Row> {
+        w.try_stream_rows(RowIdx(s), RowIdx(e)).map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn binary_witness_round_trips_in_both_layouts() {
+        let mut rng = StdRng::seed_from_u64(48);
+        let (k, t) = (3, 1000);
+        let rows: Vec<Vec<F>> = (0..t).map(|_| (0..k).map(|_| F::rand(&mut rng)).collect()).collect();
+        let dir = tempfile::tempdir().unwrap();
+        for layout in [WitnessLayout::RowMajor, "columns:64".parse().unwrap()] {
+            let path = dir.path().join("w.szw");
+            let names: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+            let mut w = WitnessWriter::create(&path, k, layout).unwrap().with_column_names(names.clone()).unwrap();
+            rows.iter().for_each(|r| w.push_row(r).unwrap());
+            let written = w.finish().unwrap();
+
+            let f = WitnessFile::open(&path).unwrap();
+            assert_eq!(f.header(), &written);
+            assert_eq!((f.header().rows, f.header().column_names.clone()), (t, names));
+            f.verify_checksum().unwrap();
+            for (s, e) in [(0, 64), (64, 128), (10, 200), (990, 1000), (995, 1200)] {
+                let got: Vec<Vec<F>> = read(&f, s, e).into_iter().map(|r| r.regs.to_vec()).collect();
+                assert_eq!(got, rows[s..e.min(t)], "{layout:?} [{s}, {e})");
+            }
+            assert_eq!(std::fs::metadata(&path).unwrap().len(), 80 + 9 + (t * k * 32) as u64);
+        }
+    }
+
+    #[test]
+    fn binary_witness_rejects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("w.szw");
+        let mut w = WitnessWriter::create(&path, 2, WitnessLayout::RowMajor).unwrap();
+        (0..10u64).for_each(|i| w.push_row(&[F::from(i), F::from(2 * i)]).unwrap());
+        w.finish().unwrap();
+
+        // Overwrite row 7, column 1 with r (non-canonical): checksum and decode both fail.
+        let mut bytes = std::fs::read(&path).unwrap();
+        let at = 80 + (7 * 2 + 1) * 32;
+        bytes[at..at + 32].copy_from_slice(&F::MODULUS.to_bytes_le());
+        std::fs::write(&path, &bytes).unwrap();
+        let f = WitnessFile::open(&path).unwrap();
+        assert!(f.verify_checksum().is_err());
+        let err = f.try_stream_rows(RowIdx(4), RowIdx(10)).find_map(Result::err).unwrap();
+        assert_eq!(err.row(), 7);
+
+        // Opening for an AIR of another width fails up front.
+        let air = |k| crate::air::AirSpec { k, id_table: Vec::new(), sigma_table: Vec::new(), selectors: Vec::new() };
+        assert!(WitnessFile::open_for(&path, &air(2)).is_ok());
+        assert!(WitnessFile::open_for(&path, &air(3)).is_err());
+
+        // Truncation is caught at open.
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(WitnessFile::open(&path).is_err());
+
+        // A zero-width header is rejected rather than decoded with empty rows.
+        // (Its data section is empty, so only the header check can catch it.)
+        let mut b = bytes[..80].to_vec();
+        b[16..20].copy_from_slice(&0u32.to_le_bytes());
+        std::fs::write(&path, &b).unwrap();
+        let err = WitnessFile::open(&path).err().unwrap();
+        assert!(err.to_string().contains("k=0"), "{err}");
+
+        // Oversized name sections and overflowing row counts fail cleanly.
+        for (at, v) in [(40, u64::MAX / 2), (24, u64::MAX / 4)] {
+            let mut b = bytes.clone();
+            b[at..at + 8].copy_from_slice(&v.to_le_bytes());
+            std::fs::write(&path, &b).unwrap();
+            assert!(WitnessFile::open(&path).is_err());
+        }
+    }
+}
@@ -0,0 +1,602 @@
+//! Compressed witness containers
+//!
+//! Witnesses shipped as one big `.zst`/`.gz` stream must be inflated to disk
+//! before proving. This container instead compresses every `frame_rows`-row
+//! chunk as an **independent frame** and ends with a frame index, so a block
+//! request decompresses only the frames that cover it. With `frame_rows` equal
+//! to the prover's `b_blk` each block is exactly one frame, and resident memory
+//! stays at one decompressed frame regardless of `T`.
+//!
+//! ## Layout (version 1, all integers little-endian)
+//!
+//! | offset | size | field                                                    |
+//! |-------:|-----:|----------------------------------------------------------|
+//! | 0      | 8    | magic `b"SSZKPwz1"`                                      |
+//! | 8      | 2    | format version (`1`)                                     |
+//! | 10     | 1    | codec: `1` zstd, `2` gzip                                |
+//! | 11     | 1    | field id ([`crate::curve::CurveId`])                     |
+//! | 12     | 4    | bytes per element (`32`)                                 |
+//! | 16     | 4    | `k` (registers per row)                                  |
+//! | 20     | 4    | reserved (`0`)                                           |
+//! | 24     | 8    | row count `T`                                            |
+//! | 32     | 8    | rows per frame `F`                                       |
+//! | 40     | 8    | byte length `L` of the column-name section               |
+//! | 48     | 8    | absolute offset of the frame index                       |
+//! | 56     | 8    | frame count `⌈T / F⌉`                                    |
+//! | 64     | 32   | BLAKE3 of the uncompressed data                          |
+//! | 96     | `L`  | column names, as in [`crate::witness`]                   |
+//! | …      | …    | frames                                                   |
+//! | index  | 16·n | per frame: absolute offset `u64`, compressed length `u64` |
+//!
+//! Frame `t` holds rows `[t·F, min((t+1)·F, T))` row-major, in the same
+//! fixed-width element encoding as [`crate::witness`], compressed on its own.
+//! The checksum covers the concatenated uncompressed frames, i.e. the data
+//! section of the equivalent row-major binary witness.
+//!
+//! [`FramedWitness`](crate::witness_frames::FramedWitness) reads a container
+//! from a file or an in-memory buffer (e.g. an upload to `/v1/prove`) and
+//! implements [`crate::stream::TryRestreamer`].
+
+#![forbid(unsafe_code)]
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    air::Row,
+    curve::{CurveId, CURVE_ID},
+    stream::{RowIdx, TryRestreamer, WitnessError},
+    witness::{check_names, encode_names, invalid_input, read_names, ELEM_BYTES},
+    F,
+};
+
+/// File magic of a framed (compressed) witness.
+pub const FRAMED_MAGIC: &[u8; 8] = b"SSZKPwz1";
+/// Current container version.
+pub const FRAMED_VERSION: u16 = 1;
+/// Fixed header length before the column-name section.
+const HEADER_LEN: usize = 96;
+/// Bytes per frame-index entry.
+const INDEX_ENTRY: u64 = 16;
+/// Largest uncompressed frame (`F·k·32` bytes) a reader will inflate; the
+/// output buffer is allocated up front, so this bounds what a header can ask for.
+pub const MAX_FRAME_BYTES: usize = 1 << 26;
+
+/// Uncompressed bytes of a `frame_rows × k` frame, if within
+/// `1..=`[`MAX_FRAME_BYTES`] (so neither `frame_rows` nor `k` is zero).
+fn frame_bytes(frame_rows: usize, k: usize) -> Option<usize> {
+    frame_rows.checked_mul(k)?.checked_mul(ELEM_BYTES).filter(|&n| n > 0 && n <= MAX_FRAME_BYTES)
+}
+
+/// Per-frame compression codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessCodec {
+    /// Zstandard (level 3, content checksum on).
+    Zstd,
+    /// gzip / DEFLATE (default level, CRC32 trailer).
+    Gzip,
+}
+
+impl WitnessCodec {
+    fn id(self) -> u8 {
+        match self {
+            Self::Zstd => 1,
+            Self::Gzip => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Zstd),
+            2 => Some(Self::Gzip),
+            _ => None,
+        }
+    }
+
+    /// Stable lowercase name (`zstd` / `gzip`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    fn compress(self, raw: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Zstd => {
+                let mut c = zstd::bulk::Compressor::new(zstd::DEFAULT_COMPRESSION_LEVEL)?;
+                c.include_checksum(true)?;
+                c.compress(raw)
+            }
+            Self::Gzip => {
+                let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                e.write_all(raw)?;
+                e.finish()
+            }
+        }
+    }
+
+    /// Inflate a frame that must decompress to exactly `len` bytes.
+    fn decompress(self, frame: &[u8], len: usize) -> std::io::Result<Vec<u8>> {
+        let out = match self {
+            Self::Zstd => zstd::bulk::decompress(frame, len)?,
+            Self::Gzip => {
+                // Cap the output so a hostile frame cannot inflate without bound.
+                let mut out = Vec::with_capacity(len);
+                flate2::read::GzDecoder::new(frame).take(len as u64 + 1).read_to_end(&mut out)?;
+                out
+            }
+        };
+        if out.len() != len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("frame inflates to {} bytes, expected {len}", out.len()),
+            ));
+        }
+        Ok(out)
+    }
+}
+
+impl std::str::FromStr for WitnessCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zstd" | "zst" => Ok(Self::Zstd),
+            "gzip" | "gz" => Ok(Self::Gzip),
+            _ => Err(format!("unknown witness codec `{s}` (expected zstd|gzip)")),
+        }
+    }
+}
+
+/// Decoded header of a framed witness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FramedHeader {
+    /// Frame codec.
+    pub codec: WitnessCodec,
+    /// Field the elements belong to.
+    pub curve: CurveId,
+    /// Registers per row.
+    pub k: usize,
+    /// Number of rows `T`.
+    pub rows: usize,
+    /// Rows per frame `F`.
+    pub frame_rows: usize,
+    /// Optional column names (`k` of them, or empty).
+    pub column_names: Vec<String>,
+    /// BLAKE3 of the uncompressed data.
+    pub checksum: [u8; 32],
+}
+
+impl FramedHeader {
+    /// Number of frames, `⌈T / F⌉`.
+    pub fn frames(&self) -> usize {
+        self.rows.div_ceil(self.frame_rows)
+    }
+
+    fn encode(&self, index_offset: u64) -> Vec<u8> {
+        let names = encode_names(&self.column_names);
+        let mut out = Vec::with_capacity(HEADER_LEN + names.len());
+        out.extend_from_slice(FRAMED_MAGIC);
+        out.extend_from_slice(&FRAMED_VERSION.to_le_bytes());
+        out.push(self.codec.id());
+        self.curve.serialize_compressed(&mut out).expect("curve id");
+        out.extend_from_slice(&(ELEM_BYTES as u32).to_le_bytes());
+        out.extend_from_slice(&(self.k as u32).to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&(self.rows as u64).to_le_bytes());
+        out.extend_from_slice(&(self.frame_rows as u64).to_le_bytes());
+        out.extend_from_slice(&(names.len() as u64).to_le_bytes());
+        out.extend_from_slice(&index_offset.to_le_bytes());
+        out.extend_from_slice(&(self.frames() as u64).to_le_bytes());
+        out.extend_from_slice(&self.checksum);
+        out.extend_from_slice(&names);
+        out
+    }
+
+    /// Parse the header of a `len`-byte container; returns it with the data
+    /// offset and index offset.
+    fn decode(mut r: impl Read, len: u64) -> Result<(Self, u64, u64), String> {
+        let mut h = [0u8; HEADER_LEN];
+        r.read_exact(&mut h).map_err(|e| format!("truncated header: {e}"))?;
+        let u32_at = |o: usize| u32::from_le_bytes(h[o..o + 4].try_into().expect("4 bytes"));
+        let u64_at = |o: usize| u64::from_le_bytes(h[o..o + 8].try_into().expect("8 bytes"));
+        if &h[..8] != FRAMED_MAGIC {
+            return Err("not a framed witness (bad magic)".into());
+        }
+        let version = u16::from_le_bytes([h[8], h[9]]);
+        if version != FRAMED_VERSION {
+            return Err(format!("unsupported framed witness version {version}"));
+        }
+        let codec = WitnessCodec::from_id(h[10]).ok_or_else(|| format!("unknown codec id {}", h[10]))?;
+        let curve = CurveId::deserialize_compressed(&h[11..12])
+            .map_err(|_| format!("unknown field id {}", h[11]))?;
+        if u32_at(12) as usize != ELEM_BYTES {
+            return Err(format!("unsupported element width {} bytes", u32_at(12)));
+        }
+        let k = u32_at(16) as usize;
+        let (rows, frame_rows) = (u64_at(24) as usize, u64_at(32) as usize);
+        if frame_bytes(frame_rows, k).is_none() {
+            return Err(format!(
+                "frames of {frame_rows} rows × k={k} are outside 1..={MAX_FRAME_BYTES} bytes"
+            ));
+        }
+        let names_len = u64_at(40);
+        let column_names = read_names(&mut r, names_len, len.saturating_sub(HEADER_LEN as u64), k)?;
+        let header = Self {
+            codec,
+            curve,
+            k,
+            rows,
+            frame_rows,
+            column_names,
+            checksum: h[64..96].try_into().expect("32 bytes"),
+        };
+        if u64_at(56) != header.frames() as u64 {
+            return Err(format!("{} frames for {rows} rows of {frame_rows}", u64_at(56)));
+        }
+        Ok((header, HEADER_LEN as u64 + names_len, u64_at(48)))
+    }
+}
+
+// ------------------------- Writer -------------------------
+
+/// Streams rows into a framed witness, compressing each full frame as it
+/// fills; the frame index is appended and the header patched by
+/// [`Self::finish`].
+pub struct FramedWitnessWriter<W: Write + Seek> {
+    out: W,
+    header: FramedHeader,
+    started: bool,
+    pos: u64,
+    frame: Vec<u8>, // uncompressed rows of the current frame
+    index: Vec<(u64, u64)>,
+    hasher: blake3::Hasher,
+}
+
+impl FramedWitnessWriter<BufWriter<File>> {
+    /// Create (truncate) `path` and write rows of `k` registers into it.
+    pub fn create(
+        path: impl AsRef<Path>,
+        k: usize,
+        codec: WitnessCodec,
+        frame_rows: usize,
+    ) -> std::io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), k, codec, frame_rows)
+    }
+}
+
+impl<W: Write + Seek> FramedWitnessWriter<W> {
+    /// Write rows of `k` registers into `out` (positioned at the start), in
+    /// frames of `frame_rows` rows. Pass the prover's `b_blk`.
+    pub fn new(out: W, k: usize, codec: WitnessCodec, frame_rows: usize) -> std::io::Result<Self> {
+        if frame_bytes(frame_rows, k).is_none() {
+            return Err(invalid_input("frame_rows × k × 32 must be within 1..=MAX_FRAME_BYTES"));
+        }
+        let header = FramedHeader {
+            codec,
+            curve: CURVE_ID,
+            k,
+            rows: 0,
+            frame_rows,
+            column_names: Vec::new(),
+            checksum: [0u8; 32],
+        };
+        Ok(Self {
+            out,
+            header,
+            started: false,
+            pos: 0,
+            frame: Vec::with_capacity(frame_rows * k * ELEM_BYTES),
+            index: Vec::new(),
+            hasher: blake3::Hasher::new(),
+        })
+    }
+
+    /// Record one name per register (must be called before the first row).
+    pub fn with_column_names(mut self, names: Vec<String>) -> std::io::Result<Self> {
+        if self.started {
+            return Err(invalid_input("column names must be set before any row"));
+        }
+        check_names(&names, self.header.k)?;
+        self.header.column_names = names;
+        Ok(self)
+    }
+
+    /// Append one row of exactly `k` registers.
+    pub fn push_row(&mut self, regs: &[F]) -> std::io::Result<()> {
+        if regs.len() != self.header.k {
+            return Err(invalid_input("row width does not match k"));
+        }
+        self.start()?;
+        for x in regs {
+            x.serialize_uncompressed(&mut self.frame).expect("field serialize");
+        }
+        self.header.rows += 1;
+        if self.header.rows.is_multiple_of(self.header.frame_rows) {
+            self.flush_frame()?;
+        }
+        Ok(())
+    }
+
+    /// Flush the last frame, append the index, patch the header and return it.
+    pub fn finish(mut self) -> std::io::Result<FramedHeader> {
+        self.start()?;
+        self.flush_frame()?;
+        let index_offset = self.pos;
+        for (off, len) in &self.index {
+            self.out.write_all(&off.to_le_bytes())?;
+            self.out.write_all(&len.to_le_bytes())?;
+        }
+        self.header.checksum = *self.hasher.finalize().as_bytes();
+        self.out.seek(SeekFrom::Start(0))?;
+        self.out.write_all(&self.header.encode(index_offset))?;
+        self.out.flush()?;
+        Ok(self.header)
+    }
+
+    fn start(&mut self) -> std::io::Result<()> {
+        if !self.started {
+            let head = self.header.encode(0);
+            self.out.write_all(&head)?;
+            self.pos = head.len() as u64;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    fn flush_frame(&mut self) -> std::io::Result<()> {
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        self.hasher.update(&self.frame);
+        let packed = self.header.codec.compress(&self.frame)?;
+        self.out.write_all(&packed)?;
+        self.index.push((self.pos, packed.len() as u64));
+        self.pos += packed.len() as u64;
+        self.frame.clear();
+        Ok(())
+    }
+}
+
+// ------------------------- Reader -------------------------
+
+enum Source {
+    File(Mutex<File>),
+    Bytes(Vec<u8>),
+}
+
+/// Random-access framed witness over a file or an in-memory buffer. Block
+/// requests decompress only the covering frames, one at a time.
+pub struct FramedWitness {
+    src: String,
+    header: FramedHeader,
+    index: Vec<(u64, u64)>,
+    source: Source,
+}
+
+impl FramedWitness {
+    /// Open a container file and validate its header and frame index.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, WitnessError> {
+        let path = path.into();
+        let src = path.display().to_string();
+        let io = |err| WitnessError::Io { src: src.clone(), row: 0, err };
+        let mut f = File::open(&path).map_err(io)?;
+        let len = f.metadata().map_err(io)?.len();
+        let (header, index) = Self::parse(&src, &mut f, len)?;
+        Ok(Self { src, header, index, source: Source::File(Mutex::new(f)) })
+    }
+
+    /// Read a container held in memory (e.g. a request body); `src` names it
+    /// in errors.
+    pub fn from_bytes(src: impl Into<String>, bytes: Vec<u8>) -> Result<Self, WitnessError> {
+        let src = src.into();
+        let (header, index) = Self::parse(&src, &mut std::io::Cursor::new(&bytes), bytes.len() as u64)?;
+        Ok(Self { src, header, index, source: Source::Bytes(bytes) })
+    }
+
+    /// Validate the header and load the frame index from `r` (`len` bytes).
+    fn parse(
+        src: &str,
+        r: &mut (impl Read + Seek),
+        len: u64,
+    ) -> Result<(FramedHeader, Vec<(u64, u64)>), WitnessError> {
+        let bad = |msg: String| WitnessError::Parse { src: src.to_string(), row: 0, msg };
+        let (header, data_offset, index_offset) = FramedHeader::decode(&mut *r, len).map_err(bad)?;
+        if header.curve != CURVE_ID {
+            return Err(bad(format!(
+                "witness is over {}, this build uses {}",
+                header.curve.as_str(),
+                CURVE_ID.as_str()
+            )));
+        }
+        let frames = header.frames() as u64;
+        if index_offset < data_offset || Some(len) != frames.checked_mul(INDEX_ENTRY).and_then(|n| n.checked_add(index_offset)) {
+            return Err(bad(format!("file is {len} bytes, index at {index_offset} describes {frames} frames")));
+        }
+        let mut raw = vec![0u8; (frames * INDEX_ENTRY) as usize];
+        r.seek(SeekFrom::Start(index_offset))
+            .and_then(|_| r.read_exact(&mut raw))
+            .map_err(|err| WitnessError::Io { src: src.to_string(), row: 0, err })?;
+        let index: Vec<(u64, u64)> = raw
+            .chunks_exact(INDEX_ENTRY as usize)
+            .map(|e| {
+                let off = u64::from_le_bytes(e[..8].try_into().expect("8 bytes"));
+                (off, u64::from_le_bytes(e[8..].try_into().expect("8 bytes")))
+            })
+            .collect();
+        for (t, &(off, n)) in index.iter().enumerate() {
+            if off < data_offset || off.checked_add(n).is_none_or(|end| end > index_offset) {
+                return Err(bad(format!("frame {t} lies outside the data section")));
+            }
+        }
+        Ok((header, index))
+    }
+
+    /// The container's header.
+    pub fn header(&self) -> &FramedHeader {
+        &self.header
+    }
+
+    /// Decompress every frame and compare the BLAKE3 with the header.
+    pub fn verify_checksum(&self) -> Result<(), WitnessError> {
+        let mut h = blake3::Hasher::new();
+        for t in 0..self.index.len() {
+            h.update(&self.inflate(t)?);
+        }
+        if *h.finalize().as_bytes() != self.header.checksum {
+            return Err(WitnessError::Parse { src: self.src.clone(), row: 0, msg: "data checksum mismatch".into() });
+        }
+        Ok(())
+    }
+
+    /// Uncompressed bytes of frame `t`.
+    fn inflate(&self, t: usize) -> Result<Vec<u8>, WitnessError> {
+        let row0 = t * self.header.frame_rows;
+        let rows = self.header.frame_rows.min(self.header.rows - row0);
+        let (off, n) = self.index[t];
+        let io = |err| WitnessError::Io { src: self.src.clone(), row: row0, err };
+        let packed = match &self.source {
+            Source::File(f) => {
+                let mut f = f.lock().unwrap_or_else(|p| p.into_inner());
+                let mut buf = vec![0u8; n as usize];
+                f.seek(SeekFrom::Start(off)).and_then(|_| f.read_exact(&mut buf)).map_err(io)?;
+                std::borrow::Cow::Owned(buf)
+            }
+            Source::Bytes(b) => std::borrow::Cow::Borrowed(&b[off as usize..(off + n) as usize]),
+        };
+        // `rows ≤ frame_rows`, and the frame size was bounded on open.
+        self.header.codec.decompress(&packed, rows * self.header.k * ELEM_BYTES).map_err(|e| WitnessError::Parse {
+            src: self.src.clone(),
+            row: row0,
+            msg: format!("frame {t} ({}): {e}", self.header.codec.as_str()),
+        })
+    }
+
+    /// Rows `[start, end)` of frame `t`, decoded.
+    fn frame_rows(&self, t: usize, start: usize, end: usize) -> Vec<Result<Row, WitnessError>> {
+        let row0 = t * self.header.frame_rows;
+        let raw = match self.inflate(t) {
+            Ok(raw) => raw,
+            Err(e) => return vec![Err(e)],
+        };
+        let row_bytes = self.header.k * ELEM_BYTES;
+        let mut out = Vec::with_capacity(end - start);
+        for (i, r) in raw.chunks_exact(row_bytes).enumerate().take(end - row0).skip(start - row0) {
+            let regs = r
+                .chunks_exact(ELEM_BYTES)
+                .enumerate()
+                .map(|(j, x)| {
+                    F::deserialize_uncompressed(x).map_err(|_| WitnessError::Parse {
+                        src: self.src.clone(),
+                        row: row0 + i,
+                        msg: format!("column {j}: element is not a canonical field element"),
+                    })
+                })
+                .collect::<Result<Vec<F>, _>>();
+            let failed = regs.is_err();
+            out.push(regs.map(|r| Row { regs: r.into_boxed_slice() }));
+            if failed {
+                break;
+            }
+        }
+        out
+    }
+}
+
+impl TryRestreamer for FramedWitness {
+    type Item = Row;
+
+    fn len_rows(&self) -> usize {
+        self.header.rows
+    }
+
+    fn try_stream_rows(
+        &self,
+        start: RowIdx,
+        end: RowIdx,
+    ) -> Box<dyn Iterator<Item = Result<Row, WitnessError>> + '_> {
+        let end = end.0.min(self.header.rows);
+        let start = start.0.min(end);
+        let f = self.header.frame_rows;
+        let frames = if start == end { 0..0 } else { start / f..(end - 1) / f + 1 };
+        Box::new(frames.flat_map(move |t| {
+            let (lo, hi) = (start.max(t * f), end.min((t + 1) * f));
+            self.frame_rows(t, lo, hi)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn framed_witness_serves_blocks_from_both_codecs() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let (k, t, frame) = (2, 300, 64);
+        let rows: Vec<Vec<F>> = (0..t).map(|_| (0..k).map(|_| F::rand(&mut rng)).collect()).collect();
+        let dir = tempfile::tempdir().unwrap();
+        for codec in [WitnessCodec::Zstd, WitnessCodec::Gzip] {
+            let path = dir.path().join(format!("w.{}", codec.as_str()));
+            let mut w = FramedWitnessWriter::create(&path, k, codec, frame).unwrap();
+            rows.iter().for_each(|r| w.push_row(r).unwrap());
+            let written = w.finish().unwrap();
+            assert_eq!(written.frames(), 5);
+
+            let bytes = std::fs::read(&path).unwrap();
+            for src in [FramedWitness::open(&path).unwrap(), FramedWitness::from_bytes("upload", bytes).unwrap()] {
+                assert_eq!(src.header(), &written);
+                src.verify_checksum().unwrap();
+                for (s, e) in [(0, 64), (64, 128), (10, 200), (256, 300), (290, 400), (5, 5)] {
+                    let got: Vec<Vec<F>> =
+                        src.try_stream_rows(RowIdx(s), RowIdx(e)).map(|r| r.unwrap().regs.to_vec()).collect();
+                    assert_eq!(got, rows[s..e.min(t)], "{codec:?} [{s}, {e})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn framed_witness_reports_the_damaged_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("w.zst");
+        let mut w = FramedWitnessWriter::create(&path, 1, WitnessCodec::Zstd, 4).unwrap();
+        (0..12u64).for_each(|i| w.push_row(&[F::from(i)]).unwrap());
+        w.finish().unwrap();
+
+        let src = FramedWitness::open(&path).unwrap();
+        let (off, n) = src.index[1];
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[(off + n / 2) as usize] ^= 0xff;
+        let src = FramedWitness::from_bytes("upload", bytes.clone()).unwrap();
+        // Frame 0 still streams; frame 1 (rows 4..8) fails at its first row.
+        assert_eq!(src.try_stream_rows(RowIdx(0), RowIdx(4)).filter(Result::is_ok).count(), 4);
+        let err = src.try_stream_rows(RowIdx(2), RowIdx(12)).find_map(Result::err).unwrap();
+        assert_eq!(err.row(), 4);
+        assert!(src.verify_checksum().is_err());
+
+        // A truncated container is rejected before any frame is read.
+        assert!(FramedWitness::from_bytes("upload", bytes[..bytes.len() - 1].to_vec()).is_err());
+
+        // Hostile headers are refused before anything is sized from them.
+        let patched = |at: usize, v: u64| {
+            let mut b = bytes.clone();
+            b[at..at + 8].copy_from_slice(&v.to_le_bytes());
+            FramedWitness::from_bytes("upload", b)
+        };
+        assert!(patched(40, u64::MAX / 2).is_err()); // column-name length
+        assert!(patched(32, 1 << 40).is_err()); // rows per frame
+        assert!(patched(24, u64::MAX).is_err()); // row count
+        let mut zero_k = bytes.clone();
+        zero_k[16..20].copy_from_slice(&0u32.to_le_bytes());
+        let err = FramedWitness::from_bytes("upload", zero_k).err().unwrap();
+        assert!(err.to_string().contains("k=0"), "{err}");
+    }
+}